@@ -1,9 +1,14 @@
-use crate::{BackendError, FtmlBackend, ParagraphOrProblemKind};
+use crate::{
+    BackendError, Capabilities, FtmlBackend, ParagraphOrProblemKind, ResourceKind, SourceFormat,
+    utils::traced,
+};
 use ftml_ontology::{
     domain::modules::{Module, ModuleLike},
     narrative::{
+        DecodeError, decode_framed,
         documents::{Document, TocElem},
         elements::{Notation, SectionLevel},
+        encode_framed,
     },
     utils::Css,
 };
@@ -59,6 +64,15 @@ impl<E: std::fmt::Display + std::fmt::Debug + From<crate::utils::async_cache::Ca
     }
 }
 
+/// Wraps an [`FtmlBackend`] and caches its results (and errors) per request for its
+/// lifetime; concurrent requests for the same resource are deduplicated.
+///
+/// With the `trace-spans` feature, every method taking a uri is instrumented with a `DEBUG`
+/// span named `CachedBackend::<method>` (e.g. `CachedBackend::get_module`) with the
+/// requested uri as field `uri` (plus `kind`, `problems` and `symbol` for
+/// [`has_resource`](FtmlBackend::has_resource),
+/// [`get_logical_paragraphs`](FtmlBackend::get_logical_paragraphs) and
+/// [`get_notation`](FtmlBackend::get_notation), respectively).
 pub struct CachedBackend<B: FtmlBackend>
 where
     B::Error: Clone + Send + Sync + From<crate::utils::async_cache::CacheError>,
@@ -87,6 +101,7 @@ where
     documents_cache: AsyncCache<DocumentUri, Document, BackendError<B::Error>>,
     toc_cache:
         AsyncCache<DocumentUri, (Box<[Css]>, SectionLevel, Box<[TocElem]>), BackendError<B::Error>>,
+    resources_cache: AsyncCache<(DocumentUri, ResourceKind), bool, BackendError<B::Error>>,
 }
 
 impl<B: FtmlBackend> CachedBackend<B>
@@ -104,10 +119,113 @@ where
             modules_cache: AsyncCache::default(),
             documents_cache: AsyncCache::default(),
             toc_cache: AsyncCache::default(),
+            resources_cache: AsyncCache::default(),
+        }
+    }
+
+    /// Removes all cached results (and errors).
+    pub fn clear(&self) {
+        self.fragment_cache.clear();
+        self.doc_html_cache.clear();
+        self.notations_cache.clear();
+        self.paragraphs_cache.clear();
+        self.modules_cache.clear();
+        self.documents_cache.clear();
+        self.toc_cache.clear();
+        self.resources_cache.clear();
+    }
+
+    /// Removes all cached errors, keeping results and pending requests; see
+    /// [`FtmlBackend::forget_errors`].
+    pub fn clear_errors(&self) {
+        self.fragment_cache.retain(|_, r| r.is_ok());
+        self.doc_html_cache.retain(|_, r| r.is_ok());
+        self.notations_cache.retain(|_, r| r.is_ok());
+        self.paragraphs_cache.retain(|_, r| r.is_ok());
+        self.modules_cache.retain(|_, r| r.is_ok());
+        self.documents_cache.retain(|_, r| r.is_ok());
+        self.toc_cache.retain(|_, r| r.is_ok());
+        self.resources_cache.retain(|_, r| r.is_ok());
+        self.inner.forget_errors();
+    }
+
+    /// Serializes the cached [`Module`]s, [`Document`]s, fragments and document HTML, e.g. to
+    /// persist them and bootstrap the cache on the next launch via [`import`](Self::import).
+    ///
+    /// Only successfully resolved entries are exported; pending requests and cached errors
+    /// are not, and neither are the (comparatively cheap) notations, paragraphs, tables of
+    /// contents and resource checks.
+    ///
+    /// The blob is [`bincode`]-encoded and framed (see [`encode_framed`]). Since the binary
+    /// encoding is not stable across versions, blobs exported by a different
+    /// [`VERSION`](ftml_ontology::narrative::VersionHeader::VERSION) are rejected on import;
+    /// the cache then simply needs to be repopulated from the backend.
+    ///
+    /// # Errors
+    /// if encoding fails.
+    pub fn export(&self) -> Result<Box<[u8]>, bincode::error::EncodeError> {
+        fn resolved<K, V, E>(cache: &AsyncCache<K, V, E>) -> Vec<(K, V)>
+        where
+            K: std::hash::Hash + Clone + Eq,
+            V: Clone + Send + Sync,
+            E: Clone + From<crate::utils::async_cache::CacheError> + Send + Sync + 'static,
+        {
+            let mut entries = Vec::new();
+            cache.all(|k, v| {
+                if let Some(Ok(v)) = v {
+                    entries.push((k.clone(), v.clone()));
+                }
+            });
+            entries
+        }
+        let snapshot = CacheSnapshot {
+            modules: resolved(&self.modules_cache),
+            documents: resolved(&self.documents_cache),
+            fragments: resolved(&self.fragment_cache),
+            document_html: resolved(&self.doc_html_cache),
+        };
+        let mut buffer = Vec::new();
+        encode_framed(&snapshot, &mut buffer)?;
+        Ok(buffer.into_boxed_slice())
+    }
+
+    /// Restores the entries of a `blob` obtained via [`export`](Self::export); entries that
+    /// are already cached (or pending) are kept. Returns the number of imported entries.
+    ///
+    /// # Errors
+    /// see [`decode_framed`]; nothing is imported in that case.
+    pub fn import(&mut self, blob: &[u8]) -> Result<usize, DecodeError> {
+        fn restore<K, V, E>(cache: &AsyncCache<K, V, E>, entries: Vec<(K, V)>) -> usize
+        where
+            K: std::hash::Hash + Clone + Eq,
+            V: Clone + Send + Sync,
+            E: Clone + From<crate::utils::async_cache::CacheError> + Send + Sync + 'static,
+        {
+            entries
+                .into_iter()
+                .map(|(k, v)| usize::from(cache.insert_ready(k, v)))
+                .sum()
         }
+        let snapshot: CacheSnapshot = decode_framed(blob)?;
+        Ok(restore(&self.modules_cache, snapshot.modules)
+            + restore(&self.documents_cache, snapshot.documents)
+            + restore(&self.fragment_cache, snapshot.fragments)
+            + restore(&self.doc_html_cache, snapshot.document_html))
     }
 }
 
+/// The entries persisted by [`CachedBackend::export`]
+#[derive(Debug, bincode::Encode, bincode::Decode)]
+struct CacheSnapshot {
+    modules: Vec<(ModuleUri, Module)>,
+    documents: Vec<(DocumentUri, Document)>,
+    fragments: Vec<((Uri, Option<NarrativeUri>), (Box<str>, Box<[Css]>, bool))>,
+    document_html: Vec<(
+        (DocumentUri, Option<NarrativeUri>),
+        (Box<str>, Box<[Css]>, bool),
+    )>,
+}
+
 impl<B: FtmlBackend> FtmlBackend for CachedBackend<B>
 where
     B::Error: Clone + Send + Sync + std::fmt::Debug + From<crate::utils::async_cache::CacheError>,
@@ -138,21 +256,54 @@ where
     }
 
     #[inline]
-    fn resource_link_url(&self, uri: &DocumentUri, kind: &'static str) -> Option<String> {
+    fn resource_link_url(&self, uri: &DocumentUri, kind: ResourceKind) -> Option<String> {
         self.inner.resource_link_url(uri, kind)
     }
 
+    #[inline]
+    fn forget_errors(&self) {
+        self.clear_errors();
+    }
+
+    fn has_resource(
+        &self,
+        uri: &DocumentUri,
+        kind: ResourceKind,
+    ) -> impl Future<Output = Result<bool, BackendError<Self::Error>>> + Send + 'static {
+        let check = self.inner.has_resource(uri, kind);
+        traced!(
+            "CachedBackend::has_resource",
+            (uri = %uri, kind = ?kind),
+            self.resources_cache
+                .get((uri.clone(), kind), move |_| check)
+                .map_err(Into::into)
+        )
+    }
+
+    /// Not cached; the capabilities of a backend are usually asked for once anyway.
+    #[inline]
+    fn capabilities(
+        &self,
+    ) -> impl Future<Output = Result<Capabilities, BackendError<Self::Error>>> + Send + 'static
+    {
+        self.inner.capabilities()
+    }
+
     fn get_fragment(
         &self,
         uri: Uri,
         context: Option<NarrativeUri>,
     ) -> impl Future<Output = Result<(Box<str>, Box<[Css]>, bool), BackendError<Self::Error>>> + 'static
     {
-        self.fragment_cache
-            .get((uri, context), |(uri, context)| {
-                self.inner.get_fragment(uri, context)
-            })
-            .map_err(Into::into)
+        traced!(
+            "CachedBackend::get_fragment",
+            (uri = %uri),
+            self.fragment_cache
+                .get((uri.normalized(), context), |(uri, context)| {
+                    self.inner.get_fragment(uri, context)
+                })
+                .map_err(Into::into)
+        )
     }
 
     fn get_solutions(
@@ -166,8 +317,10 @@ where
     > + Send
     + 'static {
         // TODO returns wrong error type
-        let fut = self.inner.get_solutions(uri);
-        async move { fut.await.map_err(|e| BackendError::ToDo(e.to_string())) }
+        traced!("CachedBackend::get_solutions", (uri = %uri), {
+            let fut = self.inner.get_solutions(uri);
+            async move { fut.await.map_err(|e| BackendError::ToDo(e.to_string())) }
+        })
     }
 
     fn get_document_html(
@@ -177,11 +330,30 @@ where
     ) -> impl Future<Output = Result<(Box<str>, Box<[Css]>, bool), BackendError<Self::Error>>>
     + Send
     + 'static {
-        self.doc_html_cache
-            .get((uri, context), |(uri, context)| {
-                self.inner.get_document_html(uri, context)
-            })
-            .map_err(Into::into)
+        traced!(
+            "CachedBackend::get_document_html",
+            (uri = %uri),
+            self.doc_html_cache
+                .get((uri, context), |(uri, context)| {
+                    self.inner.get_document_html(uri, context)
+                })
+                .map_err(Into::into)
+        )
+    }
+
+    /// Not cached, since sources are usually requested rarely and may change while
+    /// editing.
+    #[inline]
+    fn get_source(
+        &self,
+        uri: DocumentUri,
+    ) -> impl Future<Output = Result<(String, SourceFormat), BackendError<Self::Error>>> + Send + 'static
+    {
+        traced!(
+            "CachedBackend::get_source",
+            (uri = %uri),
+            self.inner.get_source(uri)
+        )
     }
 
     fn get_toc(
@@ -191,16 +363,20 @@ where
         Output = Result<(Box<[Css]>, SectionLevel, Box<[TocElem]>), BackendError<Self::Error>>,
     > + Send
     + 'static {
-        self.toc_cache
-            .get(uri, |uri| self.inner.get_toc(uri))
-            .map_err(Into::into)
+        traced!(
+            "CachedBackend::get_toc",
+            (uri = %uri),
+            self.toc_cache
+                .get(uri, |uri| self.inner.get_toc(uri))
+                .map_err(Into::into)
+        )
     }
 
     fn get_module(
         &self,
         uri: ModuleUri,
     ) -> impl Future<Output = Result<ModuleLike, BackendError<Self::Error>>> + 'static {
-        if uri.is_top() {
+        traced!("CachedBackend::get_module", (uri = %uri), if uri.is_top() {
             either::Either::Left(
                 self.modules_cache
                     .get(uri, |uri| {
@@ -240,16 +416,20 @@ where
                         )
                     }),
             )
-        }
+        })
     }
 
     fn get_document(
         &self,
         uri: DocumentUri,
     ) -> impl Future<Output = Result<Document, BackendError<Self::Error>>> + 'static {
-        self.documents_cache
-            .get(uri, |uri| self.inner.get_document(uri))
-            .map_err(Into::into)
+        traced!(
+            "CachedBackend::get_document",
+            (uri = %uri),
+            self.documents_cache
+                .get(uri, |uri| self.inner.get_document(uri))
+                .map_err(Into::into)
+        )
     }
 
     fn get_notations(
@@ -257,9 +437,13 @@ where
         uri: LeafUri,
     ) -> impl Future<Output = Result<Vec<(DocumentElementUri, Notation)>, BackendError<Self::Error>>>
     + 'static {
-        self.notations_cache
-            .get(uri, |uri| self.inner.get_notations(uri))
-            .map_err(Into::into)
+        traced!(
+            "CachedBackend::get_notations",
+            (uri = %uri),
+            self.notations_cache
+                .get(uri, |uri| self.inner.get_notations(uri))
+                .map_err(Into::into)
+        )
     }
 
     fn get_logical_paragraphs(
@@ -272,11 +456,15 @@ where
             BackendError<Self::Error>,
         >,
     > + 'static {
-        self.paragraphs_cache
-            .get(uri, move |uri| {
-                self.inner.get_logical_paragraphs(uri, problems)
-            })
-            .map_err(Into::into)
+        traced!(
+            "CachedBackend::get_logical_paragraphs",
+            (uri = %uri, problems),
+            self.paragraphs_cache
+                .get(uri, move |uri| {
+                    self.inner.get_logical_paragraphs(uri, problems)
+                })
+                .map_err(Into::into)
+        )
     }
     fn get_notation(
         &self,
@@ -284,17 +472,53 @@ where
         uri: DocumentElementUri,
     ) -> impl Future<Output = Result<Notation, BackendError<Self::Error>>> + 'static {
         let uriclone = uri.clone();
-        self.notations_cache
-            .with(
-                &symbol,
-                |v| self.inner.get_notations(v),
-                move |v| {
-                    v.iter()
-                        .find(|(u, _)| *u == uriclone)
-                        .map(|(_, n)| n.clone())
-                },
-            )
-            .map_ok_or_else(Err, move |v| v.ok_or(BackendError::NotFound(uri.into())))
+        traced!(
+            "CachedBackend::get_notation",
+            (uri = %uri, symbol = %symbol),
+            self.notations_cache
+                .with(
+                    &symbol,
+                    |v| self.inner.get_notations(v),
+                    move |v| {
+                        v.iter()
+                            .find(|(u, _)| *u == uriclone)
+                            .map(|(_, n)| n.clone())
+                    },
+                )
+                .map_ok_or_else(Err, move |v| v.ok_or(BackendError::NotFound(uri.into())))
+        )
+    }
+
+    #[inline]
+    fn get_language_variants(
+        &self,
+        uri: DocumentUri,
+    ) -> impl Future<Output = Result<Vec<ftml_uris::Language>, BackendError<Self::Error>>> + Send + 'static
+    {
+        traced!(
+            "CachedBackend::get_language_variants",
+            (uri = %uri),
+            self.inner.get_language_variants(uri)
+        )
+    }
+
+    #[inline]
+    fn list_archives(
+        &self,
+    ) -> impl Future<Output = Result<Vec<ftml_uris::ArchiveId>, BackendError<Self::Error>>>
+    + Send
+    + 'static {
+        self.inner.list_archives()
+    }
+
+    /// Not cached, since search results may change at any time.
+    #[inline]
+    fn search(
+        &self,
+        query: &str,
+        kinds: &[ftml_uris::UriKind],
+    ) -> impl Future<Output = Result<Vec<Uri>, BackendError<Self::Error>>> + Send + 'static {
+        self.inner.search(query, kinds)
     }
 }
 
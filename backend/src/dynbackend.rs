@@ -1,7 +1,7 @@
 use either::Either;
 use ftml_ontology::{
     domain::{
-        SharedDeclaration,
+        AnyDeclaration, SharedDeclaration,
         declarations::{
             morphisms::Morphism,
             structures::{MathStructure, StructureExtension},
@@ -21,16 +21,33 @@ use ftml_ontology::{
     utils::Css,
 };
 use ftml_uris::{
-    DocumentElementUri, DocumentUri, LeafUri, ModuleUri, NarrativeUri, SymbolUri, Uri,
+    ArchiveId, DocumentElementUri, DocumentUri, Language, LeafUri, ModuleUri, NarrativeUri,
+    SymbolUri, Uri, UriKind,
 };
 
-use crate::{BackendCheckResult, BackendError, FtmlBackend};
+use crate::{
+    BackendCheckResult, BackendError, Capabilities, FtmlBackend, ResourceKind, SourceFormat,
+};
 
 pub type Fut<T> = std::pin::Pin<Box<dyn Future<Output = Result<T, BackendError<String>>> + Send>>;
 
 pub trait DynBackend: Send + Sync {
     fn document_link_url(&self, uri: &DocumentUri) -> String;
-    fn resource_link_url(&self, uri: &DocumentUri, kind: &'static str) -> Option<String>;
+    fn resource_link_url(&self, uri: &DocumentUri, kind: ResourceKind) -> Option<String>;
+
+    fn forget_errors(&self);
+
+    /// String-based variant of [`resource_link_url`](Self::resource_link_url), kept for
+    /// compatibility; returns `None` if `kind` is not a known [`ResourceKind`] (see
+    /// [`ResourceKind::from_name`]).
+    #[inline]
+    fn resource_link_url_str(&self, uri: &DocumentUri, kind: &str) -> Option<String> {
+        ResourceKind::from_name(kind).and_then(|kind| self.resource_link_url(uri, kind))
+    }
+
+    fn has_resource(&self, uri: &DocumentUri, kind: ResourceKind) -> Fut<bool>;
+
+    fn capabilities(&self) -> Fut<Capabilities>;
 
     fn check_term(
         &self,
@@ -67,6 +84,8 @@ pub trait DynBackend: Send + Sync {
         uri: SymbolUri,
     ) -> Fut<Either<SharedDeclaration<MathStructure>, SharedDeclaration<StructureExtension>>>;
 
+    fn get_declaration(&self, uri: SymbolUri) -> Fut<AnyDeclaration>;
+
     fn get_variable(
         &self,
         uri: DocumentElementUri,
@@ -89,11 +108,21 @@ pub trait DynBackend: Send + Sync {
         context: Option<NarrativeUri>,
     ) -> Fut<(Box<str>, Box<[Css]>, bool)>;
 
+    fn get_source(&self, uri: DocumentUri) -> Fut<(String, SourceFormat)>;
+
     fn get_solutions(&self, uri: DocumentElementUri) -> Fut<Solutions>;
 
     fn get_notations(&self, uri: LeafUri) -> Fut<Vec<(DocumentElementUri, Notation)>>;
 
     fn get_notation(&self, symbol: LeafUri, uri: DocumentElementUri) -> Fut<Notation>;
+
+    fn get_symbol_glyph(&self, uri: SymbolUri) -> Fut<Box<str>>;
+
+    fn get_language_variants(&self, uri: DocumentUri) -> Fut<Vec<Language>>;
+
+    fn list_archives(&self) -> Fut<Vec<ArchiveId>>;
+
+    fn search(&self, query: &str, kinds: &[UriKind]) -> Fut<Vec<Uri>>;
 }
 
 fn wrap<R, E: std::fmt::Debug + std::fmt::Display>(
@@ -120,10 +149,29 @@ impl FtmlBackend for dyn DynBackend {
         <Self as DynBackend>::document_link_url(self, uri)
     }
     #[inline]
-    fn resource_link_url(&self, uri: &DocumentUri, kind: &'static str) -> Option<String> {
+    fn resource_link_url(&self, uri: &DocumentUri, kind: ResourceKind) -> Option<String> {
         <Self as DynBackend>::resource_link_url(self, uri, kind)
     }
     #[inline]
+    fn forget_errors(&self) {
+        <Self as DynBackend>::forget_errors(self);
+    }
+    #[inline]
+    fn has_resource(
+        &self,
+        uri: &DocumentUri,
+        kind: ResourceKind,
+    ) -> impl Future<Output = Result<bool, BackendError<Self::Error>>> + Send + 'static {
+        <Self as DynBackend>::has_resource(self, uri, kind)
+    }
+    #[inline]
+    fn capabilities(
+        &self,
+    ) -> impl Future<Output = Result<Capabilities, BackendError<Self::Error>>> + Send + 'static
+    {
+        <Self as DynBackend>::capabilities(self)
+    }
+    #[inline]
     fn check_term(
         &self,
         global_context: &[ModuleUri],
@@ -217,6 +265,14 @@ impl FtmlBackend for dyn DynBackend {
         <Self as DynBackend>::get_structure(self, uri)
     }
     #[inline]
+    fn get_declaration(
+        &self,
+        uri: SymbolUri,
+    ) -> impl Future<Output = Result<AnyDeclaration, BackendError<Self::Error>>> + Send + 'static
+    {
+        <Self as DynBackend>::get_declaration(self, uri)
+    }
+    #[inline]
     fn get_variable(
         &self,
         uri: DocumentElementUri,
@@ -262,6 +318,14 @@ impl FtmlBackend for dyn DynBackend {
         <Self as DynBackend>::get_document_html(self, uri, context)
     }
     #[inline]
+    fn get_source(
+        &self,
+        uri: DocumentUri,
+    ) -> impl Future<Output = Result<(String, SourceFormat), BackendError<Self::Error>>> + Send + 'static
+    {
+        <Self as DynBackend>::get_source(self, uri)
+    }
+    #[inline]
     fn get_solutions(
         &self,
         uri: DocumentElementUri,
@@ -285,6 +349,36 @@ impl FtmlBackend for dyn DynBackend {
     ) -> impl Future<Output = Result<Notation, BackendError<Self::Error>>> + Send + 'static {
         <Self as DynBackend>::get_notation(self, symbol, uri)
     }
+    #[inline]
+    fn get_symbol_glyph(
+        &self,
+        uri: SymbolUri,
+    ) -> impl Future<Output = Result<Box<str>, BackendError<Self::Error>>> + Send + 'static {
+        <Self as DynBackend>::get_symbol_glyph(self, uri)
+    }
+    #[inline]
+    fn get_language_variants(
+        &self,
+        uri: DocumentUri,
+    ) -> impl Future<Output = Result<Vec<Language>, BackendError<Self::Error>>> + Send + 'static
+    {
+        <Self as DynBackend>::get_language_variants(self, uri)
+    }
+    #[inline]
+    fn list_archives(
+        &self,
+    ) -> impl Future<Output = Result<Vec<ArchiveId>, BackendError<Self::Error>>> + Send + 'static
+    {
+        <Self as DynBackend>::list_archives(self)
+    }
+    #[inline]
+    fn search(
+        &self,
+        query: &str,
+        kinds: &[UriKind],
+    ) -> impl Future<Output = Result<Vec<Uri>, BackendError<Self::Error>>> + Send + 'static {
+        <Self as DynBackend>::search(self, query, kinds)
+    }
 }
 
 impl<B: FtmlBackend + Send + Sync> DynBackend for B {
@@ -293,10 +387,22 @@ impl<B: FtmlBackend + Send + Sync> DynBackend for B {
         <Self as FtmlBackend>::document_link_url(self, uri)
     }
     #[inline]
-    fn resource_link_url(&self, uri: &DocumentUri, kind: &'static str) -> Option<String> {
+    fn resource_link_url(&self, uri: &DocumentUri, kind: ResourceKind) -> Option<String> {
         <Self as FtmlBackend>::resource_link_url(self, uri, kind)
     }
     #[inline]
+    fn forget_errors(&self) {
+        <Self as FtmlBackend>::forget_errors(self);
+    }
+    #[inline]
+    fn has_resource(&self, uri: &DocumentUri, kind: ResourceKind) -> Fut<bool> {
+        wrap(<Self as FtmlBackend>::has_resource(self, uri, kind))
+    }
+    #[inline]
+    fn capabilities(&self) -> Fut<Capabilities> {
+        wrap(<Self as FtmlBackend>::capabilities(self))
+    }
+    #[inline]
     fn check_term(
         &self,
         global_context: &[ModuleUri],
@@ -356,6 +462,10 @@ impl<B: FtmlBackend + Send + Sync> DynBackend for B {
         wrap(<Self as FtmlBackend>::get_structure(self, uri))
     }
     #[inline]
+    fn get_declaration(&self, uri: SymbolUri) -> Fut<AnyDeclaration> {
+        wrap(<Self as FtmlBackend>::get_declaration(self, uri))
+    }
+    #[inline]
     fn get_variable(
         &self,
         uri: DocumentElementUri,
@@ -386,6 +496,10 @@ impl<B: FtmlBackend + Send + Sync> DynBackend for B {
         wrap(<Self as FtmlBackend>::get_document_html(self, uri, context))
     }
     #[inline]
+    fn get_source(&self, uri: DocumentUri) -> Fut<(String, SourceFormat)> {
+        wrap(<Self as FtmlBackend>::get_source(self, uri))
+    }
+    #[inline]
     fn get_solutions(&self, uri: DocumentElementUri) -> Fut<Solutions> {
         wrap(<Self as FtmlBackend>::get_solutions(self, uri))
     }
@@ -397,4 +511,20 @@ impl<B: FtmlBackend + Send + Sync> DynBackend for B {
     fn get_notation(&self, symbol: LeafUri, uri: DocumentElementUri) -> Fut<Notation> {
         wrap(<Self as FtmlBackend>::get_notation(self, symbol, uri))
     }
+    #[inline]
+    fn get_symbol_glyph(&self, uri: SymbolUri) -> Fut<Box<str>> {
+        wrap(<Self as FtmlBackend>::get_symbol_glyph(self, uri))
+    }
+    #[inline]
+    fn get_language_variants(&self, uri: DocumentUri) -> Fut<Vec<Language>> {
+        wrap(<Self as FtmlBackend>::get_language_variants(self, uri))
+    }
+    #[inline]
+    fn list_archives(&self) -> Fut<Vec<ArchiveId>> {
+        wrap(<Self as FtmlBackend>::list_archives(self))
+    }
+    #[inline]
+    fn search(&self, query: &str, kinds: &[UriKind]) -> Fut<Vec<Uri>> {
+        wrap(<Self as FtmlBackend>::search(self, query, kinds))
+    }
 }
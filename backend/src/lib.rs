@@ -12,6 +12,10 @@ pub mod utils;
 #[cfg(feature = "cached")]
 pub use cache::*;
 
+#[cfg(feature = "wasm")]
+mod local;
+#[cfg(feature = "wasm")]
+pub use local::LocalBackendAdapter;
 #[cfg(any(feature = "wasm", feature = "reqwest"))]
 mod remote;
 use either::Either;
@@ -24,7 +28,7 @@ pub use errors::*;
 
 use ftml_ontology::{
     domain::{
-        SharedDeclaration,
+        AnyDeclaration, SharedDeclaration,
         declarations::{
             morphisms::Morphism,
             structures::{MathStructure, StructureExtension},
@@ -33,18 +37,20 @@ use ftml_ontology::{
         modules::ModuleLike,
     },
     narrative::{
-        SharedDocumentElement,
+        Narrative, SharedDocumentElement,
         documents::{Document, TocElem},
         elements::{
-            DocumentTerm, Notation, ParagraphOrProblemKind, SectionLevel, VariableDeclaration,
-            problems::Solutions,
+            DocumentElement, DocumentElementRef, DocumentTerm, LogicalParagraph, Notation,
+            ParagraphOrProblemKind, Section, SectionLevel, VariableDeclaration,
+            notations::NotationMap, problems::Solutions,
         },
     },
     terms::{ComponentVar, Term, termpaths::TermPath},
     utils::Css,
 };
 use ftml_uris::{
-    DocumentElementUri, DocumentUri, LeafUri, ModuleUri, NarrativeUri, SymbolUri, Uri,
+    ArchiveId, DocumentElementUri, DocumentUri, Language, LeafUri, ModuleUri, NarrativeUri,
+    SymbolUri, Uri, UriKind,
 };
 use futures_util::{FutureExt, TryFutureExt};
 
@@ -125,6 +131,11 @@ macro_rules! new_global {
     (@NEW Cached($($rest:tt)*) ) => { $crate::FtmlBackend::cached($crate::new_global!(@NEW $($rest)*)) };
 }
 
+/// A backend accessible globally, usually via a `static` (see [`new_global!`]).
+///
+/// The only bound on the [`Backend`](GlobalBackend::Backend) itself is [`FtmlBackend`];
+/// in particular, it need not be [`Send`] or [`Sync`] (although [`as_dyn`](GlobalBackend::as_dyn)
+/// requires both).
 pub trait GlobalBackend: 'static {
     type Error: std::fmt::Display + std::fmt::Debug;
     type Backend: FtmlBackend<Error = Self::Error>;
@@ -137,6 +148,15 @@ pub trait GlobalBackend: 'static {
         Self::get() as _
     }
 }
+/// A [`GlobalBackend`] that can be used across threads (e.g. in server functions).
+///
+/// This is implemented automatically for every [`GlobalBackend`] `G` (that is itself [`Send`]) for
+/// which
+/// - `G::Backend: Send + Sync`, and
+/// - `G::Error: Send + Sync + Clone` and (de)serializable.
+///
+/// Backends that are not [`Send`]/[`Sync`] can be made to satisfy these bounds on single-threaded
+/// (wasm) targets by wrapping them in a `LocalBackendAdapter` (requires the `wasm` feature).
 pub trait SendBackend:
     GlobalBackend<
         Backend: Send + Sync,
@@ -151,6 +171,138 @@ where
 {
 }
 
+/// The kinds of resources associated with a document that a backend may link to (see
+/// [`FtmlBackend::resource_link_url`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ResourceKind {
+    /// The PDF generated from the document's sources (`pdf`)
+    Pdf,
+    /// The document's (LaTeX) sources (`tex`)
+    Source,
+    /// The standalone HTML of the document (`html`)
+    Html,
+    /// The document's OMDoc representation (`omdoc`)
+    Omdoc,
+}
+impl ResourceKind {
+    /// All known resource kinds.
+    pub const ALL: [Self; 4] = [Self::Pdf, Self::Source, Self::Html, Self::Omdoc];
+
+    /// The name of this kind, as used in the `format` query parameter of resource URLs.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Pdf => "pdf",
+            Self::Source => "tex",
+            Self::Html => "html",
+            Self::Omdoc => "omdoc",
+        }
+    }
+
+    /// The suffix appended to resource URLs (after the document URI) for this kind;
+    /// i.e. `&format=<name>`.
+    #[must_use]
+    pub fn url_suffix(self) -> String {
+        format!("&format={}", self.as_str())
+    }
+
+    /// Parses a resource kind from its [name](Self::as_str), ignoring case.
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|k| k.as_str().eq_ignore_ascii_case(name))
+    }
+}
+impl std::fmt::Display for ResourceKind {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// The format of the source file of a document (see [`FtmlBackend::get_source`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SourceFormat {
+    /// (La)TeX (`.tex`)
+    Tex,
+    /// Markdown (`.md`)
+    Markdown,
+    /// (X)HTML (`.html`)
+    Html,
+    /// Any other format; displayed as plain text
+    Other,
+}
+impl SourceFormat {
+    /// The usual file extension for this format (without the leading `.`).
+    #[must_use]
+    pub const fn extension(self) -> &'static str {
+        match self {
+            Self::Tex => "tex",
+            Self::Markdown => "md",
+            Self::Html => "html",
+            Self::Other => "txt",
+        }
+    }
+
+    /// Determines the format from a file extension (without the leading `.`), ignoring
+    /// case.
+    #[must_use]
+    pub fn from_extension(ext: &str) -> Self {
+        match ext.to_ascii_lowercase().as_str() {
+            "tex" | "ltx" | "sty" | "cls" => Self::Tex,
+            "md" | "markdown" => Self::Markdown,
+            "html" | "htm" | "xhtml" => Self::Html,
+            _ => Self::Other,
+        }
+    }
+
+    /// Determines the format from a MIME type (e.g. the `Content-Type` of a response),
+    /// ignoring parameters such as `charset`. Returns `None` for MIME types that do not
+    /// indicate a specific source format (e.g. `text/plain`).
+    #[must_use]
+    pub fn from_mime(mime: &str) -> Option<Self> {
+        let mime = mime.split(';').next().unwrap_or_default().trim();
+        match mime.to_ascii_lowercase().as_str() {
+            "text/x-tex" | "application/x-tex" | "text/x-latex" | "application/x-latex" => {
+                Some(Self::Tex)
+            }
+            "text/markdown" | "text/x-markdown" => Some(Self::Markdown),
+            "text/html" | "application/xhtml+xml" => Some(Self::Html),
+            _ => None,
+        }
+    }
+}
+impl std::fmt::Display for SourceFormat {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.extension())
+    }
+}
+
+/// The optional features a backend supports (see [`FtmlBackend::capabilities`]); e.g. to
+/// hide a search field if [`search`](FtmlBackend::search) would fail anyway.
+///
+/// All flags default to `false`, i.e. [`Capabilities::default`] is the capability set of a
+/// backend that relies on the default implementations of all optional methods.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde-lite",
+    derive(serde_lite::Serialize, serde_lite::Deserialize)
+)]
+pub struct Capabilities {
+    /// [`search`](FtmlBackend::search) is supported
+    #[serde(default)]
+    pub search: bool,
+    /// [`list_archives`](FtmlBackend::list_archives) is supported
+    #[serde(default)]
+    pub list_archives: bool,
+    /// [`get_source`](FtmlBackend::get_source) is supported (for documents that have sources
+    /// at all)
+    #[serde(default)]
+    pub source: bool,
+}
+
 #[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
 #[cfg_attr(
     feature = "serde-lite",
@@ -176,7 +328,50 @@ pub trait FtmlBackend {
     }
 
     fn document_link_url(&self, uri: &DocumentUri) -> String;
-    fn resource_link_url(&self, uri: &DocumentUri, kind: &'static str) -> Option<String>;
+    fn resource_link_url(&self, uri: &DocumentUri, kind: ResourceKind) -> Option<String>;
+
+    /// Discards all cached *failed* requests, so that they are sent again the next time
+    /// (e.g. to retry after a connection error); cached results are kept. A no-op for
+    /// backends that do not cache (the default).
+    #[inline]
+    fn forget_errors(&self) {}
+
+    /// Which of the optional methods this backend supports; clients should check this before
+    /// calling them, rather than relying on [`BackendError::ToDo`].
+    ///
+    /// Backends whose capabilities are known statically just return those; the default
+    /// implementation reports none (i.e. [`Capabilities::default`]). Remote backends may ask
+    /// the server instead.
+    fn capabilities(
+        &self,
+    ) -> impl Future<Output = Result<Capabilities, BackendError<Self::Error>>> + Send + 'static
+    {
+        std::future::ready(Ok(Capabilities::default()))
+    }
+
+    /// String-based variant of [`resource_link_url`](Self::resource_link_url), kept for
+    /// compatibility; returns `None` if `kind` is not a known [`ResourceKind`] (see
+    /// [`ResourceKind::from_name`]).
+    #[inline]
+    fn resource_link_url_str(&self, uri: &DocumentUri, kind: &str) -> Option<String> {
+        ResourceKind::from_name(kind).and_then(|kind| self.resource_link_url(uri, kind))
+    }
+
+    /// Whether the resource of the given kind for `uri` actually exists, e.g. to hide links
+    /// to absent PDFs instead of having them lead to a 404.
+    ///
+    /// The default implementation only checks whether
+    /// [`resource_link_url`](Self::resource_link_url) returns a link at all; remote backends
+    /// issue a `HEAD` request for that link instead. The result is not cached, except by a
+    /// [`CachedBackend`] (requires the `cached` feature), which remembers it (and errors) per
+    /// document and kind for its lifetime.
+    fn has_resource(
+        &self,
+        uri: &DocumentUri,
+        kind: ResourceKind,
+    ) -> impl Future<Output = Result<bool, BackendError<Self::Error>>> + Send + 'static {
+        std::future::ready(Ok(self.resource_link_url(uri, kind).is_some()))
+    }
 
     fn check_term(
         &self,
@@ -292,6 +487,22 @@ pub trait FtmlBackend {
         })
     }
 
+    /// Fetches the declaration `uri`, whatever its kind (symbol, structure, extension,
+    /// morphism or nested module) - for when the kind is not known in advance.
+    fn get_declaration(
+        &self,
+        uri: SymbolUri,
+    ) -> impl Future<Output = Result<AnyDeclaration, BackendError<Self::Error>>> + Send + 'static
+    {
+        let uriclone = uri.clone();
+        let uri = uri.simple_module();
+        let name = uri.name;
+        self.get_module(uri.module).map(move |r| {
+            r?.get_declaration(&name)
+                .ok_or_else(move || BackendError::NotFound(uriclone.into()))
+        })
+    }
+
     fn get_variable(
         &self,
         uri: DocumentElementUri,
@@ -356,6 +567,19 @@ pub trait FtmlBackend {
     + Send
     + 'static;
 
+    /// Returns the raw source file (e.g. the `.tex` file) the document `uri` was generated
+    /// from, together with its [format](SourceFormat), e.g. for a "view source" button.
+    ///
+    /// Sources are not supported by default; the default implementation always returns
+    /// [`BackendError::ToDo`]. Remote backends fetch the [`ResourceKind::Source`] resource.
+    fn get_source(
+        &self,
+        _uri: DocumentUri,
+    ) -> impl Future<Output = Result<(String, SourceFormat), BackendError<Self::Error>>> + Send + 'static
+    {
+        std::future::ready(Err(BackendError::ToDo("get_source".to_string())))
+    }
+
     fn get_solutions(
         &self,
         uri: DocumentElementUri,
@@ -381,6 +605,526 @@ pub trait FtmlBackend {
                 .ok_or_else(move || BackendError::NotFound(uriclone.into()))
         })
     }
+
+    /// Returns the presentation MathML (a `<math>` element) of the symbol `uri` in its
+    /// operator form, e.g. for lightweight symbol "chips", without fetching a full fragment.
+    ///
+    /// The default implementation derives it from [`get_notations`](FtmlBackend::get_notations);
+    /// see [`symbol_glyph`] for how the notation is selected. A symbol the backend does not
+    /// know (i.e. [`BackendError::NotFound`]) has no notations.
+    fn get_symbol_glyph(
+        &self,
+        uri: SymbolUri,
+    ) -> impl Future<Output = Result<Box<str>, BackendError<Self::Error>>> + Send + 'static {
+        notations_glyph(self, uri)
+    }
+
+    /// Returns the languages in which the document `uri` (in any language) is available.
+    ///
+    /// The default implementation only probes the language fallback chain of `uri` - its own
+    /// language, followed by the default language ([`Language::English`]) that documents fall
+    /// back to - via [`get_document`](FtmlBackend::get_document) (one after the other), i.e.
+    /// it issues at most two requests and can not discover any other variants.
+    /// [`BackendError::NotFound`] counts as "not available"; any other error is returned.
+    fn get_language_variants(
+        &self,
+        uri: DocumentUri,
+    ) -> impl Future<Output = Result<Vec<Language>, BackendError<Self::Error>>> + Send + 'static
+    {
+        probe_language_variants(self, uri)
+    }
+
+    /// Returns the ids of all archives known to this backend, e.g. for an archive browser.
+    ///
+    /// Not every backend has an index of its archives; the default implementation is
+    /// unsupported, i.e. fails with [`BackendError::ToDo`].
+    fn list_archives(
+        &self,
+    ) -> impl Future<Output = Result<Vec<ArchiveId>, BackendError<Self::Error>>> + Send + 'static
+    {
+        std::future::ready(Err(BackendError::ToDo("list_archives".to_string())))
+    }
+
+    /// Searches for `query`, returning the uris of the hits of the given `kinds` (or of any
+    /// kind, if `kinds` is empty), e.g. as parsed by [`parse_search_results`].
+    ///
+    /// There is no pagination: the result is whatever the backend's search returns for
+    /// `query` (typically its top hits by relevance, in that order), filtered by kind. The
+    /// default implementation is unsupported, i.e. fails with [`BackendError::ToDo`].
+    fn search(
+        &self,
+        query: &str,
+        kinds: &[UriKind],
+    ) -> impl Future<Output = Result<Vec<Uri>, BackendError<Self::Error>>> + Send + 'static {
+        let _ = (query, kinds);
+        std::future::ready(Err(BackendError::ToDo("search".to_string())))
+    }
+
+    /// Returns, for every top-level section of the document `uri` (i.e. every section not
+    /// nested in another section), its uri, its title as plain text (empty if the section
+    /// has none) and the first sentence of its first paragraph (if any), e.g. for search
+    /// result previews.
+    ///
+    /// The default implementation fetches the document via
+    /// [`get_document`](FtmlBackend::get_document) and then the fragment of the first
+    /// paragraph of each section via [`get_fragment`](FtmlBackend::get_fragment) (one after
+    /// the other); see [`html_to_text`](utils::text::html_to_text) and
+    /// [`first_sentence`](utils::text::first_sentence) for how the sentence is extracted.
+    /// Since the fragment requests depend on the document, the returned future (unlike all
+    /// others) borrows the backend.
+    #[allow(clippy::type_complexity)]
+    fn get_outline_previews(
+        &self,
+        uri: DocumentUri,
+    ) -> impl Future<
+        Output = Result<
+            Vec<(DocumentElementUri, String, Option<String>)>,
+            BackendError<Self::Error>,
+        >,
+    > + Send
+    + '_
+    where
+        Self: Sync,
+    {
+        outline_previews(self, uri)
+    }
+
+    /// Returns the definition chain of the symbol `uri`, e.g. to unfold its definition: `uri`
+    /// with its definiens, followed by the symbols occurring in that definiens with *their*
+    /// definientia, and so on (breadth-first, in order of first occurrence). Primitive
+    /// symbols (i.e. without definiens) are paired with `None`.
+    ///
+    /// Every symbol occurs in the chain at most once and is only unfolded there, so cyclic
+    /// definitions (e.g. `a := f(b)` and `b := g(a)`) terminate; a symbol whose definiens
+    /// refers back to it is simply not revisited. Symbols more than `max_depth` unfoldings
+    /// away from `uri` are omitted (so for `max_depth == 0`, the chain consists of `uri`
+    /// alone). Fails if any symbol in the chain can not be fetched. Since every request
+    /// depends on the previous ones, the returned future borrows the backend.
+    #[allow(clippy::type_complexity)]
+    fn get_definition_chain(
+        &self,
+        uri: SymbolUri,
+        max_depth: usize,
+    ) -> impl Future<Output = Result<Vec<(SymbolUri, Option<Term>)>, BackendError<Self::Error>>>
+    + Send
+    + '_
+    where
+        Self: Sync,
+    {
+        definition_chain(self, uri, max_depth)
+    }
+}
+
+/// The link to the resource of the given kind for `uri`, if it
+/// [exists](FtmlBackend::has_resource).
+pub fn available_resource_link<B: FtmlBackend + ?Sized>(
+    backend: &B,
+    uri: &DocumentUri,
+    kind: ResourceKind,
+) -> impl Future<Output = Result<Option<String>, BackendError<B::Error>>> + Send + 'static {
+    let link = backend.resource_link_url(uri, kind);
+    backend
+        .has_resource(uri, kind)
+        .map_ok(move |exists| link.filter(|_| exists))
+}
+
+/// Collects the [`Solutions`] of every problem in `document`, e.g. for generating an answer
+/// key, via [`get_solutions`](FtmlBackend::get_solutions) (one after the other).
+///
+/// Problems are returned in document order (depth-first); i.e. every problem directly
+/// precedes its sub-problems, which in turn precede the next problem. Fails if the
+/// solutions of any problem can not be obtained.
+pub fn answer_key<B: FtmlBackend + ?Sized>(
+    backend: &B,
+    document: &Document,
+) -> impl Future<Output = Result<Vec<(DocumentElementUri, Solutions)>, BackendError<B::Error>>>
++ Send
++ 'static {
+    let requests = document
+        .dfs()
+        .filter_map(|e| match e {
+            DocumentElementRef::Problem(p) => Some(p.uri.clone()),
+            _ => None,
+        })
+        .map(|uri| (uri.clone(), backend.get_solutions(uri)))
+        .collect::<Vec<_>>();
+    async move {
+        let mut key = Vec::with_capacity(requests.len());
+        for (uri, solutions) in requests {
+            key.push((uri, solutions.await?));
+        }
+        Ok(key)
+    }
+}
+
+/// Word count and estimated reading time of a document; see [`reading_stats`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct ReadingStats {
+    /// The number of words in the document's paragraphs.
+    pub words: usize,
+    /// The number of paragraphs whose text could be obtained.
+    pub paragraphs: usize,
+    /// The estimated reading time in minutes (rounded up).
+    pub minutes: usize,
+}
+impl ReadingStats {
+    /// A common estimate for the reading speed of (non-technical) prose.
+    pub const DEFAULT_WORDS_PER_MINUTE: usize = 200;
+}
+
+/// Counts the words in `document` and estimates its reading time at `words_per_minute`
+/// (see [`ReadingStats::DEFAULT_WORDS_PER_MINUTE`]), e.g. for "5 min read" badges, by
+/// fetching the fragment of every paragraph via [`get_fragment`](FtmlBackend::get_fragment)
+/// (one after the other).
+///
+/// Only the text of the document's [`LogicalParagraph`]s counts (paragraphs nested in other
+/// paragraphs are part of their parent's fragment, and hence not fetched separately), as
+/// extracted by [`html_to_prose`](utils::text::html_to_prose), which drops math and code; see
+/// [`word_count`](utils::text::word_count) for what counts as a word. Consequently, section
+/// titles, problems (including the paragraphs in them) and any text outside of paragraphs are
+/// excluded as well. Paragraphs without (available) HTML are skipped; any other error is
+/// returned.
+pub fn reading_stats<B: FtmlBackend + ?Sized>(
+    backend: &B,
+    document: &Document,
+    words_per_minute: usize,
+) -> impl Future<Output = Result<ReadingStats, BackendError<B::Error>>> + Send + 'static {
+    fn collect<'d>(elements: &'d [DocumentElement], out: &mut Vec<&'d LogicalParagraph>) {
+        for e in elements {
+            match e {
+                DocumentElement::Paragraph(p) => out.push(p),
+                DocumentElement::Problem(_) => (),
+                e => collect(e.children_lt().unwrap_or_default(), out),
+            }
+        }
+    }
+    let mut paragraphs = Vec::new();
+    collect(&document.elements, &mut paragraphs);
+    let requests = paragraphs
+        .into_iter()
+        .map(|p| backend.get_fragment(p.uri.clone().into(), None))
+        .collect::<Vec<_>>();
+    async move {
+        let mut stats = ReadingStats::default();
+        for fragment in requests {
+            match fragment.await {
+                Ok((html, _, _)) => {
+                    stats.words += utils::text::word_count(&utils::text::html_to_prose(&html));
+                    stats.paragraphs += 1;
+                }
+                Err(
+                    BackendError::NotFound(_)
+                    | BackendError::HtmlNotFound
+                    | BackendError::NoFragment,
+                ) => (),
+                Err(e) => return Err(e),
+            }
+        }
+        stats.minutes = stats.words.div_ceil(words_per_minute.max(1));
+        Ok(stats)
+    }
+}
+
+/// A [`NotationMap`] with all notations of the given `leaves`, obtained via
+/// [`get_notations`](FtmlBackend::get_notations) (one after the other); i.e. a
+/// [`NotationResolver`](ftml_ontology::narrative::elements::notations::NotationResolver)
+/// backed by `backend`, e.g. for [`Term::to_mathml`].
+///
+/// Notations are kept in the order returned by the backend, the first being the default.
+/// Leaves the backend does not know simply have no notations.
+pub fn prefetch_notations<B: FtmlBackend + ?Sized>(
+    backend: &B,
+    leaves: impl IntoIterator<Item = LeafUri>,
+) -> impl Future<Output = Result<NotationMap, BackendError<B::Error>>> + Send + 'static {
+    let requests = leaves
+        .into_iter()
+        .map(|leaf| (leaf.clone(), backend.get_notations(leaf)))
+        .collect::<Vec<_>>();
+    async move {
+        let mut map = NotationMap::default();
+        for (leaf, notations) in requests {
+            match notations.await {
+                Ok(notations) => map.extend(notations.into_iter().map(|(_, n)| (leaf.clone(), n))),
+                Err(BackendError::NotFound(_)) => (),
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(map)
+    }
+}
+
+/// The glyph of the symbol `uri` (see [`get_symbol_glyph`](FtmlBackend::get_symbol_glyph)),
+/// i.e. `<math>...</math>`, given its `notations` in the order returned by the backend.
+///
+/// The first notation with an [`op`](Notation::op) form is used (in that form); if there is
+/// none, the first notation (without arguments); and if there are no notations at all, the
+/// name of the symbol (as `<mi>name</mi>`). See [`Term::to_mathml`].
+#[must_use]
+pub fn symbol_glyph(uri: &SymbolUri, notations: impl IntoIterator<Item = Notation>) -> Box<str> {
+    let mut notations = notations.into_iter().collect::<Vec<_>>();
+    let mut map = NotationMap::default();
+    if !notations.is_empty() {
+        let default = notations
+            .iter()
+            .position(|n| n.op.is_some())
+            .unwrap_or_default();
+        map.insert(LeafUri::Symbol(uri.clone()), notations.swap_remove(default));
+    }
+    let mathml = Term::from(uri.clone()).to_mathml(&map);
+    format!("<math>{mathml}</math>").into_boxed_str()
+}
+
+/// Parses the `results` of a search endpoint into [`Uri`]s, keeping only those of the given
+/// `kinds` (or all, if `kinds` is empty) in their original order; unparsable results are
+/// skipped (and logged).
+pub fn parse_search_results<S: AsRef<str>>(
+    results: impl IntoIterator<Item = S>,
+    kinds: &[UriKind],
+) -> Vec<Uri> {
+    results
+        .into_iter()
+        .filter_map(|s| match s.as_ref().parse::<Uri>() {
+            Ok(uri) => Some(uri),
+            Err(e) => {
+                tracing::warn!("invalid search result {}: {e}", s.as_ref());
+                None
+            }
+        })
+        .filter(|uri| kinds.is_empty() || kinds.contains(&uri.kind()))
+        .collect()
+}
+
+/// The default implementation of [`FtmlBackend::get_symbol_glyph`], deriving the glyph from
+/// the [notations](FtmlBackend::get_notations) of `uri` via [`symbol_glyph`].
+pub fn notations_glyph<B: FtmlBackend + ?Sized>(
+    backend: &B,
+    uri: SymbolUri,
+) -> impl Future<Output = Result<Box<str>, BackendError<B::Error>>> + Send + 'static {
+    backend
+        .get_notations(LeafUri::Symbol(uri.clone()))
+        .map(move |r| match r {
+            Ok(notations) => Ok(symbol_glyph(&uri, notations.into_iter().map(|(_, n)| n))),
+            Err(BackendError::NotFound(_)) => Ok(symbol_glyph(&uri, [])),
+            Err(e) => Err(e),
+        })
+}
+
+/// The default implementation of [`FtmlBackend::get_language_variants`].
+pub fn probe_language_variants<B: FtmlBackend + ?Sized>(
+    backend: &B,
+    uri: DocumentUri,
+) -> impl Future<Output = Result<Vec<Language>, BackendError<B::Error>>> + Send + 'static {
+    let mut chain = vec![uri.language];
+    if uri.language != Language::default() {
+        chain.push(Language::default());
+    }
+    let probes = chain
+        .into_iter()
+        .map(|language| {
+            let uri = DocumentUri {
+                language,
+                ..uri.clone()
+            };
+            (language, backend.get_document(uri))
+        })
+        .collect::<Vec<_>>();
+    async move {
+        let mut variants = Vec::new();
+        for (language, probe) in probes {
+            match probe.await {
+                Ok(_) => variants.push(language),
+                Err(BackendError::NotFound(_)) => (),
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(variants)
+    }
+}
+
+/// The default implementation of [`FtmlBackend::get_definition_chain`].
+#[allow(clippy::type_complexity)]
+pub fn definition_chain<B: FtmlBackend + Sync + ?Sized>(
+    backend: &B,
+    uri: SymbolUri,
+    max_depth: usize,
+) -> impl Future<Output = Result<Vec<(SymbolUri, Option<Term>)>, BackendError<B::Error>>> + Send + '_
+{
+    use ftml_ontology::terms::IsTerm;
+    async move {
+        let mut chain: Vec<(SymbolUri, Option<Term>)> = Vec::new();
+        let mut queue = std::collections::VecDeque::from([(uri, 0)]);
+        while let Some((uri, depth)) = queue.pop_front() {
+            let symbol = backend.get_symbol(uri.clone()).await?;
+            let definiens =
+                either::for_both!(&symbol, s => s.data.df.checked_or_parsed()).map(|(t, _)| t);
+            if depth < max_depth
+                && let Some(definiens) = &definiens
+            {
+                for s in definiens.symbols() {
+                    let known = *s == uri
+                        || chain.iter().any(|(u, _)| u == s)
+                        || queue.iter().any(|(u, _)| u == s);
+                    if !known {
+                        queue.push_back((s.clone(), depth + 1));
+                    }
+                }
+            }
+            chain.push((uri, definiens));
+        }
+        Ok(chain)
+    }
+}
+
+/// The default implementation of [`FtmlBackend::get_outline_previews`].
+#[allow(clippy::type_complexity)]
+pub fn outline_previews<B: FtmlBackend + Sync + ?Sized>(
+    backend: &B,
+    uri: DocumentUri,
+) -> impl Future<
+    Output = Result<Vec<(DocumentElementUri, String, Option<String>)>, BackendError<B::Error>>,
+> + Send
++ '_ {
+    fn collect<'d>(
+        elements: &'d [DocumentElement],
+        out: &mut Vec<(&'d Section, Option<&'d LogicalParagraph>)>,
+    ) {
+        for e in elements {
+            match e {
+                DocumentElement::Section(s) => out.push((s, first_paragraph(&s.children))),
+                e => collect(e.children_lt().unwrap_or_default(), out),
+            }
+        }
+    }
+    fn first_paragraph(elements: &[DocumentElement]) -> Option<&LogicalParagraph> {
+        elements.iter().find_map(|e| match e {
+            DocumentElement::Paragraph(p) => Some(p),
+            e => first_paragraph(e.children_lt().unwrap_or_default()),
+        })
+    }
+
+    let document = backend.get_document(uri);
+    async move {
+        let document = document.await?;
+        let mut sections = Vec::new();
+        collect(&document.elements, &mut sections);
+        let sections = sections
+            .into_iter()
+            .map(|(s, p)| {
+                (
+                    s.uri.clone(),
+                    s.title
+                        .as_deref()
+                        .map(utils::text::html_to_text)
+                        .unwrap_or_default(),
+                    p.map(|p| p.uri.clone()),
+                )
+            })
+            .collect::<Vec<_>>();
+        drop(document);
+        let mut previews = Vec::with_capacity(sections.len());
+        for (uri, title, paragraph) in sections {
+            let sentence = if let Some(paragraph) = paragraph {
+                match backend.get_fragment(paragraph.into(), None).await {
+                    Ok((html, _, _)) => {
+                        utils::text::first_sentence(&utils::text::html_to_text(&html))
+                            .map(ToString::to_string)
+                    }
+                    Err(
+                        BackendError::NotFound(_)
+                        | BackendError::HtmlNotFound
+                        | BackendError::NoFragment,
+                    ) => None,
+                    Err(e) => return Err(e),
+                }
+            } else {
+                None
+            };
+            previews.push((uri, title, sentence));
+        }
+        Ok(previews)
+    }
+}
+
+/// Errors when [composing](compose_morphisms) two [`Morphism`]s.
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum CompositionError<E: std::fmt::Debug + std::fmt::Display> {
+    /// The codomain of `first` (i.e. the module containing it) is not (transitively)
+    /// included in the domain of `second`.
+    #[error("codomain {codomain} of {first} is not included in the domain {domain} of {second}")]
+    Incompatible {
+        first: SymbolUri,
+        codomain: ModuleUri,
+        second: SymbolUri,
+        domain: ModuleUri,
+    },
+    #[error(transparent)]
+    Backend(#[from] BackendError<E>),
+}
+
+/// The composition `second ∘ first` of two morphisms `first: A → B` and `second: B' → C`
+/// (see [`Morphism::compose`]), where the codomain `B` of a morphism is the module
+/// containing it. The modules `A` and `B'` (and everything they import) are obtained via
+/// [`get_module`](FtmlBackend::get_module); the symbols of their nested modules and
+/// structures are composed like all others.
+///
+/// Since the requests depend on each other, the returned future borrows the backend; it is
+/// [`Send`] if `B` is [`Sync`].
+///
+/// # Errors
+/// [`Incompatible`](CompositionError::Incompatible) if `B` is not `B'` or (transitively)
+/// included in it; or if any of the modules can not be obtained.
+pub fn compose_morphisms<'b, B: FtmlBackend + ?Sized>(
+    backend: &'b B,
+    first: &Morphism,
+    second: &Morphism,
+) -> impl Future<Output = Result<Morphism, CompositionError<B::Error>>> + 'b {
+    use ftml_ontology::{
+        domain::{HasDeclarations, declarations::AnyDeclarationRef},
+        utils::TreeIter,
+    };
+
+    /// All modules (transitively) included in `module`, and their symbols (including those
+    /// of nested modules and structures)
+    async fn closure<B: FtmlBackend + ?Sized>(
+        backend: &B,
+        module: ModuleUri,
+    ) -> Result<(Vec<ModuleUri>, Vec<SymbolUri>), BackendError<B::Error>> {
+        let mut modules = Vec::new();
+        let mut symbols = Vec::new();
+        let mut todos = vec![module];
+        while let Some(uri) = todos.pop() {
+            if modules.contains(&uri) {
+                continue;
+            }
+            let module = backend.get_module(uri.clone()).await?;
+            for d in module.declarations().dfs() {
+                match d {
+                    AnyDeclarationRef::Import { uri, .. } => todos.push(uri.clone()),
+                    AnyDeclarationRef::Symbol(s) => symbols.push(s.uri.clone()),
+                    _ => (),
+                }
+            }
+            modules.push(uri);
+        }
+        Ok((modules, symbols))
+    }
+
+    let first = first.clone();
+    let second = second.clone();
+    async move {
+        let codomain = first.uri.module.clone();
+        let (included, translated) = closure(backend, second.domain.clone()).await?;
+        if !included.contains(&codomain) {
+            return Err(CompositionError::Incompatible {
+                first: first.uri,
+                codomain,
+                second: second.uri,
+                domain: second.domain,
+            });
+        }
+        let (_, symbols) = closure(backend, first.domain.clone()).await?;
+        Ok(first.compose(&second, symbols, &translated))
+    }
 }
 
 #[cfg(all(feature = "server_fn", feature = "cached"))]
@@ -393,9 +1137,20 @@ impl From<crate::utils::async_cache::CacheError> for ::server_fn::error::ServerF
 #[cfg(feature = "server_fn")]
 pub trait FlamsBackend {
     fn document_link_url(&self, uri: &DocumentUri) -> String;
-    fn resource_link_url(&self, uri: &DocumentUri, kind: &'static str) -> Option<String>;
+    fn resource_link_url(&self, uri: &DocumentUri, kind: ResourceKind) -> Option<String>;
     fn stripped(&self) -> bool;
 
+    /// See [`FtmlBackend::has_resource`]
+    fn has_resource(
+        &self,
+        uri: &DocumentUri,
+        kind: ResourceKind,
+    ) -> impl Future<Output = Result<bool, BackendError<server_fn::error::ServerFnErrorErr>>>
+    + Send
+    + 'static {
+        std::future::ready(Ok(self.resource_link_url(uri, kind).is_some()))
+    }
+
     /// `/content/fragment`
     #[allow(clippy::too_many_arguments)]
     fn get_fragment(
@@ -508,6 +1263,26 @@ pub trait FlamsBackend {
     + Send
     + 'static;
 
+    /// See [`FtmlBackend::capabilities`]
+    fn capabilities(
+        &self,
+    ) -> impl Future<Output = Result<Capabilities, BackendError<server_fn::error::ServerFnErrorErr>>>
+    + Send
+    + 'static {
+        std::future::ready(Ok(Capabilities::default()))
+    }
+
+    /// See [`FtmlBackend::get_source`]
+    fn get_source(
+        &self,
+        _uri: DocumentUri,
+    ) -> impl Future<
+        Output = Result<(String, SourceFormat), BackendError<server_fn::error::ServerFnErrorErr>>,
+    > + Send
+    + 'static {
+        std::future::ready(Err(BackendError::ToDo("get_source".to_string())))
+    }
+
     /// `/content/los`
     #[allow(clippy::too_many_arguments)]
     fn get_logical_paragraphs(
@@ -550,9 +1325,17 @@ where
         <Self as FlamsBackend>::document_link_url(self, uri)
     }
     #[inline]
-    fn resource_link_url(&self, uri: &DocumentUri, kind: &'static str) -> Option<String> {
+    fn resource_link_url(&self, uri: &DocumentUri, kind: ResourceKind) -> Option<String> {
         <Self as FlamsBackend>::resource_link_url(self, uri, kind)
     }
+    #[inline]
+    fn has_resource(
+        &self,
+        uri: &DocumentUri,
+        kind: ResourceKind,
+    ) -> impl Future<Output = Result<bool, BackendError<Self::Error>>> + Send + 'static {
+        <Self as FlamsBackend>::has_resource(self, uri, kind)
+    }
 
     fn check_term(
         &self,
@@ -643,6 +1426,23 @@ where
         }
     }
 
+    #[inline]
+    fn capabilities(
+        &self,
+    ) -> impl Future<Output = Result<Capabilities, BackendError<Self::Error>>> + Send + 'static
+    {
+        <Self as FlamsBackend>::capabilities(self)
+    }
+
+    #[inline]
+    fn get_source(
+        &self,
+        uri: DocumentUri,
+    ) -> impl Future<Output = Result<(String, SourceFormat), BackendError<Self::Error>>> + Send + 'static
+    {
+        <Self as FlamsBackend>::get_source(self, uri)
+    }
+
     fn get_solutions(
         &self,
         uri: DocumentElementUri,
@@ -694,3 +1494,1048 @@ where
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ftml_ontology::{
+        domain::{
+            declarations::{Declaration, symbols::SymbolData},
+            modules::{ModuleData, NestedModule},
+        },
+        narrative::{
+            DataBuffer, DocumentRange,
+            documents::{DocumentData, DocumentKind, DocumentStyles},
+            elements::{
+                Problem,
+                paragraphs::{ParagraphFormatting, ParagraphKind},
+                problems::{ProblemData, SolutionData},
+            },
+        },
+        terms::TermContainer,
+        utils::SourceRange,
+    };
+    use std::future::ready;
+
+    /// Only knows the English and German variants of every document, two archives and a
+    /// single module (see [`mock_module`]); every search finds the same fixed results. The
+    /// module `flaky` fails with a connection error on the first request (see [`FLAKY`]).
+    ///
+    /// [`Send`] and [`Sync`] iff `T` is: [`MOCK`] is both, so that trait methods requiring
+    /// `Self: Sync` can be tested on it, whereas `MockBackend<Rc<()>>` is deliberately neither.
+    struct MockBackend<T = ()>(std::marker::PhantomData<T>);
+    const MOCK: MockBackend = MockBackend(std::marker::PhantomData);
+
+    /// Whether the module `flaky` has been requested before.
+    static FLAKY: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+
+    fn unsupported<T>() -> impl Future<Output = Result<T, BackendError<String>>> + Send + 'static {
+        ready(Err(BackendError::ToDo("mock".to_string())))
+    }
+
+    impl<T: 'static> FtmlBackend for MockBackend<T> {
+        type Error = String;
+        fn document_link_url(&self, _: &DocumentUri) -> String {
+            String::new()
+        }
+        fn resource_link_url(&self, uri: &DocumentUri, kind: ResourceKind) -> Option<String> {
+            Some(format!("mock:{uri}{}", kind.url_suffix()))
+        }
+        /// All optional methods are supported.
+        fn capabilities(
+            &self,
+        ) -> impl Future<Output = Result<Capabilities, BackendError<Self::Error>>> + Send + 'static
+        {
+            ready(Ok(Capabilities {
+                search: true,
+                list_archives: true,
+                source: true,
+            }))
+        }
+        /// Pretends that there are no PDFs, like a `HEAD` request returning 404.
+        fn has_resource(
+            &self,
+            _: &DocumentUri,
+            kind: ResourceKind,
+        ) -> impl Future<Output = Result<bool, BackendError<Self::Error>>> + Send + 'static
+        {
+            ready(Ok(kind != ResourceKind::Pdf))
+        }
+        fn check_term(
+            &self,
+            _: &[ModuleUri],
+            _: either::Either<&Term, &DocumentElementUri>,
+            _: either::Either<&Term, &TermPath>,
+        ) -> impl Future<Output = Result<BackendCheckResult, BackendError<Self::Error>>>
+        + Send
+        + use<>
+        + 'static {
+            unsupported()
+        }
+        fn get_fragment(
+            &self,
+            uri: Uri,
+            _: Option<NarrativeUri>,
+        ) -> impl Future<
+            Output = Result<(Box<str>, Box<[Css]>, bool), BackendError<Self::Error>>,
+        > + Send
+        + 'static {
+            ready(match uri {
+                Uri::DocumentElement(e) if e.name.to_string() == "intro/p" => Ok((
+                    "<div><p>Groups are <b>monoids</b> with inverses, cf. Lang. They are \
+                     <math><mi>G</mi></math> everywhere.</p></div>"
+                        .into(),
+                    Box::default(),
+                    false,
+                )),
+                uri => Err(BackendError::NotFound(uri)),
+            })
+        }
+        fn get_logical_paragraphs(
+            &self,
+            _: SymbolUri,
+            _: bool,
+        ) -> impl Future<
+            Output = Result<
+                Vec<(DocumentElementUri, ParagraphOrProblemKind)>,
+                BackendError<Self::Error>,
+            >,
+        > + Send
+        + 'static {
+            unsupported()
+        }
+        fn get_module(
+            &self,
+            uri: ModuleUri,
+        ) -> impl Future<Output = Result<ModuleLike, BackendError<Self::Error>>> + Send + 'static
+        {
+            ready(match uri.name.to_string().as_str() {
+                "module" => Ok(mock_module(uri).into()),
+                "magma" => Ok(theory(uri, &["op", "unit", "inv"], &[("ext", &["sq"])]).into()),
+                "middle" => Ok(theory(uri, &["mul", "one"], &[]).into()),
+                "target" => Ok(theory(uri, &["plus", "zero", "neg"], &[]).into()),
+                "flaky" if !FLAKY.swap(true, std::sync::atomic::Ordering::SeqCst) => {
+                    Err(BackendError::Connection("mock".to_string()))
+                }
+                "flaky" => Ok(mock_module(uri).into()),
+                _ => Err(BackendError::NotFound(uri.into())),
+            })
+        }
+        fn get_document(
+            &self,
+            uri: DocumentUri,
+        ) -> impl Future<Output = Result<Document, BackendError<Self::Error>>> + Send + 'static
+        {
+            ready(
+                if matches!(uri.language, Language::English | Language::German) {
+                    let elements = match uri.name.to_string().as_str() {
+                        "outline" => outline_elements(&uri),
+                        "exam" => exam_elements(&uri),
+                        _ => Box::default(),
+                    };
+                    Ok(DocumentData {
+                        uri,
+                        title: None,
+                        elements,
+                        styles: DocumentStyles::default(),
+                        top_section_level: SectionLevel::Section,
+                        kind: DocumentKind::Article,
+                    }
+                    .close())
+                } else {
+                    Err(BackendError::NotFound(uri.into()))
+                },
+            )
+        }
+        fn get_toc(
+            &self,
+            _: DocumentUri,
+        ) -> impl Future<
+            Output = Result<(Box<[Css]>, SectionLevel, Box<[TocElem]>), BackendError<Self::Error>>,
+        > + Send
+        + 'static {
+            unsupported()
+        }
+        fn get_document_html(
+            &self,
+            _: DocumentUri,
+            _: Option<NarrativeUri>,
+        ) -> impl Future<
+            Output = Result<(Box<str>, Box<[Css]>, bool), BackendError<Self::Error>>,
+        > + Send
+        + 'static {
+            unsupported()
+        }
+        /// A LaTeX file containing the document's name
+        fn get_source(
+            &self,
+            uri: DocumentUri,
+        ) -> impl Future<Output = Result<(String, SourceFormat), BackendError<Self::Error>>>
+        + Send
+        + 'static {
+            ready(
+                if matches!(uri.language, Language::English | Language::German) {
+                    Ok((
+                        format!("\\begin{{document}}\n{}\n\\end{{document}}\n", uri.name),
+                        SourceFormat::Tex,
+                    ))
+                } else {
+                    Err(BackendError::NotFound(uri.into()))
+                },
+            )
+        }
+        /// A single solution containing the problem's name
+        fn get_solutions(
+            &self,
+            uri: DocumentElementUri,
+        ) -> impl Future<Output = Result<Solutions, BackendError<Self::Error>>> + Send + 'static
+        {
+            ready(Ok(Solutions::merge(vec![SolutionData::Solution {
+                html: uri.name.to_string().into(),
+                answer_class: None,
+            }])))
+        }
+        /// Only knows the notations of [`mock_glyph_notations`].
+        fn get_notations(
+            &self,
+            uri: LeafUri,
+        ) -> impl Future<
+            Output = Result<Vec<(DocumentElementUri, Notation)>, BackendError<Self::Error>>,
+        > + Send
+        + 'static {
+            ready(match uri {
+                LeafUri::Symbol(s) if s.name().last() == "plus" => Ok(mock_glyph_notations()),
+                uri => Err(BackendError::NotFound(uri.into())),
+            })
+        }
+        fn list_archives(
+            &self,
+        ) -> impl Future<Output = Result<Vec<ArchiveId>, BackendError<Self::Error>>> + Send + 'static
+        {
+            ready(
+                ["smglom/sets", "courses/FAU/AI/course"]
+                    .into_iter()
+                    .map(|id| {
+                        id.parse()
+                            .map_err(|e| BackendError::Connection(format!("{e}")))
+                    })
+                    .collect(),
+            )
+        }
+        fn search(
+            &self,
+            _: &str,
+            kinds: &[UriKind],
+        ) -> impl Future<Output = Result<Vec<Uri>, BackendError<Self::Error>>> + Send + 'static
+        {
+            ready(Ok(parse_search_results(
+                [
+                    "http://example.com?a=smglom/sets&m=set&s=set",
+                    "http://example.com?a=smglom/sets&d=sets&l=en",
+                    "not a uri",
+                    "http://example.com?a=smglom/sets&m=set&s=emptyset",
+                ],
+                kinds,
+            )))
+        }
+    }
+
+    /// A problem `name` in the document `uri` without solutions.
+    fn mock_problem(
+        uri: &DocumentUri,
+        name: &str,
+        children: Vec<DocumentElement>,
+    ) -> DocumentElement {
+        DocumentElement::Problem(Problem {
+            uri: DocumentElementUri {
+                name: name.parse().expect("valid name"),
+                document: uri.clone(),
+            },
+            range: DocumentRange::default(),
+            children: children.into_boxed_slice(),
+            data: Box::new(ProblemData::new(
+                DataBuffer::default()
+                    .push(&Solutions::default())
+                    .expect("encodable"),
+            )),
+        })
+    }
+
+    fn exam_elements(uri: &DocumentUri) -> Box<[DocumentElement]> {
+        let problem = |name: &str, children| mock_problem(uri, name, children);
+        Box::new([
+            problem("first", Vec::new()),
+            DocumentElement::Section(Section {
+                range: DocumentRange::default(),
+                uri: DocumentElementUri {
+                    name: "part2".parse().expect("valid name"),
+                    document: uri.clone(),
+                },
+                title: None,
+                children: Box::new([problem("part2/second", Vec::new())]),
+                source: SourceRange::default(),
+            }),
+        ])
+    }
+
+    /// The symbol `name` in the module `http://example.com?a=archive&m=module`.
+    fn sym(name: &str) -> SymbolUri {
+        format!("http://example.com?a=archive&m=module&s={name}")
+            .parse()
+            .expect("valid uri")
+    }
+
+    /// A module containing a symbol `sym`, a structure `struct`, a nested module `nested` and
+    /// the symbols `defined := sym` and `cyclic := cyclic`.
+    fn mock_module(uri: ModuleUri) -> ftml_ontology::domain::modules::Module {
+        let symbol =
+            |name: &str| -> SymbolUri { format!("{uri}&s={name}").parse().expect("valid uri") };
+        let declarations: Box<[Declaration]> = Box::new([
+            Declaration::Symbol(Symbol {
+                uri: symbol("sym"),
+                data: Box::default(),
+            }),
+            Declaration::Symbol(Symbol {
+                uri: symbol("defined"),
+                data: Box::new(SymbolData {
+                    df: TermContainer::new(symbol("sym").into(), None),
+                    ..SymbolData::default()
+                }),
+            }),
+            Declaration::Symbol(Symbol {
+                uri: symbol("cyclic"),
+                data: Box::new(SymbolData {
+                    df: TermContainer::new(symbol("cyclic").into(), None),
+                    ..SymbolData::default()
+                }),
+            }),
+            Declaration::MathStructure(MathStructure {
+                uri: symbol("struct"),
+                elements: Box::default(),
+                macroname: None,
+                source: SourceRange::DEFAULT,
+            }),
+            Declaration::NestedModule(NestedModule {
+                uri: symbol("nested"),
+                declarations: Box::default(),
+                source: SourceRange::DEFAULT,
+            }),
+        ]);
+        ModuleData {
+            uri,
+            meta_module: None,
+            signature: None,
+            declarations,
+            source: SourceRange::DEFAULT,
+        }
+        .close()
+    }
+
+    /// A module containing (only) the given undefined symbols and nested modules (each
+    /// containing only the given undefined symbols).
+    fn theory(
+        uri: ModuleUri,
+        symbols: &[&str],
+        nested: &[(&str, &[&str])],
+    ) -> ftml_ontology::domain::modules::Module {
+        let undefined = |module: &ModuleUri, name: &&str| {
+            Declaration::Symbol(Symbol {
+                uri: format!("{module}&s={name}").parse().expect("valid uri"),
+                data: Box::default(),
+            })
+        };
+        let nested = nested.iter().map(|(name, symbols)| {
+            let module: ModuleUri = format!("{uri}/{name}").parse().expect("valid uri");
+            Declaration::NestedModule(NestedModule {
+                uri: format!("{uri}&s={name}").parse().expect("valid uri"),
+                declarations: symbols.iter().map(|s| undefined(&module, s)).collect(),
+                source: SourceRange::DEFAULT,
+            })
+        });
+        ModuleData {
+            declarations: symbols
+                .iter()
+                .map(|s| undefined(&uri, s))
+                .chain(nested)
+                .collect(),
+            uri,
+            meta_module: None,
+            signature: None,
+            source: SourceRange::DEFAULT,
+        }
+        .close()
+    }
+
+    fn outline_elements(uri: &DocumentUri) -> Box<[DocumentElement]> {
+        let elem = |name: &str| DocumentElementUri {
+            name: name.parse().expect("valid name"),
+            document: uri.clone(),
+        };
+        let paragraph = LogicalParagraph {
+            kind: ParagraphKind::Paragraph,
+            uri: elem("intro/p"),
+            formatting: ParagraphFormatting::Block,
+            range: DocumentRange::default(),
+            title: None,
+            styles: Box::default(),
+            premises: Box::default(),
+            binds_variables: Box::default(),
+            children: Box::default(),
+            fors: Box::default(),
+            steps: Box::default(),
+            source: SourceRange::default(),
+        };
+        let section = |name: &str, title: &str, children: Vec<DocumentElement>| {
+            DocumentElement::Section(Section {
+                range: DocumentRange::default(),
+                uri: elem(name),
+                title: Some(title.into()),
+                children: children.into_boxed_slice(),
+                source: SourceRange::default(),
+            })
+        };
+        Box::new([
+            DocumentElement::UseModule {
+                uri: "http://example.com?a=archive&m=module"
+                    .parse()
+                    .expect("valid uri"),
+                source: SourceRange::default(),
+            },
+            section(
+                "intro",
+                "<b>Intro</b>duction",
+                vec![DocumentElement::Paragraph(paragraph)],
+            ),
+            DocumentElement::SkipSection(Box::new([section(
+                "groups",
+                "Groups &amp; Monoids",
+                vec![section("groups/sub", "Nested", Vec::new())],
+            )])),
+        ])
+    }
+
+    #[tokio::test]
+    async fn outline_previews_two_sections() {
+        let uri: DocumentUri = "http://example.com?a=archive&d=outline&l=en"
+            .parse()
+            .expect("valid uri");
+        let previews = MOCK
+            .get_outline_previews(uri)
+            .await
+            .expect("mock backend does not fail");
+        let previews = previews
+            .into_iter()
+            .map(|(uri, title, sentence)| (uri.name.to_string(), title, sentence))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            previews,
+            vec![
+                (
+                    "intro".to_string(),
+                    "Introduction".to_string(),
+                    Some("Groups are monoids with inverses, cf. Lang.".to_string())
+                ),
+                ("groups".to_string(), "Groups & Monoids".to_string(), None),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn compose_two_morphisms() {
+        use ftml_ontology::domain::declarations::morphisms::{Assignment, Elaboration};
+        let symbol = |module: &str, name: &str| -> SymbolUri {
+            format!("http://example.com?a=archive&m={module}&s={name}")
+                .parse()
+                .expect("valid uri")
+        };
+        let morphism = |uri: SymbolUri, domain: &str, elements: Vec<(SymbolUri, Term)>| Morphism {
+            domain: symbol(domain, "x").module,
+            total: true,
+            elements: elements
+                .into_iter()
+                .map(|(original, df)| Assignment {
+                    original,
+                    morphism: uri.clone(),
+                    definiens: Some(df),
+                    refined_type: None,
+                    new_name: None,
+                    macroname: None,
+                    source: SourceRange::DEFAULT,
+                })
+                .collect(),
+            uri,
+            elaboration: Elaboration::default(),
+            source: SourceRange::DEFAULT,
+        };
+        // f: magma -> middle; op ↦ mul, unit ↦ one, inv unassigned, ext/sq ↦ mul
+        let f = morphism(
+            symbol("middle", "f"),
+            "magma",
+            vec![
+                (symbol("magma", "op"), symbol("middle", "mul").into()),
+                (symbol("magma", "unit"), symbol("middle", "one").into()),
+                (symbol("magma/ext", "sq"), symbol("middle", "mul").into()),
+            ],
+        );
+        // g: middle -> target; mul ↦ plus, one unassigned, f/inv ↦ neg
+        let g = morphism(
+            symbol("target", "g"),
+            "middle",
+            vec![
+                (symbol("middle", "mul"), symbol("target", "plus").into()),
+                (symbol("middle", "f/inv"), symbol("target", "neg").into()),
+            ],
+        );
+
+        let composed = compose_morphisms(&MOCK, &f, &g).await.expect("compatible");
+        assert_eq!(composed.uri, symbol("target", "g/f"));
+        assert_eq!(composed.domain, f.domain);
+        let assigned = composed
+            .elements
+            .iter()
+            .map(|a| (a.original.clone(), a.definiens.clone()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            assigned,
+            vec![
+                (symbol("magma", "op"), Some(symbol("target", "plus").into())),
+                (
+                    symbol("magma", "unit"),
+                    Some(symbol("target", "g/one").into())
+                ),
+                (symbol("magma", "inv"), Some(symbol("target", "neg").into())),
+                // assignments to symbols of nested modules are kept
+                (
+                    symbol("magma/ext", "sq"),
+                    Some(symbol("target", "plus").into())
+                ),
+            ]
+        );
+
+        // the codomain of g is not the domain of f
+        assert!(matches!(
+            compose_morphisms(&MOCK, &g, &f).await,
+            Err(CompositionError::Incompatible { codomain, .. }) if codomain == g.uri.module
+        ));
+    }
+
+    #[tokio::test]
+    async fn reading_stats_of_outline() {
+        let uri: DocumentUri = "http://example.com?a=archive&d=outline&l=en"
+            .parse()
+            .expect("valid uri");
+        let document = MOCK
+            .get_document(uri)
+            .await
+            .expect("mock backend does not fail");
+        let stats = reading_stats(&MOCK, &document, ReadingStats::DEFAULT_WORDS_PER_MINUTE)
+            .await
+            .expect("mock backend does not fail");
+        // the math in the paragraph does not count
+        assert_eq!(
+            stats,
+            ReadingStats {
+                words: 10,
+                paragraphs: 1,
+                minutes: 1
+            }
+        );
+        let stats = reading_stats(&MOCK, &document, 4)
+            .await
+            .expect("mock backend does not fail");
+        assert_eq!(stats.minutes, 3);
+    }
+
+    #[tokio::test]
+    async fn reading_stats_skip_problems() {
+        let uri: DocumentUri = "http://example.com?a=archive&d=outline&l=en"
+            .parse()
+            .expect("valid uri");
+        // the (countable) paragraph of the outline, but in a problem
+        let paragraph = outline_elements(&uri)
+            .iter()
+            .find_map(|e| match e {
+                DocumentElement::Section(s) => s.children.first().cloned(),
+                _ => None,
+            })
+            .expect("the outline has a paragraph");
+        assert!(matches!(paragraph, DocumentElement::Paragraph(_)));
+        let document = DocumentData {
+            uri: uri.clone(),
+            title: None,
+            elements: Box::new([mock_problem(&uri, "problem", vec![paragraph])]),
+            styles: DocumentStyles::default(),
+            top_section_level: SectionLevel::Section,
+            kind: DocumentKind::Article,
+        }
+        .close();
+        let stats = reading_stats(&MOCK, &document, ReadingStats::DEFAULT_WORDS_PER_MINUTE)
+            .await
+            .expect("mock backend does not fail");
+        assert_eq!(stats, ReadingStats::default());
+    }
+
+    #[tokio::test]
+    async fn language_variants_probe() {
+        let uri: DocumentUri = "http://example.com?a=archive&d=doc&l=de"
+            .parse()
+            .expect("valid uri");
+        let variants = MOCK
+            .get_language_variants(uri)
+            .await
+            .expect("mock backend does not fail");
+        assert_eq!(variants, vec![Language::German, Language::English]);
+
+        // only the fallback chain is probed
+        let uri: DocumentUri = "http://example.com?a=archive&d=doc&l=fr"
+            .parse()
+            .expect("valid uri");
+        let variants = MOCK
+            .get_language_variants(uri)
+            .await
+            .expect("mock backend does not fail");
+        assert_eq!(variants, vec![Language::English]);
+    }
+
+    /// The error type of the [`RemoteBackend`]s under test; never produced, since no
+    /// requests are sent.
+    #[cfg(any(feature = "wasm", feature = "reqwest"))]
+    #[derive(Debug)]
+    struct RemoteError(RequestError);
+    #[cfg(any(feature = "wasm", feature = "reqwest"))]
+    impl std::fmt::Display for RemoteError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            self.0.fmt(f)
+        }
+    }
+    #[cfg(any(feature = "wasm", feature = "reqwest"))]
+    impl From<RequestError> for RemoteError {
+        fn from(value: RequestError) -> Self {
+            Self(value)
+        }
+    }
+    #[cfg(any(feature = "wasm", feature = "reqwest"))]
+    impl std::str::FromStr for RemoteError {
+        type Err = BackendError<Self>;
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            Ok(Self(RequestError::Request(s.to_string())))
+        }
+    }
+
+    #[cfg(any(feature = "wasm", feature = "reqwest"))]
+    #[tokio::test]
+    async fn capability_probe() {
+        let mut backend: RemoteBackend<RemoteError> = RemoteBackend::new(
+            "http://localhost/fragment",
+            "http://localhost/document",
+            "http://localhost/notations",
+            "http://localhost/solutions",
+            "http://localhost/paragraphs",
+            "http://localhost/modules",
+            "http://localhost/documents",
+            "http://localhost/toc",
+            "http://localhost/check",
+        );
+        let caps = backend
+            .capabilities()
+            .await
+            .expect("derived without a request");
+        assert_eq!(caps, Capabilities::default());
+
+        // only the endpoints that are set are reported
+        backend.search_url = Some("http://localhost/search");
+        backend.resources_url = Some("http://localhost/resources");
+        let caps = backend
+            .capabilities()
+            .await
+            .expect("derived without a request");
+        assert_eq!(
+            caps,
+            Capabilities {
+                search: true,
+                list_archives: false,
+                source: true
+            }
+        );
+        // and the unsupported ones fail without sending a request
+        assert!(matches!(
+            backend.list_archives().await,
+            Err(BackendError::ToDo(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn archive_listing() {
+        let archives = MOCK
+            .list_archives()
+            .await
+            .expect("mock backend knows its archives");
+        assert_eq!(
+            archives.iter().map(ToString::to_string).collect::<Vec<_>>(),
+            ["smglom/sets", "courses/FAU/AI/course"]
+        );
+        assert_eq!(archives[1].steps().count(), 4);
+    }
+
+    #[tokio::test]
+    async fn search_by_kind() {
+        let hits = |kinds: &'static [UriKind]| async move {
+            MOCK.search("set", kinds)
+                .await
+                .expect("mock backend does not fail")
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            hits(&[UriKind::Symbol]).await,
+            [
+                "http://example.com?a=smglom/sets&m=set&s=set",
+                "http://example.com?a=smglom/sets&m=set&s=emptyset"
+            ]
+        );
+        assert_eq!(hits(&[]).await.len(), 3);
+        assert!(hits(&[UriKind::Module]).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn answer_key_in_document_order() {
+        let uri: DocumentUri = "http://example.com?a=archive&d=exam&l=en"
+            .parse()
+            .expect("valid uri");
+        let document = MOCK
+            .get_document(uri)
+            .await
+            .expect("mock backend does not fail");
+        let key = answer_key(&MOCK, &document)
+            .await
+            .expect("mock backend does not fail");
+        let key = key
+            .iter()
+            .map(|(uri, solutions)| {
+                (
+                    uri.name.to_string(),
+                    solutions
+                        .inner()
+                        .iter()
+                        .map(|s| match s {
+                            SolutionData::Solution { html, .. } => html.to_string(),
+                            _ => String::new(),
+                        })
+                        .collect::<Vec<_>>(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            key,
+            vec![
+                ("first".to_string(), vec!["first".to_string()]),
+                ("part2/second".to_string(), vec!["part2/second".to_string()]),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn declaration_of_unknown_kind() {
+        let decl = MOCK
+            .get_declaration(sym("struct"))
+            .await
+            .expect("structure exists");
+        assert!(matches!(decl, AnyDeclaration::MathStructure(_)));
+        assert_eq!(decl.uri(), &sym("struct"));
+        assert_eq!(decl.as_ref().kind_name(), "structure");
+        assert!(matches!(
+            MOCK.get_declaration(sym("sym")).await,
+            Ok(AnyDeclaration::Symbol(_))
+        ));
+        assert!(matches!(
+            MOCK.get_declaration(sym("nested")).await,
+            Ok(AnyDeclaration::NestedModule(_))
+        ));
+        assert!(matches!(
+            MOCK.get_declaration(sym("missing")).await,
+            Err(BackendError::NotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn definition_chain_to_primitive() {
+        let chain = MOCK
+            .get_definition_chain(sym("defined"), 10)
+            .await
+            .expect("symbols exist");
+        assert_eq!(
+            chain,
+            vec![
+                (sym("defined"), Some(Term::from(sym("sym")))),
+                (sym("sym"), None)
+            ]
+        );
+
+        let chain = MOCK
+            .get_definition_chain(sym("defined"), 0)
+            .await
+            .expect("symbol exists");
+        assert_eq!(chain.len(), 1);
+
+        let chain = MOCK
+            .get_definition_chain(sym("cyclic"), 10)
+            .await
+            .expect("symbol exists");
+        assert_eq!(
+            chain,
+            vec![(sym("cyclic"), Some(Term::from(sym("cyclic"))))]
+        );
+    }
+
+    #[tokio::test]
+    async fn absent_resources() {
+        let uri: DocumentUri = "http://example.com?a=archive&d=doc&l=en"
+            .parse()
+            .expect("valid uri");
+        assert!(MOCK.resource_link_url(&uri, ResourceKind::Pdf).is_some());
+        let pdf = available_resource_link(&MOCK, &uri, ResourceKind::Pdf)
+            .await
+            .expect("mock backend does not fail");
+        assert_eq!(pdf, None);
+        let source = available_resource_link(&MOCK, &uri, ResourceKind::Source)
+            .await
+            .expect("mock backend does not fail");
+        assert!(source.is_some_and(|s| s.ends_with("&format=tex")));
+    }
+
+    #[tokio::test]
+    async fn document_sources() {
+        let uri = |lang: &str| -> DocumentUri {
+            format!("http://example.com?a=archive&d=doc&l={lang}")
+                .parse()
+                .expect("valid uri")
+        };
+        let (source, format) = MOCK
+            .get_source(uri("en"))
+            .await
+            .expect("mock backend does not fail");
+        assert_eq!(format, SourceFormat::Tex);
+        assert_eq!(source, "\\begin{document}\ndoc\n\\end{document}\n");
+        assert!(matches!(
+            MOCK.get_source(uri("fr")).await,
+            Err(BackendError::NotFound(_))
+        ));
+
+        assert_eq!(SourceFormat::from_extension("TEX"), SourceFormat::Tex);
+        assert_eq!(SourceFormat::from_extension("md"), SourceFormat::Markdown);
+        assert_eq!(SourceFormat::from_extension("docx"), SourceFormat::Other);
+        assert_eq!(
+            SourceFormat::from_mime("text/x-tex; charset=utf-8"),
+            Some(SourceFormat::Tex)
+        );
+        assert_eq!(SourceFormat::from_mime("text/plain"), None);
+    }
+
+    #[test]
+    fn resource_kinds() {
+        assert_eq!(
+            ResourceKind::ALL.map(ResourceKind::url_suffix),
+            [
+                "&format=pdf",
+                "&format=tex",
+                "&format=html",
+                "&format=omdoc"
+            ]
+        );
+        for kind in ResourceKind::ALL {
+            assert_eq!(
+                ResourceKind::from_name(&kind.as_str().to_uppercase()),
+                Some(kind)
+            );
+        }
+        assert_eq!(ResourceKind::from_name("docx"), None);
+    }
+
+    #[cfg(feature = "cached")]
+    impl From<crate::utils::async_cache::CacheError> for String {
+        fn from(_: crate::utils::async_cache::CacheError) -> Self {
+            "cache error".to_string()
+        }
+    }
+
+    /// A notation without an operator form followed by one with `<mo>+</mo>`.
+    fn mock_glyph_notations() -> Vec<(DocumentElementUri, Notation)> {
+        use ftml_ontology::narrative::elements::notations::{NodeOrText, NotationNode};
+        let uri = |name: &str| -> DocumentElementUri {
+            format!("http://example.com?a=archive&d=doc&l=en&e={name}")
+                .parse()
+                .expect("valid uri")
+        };
+        let spec = "ii".parse().expect("valid argument spec");
+        let plain = Notation {
+            op: None,
+            ..Notation::synthesize("plus", &spec)
+        };
+        let infix = Notation {
+            op: Some(NotationNode {
+                tag: "mo".parse().expect("valid id"),
+                attributes: Box::default(),
+                children: Box::new([NodeOrText::Text("+".into())]),
+            }),
+            ..Notation::synthesize("plus", &spec)
+        };
+        vec![(uri("plain"), plain), (uri("infix"), infix)]
+    }
+
+    #[tokio::test]
+    async fn symbol_glyphs() {
+        let glyph = MOCK
+            .get_symbol_glyph(sym("plus"))
+            .await
+            .expect("plus has notations");
+        assert_eq!(&*glyph, "<math><mo>+</mo></math>");
+        // unknown symbols fall back to their name
+        let glyph = MOCK
+            .get_symbol_glyph(sym("zero"))
+            .await
+            .expect("falls back to the name");
+        assert_eq!(&*glyph, "<math><mi>zero</mi></math>");
+    }
+
+    #[cfg(feature = "cached")]
+    #[tokio::test]
+    async fn cached_backend_forgets_errors() {
+        let uri: ModuleUri = "http://example.com?a=archive&m=flaky"
+            .parse()
+            .expect("valid uri");
+        let backend = MOCK.cached();
+        assert!(backend.get_module(uri.clone()).await.is_err());
+        // the error is cached...
+        assert!(backend.get_module(uri.clone()).await.is_err());
+        // ...until forgotten
+        backend.forget_errors();
+        let m = backend.get_module(uri.clone()).await.expect("succeeds now");
+        // results are kept
+        backend.forget_errors();
+        assert_eq!(backend.get_module(uri).await.expect("cached"), m);
+    }
+
+    #[cfg(feature = "cached")]
+    #[tokio::test]
+    async fn cache_export_round_trip() {
+        let module: ModuleUri = "http://example.com?a=archive&m=module"
+            .parse()
+            .expect("valid uri");
+        let document: DocumentUri = "http://example.com?a=archive&d=outline&l=en"
+            .parse()
+            .expect("valid uri");
+        let missing: DocumentUri = "http://example.com?a=archive&d=outline&l=fr"
+            .parse()
+            .expect("valid uri");
+        let fragment: Uri = "http://example.com?a=archive&d=outline&l=en&e=intro/p"
+            .parse()
+            .expect("valid uri");
+
+        let mut backend = MOCK.cached();
+        let m = backend.get_module(module.clone()).await.expect("exists");
+        let d = backend
+            .get_document(document.clone())
+            .await
+            .expect("exists");
+        let f = backend
+            .get_fragment(fragment.clone(), None)
+            .await
+            .expect("exists");
+        assert!(backend.get_document(missing).await.is_err());
+
+        let blob = backend.export().expect("encodable");
+        backend.clear();
+        // the error for `missing` is not exported
+        assert_eq!(backend.import(&blob).expect("valid export"), 3);
+        // already cached
+        assert_eq!(backend.import(&blob).expect("valid export"), 0);
+        assert_eq!(backend.get_module(module).await.expect("cached"), m);
+        assert_eq!(backend.get_document(document).await.expect("cached"), d);
+        assert_eq!(
+            backend.get_fragment(fragment, None).await.expect("cached"),
+            f
+        );
+
+        backend.clear();
+        assert!(matches!(
+            backend.import(&blob[..blob.len() - 2]),
+            Err(ftml_ontology::narrative::DecodeError::Truncated)
+        ));
+        assert!(backend.import(&[]).is_err());
+    }
+
+    /// Records the names and `uri` fields of all created spans.
+    #[cfg(all(feature = "cached", feature = "trace-spans"))]
+    #[derive(Clone, Default)]
+    struct SpanRecorder(std::sync::Arc<std::sync::Mutex<Vec<(&'static str, Option<String>)>>>);
+    #[cfg(all(feature = "cached", feature = "trace-spans"))]
+    impl<S: tracing::Subscriber> tracing_subscriber::Layer<S> for SpanRecorder {
+        fn on_new_span(
+            &self,
+            span: &tracing::span::Attributes<'_>,
+            _: &tracing::span::Id,
+            _: tracing_subscriber::layer::Context<'_, S>,
+        ) {
+            struct UriField(Option<String>);
+            impl tracing::field::Visit for UriField {
+                fn record_debug(
+                    &mut self,
+                    field: &tracing::field::Field,
+                    value: &dyn std::fmt::Debug,
+                ) {
+                    if field.name() == "uri" {
+                        self.0 = Some(format!("{value:?}"));
+                    }
+                }
+            }
+            let mut uri = UriField(None);
+            span.record(&mut uri);
+            self.0
+                .lock()
+                .expect("not poisoned")
+                .push((span.metadata().name(), uri.0));
+        }
+    }
+
+    #[cfg(all(feature = "cached", feature = "trace-spans"))]
+    #[test]
+    fn cached_backend_spans() {
+        let uri: ModuleUri = "http://example.com?a=archive&m=module"
+            .parse()
+            .expect("valid uri");
+        let backend = MOCK.cached();
+        use tracing_subscriber::layer::SubscriberExt;
+        let recorder = SpanRecorder::default();
+        let subscriber = tracing_subscriber::registry().with(recorder.clone());
+        tracing::subscriber::with_default(subscriber, || {
+            let _ = futures::executor::block_on(backend.get_module(uri.clone()));
+        });
+        let spans = recorder.0.lock().expect("not poisoned");
+        assert!(
+            spans.contains(&("CachedBackend::get_module", Some(uri.to_string()))),
+            "{spans:?}"
+        );
+    }
+
+    #[cfg(feature = "wasm")]
+    #[test]
+    fn local_adapter_is_send_backend() {
+        struct Local;
+        impl GlobalBackend for Local {
+            type Error = String;
+            type Backend = LocalBackendAdapter<MockBackend<std::rc::Rc<()>>>;
+            fn get() -> &'static Self::Backend {
+                static BACKEND: std::sync::LazyLock<
+                    LocalBackendAdapter<MockBackend<std::rc::Rc<()>>>,
+                > = std::sync::LazyLock::new(|| {
+                    LocalBackendAdapter::new(MockBackend(std::marker::PhantomData))
+                });
+                &BACKEND
+            }
+        }
+        const fn assert_send_backend<B: SendBackend>() {}
+        assert_send_backend::<Local>();
+        let _ = Local::as_dyn();
+    }
+}
@@ -0,0 +1,333 @@
+use crate::{
+    BackendCheckResult, BackendError, Capabilities, FtmlBackend, ResourceKind, SourceFormat,
+};
+use either::Either;
+use ftml_ontology::{
+    domain::{
+        AnyDeclaration, SharedDeclaration,
+        declarations::{
+            morphisms::Morphism,
+            structures::{MathStructure, StructureExtension},
+            symbols::Symbol,
+        },
+        modules::ModuleLike,
+    },
+    narrative::{
+        SharedDocumentElement,
+        documents::{Document, TocElem},
+        elements::{
+            DocumentTerm, Notation, ParagraphOrProblemKind, SectionLevel, VariableDeclaration,
+            problems::Solutions,
+        },
+    },
+    terms::{Term, termpaths::TermPath},
+    utils::Css,
+};
+use ftml_uris::{
+    ArchiveId, DocumentElementUri, DocumentUri, Language, LeafUri, ModuleUri, NarrativeUri,
+    SymbolUri, Uri, UriKind,
+};
+use send_wrapper::SendWrapper;
+
+/// Wraps a backend that is not [`Send`]/[`Sync`] (e.g. one holding `Rc`s or `JsValue`s)
+/// so that it can be stored in a `static` and used wherever a [`SendBackend`](crate::SendBackend)
+/// is required.
+///
+/// This is only sound for single-threaded targets (i.e. `wasm32-unknown-unknown` without
+/// threads): the wrapped backend may only be accessed from the thread that created the
+/// adapter; any access from a different thread panics (see [`SendWrapper`]). Note that the
+/// *futures* returned by the wrapped backend still need to be `Send` (as demanded by
+/// [`FtmlBackend`]); use [`FutWrap`](crate::utils::FutWrap) for that.
+pub struct LocalBackendAdapter<B: FtmlBackend>(SendWrapper<B>);
+
+impl<B: FtmlBackend> LocalBackendAdapter<B> {
+    #[inline]
+    #[must_use]
+    pub fn new(backend: B) -> Self {
+        Self(SendWrapper::new(backend))
+    }
+
+    /// Returns the wrapped backend.
+    ///
+    /// # Panics
+    /// if called from a different thread than the one that created this adapter.
+    #[inline]
+    #[must_use]
+    pub fn into_inner(self) -> B {
+        self.0.take()
+    }
+}
+impl<B: FtmlBackend> std::ops::Deref for LocalBackendAdapter<B> {
+    type Target = B;
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<B: FtmlBackend> FtmlBackend for LocalBackendAdapter<B> {
+    type Error = B::Error;
+
+    #[inline]
+    fn document_link_url(&self, uri: &DocumentUri) -> String {
+        self.0.document_link_url(uri)
+    }
+
+    #[inline]
+    fn resource_link_url(&self, uri: &DocumentUri, kind: ResourceKind) -> Option<String> {
+        self.0.resource_link_url(uri, kind)
+    }
+
+    #[inline]
+    fn forget_errors(&self) {
+        self.0.forget_errors();
+    }
+
+    #[inline]
+    fn has_resource(
+        &self,
+        uri: &DocumentUri,
+        kind: ResourceKind,
+    ) -> impl Future<Output = Result<bool, BackendError<Self::Error>>> + Send + 'static {
+        self.0.has_resource(uri, kind)
+    }
+
+    #[inline]
+    fn capabilities(
+        &self,
+    ) -> impl Future<Output = Result<Capabilities, BackendError<Self::Error>>> + Send + 'static
+    {
+        self.0.capabilities()
+    }
+
+    #[inline]
+    fn check_term(
+        &self,
+        global_context: &[ModuleUri],
+        in_term: Either<&Term, &DocumentElementUri>,
+        subterm: Either<&Term, &TermPath>,
+    ) -> impl Future<Output = Result<BackendCheckResult, BackendError<Self::Error>>>
+    + Send
+    + use<B>
+    + 'static {
+        self.0.check_term(global_context, in_term, subterm)
+    }
+
+    #[inline]
+    fn get_fragment(
+        &self,
+        uri: Uri,
+        context: Option<NarrativeUri>,
+    ) -> impl Future<Output = Result<(Box<str>, Box<[Css]>, bool), BackendError<Self::Error>>>
+    + Send
+    + 'static {
+        self.0.get_fragment(uri, context)
+    }
+
+    #[inline]
+    fn get_logical_paragraphs(
+        &self,
+        uri: SymbolUri,
+        problems: bool,
+    ) -> impl Future<
+        Output = Result<
+            Vec<(DocumentElementUri, ParagraphOrProblemKind)>,
+            BackendError<Self::Error>,
+        >,
+    > + Send
+    + 'static {
+        self.0.get_logical_paragraphs(uri, problems)
+    }
+
+    #[inline]
+    fn get_module(
+        &self,
+        uri: ModuleUri,
+    ) -> impl Future<Output = Result<ModuleLike, BackendError<Self::Error>>> + Send + 'static {
+        self.0.get_module(uri)
+    }
+
+    #[inline]
+    fn get_document(
+        &self,
+        uri: DocumentUri,
+    ) -> impl Future<Output = Result<Document, BackendError<Self::Error>>> + Send + 'static {
+        self.0.get_document(uri)
+    }
+
+    #[inline]
+    fn get_toc(
+        &self,
+        uri: DocumentUri,
+    ) -> impl Future<
+        Output = Result<(Box<[Css]>, SectionLevel, Box<[TocElem]>), BackendError<Self::Error>>,
+    > + Send
+    + 'static {
+        self.0.get_toc(uri)
+    }
+
+    #[inline]
+    fn get_symbol(
+        &self,
+        uri: SymbolUri,
+    ) -> impl Future<
+        Output = Result<Either<Symbol, SharedDeclaration<Symbol>>, BackendError<Self::Error>>,
+    > + Send
+    + 'static {
+        self.0.get_symbol(uri)
+    }
+
+    #[inline]
+    fn get_morphism(
+        &self,
+        uri: SymbolUri,
+    ) -> impl Future<
+        Output = Result<Either<Morphism, SharedDeclaration<Morphism>>, BackendError<Self::Error>>,
+    > + Send
+    + 'static {
+        self.0.get_morphism(uri)
+    }
+
+    #[inline]
+    #[allow(clippy::type_complexity)]
+    fn get_structure(
+        &self,
+        uri: SymbolUri,
+    ) -> impl Future<
+        Output = Result<
+            Either<SharedDeclaration<MathStructure>, SharedDeclaration<StructureExtension>>,
+            BackendError<Self::Error>,
+        >,
+    > + Send
+    + 'static {
+        self.0.get_structure(uri)
+    }
+
+    #[inline]
+    fn get_declaration(
+        &self,
+        uri: SymbolUri,
+    ) -> impl Future<Output = Result<AnyDeclaration, BackendError<Self::Error>>> + Send + 'static
+    {
+        self.0.get_declaration(uri)
+    }
+
+    #[inline]
+    fn get_variable(
+        &self,
+        uri: DocumentElementUri,
+    ) -> impl Future<
+        Output = Result<
+            Either<VariableDeclaration, SharedDocumentElement<VariableDeclaration>>,
+            BackendError<Self::Error>,
+        >,
+    > + Send
+    + 'static {
+        self.0.get_variable(uri)
+    }
+
+    #[inline]
+    fn get_document_term(
+        &self,
+        uri: DocumentElementUri,
+    ) -> impl Future<
+        Output = Result<
+            Either<DocumentTerm, SharedDocumentElement<DocumentTerm>>,
+            BackendError<Self::Error>,
+        >,
+    > + Send
+    + 'static {
+        self.0.get_document_term(uri)
+    }
+
+    #[inline]
+    fn get_definition(
+        &self,
+        uri: SymbolUri,
+        context: Option<NarrativeUri>,
+    ) -> impl Future<Output = Result<(Box<str>, Box<[Css]>), BackendError<Self::Error>>> + Send + 'static
+    {
+        self.0.get_definition(uri, context)
+    }
+
+    #[inline]
+    fn get_document_html(
+        &self,
+        uri: DocumentUri,
+        context: Option<NarrativeUri>,
+    ) -> impl Future<Output = Result<(Box<str>, Box<[Css]>, bool), BackendError<Self::Error>>>
+    + Send
+    + 'static {
+        self.0.get_document_html(uri, context)
+    }
+
+    #[inline]
+    fn get_source(
+        &self,
+        uri: DocumentUri,
+    ) -> impl Future<Output = Result<(String, SourceFormat), BackendError<Self::Error>>> + Send + 'static
+    {
+        self.0.get_source(uri)
+    }
+
+    #[inline]
+    fn get_solutions(
+        &self,
+        uri: DocumentElementUri,
+    ) -> impl Future<Output = Result<Solutions, BackendError<Self::Error>>> + Send + 'static {
+        self.0.get_solutions(uri)
+    }
+
+    #[inline]
+    fn get_notations(
+        &self,
+        uri: LeafUri,
+    ) -> impl Future<Output = Result<Vec<(DocumentElementUri, Notation)>, BackendError<Self::Error>>>
+    + Send
+    + 'static {
+        self.0.get_notations(uri)
+    }
+
+    #[inline]
+    fn get_notation(
+        &self,
+        symbol: LeafUri,
+        uri: DocumentElementUri,
+    ) -> impl Future<Output = Result<Notation, BackendError<Self::Error>>> + Send + 'static {
+        self.0.get_notation(symbol, uri)
+    }
+
+    #[inline]
+    fn get_symbol_glyph(
+        &self,
+        uri: SymbolUri,
+    ) -> impl Future<Output = Result<Box<str>, BackendError<Self::Error>>> + Send + 'static {
+        self.0.get_symbol_glyph(uri)
+    }
+
+    #[inline]
+    fn get_language_variants(
+        &self,
+        uri: DocumentUri,
+    ) -> impl Future<Output = Result<Vec<Language>, BackendError<Self::Error>>> + Send + 'static
+    {
+        self.0.get_language_variants(uri)
+    }
+
+    #[inline]
+    fn list_archives(
+        &self,
+    ) -> impl Future<Output = Result<Vec<ArchiveId>, BackendError<Self::Error>>> + Send + 'static
+    {
+        self.0.list_archives()
+    }
+
+    #[inline]
+    fn search(
+        &self,
+        query: &str,
+        kinds: &[UriKind],
+    ) -> impl Future<Output = Result<Vec<Uri>, BackendError<Self::Error>>> + Send + 'static {
+        self.0.search(query, kinds)
+    }
+}
@@ -6,7 +6,7 @@ use ftml_uris::{
     DocumentElementUri, DocumentUri, FtmlUri, LeafUri, ModuleUri, NarrativeUri, SymbolUri, Uri,
 };
 
-use crate::BackendError;
+use crate::{BackendError, Capabilities, ResourceKind, SourceFormat};
 
 pub trait Redirects {
     #[inline]
@@ -60,6 +60,22 @@ pub struct RemoteBackend<
     pub documents_url: Url,
     pub toc_url: Url,
     pub resources_url: Option<Url>,
+    /// If not set, [`get_language_variants`](super::FtmlBackend::get_language_variants)
+    /// falls back to [`probe_language_variants`](super::probe_language_variants).
+    pub language_variants_url: Option<Url>,
+    /// An index endpoint returning all archive ids; if not set,
+    /// [`list_archives`](super::FtmlBackend::list_archives) is unsupported.
+    pub archives_url: Option<Url>,
+    /// A search endpoint taking the query as parameter `query` and returning a list of uris;
+    /// if not set, [`search`](super::FtmlBackend::search) is unsupported.
+    pub search_url: Option<Url>,
+    /// A `/capabilities` endpoint returning the [`Capabilities`] of the server; if not set,
+    /// they are derived from which of the optional urls above are set.
+    pub capabilities_url: Option<Url>,
+    /// An endpoint returning the glyph of a symbol; if not set,
+    /// [`get_symbol_glyph`](super::FtmlBackend::get_symbol_glyph) derives it from the
+    /// notations (see [`notations_glyph`](super::notations_glyph)).
+    pub symbol_glyph_url: Option<Url>,
     pub redirects: Re,
     __phantom: PhantomData<E>,
 }
@@ -108,6 +124,11 @@ where
             documents_url,
             toc_url,
             resources_url: None,
+            language_variants_url: None,
+            archives_url: None,
+            search_url: None,
+            capabilities_url: None,
+            symbol_glyph_url: None,
             redirects,
             __phantom: PhantomData,
         }
@@ -142,6 +163,11 @@ where
             documents_url,
             toc_url,
             resources_url: None,
+            language_variants_url: None,
+            archives_url: None,
+            search_url: None,
+            capabilities_url: None,
+            symbol_glyph_url: None,
             redirects: NoRedirects,
             __phantom: PhantomData,
         }
@@ -231,10 +257,53 @@ where
             |r| r.to_string(),
         )
     }
-    fn resource_link_url(&self, uri: &DocumentUri, kind: &'static str) -> Option<String> {
+    fn resource_link_url(&self, uri: &DocumentUri, kind: ResourceKind) -> Option<String> {
         self.resources_url
             .as_ref()
-            .map(|s| format!("{s}?uri={}&format={kind}", uri.url_encoded()))
+            .map(|s| format!("{s}?uri={}{}", uri.url_encoded(), kind.url_suffix()))
+    }
+
+    fn has_resource(
+        &self,
+        uri: &DocumentUri,
+        kind: ResourceKind,
+    ) -> impl Future<Output = Result<bool, BackendError<Self::Error>>> + Send + 'static {
+        if let Some(url) = self.resource_link_url(uri, kind) {
+            futures_util::future::Either::Left(head(url))
+        } else {
+            futures_util::future::Either::Right(std::future::ready(Ok(false)))
+        }
+    }
+
+    fn capabilities(
+        &self,
+    ) -> impl Future<Output = Result<Capabilities, BackendError<Self::Error>>> + Send + 'static
+    {
+        if let Some(url) = &self.capabilities_url {
+            futures_util::future::Either::Left(call(url.to_string()))
+        } else {
+            futures_util::future::Either::Right(std::future::ready(Ok(Capabilities {
+                search: self.search_url.is_some(),
+                list_archives: self.archives_url.is_some(),
+                source: self.resources_url.is_some(),
+            })))
+        }
+    }
+
+    /// Fetches the [`ResourceKind::Source`] resource, if [`resources_url`](Self::resources_url)
+    /// is set.
+    fn get_source(
+        &self,
+        uri: DocumentUri,
+    ) -> impl Future<Output = Result<(String, SourceFormat), BackendError<Self::Error>>> + Send + 'static
+    {
+        if let Some(url) = self.resource_link_url(&uri, ResourceKind::Source) {
+            futures_util::future::Either::Left(source(uri, url))
+        } else {
+            futures_util::future::Either::Right(std::future::ready(Err(BackendError::ToDo(
+                "get_source".to_string(),
+            ))))
+        }
     }
 
     #[allow(clippy::similar_names)]
@@ -371,13 +440,84 @@ where
         );
         call(url)
     }
+
+    fn get_language_variants(
+        &self,
+        uri: DocumentUri,
+    ) -> impl Future<Output = Result<Vec<ftml_uris::Language>, BackendError<Self::Error>>> + Send + 'static
+    {
+        if let Some(url) = &self.language_variants_url {
+            let url = Self::make_url(url, &uri.into(), None);
+            futures_util::future::Either::Left(call(url))
+        } else {
+            futures_util::future::Either::Right(super::probe_language_variants(self, uri))
+        }
+    }
+
+    fn get_symbol_glyph(
+        &self,
+        uri: SymbolUri,
+    ) -> impl Future<Output = Result<Box<str>, BackendError<Self::Error>>> + Send + 'static {
+        use futures_util::TryFutureExt;
+        if let Some(url) = &self.symbol_glyph_url {
+            let url = Self::make_url(url, &uri.into(), None);
+            futures_util::future::Either::Left(
+                call::<String, E>(url).map_ok(String::into_boxed_str),
+            )
+        } else {
+            futures_util::future::Either::Right(super::notations_glyph(self, uri))
+        }
+    }
+
+    fn list_archives(
+        &self,
+    ) -> impl Future<Output = Result<Vec<ftml_uris::ArchiveId>, BackendError<Self::Error>>>
+    + Send
+    + 'static {
+        if let Some(url) = &self.archives_url {
+            futures_util::future::Either::Left(call(url.to_string()))
+        } else {
+            futures_util::future::Either::Right(std::future::ready(Err(BackendError::ToDo(
+                "list_archives".to_string(),
+            ))))
+        }
+    }
+
+    fn search(
+        &self,
+        query: &str,
+        kinds: &[ftml_uris::UriKind],
+    ) -> impl Future<Output = Result<Vec<Uri>, BackendError<Self::Error>>> + Send + 'static {
+        use futures_util::TryFutureExt;
+        if let Some(url) = &self.search_url {
+            let url = format!("{url}?query={}", urlencoding::encode(query));
+            let kinds = kinds.to_vec();
+            futures_util::future::Either::Left(
+                call::<Vec<String>, E>(url)
+                    .map_ok(move |results| super::parse_search_results(results, &kinds)),
+            )
+        } else {
+            futures_util::future::Either::Right(std::future::ready(Err(BackendError::ToDo(
+                "search".to_string(),
+            ))))
+        }
+    }
 }
 
+/// An [`FtmlBackend`](crate::FtmlBackend) talking to a remote FLAMS server at `url`.
+///
+/// With the `trace-spans` feature, every request taking a uri is instrumented with a `DEBUG`
+/// span named `RemoteFlamsBackend::<method>` (e.g. `RemoteFlamsBackend::get_module`) with
+/// the requested uri as field `uri` (absent if the request is by uri components only; plus
+/// `kind` and `problems` for [`has_resource`](crate::FtmlBackend::has_resource) and
+/// [`get_logical_paragraphs`](crate::FtmlBackend::get_logical_paragraphs), respectively).
 #[cfg(feature = "server_fn")]
 pub struct RemoteFlamsBackend<Url: std::fmt::Display, Re: Redirects = NoRedirects> {
     pub url: Url,
     pub redirects: Re,
     pub stripped: bool,
+    /// See [`with_public_base`](Self::with_public_base).
+    public_base: Option<String>,
 }
 
 #[cfg(feature = "server_fn")]
@@ -390,8 +530,39 @@ where
             url,
             redirects,
             stripped,
+            public_base: None,
         }
     }
+
+    /// Rewrites the links generated by [`document_link_url`](crate::FlamsBackend::document_link_url)
+    /// and [`resource_link_url`](crate::FlamsBackend::resource_link_url) to point to `public_base`
+    /// instead of [`url`](Self::url), e.g. if the server is only reachable from the outside via a
+    /// reverse proxy. Requests made by the backend itself still go to [`url`](Self::url).
+    ///
+    /// Links from the [`Redirects`] table are rewritten only if they point to
+    /// [`url`](Self::url), too (i.e. start with it, followed by a `/`, `?`, `#` or nothing);
+    /// redirects to other hosts are used as is. Both urls should hence be given consistently,
+    /// e.g. both without trailing slash.
+    #[must_use]
+    pub fn with_public_base(mut self, public_base: impl Into<String>) -> Self {
+        self.public_base = Some(public_base.into());
+        self
+    }
+
+    /// `link`, with the prefix [`url`](Self::url) replaced by the
+    /// [public base](Self::with_public_base) (if set).
+    fn public_link(&self, link: String) -> String {
+        let Some(public_base) = &self.public_base else {
+            return link;
+        };
+        let url = self.url.to_string();
+        if let Some(rest) = link.strip_prefix(url.as_str())
+            && (url.ends_with('/') || rest.is_empty() || rest.starts_with(['/', '?', '#']))
+        {
+            return format!("{public_base}{rest}");
+        }
+        link
+    }
 }
 
 #[cfg(feature = "server_fn")]
@@ -401,6 +572,7 @@ impl<Url: std::fmt::Display> RemoteFlamsBackend<Url> {
             url,
             redirects: NoRedirects,
             stripped,
+            public_base: None,
         }
     }
 }
@@ -408,7 +580,8 @@ impl<Url: std::fmt::Display> RemoteFlamsBackend<Url> {
 #[cfg(feature = "server_fn")]
 mod server_fn {
     use crate::{
-        BackendError, FlamsBackend, ParagraphOrProblemKind, Redirects, RemoteFlamsBackend,
+        BackendError, Capabilities, FlamsBackend, ParagraphOrProblemKind, Redirects,
+        RemoteFlamsBackend, ResourceKind, SourceFormat,
     };
     use ::server_fn::error::ServerFnErrorErr;
     use ftml_ontology::{
@@ -421,7 +594,19 @@ mod server_fn {
     };
     use futures_util::TryFutureExt;
 
+    use crate::utils::traced;
+
     impl<Url: std::fmt::Display, Re: Redirects> RemoteFlamsBackend<Url, Re> {
+        /// The (non-public) url of the resource of the given kind for `uri`
+        fn resource_url(&self, uri: &DocumentUri, kind: ResourceKind) -> String {
+            format!(
+                "{}/doc?uri={}{}",
+                self.url,
+                uri.url_encoded(),
+                kind.url_suffix()
+            )
+        }
+
         #[cfg(feature = "rdf")]
         pub fn sparql(
             &self,
@@ -451,17 +636,53 @@ mod server_fn {
             self.stripped
         }
         fn document_link_url(&self, uri: &DocumentUri) -> String {
-            self.redirects.for_documents(uri).map_or_else(
+            self.public_link(self.redirects.for_documents(uri).map_or_else(
                 || format!("{}?uri={}", self.url, uri.url_encoded()),
                 |r| r.to_string(),
+            ))
+        }
+        fn resource_link_url(&self, uri: &DocumentUri, kind: ResourceKind) -> Option<String> {
+            Some(self.public_link(self.resource_url(uri, kind)))
+        }
+
+        fn has_resource(
+            &self,
+            uri: &DocumentUri,
+            kind: ResourceKind,
+        ) -> impl Future<Output = Result<bool, BackendError<ServerFnErrorErr>>> + Send + 'static
+        {
+            let url = self.resource_url(uri, kind);
+            traced!(
+                "RemoteFlamsBackend::has_resource",
+                (uri = %uri, kind = ?kind),
+                super::head::<SFnE>(url).map_err(BackendError::from_other)
             )
         }
-        fn resource_link_url(&self, uri: &DocumentUri, kind: &'static str) -> Option<String> {
-            Some(format!(
-                "{}/doc?uri={}&format={kind}",
-                self.url,
-                uri.url_encoded()
-            ))
+
+        /// Sources are supported; archive listings and search are not.
+        fn capabilities(
+            &self,
+        ) -> impl Future<Output = Result<Capabilities, BackendError<ServerFnErrorErr>>> + Send + 'static
+        {
+            std::future::ready(Ok(Capabilities {
+                source: true,
+                ..Capabilities::default()
+            }))
+        }
+
+        /// `/doc?format=tex`
+        fn get_source(
+            &self,
+            uri: DocumentUri,
+        ) -> impl Future<Output = Result<(String, SourceFormat), BackendError<ServerFnErrorErr>>>
+        + Send
+        + 'static {
+            let url = self.resource_url(&uri, ResourceKind::Source);
+            traced!(
+                "RemoteFlamsBackend::get_source",
+                (uri = %uri),
+                super::source::<SFnE>(uri, url).map_err(BackendError::from_other)
+            )
         }
 
         fn check_term(
@@ -535,7 +756,11 @@ mod server_fn {
                 } else {
                     make_url(&self.url, &uri, context.as_ref())
                 };
-                super::call::<_,SFnE>(url).map_err(BackendError::from_other)
+                traced!(
+                    "RemoteFlamsBackend::get_fragment",
+                    (uri = uri.uri.as_ref().map(::tracing::field::display)),
+                    super::call::<_,SFnE>(url).map_err(BackendError::from_other)
+                )
             }
         }
 
@@ -551,7 +776,7 @@ mod server_fn {
         > + Send
         + 'static {
             let url = format!("{}/content/solution?uri={}", &self.url, uri.url_encoded());
-            async move {
+            traced!("RemoteFlamsBackend::get_solutions", (uri = %uri), async move {
                 let s = super::call::<String, SFnE>(url)
                     .await
                     .map_err(BackendError::from_other)?;
@@ -560,7 +785,7 @@ mod server_fn {
                     .ok_or_else(|| BackendError::ToDo("illegal solution string".to_string()));
                 //tracing::error!("Result: {r:#?}");
                 r
-            }
+            })
         }
 
         #[allow(clippy::similar_names)]
@@ -581,13 +806,17 @@ mod server_fn {
             if let Some(uri) = &uri
                 && let Some(url) = self.redirects.for_document_html(uri)
             {
-                return super::call::<_, SFnE>(url.to_string()).map_err(BackendError::from_other);
+                return traced!(
+                    "RemoteFlamsBackend::get_document_html",
+                    (uri = %uri),
+                    super::call::<_, SFnE>(url.to_string()).map_err(BackendError::from_other)
+                );
             }
             let url = {
                 let mut s = String::with_capacity(64);
                 let _ = write!(&mut s, "{}/content/document", &self.url);
                 let mut sep = '?';
-                if let Some(uri) = uri {
+                if let Some(uri) = &uri {
                     let _ = write!(&mut s, "?uri={}", uri.url_encoded());
                     sep = '&';
                 }
@@ -611,7 +840,11 @@ mod server_fn {
                 }
                 s
             };
-            super::call::<_, SFnE>(url).map_err(BackendError::from_other)
+            traced!(
+                "RemoteFlamsBackend::get_document_html",
+                (uri = uri.as_ref().map(::tracing::field::display)),
+                super::call::<_, SFnE>(url).map_err(BackendError::from_other)
+            )
         }
 
         #[allow(clippy::similar_names)]
@@ -640,13 +873,17 @@ mod server_fn {
             if let Some(uri) = &uri
                 && let Some(url) = self.redirects.for_document_html(uri)
             {
-                return super::call::<_, SFnE>(url.to_string()).map_err(BackendError::from_other);
+                return traced!(
+                    "RemoteFlamsBackend::get_toc",
+                    (uri = %uri),
+                    super::call::<_, SFnE>(url.to_string()).map_err(BackendError::from_other)
+                );
             }
             let url = {
                 let mut s = String::with_capacity(64);
                 let _ = write!(&mut s, "{}/content/toc", &self.url);
                 let mut sep = '?';
-                if let Some(uri) = uri {
+                if let Some(uri) = &uri {
                     let _ = write!(&mut s, "?uri={}", uri.url_encoded());
                     sep = '&';
                 }
@@ -670,7 +907,11 @@ mod server_fn {
                 }
                 s
             };
-            super::call::<_, SFnE>(url).map_err(BackendError::from_other)
+            traced!(
+                "RemoteFlamsBackend::get_toc",
+                (uri = uri.as_ref().map(::tracing::field::display)),
+                super::call::<_, SFnE>(url).map_err(BackendError::from_other)
+            )
         }
 
         #[allow(clippy::similar_names)]
@@ -691,13 +932,17 @@ mod server_fn {
             if let Some(uri) = &uri
                 && let Some(url) = self.redirects.for_modules(uri)
             {
-                return super::call::<_, SFnE>(url.to_string()).map_err(BackendError::from_other);
+                return traced!(
+                    "RemoteFlamsBackend::get_module",
+                    (uri = %uri),
+                    super::call::<_, SFnE>(url.to_string()).map_err(BackendError::from_other)
+                );
             }
             let url = {
                 let mut s = String::with_capacity(64);
                 let _ = write!(&mut s, "{}/domain/module", &self.url);
                 let mut sep = '?';
-                if let Some(uri) = uri {
+                if let Some(uri) = &uri {
                     let _ = write!(&mut s, "?uri={}", uri.url_encoded());
                     sep = '&';
                 }
@@ -714,7 +959,11 @@ mod server_fn {
                 }
                 s
             };
-            super::call::<_, SFnE>(url).map_err(BackendError::from_other)
+            traced!(
+                "RemoteFlamsBackend::get_module",
+                (uri = uri.as_ref().map(::tracing::field::display)),
+                super::call::<_, SFnE>(url).map_err(BackendError::from_other)
+            )
         }
 
         #[allow(clippy::similar_names)]
@@ -738,13 +987,17 @@ mod server_fn {
             if let Some(uri) = &uri
                 && let Some(url) = self.redirects.for_documents(uri)
             {
-                return super::call::<_, SFnE>(url.to_string()).map_err(BackendError::from_other);
+                return traced!(
+                    "RemoteFlamsBackend::get_document",
+                    (uri = %uri),
+                    super::call::<_, SFnE>(url.to_string()).map_err(BackendError::from_other)
+                );
             }
             let url = {
                 let mut s = String::with_capacity(64);
                 let _ = write!(&mut s, "{}/domain/document", &self.url);
                 let mut sep = '?';
-                if let Some(uri) = uri {
+                if let Some(uri) = &uri {
                     let _ = write!(&mut s, "?uri={}", uri.url_encoded());
                     sep = '&';
                 }
@@ -768,7 +1021,11 @@ mod server_fn {
                 }
                 s
             };
-            super::call::<_, SFnE>(url).map_err(BackendError::from_other)
+            traced!(
+                "RemoteFlamsBackend::get_document",
+                (uri = uri.as_ref().map(::tracing::field::display)),
+                super::call::<_, SFnE>(url).map_err(BackendError::from_other)
+            )
         }
 
         ftml_uris::compfun! {!!
@@ -787,6 +1044,8 @@ mod server_fn {
                         |r| r.to_string()
                     )
                 }
+                #[cfg(feature = "trace-spans")]
+                let span_uri = uri.uri.clone();
                 let url = match uri.uri {
                     Some(Uri::Symbol(s)) => {
                         let uri = s.into();
@@ -802,7 +1061,11 @@ mod server_fn {
                         uri.as_query(),
                     )
                 };
-                super::call::<_,SFnE>(url).map_err(BackendError::from_other)
+                traced!(
+                    "RemoteFlamsBackend::get_notations",
+                    (uri = span_uri.map(::tracing::field::display)),
+                    super::call::<_,SFnE>(url).map_err(BackendError::from_other)
+                )
             }
         }
 
@@ -831,7 +1094,11 @@ mod server_fn {
                         )
                     },|s| s.to_string())
                 );
-                super::call::<_,SFnE>(url).map_err(BackendError::from_other)
+                traced!(
+                    "RemoteFlamsBackend::get_logical_paragraphs",
+                    (uri = uri.uri.as_ref().map(::tracing::field::display), problems),
+                    super::call::<_,SFnE>(url).map_err(BackendError::from_other)
+                )
             }
         }
     }
@@ -1212,6 +1479,135 @@ where
 #[cfg(not(feature = "serde-lite"))]
 use post_serde as post;
 
+/// Interprets the status code of a (`HEAD` or `GET`) request for a resource: `Some(false)` if
+/// it does not exist (`404`/`410`), `None` for any other error status, and `Some(true)`
+/// otherwise.
+const fn resource_status(status: u16) -> Option<bool> {
+    match status {
+        404 | 410 => Some(false),
+        400..=599 => None,
+        _ => Some(true),
+    }
+}
+
+fn status_error<E: From<RequestError> + std::fmt::Display + std::fmt::Debug>(
+    method: &str,
+    url: &str,
+    status: u16,
+) -> BackendError<E> {
+    BackendError::Connection(E::from(RequestError::Request(format!(
+        "{method} {url} returned status {status}"
+    ))))
+}
+
+#[cfg(feature = "wasm")]
+fn head<E>(url: String) -> impl Future<Output = Result<bool, BackendError<E>>>
+where
+    E: From<RequestError> + std::fmt::Display + std::fmt::Debug,
+{
+    #[allow(clippy::future_not_send)]
+    async fn head_i<E>(url: String) -> Result<bool, BackendError<E>>
+    where
+        E: From<RequestError> + std::fmt::Display + std::fmt::Debug,
+    {
+        let res = gloo_net::http::RequestBuilder::new(&url)
+            .method(gloo_net::http::Method::HEAD)
+            .send()
+            .await
+            .map_err(|e| BackendError::Connection(E::from(e.into())))?;
+        let status = res.status();
+        resource_status(status).ok_or_else(|| status_error("HEAD", &url, status))
+    }
+
+    crate::utils::FutWrap::new(head_i(url))
+}
+
+#[cfg(not(feature = "wasm"))]
+async fn head<E>(url: String) -> Result<bool, BackendError<E>>
+where
+    E: From<RequestError> + std::fmt::Display + std::fmt::Debug,
+{
+    let res = ::reqwest::Client::new()
+        .head(&url)
+        .send()
+        .await
+        .map_err(|e| BackendError::Connection(E::from(e.into())))?;
+    let status = res.status().as_u16();
+    resource_status(status).ok_or_else(|| status_error("HEAD", &url, status))
+}
+
+/// The [`SourceFormat`] of a fetched source file with the given `Content-Type`; since sources
+/// are requested as `tex` (see [`ResourceKind::Source`]), unknown or missing content types
+/// are assumed to be [`SourceFormat::Tex`].
+fn source_format(content_type: Option<&str>) -> SourceFormat {
+    content_type
+        .and_then(SourceFormat::from_mime)
+        .unwrap_or(SourceFormat::Tex)
+}
+
+#[cfg(feature = "wasm")]
+fn source<E>(
+    uri: DocumentUri,
+    url: String,
+) -> impl Future<Output = Result<(String, SourceFormat), BackendError<E>>>
+where
+    E: From<RequestError> + std::fmt::Display + std::fmt::Debug,
+{
+    #[allow(clippy::future_not_send)]
+    async fn source_i<E>(
+        uri: DocumentUri,
+        url: String,
+    ) -> Result<(String, SourceFormat), BackendError<E>>
+    where
+        E: From<RequestError> + std::fmt::Display + std::fmt::Debug,
+    {
+        let res = gloo_net::http::Request::get(&url)
+            .send()
+            .await
+            .map_err(|e| BackendError::Connection(E::from(e.into())))?;
+        let status = res.status();
+        match resource_status(status) {
+            Some(true) => (),
+            Some(false) => return Err(BackendError::NotFound(uri.into())),
+            None => return Err(status_error("GET", &url, status)),
+        }
+        let format = source_format(res.headers().get("content-type").as_deref());
+        let text = res
+            .text()
+            .await
+            .map_err(|e| BackendError::Connection(E::from(e.into())))?;
+        Ok((text, format))
+    }
+
+    crate::utils::FutWrap::new(source_i(uri, url))
+}
+
+#[cfg(not(feature = "wasm"))]
+async fn source<E>(uri: DocumentUri, url: String) -> Result<(String, SourceFormat), BackendError<E>>
+where
+    E: From<RequestError> + std::fmt::Display + std::fmt::Debug,
+{
+    let res = ::reqwest::get(&url)
+        .await
+        .map_err(|e| BackendError::Connection(E::from(e.into())))?;
+    let status = res.status().as_u16();
+    match resource_status(status) {
+        Some(true) => (),
+        Some(false) => return Err(BackendError::NotFound(uri.into())),
+        None => return Err(status_error("GET", &url, status)),
+    }
+    let format = source_format(
+        res.headers()
+            .get(::reqwest::header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok()),
+    );
+    let text = res
+        .text()
+        .await
+        .map_err(|e| BackendError::Connection(E::from(e.into())))?;
+    Ok((text, format))
+}
+
 #[cfg(feature = "wasm")]
 impl From<gloo_net::Error> for RequestError {
     fn from(value: gloo_net::Error) -> Self {
@@ -1234,3 +1630,88 @@ impl From<::reqwest::Error> for RequestError {
         }
     }
 }
+
+#[cfg(all(test, feature = "server_fn"))]
+mod tests {
+    use super::{Redirects, RemoteFlamsBackend};
+    use crate::{BackendError, Capabilities, FlamsBackend, FtmlBackend, ResourceKind};
+    use ftml_uris::{DocumentUri, FtmlUri};
+
+    struct ToOrigin;
+    impl Redirects for ToOrigin {
+        fn for_documents<'s>(&'s self, uri: &DocumentUri) -> Option<impl std::fmt::Display + 's> {
+            Some(match uri.name.as_ref() {
+                "moved" => "http://localhost:3000/moved",
+                "port" => "http://localhost:30001/doc",
+                _ => "https://elsewhere.org/doc",
+            })
+        }
+    }
+
+    #[test]
+    fn public_base_rewrites_links() {
+        let uri: DocumentUri = "http://example.com?a=archive&d=doc&l=en"
+            .parse()
+            .expect("valid uri");
+        let backend = RemoteFlamsBackend::new("http://localhost:3000", false);
+        assert_eq!(
+            backend.document_link_url(&uri),
+            format!("http://localhost:3000?uri={}", uri.url_encoded())
+        );
+
+        let backend = backend.with_public_base("https://flams.example.org");
+        assert_eq!(
+            backend.document_link_url(&uri),
+            format!("https://flams.example.org?uri={}", uri.url_encoded())
+        );
+        assert_eq!(
+            backend.resource_link_url(&uri, ResourceKind::Pdf),
+            Some(format!(
+                "https://flams.example.org/doc?uri={}{}",
+                uri.url_encoded(),
+                ResourceKind::Pdf.url_suffix()
+            ))
+        );
+
+        // redirects are only rewritten if they point to the origin
+        let backend =
+            RemoteFlamsBackend::new_with_redirects("http://localhost:3000", ToOrigin, false)
+                .with_public_base("https://flams.example.org");
+        let moved: DocumentUri = "http://example.com?a=archive&d=moved&l=en"
+            .parse()
+            .expect("valid uri");
+        assert_eq!(
+            backend.document_link_url(&moved),
+            "https://flams.example.org/moved"
+        );
+        assert_eq!(backend.document_link_url(&uri), "https://elsewhere.org/doc");
+        // a mere string prefix is not enough
+        let port: DocumentUri = "http://example.com?a=archive&d=port&l=en"
+            .parse()
+            .expect("valid uri");
+        assert_eq!(
+            backend.document_link_url(&port),
+            "http://localhost:30001/doc"
+        );
+    }
+
+    #[tokio::test]
+    async fn flams_capabilities() {
+        let backend = RemoteFlamsBackend::new("http://localhost:3000", false);
+        let caps = FtmlBackend::capabilities(&backend)
+            .await
+            .expect("known statically");
+        assert_eq!(
+            caps,
+            Capabilities {
+                search: false,
+                list_archives: false,
+                source: true
+            }
+        );
+        assert!(matches!(
+            FtmlBackend::search(&backend, "set", &[]).await,
+            Err(BackendError::ToDo(_))
+        ));
+    }
+}
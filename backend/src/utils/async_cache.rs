@@ -157,6 +157,27 @@ impl<
         }
     }
 
+    /// Inserts the already resolved `val` for `key`, unless `key` is already present
+    /// (resolved or pending); returns whether it was inserted.
+    pub fn insert_ready(&self, key: Key, val: Val) -> bool {
+        use dashmap::Entry;
+        if self.max.is_some_and(|max| self.map.len() >= max) {
+            self.retain(|_, _| false);
+        }
+        match self.map.entry(key) {
+            Entry::Occupied(_) => false,
+            Entry::Vacant(entry) => {
+                let (a, inner, _) = Awaitable::new_sync();
+                if let Ok(mut guard) = inner.0.lock() {
+                    *guard = Some(Ok(val));
+                }
+                let r = entry.insert(a);
+                drop(r);
+                true
+            }
+        }
+    }
+
     pub fn has<Q: std::hash::Hash + Eq + ?Sized>(
         &self,
         key: &Q,
@@ -1,5 +1,25 @@
 #[cfg(feature = "cached")]
 pub mod async_cache;
+pub mod text;
+
+/// `traced!("Name", (fields...), future)` instruments `future` with a `DEBUG`-level
+/// [`Span`](tracing::Span) named `"Name"` with the given fields (in the syntax of
+/// [`tracing::span!`]), if the `trace-spans` feature is active, and is just `future`
+/// otherwise. The span (and its fields) are created *before* `future` is evaluated.
+#[cfg(feature = "trace-spans")]
+macro_rules! traced {
+    ($name:literal, ($($fields:tt)*), $fut:expr) => {{
+        let span = ::tracing::debug_span!($name, $($fields)*);
+        ::tracing::Instrument::instrument($fut, span)
+    }};
+}
+#[cfg(not(feature = "trace-spans"))]
+macro_rules! traced {
+    ($name:literal, ($($fields:tt)*), $fut:expr) => {
+        $fut
+    };
+}
+pub(crate) use traced;
 
 #[cfg(feature = "wasm")]
 mod wasm {
@@ -0,0 +1,249 @@
+//! Plain text extraction from (fragment) HTML, e.g. for previews.
+
+/// Extracts the text content of an HTML fragment.
+///
+/// Tags are dropped (the contents of `<script>`, `<style>` and `<head>` elements entirely),
+/// block-level tags (`<p>`, `<div>`, `<br>`, ...) separate words, the common named entities
+/// and all numeric character references are decoded, and whitespace is collapsed into single
+/// spaces and trimmed. Math is *not* treated specially, i.e. the text content of `<math>`
+/// elements ends up verbatim in the result.
+#[must_use]
+pub fn html_to_text(html: &str) -> String {
+    extract_text(html, &["script", "style", "head"])
+}
+
+/// Like [`html_to_text`], but additionally drops math (`<math>`) and code (`<code>`,
+/// `<pre>`) entirely, i.e. only keeps the prose of an HTML fragment, e.g. for
+/// [`word_count`]s.
+#[must_use]
+pub fn html_to_prose(html: &str) -> String {
+    extract_text(html, &["script", "style", "head", "math", "code", "pre"])
+}
+
+/// The number of words in `text` (as e.g. returned by [`html_to_prose`]), i.e. of
+/// whitespace-separated tokens containing at least one alphanumeric character (so that e.g.
+/// dashes do not count).
+#[must_use]
+pub fn word_count(text: &str) -> usize {
+    text.split_whitespace()
+        .filter(|w| w.chars().any(char::is_alphanumeric))
+        .count()
+}
+
+/// the contents of the `skipped` elements are dropped entirely
+fn extract_text(html: &str, skipped: &[&str]) -> String {
+    const BLOCKS: [&str; 24] = [
+        "p",
+        "div",
+        "br",
+        "hr",
+        "li",
+        "ul",
+        "ol",
+        "dl",
+        "dt",
+        "dd",
+        "table",
+        "tr",
+        "td",
+        "th",
+        "section",
+        "blockquote",
+        "figure",
+        "figcaption",
+        "h1",
+        "h2",
+        "h3",
+        "h4",
+        "h5",
+        "h6",
+    ];
+    let mut out = String::with_capacity(html.len() / 2);
+    let mut rest = html;
+    while let Some(i) = rest.find(['<', '&']) {
+        push_text(&mut out, &rest[..i]);
+        rest = &rest[i..];
+        if rest.starts_with('&') {
+            if let Some(e) = rest.find(';').filter(|&e| e <= 10)
+                && let Some(c) = decode_entity(&rest[1..e])
+            {
+                push_text(&mut out, c.encode_utf8(&mut [0; 4]));
+                rest = &rest[e + 1..];
+            } else {
+                out.push('&');
+                rest = &rest[1..];
+            }
+            continue;
+        }
+        let Some(end) = rest.find('>') else {
+            rest = "";
+            break;
+        };
+        let inner = &rest[1..end];
+        let closing = inner.starts_with('/');
+        let self_closing = inner.ends_with('/');
+        let name = inner
+            .trim_start_matches('/')
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or_default();
+        rest = &rest[end + 1..];
+        if !closing && !self_closing && skipped.iter().any(|s| s.eq_ignore_ascii_case(name)) {
+            let close = format!("</{}", name.to_ascii_lowercase());
+            rest = rest
+                .to_ascii_lowercase()
+                .find(&close)
+                .map_or("", |i| &rest[i..]);
+        } else if BLOCKS.iter().any(|s| s.eq_ignore_ascii_case(name))
+            && !out.is_empty()
+            && !out.ends_with(' ')
+        {
+            out.push(' ');
+        }
+    }
+    push_text(&mut out, rest);
+    out.truncate(out.trim_end().len());
+    out
+}
+
+fn push_text(out: &mut String, s: &str) {
+    for c in s.chars() {
+        if c.is_whitespace() {
+            if !out.is_empty() && !out.ends_with(' ') {
+                out.push(' ');
+            }
+        } else {
+            out.push(c);
+        }
+    }
+}
+
+fn decode_entity(e: &str) -> Option<char> {
+    if let Some(num) = e.strip_prefix('#') {
+        let code = if let Some(hex) = num.strip_prefix(['x', 'X']) {
+            u32::from_str_radix(hex, 16).ok()?
+        } else {
+            num.parse().ok()?
+        };
+        return char::from_u32(code);
+    }
+    Some(match e {
+        "amp" => '&',
+        "lt" => '<',
+        "gt" => '>',
+        "quot" => '"',
+        "apos" => '\'',
+        "nbsp" => '\u{a0}',
+        "ndash" => '–',
+        "mdash" => '—',
+        _ => return None,
+    })
+}
+
+/// Words that usually end in a period without ending a sentence (compared case-insensitively,
+/// without the trailing period).
+const ABBREVIATIONS: &[&str] = &[
+    "cf", "def", "eq", "etc", "ex", "fig", "lem", "no", "prop", "resp", "sec", "thm", "vs", "wrt",
+    "bzw", "bspw", "ca", "vgl", "usw", "dh", "zb",
+];
+
+/// Returns the first sentence of `text` (as e.g. returned by [`html_to_text`]), or `None`
+/// if `text` is empty.
+///
+/// A sentence ends with `.`, `!` or `?` (optionally followed by closing quotes or brackets)
+/// that is followed by whitespace or the end of the text. A `.` does *not* end a sentence
+/// if it follows
+/// - a single letter (initials, `i.e.`, `e.g.`),
+/// - a word containing another `.` (`e.g.`, `z.B.`),
+/// - a number (enumerations like `1.`), or
+/// - one of a few common abbreviations (`cf.`, `etc.`, `Thm.`, `resp.`, ...).
+///
+/// If no sentence end is found, the whole (trimmed) text is returned.
+#[must_use]
+pub fn first_sentence(text: &str) -> Option<&str> {
+    let text = text.trim();
+    if text.is_empty() {
+        return None;
+    }
+    let mut chars = text.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        if !matches!(c, '.' | '!' | '?') {
+            continue;
+        }
+        let mut end = i + c.len_utf8();
+        while let Some(&(j, d)) = chars.peek() {
+            if matches!(d, ')' | ']' | '"' | '\'' | '”' | '’' | '»') {
+                end = j + d.len_utf8();
+                chars.next();
+            } else {
+                break;
+            }
+        }
+        let at_boundary = chars.peek().is_none_or(|(_, d)| d.is_whitespace());
+        if !at_boundary || (c == '.' && is_abbreviation(&text[..i])) {
+            continue;
+        }
+        return Some(&text[..end]);
+    }
+    Some(text)
+}
+
+/// whether the word immediately before a `.` (i.e. at the end of `before`) is an abbreviation
+fn is_abbreviation(before: &str) -> bool {
+    let word = before
+        .rsplit(|c: char| c.is_whitespace() || c == '(')
+        .next()
+        .unwrap_or_default();
+    if word.is_empty() {
+        return false;
+    }
+    word.chars().count() == 1
+        || word.contains('.')
+        || word.chars().all(|c| c.is_ascii_digit())
+        || ABBREVIATIONS.iter().any(|a| a.eq_ignore_ascii_case(word))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn text_extraction() {
+        assert_eq!(
+            html_to_text(
+                "<div class=\"p\"><style>.p{color:red}</style><b>Groups</b>&nbsp;are\n  <i>monoids</i> &amp; more.</div>"
+            ),
+            "Groups are monoids & more."
+        );
+    }
+
+    #[test]
+    fn prose_extraction() {
+        let html = "<p>Let <math><mi>x</mi><mo>=</mo><mn>1</mn></math> – then <code>x + 1</code> is \
+                    two.</p><pre>let y = 2;</pre>";
+        assert_eq!(
+            html_to_text(html),
+            "Let x=1 – then x + 1 is two. let y = 2;"
+        );
+        assert_eq!(html_to_prose(html), "Let – then is two.");
+        assert_eq!(word_count(&html_to_prose(html)), 4);
+    }
+
+    #[test]
+    fn sentence_splitting() {
+        assert_eq!(
+            first_sentence("A group is a monoid with inverses. Every group is a monoid."),
+            Some("A group is a monoid with inverses.")
+        );
+        assert_eq!(
+            first_sentence("This holds e.g. for groups (cf. Thm. 3). It fails otherwise."),
+            Some("This holds e.g. for groups (cf. Thm. 3).")
+        );
+        assert_eq!(
+            first_sentence("Is 1.5 a number? Yes."),
+            Some("Is 1.5 a number?")
+        );
+        assert_eq!(first_sentence("No terminator"), Some("No terminator"));
+        assert_eq!(first_sentence("  "), None);
+    }
+}
@@ -171,28 +171,47 @@ impl AnchorData {
     }
 }
 
+/// The distance (in pixels) from the top of the viewport within which an anchor counts as
+/// reached; see [`active_anchor`].
+pub const ACTIVE_ANCHOR_OFFSET: f64 = 50.0;
+
+/// The index of the currently active anchor (e.g. section), given the `tops` of the anchors'
+/// elements relative to the viewport, in document order (`None` for elements not in the DOM).
+///
+/// The active anchor is the last one scrolled past (i.e. with a negative top), unless the
+/// next one is within [`ACTIVE_ANCHOR_OFFSET`] of the top of the viewport, in which case
+/// that one is. Missing elements are skipped before the first one scrolled past, and end the
+/// search after it.
+pub fn active_anchor(tops: impl IntoIterator<Item = Option<f64>>) -> Option<usize> {
+    let mut active = None;
+    for (i, top) in tops.into_iter().enumerate() {
+        match top {
+            Some(top) if top >= 0.0 => {
+                if top <= ACTIVE_ANCHOR_OFFSET {
+                    active = Some(i);
+                }
+                break;
+            }
+            Some(_) => active = Some(i),
+            None if active.is_some() => break,
+            None => (),
+        }
+    }
+    active
+}
+
 #[cfg(any(feature = "csr", feature = "hydrate"))]
 fn scroll_listener(element_ids: RwSignal<Vec<String>>, active_id: RwSignal<Option<String>>) {
     use leptos::ev;
 
     let on_scroll = move || {
         element_ids.with(|ids| {
-            let mut temp_link = None;
-            for id in ids {
-                if let Some(link_el) = document().get_element_by_id(&id[1..]) {
-                    let top = link_el.get_bounding_client_rect().top();
-                    if top >= 0.0 {
-                        if top <= 50.0 {
-                            temp_link = Some(id);
-                        }
-                        break;
-                    }
-                    temp_link = Some(id);
-                } else if temp_link.is_some() {
-                    break;
-                }
-            }
-            active_id.set(temp_link.cloned());
+            let active = active_anchor(ids.iter().map(|id| {
+                document()
+                    .get_element_by_id(&id[1..])
+                    .map(|el| el.get_bounding_client_rect().top())
+            }));
+            active_id.set(active.map(|i| ids[i].clone()));
         });
     };
     let cb = crate::js::throttle(
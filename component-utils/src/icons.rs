@@ -59,6 +59,7 @@ icon! {
     FolderIcon = icondata_bi::BiFolderRegular,
     FileIcon = icondata_bi::BiFileRegular,
     PdfIcon = icondata_bs::BsFiletypePdf,
+    HtmlIcon = icondata_bs::BsFiletypeHtml,
     SearchIcon = icondata_ai::AiSearchOutlined,
     VSCodeIcon = icondata_tb::TbBrandVscodeOutline,
     BurgerIcon = icondata_ch::ChMenuHamburger
@@ -118,6 +118,9 @@ fn do_inject_css(id: Cow<'static, str>, content: Cow<'static, str>, after: bool)
     #[cfg(not(feature = "ssr"))]
     {
         use leptos::prelude::document;
+        if InjectedCss::is_injected(&id) {
+            return;
+        }
         let Some(head) = document().head() else {
             tracing::error!("head does not exist");
             return;
@@ -127,6 +130,7 @@ fn do_inject_css(id: Cow<'static, str>, content: Cow<'static, str>, after: bool)
             return;
         };
         if style.is_some() {
+            InjectedCss::with(|ids| ids.insert(id));
             return;
         }
 
@@ -141,6 +145,72 @@ fn do_inject_css(id: Cow<'static, str>, content: Cow<'static, str>, after: bool)
         } else {
             head.prepend_with_node_1(&style)
         };
+        InjectedCss::with(|ids| ids.insert(id));
+    }
+}
+
+/// Client-side record of all CSS injected into the `<head>` (via [`inject_css`],
+/// [`inject_css_after`] and `ftml_dom`'s `CssExt::inject`), so that components rendered
+/// repeatedly (e.g. when navigating between documents) don't accumulate duplicate
+/// `<style>`/`<link>` elements. (During SSR, [`ssr::CssIds`] serves the same purpose.)
+///
+/// The dedup key is the `id` of the injected element only: Injecting different CSS under an
+/// already recorded id is a no-op. The record is authoritative - the document is only
+/// queried for ids that are *not* recorded (to pick up elements rendered during SSR) - so
+/// whoever removes an injected element has to [`remove`](Self::remove) its id as well.
+///
+/// Ids are kept in injection order (see [`iter`](Self::iter)).
+#[derive(Debug, Default)]
+pub struct InjectedCss {
+    ids: rustc_hash::FxHashSet<Cow<'static, str>>,
+    order: Vec<Cow<'static, str>>,
+}
+impl InjectedCss {
+    /// Calls `f` on the global record of injected ids.
+    pub fn with<R>(f: impl FnOnce(&mut Self) -> R) -> R {
+        static INJECTED: std::sync::LazyLock<std::sync::Mutex<InjectedCss>> =
+            std::sync::LazyLock::new(Default::default);
+        let mut ids = INJECTED
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        f(&mut ids)
+    }
+
+    #[inline]
+    pub fn contains(&self, id: &str) -> bool {
+        self.ids.contains(id)
+    }
+
+    /// Records `id` as injected last; returns `false` (and keeps its position) if it already
+    /// was.
+    pub fn insert(&mut self, id: impl Into<Cow<'static, str>>) -> bool {
+        let id = id.into();
+        if self.ids.contains(&id) {
+            return false;
+        }
+        self.ids.insert(id.clone());
+        self.order.push(id);
+        true
+    }
+
+    /// Forgets `id`, e.g. after removing its element; returns `false` if it was not recorded.
+    pub fn remove(&mut self, id: &str) -> bool {
+        if !self.ids.remove(id) {
+            return false;
+        }
+        self.order.retain(|i| i != id);
+        true
+    }
+
+    /// The recorded ids, in the order they were injected.
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = &str> {
+        self.order.iter().map(|i| &**i)
+    }
+
+    /// Whether `id` is recorded in the global record.
+    #[inline]
+    pub fn is_injected(id: &str) -> bool {
+        Self::with(|ids| ids.contains(id))
     }
 }
 
@@ -194,3 +264,34 @@ pub mod ssr {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::InjectedCss;
+
+    #[test]
+    fn injected_css_dedup() {
+        let mut ids = InjectedCss::default();
+        assert!(!ids.contains("ftml-toc"));
+        assert!(ids.insert("ftml-toc"));
+        assert!(ids.contains("ftml-toc"));
+        assert!(!ids.insert("ftml-toc"));
+        assert!(ids.insert(String::from("id_1f")));
+        assert!(!ids.contains("ftml-sections"));
+        assert!(ids.insert("ftml-sections"));
+        assert_eq!(
+            ids.iter().collect::<Vec<_>>(),
+            ["ftml-toc", "id_1f", "ftml-sections"]
+        );
+        assert!(ids.remove("id_1f"));
+        assert!(!ids.remove("id_1f"));
+        assert!(ids.insert("id_1f"));
+        assert_eq!(
+            ids.iter().collect::<Vec<_>>(),
+            ["ftml-toc", "ftml-sections", "id_1f"]
+        );
+
+        assert!(InjectedCss::with(|ids| ids.insert("ftml-test")));
+        assert!(!InjectedCss::with(|ids| ids.insert("ftml-test")));
+    }
+}
@@ -115,7 +115,9 @@ impl super::FtmlViewable for DocumentElement {
                     || "error".into_any(),
                 )
             }
-            Self::DocumentReference { target, .. } => view_inputref(target).into_any(),
+            Self::DocumentReference { target, anchor, .. } => {
+                view_inputref(target, anchor.as_ref()).into_any()
+            }
             Self::Module {
                 module, children, ..
             } => view_module(module, children).into_any(),
@@ -182,6 +184,7 @@ impl super::FtmlViewable for DocumentElement {
                 children,
                 ..
             }) => paragraphs::slide(uri, title.as_deref(), children),
+            Self::Figure(f) => paragraphs::figure(f),
             Self::Term(term) => view_term(term),
             Self::Problem(p) => {
                 let txt = format!("{p:?}");
@@ -211,7 +214,7 @@ impl super::FtmlViewable for Problem {
         } = &**data;
 
         let title = title.as_ref().map_or_else(
-            || Right(uri.name().last().to_string()),
+            || Right(super::short_name(uri.name().last())),
             |t| Left(crate::Views::render_ftml(t.to_string(), None)),
         );
         let uses = children.iter().flat().filter_map(|e| {
@@ -251,7 +254,8 @@ impl super::FtmlViewable for Problem {
 }
 
 fn view_term(term: &DocumentTerm) -> AnyView {
-    let name = view!(<span title=term.uri.to_string()>{term.uri.name().last().to_string()}</span>);
+    let name =
+        view!(<span title=term.uri.to_string()>{super::short_name(term.uri.name().last())}</span>);
     let tm = ReactiveStore::render_term(term.presentation());
     let tp = term
         .get_type()
@@ -272,11 +276,11 @@ fn view_term(term: &DocumentTerm) -> AnyView {
 
 fn view_notation(uri: DocumentElementUri, head: VarOrSym) -> impl IntoView + 'static {
     use leptos::either::EitherOf3::{A, B, C};
-    let name = view!(<span title=uri.to_string()>{uri.name().last().to_string()}</span>);
+    let name = view!(<span title=uri.to_string()>{super::short_name(uri.name().last())}</span>);
     let (target, leaf) = match &head {
         VarOrSym::Sym(s) => (A(s.as_view()), Some(s.clone().into())),
         VarOrSym::Var(Variable::Ref { declaration, .. }) => {
-            let name = declaration.name().last().to_string();
+            let name = super::short_name(declaration.name().last());
             (
                 B(view!(<Text class="ftml-var-comp">{name}</Text>)),
                 Some(declaration.clone().into()),
@@ -308,14 +312,28 @@ fn view_notation(uri: DocumentElementUri, head: VarOrSym) -> impl IntoView + 'st
     }
 }
 
-fn view_inputref(uri: &DocumentUri) -> AnyView {
+fn view_inputref(uri: &DocumentUri, anchor: Option<&DocumentElementUri>) -> AnyView {
     use ftml_component_utils::LazyCollapsible;
     let name = uri.as_view();
+    // links directly to the anchored element of the referenced document
+    let anchor = anchor.map(|a| {
+        let href = format!(
+            "{}#{}",
+            crate::backend().document_link_url(uri),
+            a.anchor_id()
+        );
+        view! {
+            <Text attr:title=a.to_string()>" at "{a.name().to_string()}</Text>
+            <a style="display:inline-block;" target="_blank" href=href>
+                <ftml_component_utils::icons::LinkIcon/>
+            </a>
+        }
+    });
     let uri = uri.clone();
     view! {
     <LazyCollapsible>
         <Header slot>
-            <BoldCaption>"Document "{name}</BoldCaption>
+            <BoldCaption>"Document "{name}{anchor}</BoldCaption>
         </Header>
         <div style="padding-left:15px;">{
             let uri = uri.clone();
@@ -1,23 +1,27 @@
-use crate::components::content::{CommaSep, FtmlViewable};
+use crate::{
+    components::content::{CommaSep, FtmlViewable},
+    utils::LocalCacheExt,
+};
 use ftml_component_utils::{
     Block, BoldCaption, Caption, Divider, Header, HeaderLeft, HeaderRight, Text,
 };
-use ftml_dom::notations::TermExt;
+use ftml_dom::{notations::TermExt, utils::local_cache::LocalCache};
 use ftml_ontology::{
     domain::{
         HasDeclarations,
         declarations::{
-            AnyDeclarationRef,
+            AnyDeclarationRef, Declaration,
             morphisms::{Assignment, Morphism},
             structures::{MathStructure, StructureExtension},
             symbols::ArgumentSpec,
         },
         modules::{Module, ModuleLike, NestedModule},
     },
-    terms::Term,
+    terms::{Argument, Term},
 };
-use ftml_uris::Id;
+use ftml_uris::{Id, ModuleUri, SymbolUri, UriName};
 use leptos::prelude::*;
+use std::sync::Arc;
 
 impl FtmlViewable for ModuleLike {
     fn as_view(&self) -> AnyView {
@@ -180,7 +184,16 @@ impl FtmlViewable for Module {
 pub fn morphism(m: &Morphism, doc_elems: Option<AnyView>) -> AnyView {
     let domain = m.domain.as_view();
     let name = m.uri.as_view();
-    let assignments = m.elements.iter().map(do_assignment).collect_view();
+    let elaboration = m
+        .elements
+        .iter()
+        .any(|a| a.definiens.is_none())
+        .then(|| elaboration(m));
+    let assignments = m
+        .elements
+        .iter()
+        .map(|a| do_assignment(a, elaboration))
+        .collect_view();
     let elems = doc_elems.map(move |elems| {
         view! {
             <div style="margin:5px;"><Divider/></div>
@@ -199,9 +212,15 @@ pub fn morphism(m: &Morphism, doc_elems: Option<AnyView>) -> AnyView {
     .into_any()
 }
 
-fn do_assignment(a: &Assignment) -> AnyView {
+fn do_assignment(
+    a: &Assignment,
+    elaboration: Option<Signal<Option<Arc<[Declaration]>>>>,
+) -> AnyView {
     let elaborated_uri = a.elaborated_uri();
-    let name = super::symbol_uri(elaborated_uri.name().to_string(), &elaborated_uri);
+    let name = super::symbol_uri(
+        super::short_name(elaborated_uri.name().as_ref()),
+        &elaborated_uri,
+    );
     let header = view!(<BoldCaption>"Symbol "{name}</BoldCaption>);
     let orig = a.original.as_view();
     let paragraphs = super::symbols::do_paragraphs(elaborated_uri.clone());
@@ -214,16 +233,477 @@ fn do_assignment(a: &Assignment) -> AnyView {
         }
         .attr("style", "white-space:nowrap;")
     });
+    let default = elaboration
+        .filter(|_| a.definiens.is_none())
+        .map(|e| assignment_default(a, e));
     view! {
         <Block show_separator=true>
             <Header slot>{header}</Header>
             <HeaderLeft slot>
                 <Caption>"Elaborated from "{orig}</Caption>
             </HeaderLeft>
-            <HeaderRight slot>{df}</HeaderRight>
+            <HeaderRight slot>{df}{default}</HeaderRight>
             {notations}
             {paragraphs}
         </Block>
     }
     .into_any()
 }
+
+/// Elaborates `m` (see [`Morphism::elaborate`]) once its (transitive) domain has been
+/// fetched via the [`LocalCache`] - i.e. from the backend unless already cached. The signal is
+/// `None` until then, or if fetching fails.
+fn elaboration(m: &Morphism) -> Signal<Option<Arc<[Declaration]>>> {
+    let morphism = m.clone();
+    let elaborated = LocalCache::resource(move |c| async move {
+        let mut modules = rustc_hash::FxHashMap::default();
+        let mut todos = vec![morphism.domain.clone()];
+        while let Some(uri) = todos.pop() {
+            if modules.contains_key(&uri) {
+                continue;
+            }
+            let module = match c.get_module(crate::backend(), uri.clone()).await {
+                Ok(m) => m,
+                Err(e) => {
+                    tracing::warn!("failed to fetch domain of morphism: {e:?}");
+                    return Err(e);
+                }
+            };
+            todos.extend(module.declarations().filter_map(|d| match d {
+                AnyDeclarationRef::Import { uri, .. } => Some(uri.clone()),
+                _ => None,
+            }));
+            modules.insert(uri, module);
+        }
+        Ok(morphism
+            .elaborate(|uri| modules.get(uri).cloned().ok_or("module not fetched"))
+            .map(Arc::from)
+            .unwrap_or_default())
+    });
+    Signal::derive(move || elaborated.with(|e| e.as_ref()?.as_ref().ok().cloned()))
+}
+
+/// Renders the default value of the field [`original`](Assignment::original) that `a` does not
+/// assign as "(default: ...)" (see [`field_default`]); nothing is shown until the
+/// `elaboration` of the morphism is available, or if the field has no default.
+fn assignment_default(a: &Assignment, elaboration: Signal<Option<Arc<[Declaration]>>>) -> AnyView {
+    let assignment = a.clone();
+    (move || {
+        elaboration.with(|e| {
+            let t = field_default(&assignment, e.as_deref()?)?;
+            let t = t.into_view::<crate::Views>(crate::backend(), false);
+            Some(
+                view! {<Caption>
+                    "(default: "{ftml_dom::utils::math(|| t)}")"
+                    </Caption>
+                }
+                .attr("style", "white-space:nowrap;"),
+            )
+        })
+    })
+    .into_any()
+}
+
+/// The default value of the field [`original`](Assignment::original) (of the domain of a
+/// morphism), if `assignment` leaves it unassigned - i.e. the definiens of the corresponding
+/// symbol in the `elaborated` morphism (see [`Morphism::elaborate`]), which is the definiens of
+/// the original field mapped along the morphism.
+fn field_default(assignment: &Assignment, elaborated: &[Declaration]) -> Option<Term> {
+    if assignment.definiens.is_some() {
+        return None;
+    }
+    let uri = assignment.elaborated_uri();
+    elaborated.iter().find_map(|d| match d {
+        Declaration::Symbol(s) if s.uri == uri => s.data.df.checked_or_parsed().map(|(t, _)| t),
+        _ => None,
+    })
+}
+
+/// A child in the dependency tree of a module; see [`module_tree`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ModuleTreeNode {
+    /// A module imported by the parent.
+    Import(ModuleUri),
+    /// A module nested in the parent.
+    Nested(ModuleUri),
+}
+impl ModuleTreeNode {
+    #[inline]
+    #[must_use]
+    pub const fn uri(&self) -> &ModuleUri {
+        match self {
+            Self::Import(uri) | Self::Nested(uri) => uri,
+        }
+    }
+
+    /// The children of `module` in its dependency tree, i.e. its imports and nested modules
+    /// in the order they are declared. Imports of nested modules are children of the nested
+    /// module, not of `module` itself.
+    #[must_use]
+    pub fn children<M: HasDeclarations>(module: &M) -> Vec<Self> {
+        module
+            .declarations()
+            .filter_map(|d| match d {
+                AnyDeclarationRef::Import { uri, .. } => Some(Self::Import(uri.clone())),
+                AnyDeclarationRef::NestedModule(m) => {
+                    Some(Self::Nested(m.uri.clone().into_module()))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+/// Renders the module `uri` as an expandable tree of its imports and nested modules (see
+/// [`ModuleTreeNode::children`]).
+///
+/// Nodes are loaded lazily: a node only fetches its module from the backend (via the
+/// [`LocalCache`]) when it is expanded, and only then renders its children as collapsed
+/// nodes. Hence rendering the tree costs nothing beyond the root, and cyclic imports do not
+/// lead to infinite recursion.
+pub fn module_tree(uri: ModuleUri) -> AnyView {
+    module_tree_node(None, uri)
+}
+
+fn module_tree_node(node: Option<&'static str>, uri: ModuleUri) -> AnyView {
+    use ftml_component_utils::lazy_collapsible;
+    let header = uri.as_view();
+    let children = move || {
+        let uri = uri.clone();
+        LocalCache::with_or_toast(
+            move |c| c.get_module(crate::backend(), uri),
+            |m| {
+                let children = ModuleTreeNode::children(&m);
+                if children.is_empty() {
+                    return view!(<Caption>"(no imports or nested modules)"</Caption>).into_any();
+                }
+                children
+                    .into_iter()
+                    .map(|c| {
+                        let (kind, uri) = match c {
+                            ModuleTreeNode::Import(uri) => ("Import ", uri),
+                            ModuleTreeNode::Nested(uri) => ("Nested ", uri),
+                        };
+                        view!(<div style="padding-left:15px">{module_tree_node(Some(kind), uri)}</div>)
+                    })
+                    .collect_view()
+                    .into_any()
+            },
+            || view!(<Text style="color:red">"Error"</Text>).into_any(),
+        )
+    };
+    lazy_collapsible(
+        Some(move || view!(<Caption>{node}</Caption>{header})),
+        children,
+    )
+    .into_any()
+}
+
+/// Renders a record term instantiating `structure`, labelling each field value with (a link
+/// to) the corresponding field symbol of the structure (see [`record_fields`]).
+pub fn record_view(record: &Term, structure: &MathStructure) -> AnyView {
+    let name = structure.uri.as_view();
+    let fields = record_fields(record, structure)
+        .into_iter()
+        .map(|(label, field, value)| {
+            let label = field.map_or_else(
+                || view!(<Text>{label.to_string()}</Text>).into_any(),
+                |uri| super::symbol_uri(super::short_name(label.last()), uri),
+            );
+            let value = value.map(|t| {
+                let t = t.clone().into_view::<crate::Views>(crate::backend(), false);
+                ftml_dom::utils::math(|| t)
+            });
+            view!(<div><Caption>{label}" := "{value}</Caption></div>)
+        })
+        .collect_view();
+    view! {<Block>
+            <Header slot>
+                <BoldCaption>"Instance of "{name}</BoldCaption>
+            </Header>
+            {fields}
+        </Block>
+    }
+    .into_any()
+}
+
+/// Renders `df` as an instance of the structure `tp` (see [`record_view`]), if `df` is a record
+/// term (i.e. has [`Term::Label`] arguments) and `tp` is a symbol; if `tp` turns out not to be
+/// a [`MathStructure`], `df` is rendered as a plain term.
+pub fn structure_instance_view(df: &Term, tp: Option<&Term>) -> Option<AnyView> {
+    let Some(Term::Symbol { uri, .. }) = tp else {
+        return None;
+    };
+    let Term::Application(app) = df else {
+        return None;
+    };
+    if !app
+        .arguments
+        .iter()
+        .flat_map(Argument::terms)
+        .any(|t| matches!(t, Term::Label { .. }))
+    {
+        return None;
+    }
+    let uri = uri.clone();
+    let record = df.clone();
+    let plain = move || {
+        let t = record
+            .clone()
+            .into_view::<crate::Views>(crate::backend(), false);
+        ftml_dom::utils::math(|| t).into_any()
+    };
+    let df = df.clone();
+    Some(LocalCache::with_or_err(
+        move |c| c.get_structure(crate::backend(), uri),
+        {
+            let plain = plain.clone();
+            move |s| match s {
+                either::Either::Left(s) => record_view(&df, &s),
+                either::Either::Right(_) => plain(),
+            }
+        },
+        move |_| plain(),
+    ))
+}
+
+/// Matches the fields of `record` with the [`fields`](MathStructure::fields) of `structure`.
+///
+/// The fields of a record term are the [`Term::Label`]s among the arguments of an
+/// application (usually of [`ANONYMOUS_RECORD`](ftml_uris::metatheory::ANONYMOUS_RECORD));
+/// all other arguments are ignored. A label is matched with the structure field whose name is
+/// equal to the label's name, or - if there is no such field - whose *last* name segment is
+/// equal to the label's last name segment (i.e. `foo` matches the field `structure/foo`).
+///
+/// Returns the label names in order, each with the uri of the matched field (if any) and the
+/// label's definiens (if any). Fields of the structure that have no label in the record are
+/// omitted, as are fields the structure only inherits via imports.
+fn record_fields<'r>(
+    record: &'r Term,
+    structure: &'r MathStructure,
+) -> Vec<(&'r UriName, Option<&'r SymbolUri>, Option<&'r Term>)> {
+    use ftml_ontology::domain::declarations::IsDeclaration;
+    let Term::Application(app) = record else {
+        return Vec::new();
+    };
+    let find = |name: &UriName| {
+        structure
+            .fields()
+            .filter_map(IsDeclaration::uri)
+            .find(|uri| uri.name == *name)
+            .or_else(|| {
+                structure
+                    .fields()
+                    .filter_map(IsDeclaration::uri)
+                    .find(|uri| uri.name.last() == name.last())
+            })
+    };
+    app.arguments
+        .iter()
+        .flat_map(Argument::terms)
+        .filter_map(|t| match t {
+            Term::Label { name, df, .. } => Some((name, find(name), df.as_deref())),
+            _ => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ModuleTreeNode, field_default, record_fields};
+    use ftml_ontology::{
+        domain::{
+            declarations::{
+                Declaration,
+                morphisms::{Assignment, Elaboration, Morphism},
+                structures::{MathStructure, StructureDeclaration},
+                symbols::{Symbol, SymbolData},
+            },
+            modules::{ModuleData, ModuleLike, NestedModule},
+        },
+        terms::{ApplicationTerm, Argument, MaybeSequence, Numeric, Term, TermContainer},
+        utils::SourceRange,
+    };
+    use ftml_uris::{ModuleUri, SymbolUri, UriName, metatheory};
+
+    #[test]
+    fn defaulted_fields() {
+        let uri = |s: &str| -> SymbolUri { s.parse().expect("valid uri") };
+        let int = |i| Term::Number(Numeric::Int(i));
+        let field = |name: &str, df: Option<Term>| {
+            Declaration::Symbol(Symbol {
+                uri: uri(&format!("http://example.com?a=archive&m=struct&s={name}")),
+                data: Box::new(SymbolData {
+                    df: df.map(|t| TermContainer::new(t, None)).unwrap_or_default(),
+                    ..SymbolData::default()
+                }),
+            })
+        };
+        let domain = ModuleData {
+            uri: "http://example.com?a=archive&m=struct"
+                .parse()
+                .expect("valid uri"),
+            meta_module: None,
+            signature: None,
+            declarations: Box::new([
+                field("unit", Some(int(0))),
+                field("inv", Some(int(1))),
+                field("op", None),
+            ]),
+            source: SourceRange::DEFAULT,
+        }
+        .close();
+        let morph = uri("http://example.com?a=archive&m=mod&s=morph");
+        let assignment = |name: &str, definiens: Option<Term>| Assignment {
+            original: uri(&format!("http://example.com?a=archive&m=struct&s={name}")),
+            morphism: morph.clone(),
+            definiens,
+            refined_type: None,
+            new_name: None,
+            macroname: None,
+            source: SourceRange::DEFAULT,
+        };
+        let morphism = Morphism {
+            uri: morph.clone(),
+            domain: domain.uri.clone(),
+            total: false,
+            elements: Box::new([
+                assignment("unit", None),
+                assignment("inv", Some(int(2))),
+                assignment("op", None),
+            ]),
+            elaboration: Elaboration::default(),
+            source: SourceRange::DEFAULT,
+        };
+        let elaborated = morphism
+            .elaborate(|_| Ok::<_, &str>(ModuleLike::Module(domain.clone())))
+            .expect("domain is known");
+
+        // the original definiens, mapped along the morphism
+        let mapped = Term::Application(ApplicationTerm::new(
+            Term::Symbol {
+                uri: morph.clone(),
+                presentation: None,
+            },
+            Box::new([Argument::Simple(int(0))]),
+            None,
+        ));
+        assert_eq!(
+            field_default(&morphism.elements[0], elaborated),
+            Some(mapped)
+        );
+        assert_eq!(field_default(&morphism.elements[1], elaborated), None);
+        assert_eq!(field_default(&morphism.elements[2], elaborated), None);
+        assert_eq!(field_default(&morphism.elements[0], &[]), None);
+    }
+
+    #[test]
+    fn module_tree_children() {
+        let module = |name: &str| -> ModuleUri {
+            format!("http://example.com?a=archive&m={name}")
+                .parse()
+                .expect("valid uri")
+        };
+        let symbol = |name: &str| -> SymbolUri {
+            format!("http://example.com?a=archive&m=mod&s={name}")
+                .parse()
+                .expect("valid uri")
+        };
+        let import = |name: &str| Declaration::Import {
+            uri: module(name),
+            source: SourceRange::DEFAULT,
+        };
+        let inner = NestedModule {
+            uri: symbol("inner"),
+            declarations: Box::new([import("deep")]),
+            source: SourceRange::DEFAULT,
+        };
+        let outer = NestedModule {
+            uri: "http://example.com?a=archive&m=top&s=outer"
+                .parse()
+                .expect("valid uri"),
+            declarations: Box::new([
+                import("first"),
+                Declaration::Symbol(Symbol {
+                    uri: symbol("sym"),
+                    data: Box::default(),
+                }),
+                Declaration::NestedModule(inner.clone()),
+                import("second"),
+            ]),
+            source: SourceRange::DEFAULT,
+        };
+        let children = ModuleTreeNode::children(&outer);
+        assert_eq!(
+            children,
+            [
+                ModuleTreeNode::Import(module("first")),
+                ModuleTreeNode::Nested(module("mod/inner")),
+                ModuleTreeNode::Import(module("second")),
+            ]
+        );
+        assert_eq!(children[1].uri(), &module("mod/inner"));
+        assert_eq!(
+            ModuleTreeNode::children(&inner),
+            [ModuleTreeNode::Import(module("deep"))]
+        );
+    }
+
+    #[test]
+    fn record_fields_to_symbols() {
+        let field = |name: &str| -> SymbolUri {
+            format!("http://example.com?a=archive&m=mod&s=struct/{name}")
+                .parse()
+                .expect("valid uri")
+        };
+        let name = |name: &str| -> UriName { name.parse().expect("valid name") };
+        let label = |n: &str, value: i64| Term::Label {
+            name: name(n),
+            df: Some(Box::new(Term::Number(Numeric::Int(value)))),
+            tp: None,
+        };
+        let structure = MathStructure {
+            uri: "http://example.com?a=archive&m=mod&s=struct"
+                .parse()
+                .expect("valid uri"),
+            elements: [field("carrier"), field("op")]
+                .into_iter()
+                .map(|uri| {
+                    StructureDeclaration::Symbol(Symbol {
+                        uri,
+                        data: Box::default(),
+                    })
+                })
+                .collect(),
+            macroname: None,
+            source: SourceRange::DEFAULT,
+        };
+        let record = Term::Application(ApplicationTerm::new(
+            Term::Symbol {
+                uri: metatheory::ANONYMOUS_RECORD.clone(),
+                presentation: None,
+            },
+            Box::new([Argument::Sequence(MaybeSequence::Seq(Box::new([
+                label("struct/op", 1),
+                label("carrier", 2),
+                label("unit", 3),
+            ])))]),
+            None,
+        ));
+
+        let fields = record_fields(&record, &structure);
+        assert_eq!(
+            fields
+                .iter()
+                .map(|(label, field, _)| (label.to_string(), field.cloned()))
+                .collect::<Vec<_>>(),
+            [
+                ("struct/op".to_string(), Some(field("op"))),
+                ("carrier".to_string(), Some(field("carrier"))),
+                ("unit".to_string(), None),
+            ]
+        );
+        assert_eq!(fields[1].2, Some(&Term::Number(Numeric::Int(2))));
+        assert!(record_fields(&Term::Number(Numeric::Int(0)), &structure).is_empty());
+    }
+}
@@ -13,7 +13,10 @@ use ftml_dom::{
     },
 };
 use ftml_ontology::terms::{VarOrSym, Variable};
-use ftml_uris::{DocumentElementUri, DocumentUri, IsDomainUri, LeafUri, ModuleUri, SymbolUri};
+use ftml_uris::{
+    DocumentElementUri, DocumentUri, DomainUri, IsDomainUri, LeafUri, ModuleUri, NarrativeUri,
+    SymbolUri, Uri,
+};
 use leptos::prelude::*;
 
 pub trait FtmlViewable {
@@ -29,11 +32,64 @@ impl FtmlViewable for LeafUri {
     fn as_view(&self) -> AnyView {
         match self {
             Self::Symbol(s) => s.as_view(),
-            Self::Element(v) => variable_uri(v.name().last().to_string(), v),
+            Self::Element(v) => variable_uri(short_name(v.name().last()), v),
+        }
+    }
+}
+
+/// Delegates to the view of the respective variant; base, archive and path URIs (which have
+/// no dedicated view) are shown by name, with the full URI as tooltip.
+impl FtmlViewable for Uri {
+    fn as_view(&self) -> AnyView {
+        match self {
+            Self::Base(b) => plain_uri(b.to_string(), self),
+            Self::Archive(a) => plain_uri(short_name(a.id.as_ref()), self),
+            Self::Path(p) => plain_uri(
+                p.path
+                    .as_ref()
+                    .map_or_else(|| p.archive.id.to_string(), ToString::to_string),
+                self,
+            ),
+            Self::Module(m) => m.as_view(),
+            Self::Symbol(s) => s.as_view(),
+            Self::Document(d) => d.as_view(),
+            Self::DocumentElement(e) => e.as_view(),
+        }
+    }
+}
+/// Delegates to the view of the respective variant.
+impl FtmlViewable for DomainUri {
+    fn as_view(&self) -> AnyView {
+        match self {
+            Self::Module(m) => m.as_view(),
+            Self::Symbol(s) => s.as_view(),
+        }
+    }
+}
+/// Delegates to the view of the respective variant.
+impl FtmlViewable for NarrativeUri {
+    fn as_view(&self) -> AnyView {
+        match self {
+            Self::Document(d) => d.as_view(),
+            Self::Element(e) => e.as_view(),
         }
     }
 }
 
+/// Names (of symbols, documents, ...) longer than this many characters are
+/// [truncated](crate::utils::truncate_display) in the content views.
+pub const MAX_NAME_LENGTH: usize = 48;
+
+#[inline]
+pub(crate) fn short_name(name: &str) -> String {
+    crate::utils::truncate_display(name, MAX_NAME_LENGTH)
+}
+
+fn plain_uri(name: String, uri: &Uri) -> AnyView {
+    let uristring = uri.to_string();
+    view!(<Text class="ftml-comp" attr:title=uristring>{name}</Text>).into_any()
+}
+
 pub struct CommaSep<V: IntoView + 'static, I: IntoIterator<Item = V>>(pub &'static str, pub I);
 
 impl<V: IntoView + 'static, I: IntoIterator<Item = V>> CommaSep<V, I> {
@@ -64,7 +120,7 @@ pub fn uses<'a, I: IntoIterator<Item = &'a ModuleUri>>(header: &'static str, use
 impl FtmlViewable for DocumentUri {
     fn as_view(&self) -> AnyView {
         let uristring = self.to_string();
-        let name = self.name.to_string();
+        let name = short_name(self.name.as_ref());
         view! {
           <div style="display:inline-block;">
             <Text class="ftml-comp" attr:title=uristring>{name}</Text>
@@ -83,7 +139,7 @@ impl FtmlViewable for DocumentUri {
 
 impl FtmlViewable for DocumentElementUri {
     fn as_view(&self) -> AnyView {
-        let name = self.name.last().to_string();
+        let name = short_name(self.name.last());
         let title = view!(<Text class="ftml-comp">{name}</Text>).into_any();
         hover_paragraph(self.clone(), title)
     }
@@ -102,7 +158,7 @@ pub fn hover_paragraph(uri: DocumentElementUri, title: AnyView) -> AnyView {
           <div style="margin-bottom:5px;"><Divider/></div>
           <div class="ftml-symbol-popup">
           {
-              LocalCache::with_or_err(
+              FtmlConfig::nested_hover(move || LocalCache::with_or_err(
                   |b| b.get_fragment(crate::backend(),uri.into(), None),
                   |(html,css,_)| {
                       for c in css {
@@ -111,7 +167,7 @@ pub fn hover_paragraph(uri: DocumentElementUri, title: AnyView) -> AnyView {
                       crate::Views::render_ftml(html.into_string(),None).into_any()
                   },
                   |e| view!(<Code>{e.to_string()}</Code>).into_any()
-              )
+              ))
           }
           </div>
         </Popover>
@@ -122,7 +178,7 @@ pub fn hover_paragraph(uri: DocumentElementUri, title: AnyView) -> AnyView {
 #[must_use]
 pub fn module_with_hover(uri: &ModuleUri) -> AnyView {
     use ftml_component_utils::{Popover, PopoverTrigger};
-    let name = uri.module_name().to_string();
+    let name = short_name(uri.module_name().as_ref());
     let uri = uri.to_string();
     view! {
         <Popover>
@@ -138,7 +194,7 @@ pub fn module_with_hover(uri: &ModuleUri) -> AnyView {
 impl FtmlViewable for ModuleUri {
     fn as_view(&self) -> AnyView {
         use ftml_component_utils::{Dialog, DialogSurface, Popover, PopoverTrigger, Scrollbar};
-        let name = self.module_name().to_string();
+        let name = short_name(self.module_name().as_ref());
         let uri = self.to_string();
         let on_click = RwSignal::new(false);
         let origuri = self.clone();
@@ -167,7 +223,7 @@ impl FtmlViewable for ModuleUri {
 
 impl FtmlViewable for SymbolUri {
     fn as_view(&self) -> AnyView {
-        symbol_uri(self.name().last().to_string(), self)
+        symbol_uri(short_name(self.name().last()), self)
     }
 }
 
@@ -193,3 +249,25 @@ pub fn variable_uri(name: String, uri: &DocumentElementUri) -> AnyView {
     });
     super::terms::comp::comp_like(vos, None, false, move || view!(<Text>{name}</Text>)).into_any()
 }
+
+#[cfg(all(test, feature = "ssr"))]
+mod tests {
+    use super::FtmlViewable;
+    use crate::config::AllowHovers;
+    use ftml_uris::{SymbolUri, Uri};
+    use leptos::{prelude::*, tachys::view::RenderHtml};
+
+    #[test]
+    fn uri_delegates() {
+        let uri: SymbolUri = "http://example.com?a=archive&m=module&s=symbol"
+            .parse()
+            .expect("valid uri");
+        Owner::new().with(|| {
+            provide_context(AllowHovers(false));
+            assert_eq!(
+                Uri::Symbol(uri.clone()).as_view().to_html(),
+                uri.as_view().to_html()
+            );
+        });
+    }
+}
@@ -1,8 +1,12 @@
 use crate::components::content::{CommaSep, FtmlViewable};
 use ftml_component_utils::{Block, BoldCaption, Header, HeaderLeft, HeaderRight};
 use ftml_dom::{FtmlViews, notations::TermExt};
-use ftml_ontology::narrative::elements::{
-    DocumentElement, FlatIterable, LogicalParagraph, Section,
+use ftml_ontology::{
+    narrative::elements::{
+        DocumentElement, Figure, FigureKind, FlatIterable, LogicalParagraph, Section,
+        paragraphs::{ParagraphKind, ParagraphStepKind},
+    },
+    terms::IsTerm,
 };
 use ftml_uris::DocumentElementUri;
 use leptos::prelude::*;
@@ -17,7 +21,7 @@ pub(super) fn slide(
         uri.clone(),
         view!(<span style="font-style:italic;">{
             title.as_ref().map_or_else(
-                || Right(uri.name().last().to_string()),
+                || Right(super::short_name(uri.name().last())),
                 |t| Left(crate::Views::render_ftml((*t).to_string(), None)),
             )
         }</span>)
@@ -45,6 +49,42 @@ pub(super) fn slide(
     .into_any()
 }
 
+pub(super) fn figure(figure: &Figure) -> AnyView {
+    use leptos::either::Either::{Left, Right};
+    let Figure {
+        uri,
+        kind,
+        caption,
+        children,
+        ..
+    } = figure;
+    let prefix = match kind {
+        FigureKind::Figure => "Figure ",
+        FigureKind::Table => "Table ",
+    };
+    let caption = super::hover_paragraph(
+        uri.clone(),
+        view!(<span style="font-style:italic;">{
+            caption.as_ref().map_or_else(
+                || Right(super::short_name(uri.name().last())),
+                |t| Left(crate::Views::render_ftml(t.to_string(), None)),
+            )
+        }</span>)
+        .into_any(),
+    );
+    let children = children.iter().map(FtmlViewable::as_view).collect_view();
+
+    view! {
+      <Block>
+        <Header slot><BoldCaption>
+            {prefix}{caption}
+        </BoldCaption></Header>
+        {children}
+      </Block>
+    }
+    .into_any()
+}
+
 impl FtmlViewable for Section {
     fn as_view(&self) -> AnyView {
         use leptos::either::Either::{Left, Right};
@@ -55,7 +95,7 @@ impl FtmlViewable for Section {
             ..
         } = self;
         let title = title.as_ref().map_or_else(
-            || Right(uri.name().last().to_string()),
+            || Right(super::short_name(uri.name().last())),
             |t| Left(crate::Views::render_ftml(t.to_string(), None)),
         );
         let uses = children.iter().flat().filter_map(|e| {
@@ -113,6 +153,7 @@ impl FtmlViewable for LogicalParagraph {
             }
         });
         let uses = super::uses("Uses", uses);
+        let steps = proof_steps(self);
         let definition_like = kind.is_definition_like(styles);
         let kind = kind.as_display_str();
         let children = children.iter().map(FtmlViewable::as_view).collect_view();
@@ -142,8 +183,47 @@ impl FtmlViewable for LogicalParagraph {
             <HeaderLeft slot>{uses}</HeaderLeft>
             <HeaderRight slot>{fors}</HeaderRight>
             {children}
+            {steps}
           </Block>
         }
         .into_any()
     }
 }
+
+/// The (hierarchically numbered) steps of a structured proof, with the symbols referenced
+/// in their justifications linked.
+fn proof_steps(paragraph: &LogicalParagraph) -> Option<AnyView> {
+    if !matches!(
+        paragraph.kind,
+        ParagraphKind::Proof | ParagraphKind::SubProof
+    ) || paragraph.steps.is_empty()
+    {
+        return None;
+    }
+    let steps = paragraph
+        .numbered_steps()
+        .into_iter()
+        .map(|(num, step)| {
+            let indent = format!("margin-left:{}em;", num.matches('.').count());
+            let kind = match step.kind() {
+                ParagraphStepKind::Assumption => "Assume ",
+                ParagraphStepKind::Conclusion => "Hence ",
+                ParagraphStepKind::SubProof => "Subproof ",
+                ParagraphStepKind::ProofStep | ParagraphStepKind::EquationStep => "",
+            };
+            let yields = step.yields().map(|t| {
+                let t = t.clone().into_view_safe::<crate::Views>(crate::backend());
+                ftml_dom::utils::math(|| t)
+            });
+            let mut symbols = Vec::new();
+            for s in step.justification().into_iter().flat_map(IsTerm::symbols) {
+                if !symbols.contains(&s) {
+                    symbols.push(s);
+                }
+            }
+            let by = CommaSep("by", symbols.into_iter().map(FtmlViewable::as_view)).into_view();
+            view!(<div style=indent><b>{num}". "</b>{kind}{yields}" "{by}</div>)
+        })
+        .collect_view();
+    Some(view!(<div>{steps}</div>).into_any())
+}
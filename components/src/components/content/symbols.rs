@@ -14,9 +14,10 @@ use ftml_dom::{
 use ftml_ontology::{
     domain::declarations::symbols::{ArgumentSpec, Symbol, SymbolData},
     narrative::elements::{
-        Notation, ParagraphOrProblemKind, VariableDeclaration, variables::VariableData,
+        Notation, ParagraphOrProblemKind, VariableDeclaration, notations::NotationMap,
+        variables::VariableData,
     },
-    terms::{ArgumentMode, VarOrSym, Variable},
+    terms::{Argument, ArgumentMode, Term, VarOrSym, Variable},
 };
 use ftml_uris::{DocumentElementUri, Id, IsNarrativeUri, LeafUri, SymbolUri};
 use leptos::{html::span, prelude::*};
@@ -48,7 +49,7 @@ pub fn symbol_view(s: &Symbol, show_paras: bool) -> AnyView {
         tp,
         df,
         return_type,
-        argument_types,
+        arg_types,
         assoctype,
         reordering,
         ..
@@ -61,7 +62,7 @@ pub fn symbol_view(s: &Symbol, show_paras: bool) -> AnyView {
         "Symbol "
     };
     let name = span()
-        .child(uri.name().last().to_string())
+        .child(super::short_name(uri.name().last()))
         .title(uri.to_string());
     let macroname = macroname.as_ref().map(|n| do_macroname(n, arity));
     let tp = tp.map(|t| {
@@ -104,6 +105,145 @@ pub fn symbol_view(s: &Symbol, show_paras: bool) -> AnyView {
 }
 //}
 
+/// An argument in the signature of a [`Symbol`]; see [`signature_arguments`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SignatureArgument<'s> {
+    pub mode: ArgumentMode,
+    /// The type of the argument, if known (without the implicit marker).
+    pub tp: Option<&'s Term>,
+    pub implicit: bool,
+}
+
+/// The arguments of the signature of a symbol with the given `arity` and `arg_types`
+/// (see [`SymbolData::arg_types`]), in order. If there are more argument types than
+/// modes in `arity`, the additional arguments are [`Simple`](ArgumentMode::Simple); if there
+/// are fewer, the types of the remaining arguments are unknown.
+///
+/// An argument is *implicit*, if its type is marked as such by the metatheory (see
+/// [`is_implicit_argument`](ftml_dom::notations::is_implicit_argument)); its
+/// [`tp`](SignatureArgument::tp) is then the marked type.
+#[must_use]
+pub fn signature_arguments<'s>(
+    arity: &ArgumentSpec,
+    arg_types: &'s [Option<Term>],
+) -> Vec<SignatureArgument<'s>> {
+    let len = arg_types.len().max(arity.num() as usize);
+    let mut modes = arity.iter();
+    (0..len)
+        .map(|i| {
+            let mode = modes.next().unwrap_or_default();
+            match arg_types.get(i).and_then(Option::as_ref) {
+                Some(t @ Term::Application(app))
+                    if ftml_dom::notations::is_implicit_argument(t, ArgumentMode::Simple) =>
+                {
+                    let tp = match app.arguments.first() {
+                        Some(Argument::Simple(t)) => Some(t),
+                        _ => None,
+                    };
+                    SignatureArgument {
+                        mode,
+                        tp,
+                        implicit: true,
+                    }
+                }
+                tp => SignatureArgument {
+                    mode,
+                    tp,
+                    implicit: false,
+                },
+            }
+        })
+        .collect()
+}
+
+/// Assembles the signature of a symbol as `name : A → B → C` (see [`symbol_signature`] for the
+/// layout) from the given `name`, the known types rendered via `tp` and punctuation (including
+/// unknown types) rendered via `text`; or `None` if neither its
+/// [`arg_types`](SymbolData::arg_types) nor its [`return_type`](SymbolData::return_type) are
+/// known.
+fn signature_parts<T>(
+    name: T,
+    data: &SymbolData,
+    tp: impl Fn(&Term) -> T,
+    text: impl Fn(&'static str) -> T,
+) -> Option<Vec<T>> {
+    if data.arg_types.is_empty() && data.return_type.is_none() {
+        return None;
+    }
+    let tp = |t: Option<&Term>| t.map_or_else(|| text("?"), &tp);
+    let mut parts = vec![name, text(" : ")];
+    for arg in signature_arguments(&data.arity, &data.arg_types) {
+        if arg.implicit {
+            parts.extend([text("{"), tp(arg.tp), text("}")]);
+        } else {
+            parts.push(tp(arg.tp));
+        }
+        if is_sequence(arg.mode) {
+            parts.push(text("*"));
+        }
+        parts.push(text(" → "));
+    }
+    parts.push(tp(data.return_type.as_ref()));
+    Some(parts)
+}
+
+/// The [signature](signature_parts) of the symbol `name` as plain text; types that are symbols
+/// are given by name, others as LaTeX (without notations).
+fn signature_string(name: &str, data: &SymbolData) -> Option<String> {
+    fn tp_string(tp: &Term) -> String {
+        match tp {
+            Term::Symbol { uri, .. } => uri.name().last().to_string(),
+            t => t
+                .to_latex(&NotationMap::default())
+                .unwrap_or_else(|| "?".to_string()),
+        }
+    }
+    signature_parts(name.to_string(), data, tp_string, str::to_string).map(|p| p.concat())
+}
+
+const fn is_sequence(mode: ArgumentMode) -> bool {
+    matches!(
+        mode,
+        ArgumentMode::Sequence | ArgumentMode::BoundVariableSequence
+    )
+}
+
+/// Renders the signature of the symbol `uri` as `name : A → B → C`, i.e. the types of its
+/// arguments (see [`SymbolData::arg_types`]) followed by its
+/// [`return_type`](SymbolData::return_type); types that are symbols link to them, unknown
+/// types are shown as `?`. Sequence arguments are marked with a trailing `*` (e.g. `A*`),
+/// implicit arguments (see [`signature_arguments`]) are wrapped in braces (e.g. `{A}`).
+/// Symbols without argument or return types are rendered by name only.
+#[must_use]
+pub fn symbol_signature(uri: SymbolUri) -> AnyView {
+    LocalCache::with_or_toast(
+        move |e| e.get_symbol(crate::backend(), uri),
+        |s| match s {
+            either::Either::Left(s) => signature_view(&s),
+            either::Either::Right(s) => signature_view(&s),
+        },
+        || "error".into_any(),
+    )
+}
+
+fn signature_view(s: &Symbol) -> AnyView {
+    fn tp_view(tp: &Term) -> AnyView {
+        match tp {
+            Term::Symbol { uri, .. } => uri.as_view(),
+            t => {
+                let t = t.clone();
+                ftml_dom::utils::math(move || t.into_view::<crate::Views>(crate::backend(), false))
+                    .into_any()
+            }
+        }
+    }
+    let title = signature_string(s.uri.name().last(), &s.data);
+    signature_parts(s.uri.as_view(), &s.data, tp_view, IntoAny::into_any).map_or_else(
+        || s.uri.as_view(),
+        |parts| view!(<span class="ftml-signature" title=title>{parts}</span>).into_any(),
+    )
+}
+
 impl super::FtmlViewable for VariableDeclaration {
     fn as_view(&self) -> AnyView {
         let Self { uri, data } = self;
@@ -124,7 +264,7 @@ impl super::FtmlViewable for VariableDeclaration {
         let tp = tp.presentation();
         let df = df.presentation();
         let name = span()
-            .child(uri.name().last().to_string())
+            .child(super::short_name(uri.name().last()))
             .title(uri.to_string());
         let macroname = macroname.as_ref().map(|n| do_macroname(n, arity));
         let tp = tp.map(|t| {
@@ -279,3 +419,55 @@ fn do_table(
         .into_any()
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::signature_string;
+    use ftml_ontology::{
+        domain::declarations::symbols::SymbolData,
+        terms::{Term, helpers::IntoTerm},
+    };
+    use ftml_uris::{Id, SymbolUri};
+
+    #[test]
+    fn signature() {
+        let sym = |name: &str| -> SymbolUri {
+            format!("http://example.com?a=archive&m=mod&s={name}")
+                .parse()
+                .expect("valid uri")
+        };
+        let mut data = SymbolData::default();
+        assert_eq!(signature_string("f", &data), None);
+
+        data.arity = "iai".parse().expect("valid argument spec");
+        data.arg_types = Box::new([
+            Some(sym("A").into()),
+            Some(sym("B").into()),
+            Some(
+                ftml_uris::metatheory::APPLY_IMPLICIT
+                    .clone()
+                    .apply_tms([Term::from(sym("C"))]),
+            ),
+        ]);
+        data.return_type = Some(sym("D").into());
+        assert_eq!(
+            signature_string("f", &data).as_deref(),
+            Some("f : A → B* → {C} → D")
+        );
+
+        // missing types
+        data.arg_types = Box::new([None, Some(sym("A").into())]);
+        data.return_type = None;
+        assert_eq!(
+            signature_string("f", &data).as_deref(),
+            Some("f : ? → A* → ? → ?")
+        );
+
+        // types that are not symbols
+        data.return_type = Some("T".parse::<Id>().expect("valid id").into());
+        assert_eq!(
+            signature_string("f", &data).as_deref(),
+            Some("f : ? → A* → ? → T")
+        );
+    }
+}
@@ -136,20 +136,7 @@ fn do_notation_selector(
     use ftml_dom::notations::NotationExt;
     use leptos::prelude::*;
     tracing::warn!("Rendering notation selector");
-    let mut all = notations.local.unwrap_or_default();
-    match notations.global {
-        None => (),
-        Some(Err(e)) => {
-            crate::utils::error_toast(e.to_string());
-        }
-        Some(Ok(v)) => {
-            for (u, n) in v {
-                if !all.iter().any(|(u2, _)| *u2 == u) {
-                    all.push((u, n));
-                }
-            }
-        }
-    }
+    let all = all_notations(notations);
     let current = FtmlConfig::notation_preference_signal(uri);
     let string_signal = RwSignal::new(String::new());
     let mut has_changed = false;
@@ -194,3 +181,245 @@ fn do_notation_selector(
         </div></div>
     }
 }
+
+/// The local notations followed by those global ones not already known locally; errors
+/// are reported via [`error_toast`](crate::utils::error_toast).
+fn all_notations(
+    notations: GlobalLocal<Vec<(DocumentElementUri, Notation)>, ftml_backend::BackendError<String>>,
+) -> Vec<(DocumentElementUri, Notation)> {
+    let mut all = notations.local.unwrap_or_default();
+    match notations.global {
+        None => (),
+        Some(Err(e)) => {
+            crate::utils::error_toast(e.to_string());
+        }
+        Some(Ok(v)) => {
+            for (u, n) in v {
+                if !all.iter().any(|(u2, _)| *u2 == u) {
+                    all.push((u, n));
+                }
+            }
+        }
+    }
+    all
+}
+
+/// The key that cycles through the available notations of the *active* symbol (with
+/// <kbd>Shift</kbd>: backwards); see [`notation_shortcut_target`].
+pub const NOTATION_SHORTCUT_KEY: &str = "n";
+
+/// Registers `uri` as the target of the notation shortcut ([`NOTATION_SHORTCUT_KEY`])
+/// whenever `active` is true; i.e. while the symbol is hovered (its popover is open) or has
+/// keyboard focus. If several symbols are active at the same time (e.g. one focused, another
+/// hovered), the most recently activated one wins.
+///
+/// Pressing the key (without <kbd>Ctrl</kbd>, <kbd>Alt</kbd> or <kbd>Meta</kbd>, and not in
+/// an input field) then moves the symbol's [notation preference](FtmlConfig::notation_preference)
+/// to the next available notation, wrapping around via "no preference" (i.e. the notation
+/// used in the document).
+pub(crate) fn notation_shortcut_target(uri: LeafUri, active: Signal<bool>) {
+    let store = crate::utils::ReactiveStore::get();
+    let _ = Effect::new(move || {
+        let active = active.get();
+        store.update_value(|s| {
+            if active {
+                s.shortcut_target = Some(uri.clone());
+            } else if s.shortcut_target.as_ref() == Some(&uri) {
+                s.shortcut_target = None;
+            }
+        });
+    });
+}
+
+/// The symbols currently taking part in the roving tabindex (see [`roving_tabindex`]) by id,
+/// and the one that was focused last (if still rendered).
+#[derive(Copy, Clone)]
+pub(crate) struct TabStops {
+    ids: RwSignal<std::collections::BTreeSet<u64>>,
+    focused: RwSignal<Option<u64>>,
+    /// The symbol in the tab order: the one focused last, or else the first one rendered.
+    current: Memo<Option<u64>>,
+    next_id: u64,
+}
+impl TabStops {
+    pub(crate) fn new() -> Self {
+        let ids = RwSignal::new(std::collections::BTreeSet::new());
+        let focused = RwSignal::new(None);
+        Self {
+            ids,
+            focused,
+            current: Memo::new(move |_| {
+                focused
+                    .get()
+                    .or_else(|| ids.with(|ids| ids.first().copied()))
+            }),
+            next_id: 0,
+        }
+    }
+}
+
+/// The attribute marking the symbols taking part in the roving tabindex.
+pub(crate) const ROVING_ATTR: &str = "data-roving-tabindex";
+
+/// Makes a symbol the notation shortcut applies to reachable via the keyboard using a
+/// *roving tabindex*: Of all such symbols, only one is in the tab order (`tabindex="0"`) -
+/// the one focused last, or initially the first one rendered; all others have
+/// `tabindex="-1"` and are reached from there with the arrow keys (see [`on_roving_keydown`]).
+/// Hence <kbd>Tab</kbd> does not have to step through every symbol on the page.
+///
+/// Returns the (reactive) `tabindex` of the new symbol and the callback to invoke when it
+/// receives focus; the symbol is removed again when the current reactive owner is cleaned up.
+pub(crate) fn roving_tabindex() -> (Signal<&'static str>, impl Fn() + Copy + Send + 'static) {
+    let store = crate::utils::ReactiveStore::get();
+    let (stops, id) = store.update_value(|s| {
+        let id = s.tab_stops.next_id;
+        s.tab_stops.next_id += 1;
+        (s.tab_stops, id)
+    });
+    stops.ids.update(|ids| {
+        ids.insert(id);
+    });
+    on_cleanup(move || {
+        stops.ids.try_update(|ids| ids.remove(&id));
+        if stops.focused.try_get_untracked().flatten() == Some(id) {
+            stops.focused.try_set(None);
+        }
+    });
+    let tabindex = Signal::derive(move || {
+        if stops.current.get() == Some(id) {
+            "0"
+        } else {
+            "-1"
+        }
+    });
+    (tabindex, move || stops.focused.set(Some(id)))
+}
+
+/// Handles a `keydown` event on a symbol taking part in the roving tabindex (see
+/// [`roving_tabindex`]): the arrow keys move the focus to the next (<kbd>→</kbd>,
+/// <kbd>↓</kbd>) or previous (<kbd>←</kbd>, <kbd>↑</kbd>) such symbol in document order.
+#[cfg(any(feature = "csr", feature = "hydrate"))]
+pub(crate) fn on_roving_keydown(event: &leptos::web_sys::KeyboardEvent) {
+    use leptos::wasm_bindgen::JsCast;
+    let forward = match event.key().as_str() {
+        "ArrowRight" | "ArrowDown" => true,
+        "ArrowLeft" | "ArrowUp" => false,
+        _ => return,
+    };
+    let Some(current) = event
+        .current_target()
+        .and_then(|t| t.dyn_into::<leptos::web_sys::Node>().ok())
+    else {
+        return;
+    };
+    let Ok(all) = document().query_selector_all(&format!("[{ROVING_ATTR}]")) else {
+        return;
+    };
+    let Some(index) =
+        (0..all.length()).find(|i| all.item(*i).is_some_and(|n| n.is_same_node(Some(&current))))
+    else {
+        return;
+    };
+    let next = if forward {
+        index.checked_add(1)
+    } else {
+        index.checked_sub(1)
+    };
+    if let Some(next) = next.and_then(|i| all.item(i)) {
+        event.prevent_default();
+        // symbols are usually MathML elements; `focus` is called structurally, so this works
+        // for those as well
+        let _ = next
+            .unchecked_into::<leptos::web_sys::HtmlElement>()
+            .focus();
+    }
+}
+
+/// Handles a `keydown` event for the notation shortcut; see [`notation_shortcut_target`].
+#[cfg(any(feature = "csr", feature = "hydrate"))]
+pub(crate) fn on_notation_shortcut(
+    store: &crate::utils::ReactiveStore,
+    event: &leptos::web_sys::KeyboardEvent,
+) {
+    use leptos::wasm_bindgen::JsCast;
+    if !event.key().eq_ignore_ascii_case(NOTATION_SHORTCUT_KEY)
+        || event.ctrl_key()
+        || event.alt_key()
+        || event.meta_key()
+    {
+        return;
+    }
+    let in_input = event
+        .target()
+        .and_then(|t| t.dyn_into::<leptos::web_sys::Element>().ok())
+        .is_some_and(|e| {
+            matches!(e.tag_name().as_str(), "INPUT" | "TEXTAREA" | "SELECT")
+                || e.has_attribute("contenteditable")
+        });
+    if in_input {
+        return;
+    }
+    let Some(uri) = store.with_value(|s| s.shortcut_target.clone()) else {
+        return;
+    };
+    event.prevent_default();
+    let forward = !event.shift_key();
+    let current = store.with(|| FtmlConfig::notation_preference_signal(&uri));
+    leptos::task::spawn_local(async move {
+        let notations = LocalCache::get().get_notations(crate::backend(), uri).await;
+        let available = all_notations(notations)
+            .into_iter()
+            .map(|(u, _)| u)
+            .collect::<Vec<_>>();
+        let next = current.with_untracked(|c| next_notation(&available, c.as_ref(), forward));
+        tracing::debug!("cycling notation to {next:?}");
+        current.set(next);
+    });
+}
+
+/// The notation following `current` in `available`, where "no preference" (`None`) precedes
+/// the first one; wraps around in both directions. An unknown `current` counts as `None`.
+#[cfg_attr(not(any(feature = "csr", feature = "hydrate")), allow(dead_code))]
+fn next_notation(
+    available: &[DocumentElementUri],
+    current: Option<&DocumentElementUri>,
+    forward: bool,
+) -> Option<DocumentElementUri> {
+    let len = available.len() + 1;
+    let index = current
+        .and_then(|c| available.iter().position(|a| a == c))
+        .map_or(0, |i| i + 1);
+    let next = if forward {
+        (index + 1) % len
+    } else {
+        (index + len - 1) % len
+    };
+    next.checked_sub(1).and_then(|i| available.get(i).cloned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::next_notation;
+    use ftml_uris::DocumentElementUri;
+
+    #[test]
+    fn cycle_notations() {
+        let available = ["a", "b"]
+            .map(|n| {
+                format!("http://example.com?a=archive&d=doc&l=en&e={n}")
+                    .parse::<DocumentElementUri>()
+                    .expect("valid uri")
+            })
+            .to_vec();
+        let [a, b] = [&available[0], &available[1]];
+
+        assert_eq!(next_notation(&available, None, true).as_ref(), Some(a));
+        assert_eq!(next_notation(&available, Some(a), true).as_ref(), Some(b));
+        assert_eq!(next_notation(&available, Some(b), true), None);
+
+        assert_eq!(next_notation(&available, None, false).as_ref(), Some(b));
+        assert_eq!(next_notation(&available, Some(a), false), None);
+
+        assert_eq!(next_notation(&[], None, true), None);
+    }
+}
@@ -21,7 +21,7 @@ pub fn paragraph<V: IntoView>(
     }
     if info.formatting == ParagraphFormatting::Inline {
         B(view! {
-            <span class=info.class style=info.style>{
+            <span id=info.uri.anchor_id() class=info.class style=info.style>{
                 FtmlConfig::wrap_paragraph(&info.uri,info.kind,then)
             }</span>
         })
@@ -30,7 +30,7 @@ pub fn paragraph<V: IntoView>(
         //leptos::logging::log!("Here: Class: {:?}; Style: {:?}", info.class, info.style);
         C(
             view! {
-                <div class=info.class style=info.style>{
+                <div id=info.uri.anchor_id() class=info.class style=info.style>{
                     FtmlConfig::wrap_paragraph(&info.uri,info.kind,then)
                 }</div>
             }, //.attr("data-uri", uri)
@@ -3,7 +3,7 @@ use ftml_dom::utils::css::inject_css;
 use ftml_dom::utils::local_cache::LocalCache;
 use ftml_js_utils::JsDisplay;
 use ftml_ontology::narrative::elements::problems::{
-    BlockFeedback, CheckedResult, ChoiceBlockStyle, FillinFeedback, FillinFeedbackKind,
+    BlockFeedback, CheckedResult, ChoiceBlockStyle, FillInSol, FillinFeedback, FillinFeedbackKind,
     ProblemFeedback, ProblemFeedbackJson, ProblemResponse, ProblemResponseType, SolutionData,
     Solutions,
 };
@@ -135,6 +135,7 @@ pub struct CurrentProblem {
     interactive: bool,
     has_subproblems: RwSignal<bool>,
     feedback: RwSignal<Option<ProblemFeedback>>,
+    points: Option<f32>,
 }
 impl std::fmt::Debug for CurrentProblem {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -207,6 +208,7 @@ pub fn problem<V: IntoView>(
         has_subproblems: RwSignal::new(false),
         responses: RwSignal::new(Vec::new()),
         feedback: RwSignal::new(None),
+        points,
     };
     let responses = ex.responses;
     tracing::debug!("Problem {}", ex.uri);
@@ -256,12 +258,14 @@ pub fn problem<V: IntoView>(
 
     let uri = ex.uri.clone();
     let uri2 = uri.clone();
+    let anchor = uri.anchor_id();
     let has_subproblems = ex.has_subproblems;
     let autogradable = ex.autogradable;
     provide_context(ex);
     let inner = view! {
       //<Provider value=ForcedName::default()>
-        <div class=class style=style>
+        <div id=anchor class=class style=style>
+          {minutes.map(estimated_time)}
           {
             let r = children();
             match is_done {
@@ -304,6 +308,7 @@ fn submit_answer() -> AnyView {
         let uri = current.uri.clone();
         let responses = current.responses;
         let feedback = current.feedback;
+        let points = current.points;
         move || {
             if feedback.with(Option::is_none) {
                 let do_solution = move |uri: &_, r: &Solutions| {
@@ -356,15 +361,39 @@ fn submit_answer() -> AnyView {
                   <div style="margin:5px 0;"><div style="margin-left:auto;width:fit-content;">
                     <Button size=ButtonSize::Small on_click=move |_| {foract()}>"Submit Answer"</Button>
                   </div></div>
-                })
+                }.into_any())
             } else {
-                None
+                points.map(|points| score(feedback, points))
             }
         }
     }).into_any()
 }
 }
 
+fn estimated_time(minutes: f32) -> impl IntoView {
+    use ftml_component_utils::Caption;
+    view! {
+      <div style="margin:5px 0;"><div style="margin-left:auto;width:fit-content;">
+        <Caption>{format!("Estimated time: {} min", FtmlConfig::format_minutes(minutes))}</Caption>
+      </div></div>
+    }
+}
+
+fn score(feedback: RwSignal<Option<ProblemFeedback>>, points: f32) -> AnyView {
+    use ftml_component_utils::Caption;
+    let score = feedback.with(|f| f.as_ref().map_or(0.0, |f| f.score_fraction * points));
+    view! {
+      <div style="margin:5px 0;"><div style="margin-left:auto;width:fit-content;">
+        <Caption>{format!(
+            "Score: {} / {}",
+            FtmlConfig::format_points(score),
+            FtmlConfig::format_points(points)
+        )}</Caption>
+      </div></div>
+    }
+    .into_any()
+}
+
 pub fn hint<V: IntoView + 'static>(children: impl FnOnce() -> V + Send + 'static) -> impl IntoView {
     use ftml_component_utils::{Collapsible, Header};
     view! {
@@ -394,7 +423,7 @@ pub fn fillinsol(wd: Option<f32>) -> impl IntoView {
     };
     let feedback = ex.feedback;
     Some(move || {
-        let style = wd.map(|wd| format!("width:{wd}px;"));
+        let style = wd.map(|wd| format!("width:{}px;", FillInSol::clamp_width(Some(wd))));
         feedback.with(|v|
     if let Some(feedback) = v.as_ref() {
       let err = || {
@@ -4,6 +4,7 @@ use ftml_dom::{
     DocumentState, counters::LogicalLevel, structure::SectionInfo, utils::css::inject_css,
 };
 use ftml_ontology::narrative::elements::SectionLevel;
+use ftml_uris::DocumentElementUri;
 use leptos::prelude::*;
 use leptos_posthoc::OriginalNode;
 use send_wrapper::SendWrapper;
@@ -32,6 +33,11 @@ pub fn section<V: IntoView>(info: SectionInfo, children: impl FnOnce() -> V) ->
         }
     }
     let visible = RwSignal::new(true);
+    if let Some(focus) = use_context::<FocusState>() {
+        let index = focus.register(info.uri.clone(), info.id.to_string());
+        on_cleanup(move || focus.unregister(index));
+        Effect::new(move || visible.set(focus.is_expanded(index)));
+    }
     let uri = info.uri.clone();
     let inner = fancy_collapsible(
         move || {
@@ -72,6 +78,163 @@ pub fn section<V: IntoView>(info: SectionInfo, children: impl FnOnce() -> V) ->
     })
 }
 
+/// The state of [focus mode](FtmlConfig::focus_mode): the (collapsible) sections of the
+/// document, in document order, and the active one.
+#[derive(Clone, Copy)]
+pub(crate) struct FocusState {
+    sections: StoredValue<Vec<FocusEntry>>,
+    active: RwSignal<Option<usize>>,
+    expanded: Memo<Vec<bool>>,
+}
+struct FocusEntry {
+    uri: DocumentElementUri,
+    /// empty once the section is removed
+    id: String,
+    /// the index of the closest enclosing section
+    parent: Option<usize>,
+}
+impl FocusState {
+    pub(crate) fn new() -> Self {
+        let sections = StoredValue::new(Vec::<FocusEntry>::new());
+        let active = RwSignal::new(None);
+        let expanded = Memo::new(move |_| {
+            let active = active.get();
+            sections.with_value(|s| {
+                let parents = s.iter().map(|e| e.parent).collect::<Vec<_>>();
+                expanded_sections(&parents, active)
+            })
+        });
+        let state = Self {
+            sections,
+            active,
+            expanded,
+        };
+        #[cfg(any(feature = "csr", feature = "hydrate"))]
+        state.listen();
+        state
+    }
+
+    fn register(self, uri: DocumentElementUri, id: String) -> usize {
+        self.sections
+            .try_update_value(|sections| {
+                let parent = std::iter::successors(uri.parent(), DocumentElementUri::parent)
+                    .find_map(|p| sections.iter().rposition(|e| e.uri == p));
+                sections.push(FocusEntry { uri, id, parent });
+                sections.len() - 1
+            })
+            .unwrap_or_default()
+    }
+
+    fn unregister(self, index: usize) {
+        self.sections.update_value(|sections| {
+            if let Some(e) = sections.get_mut(index) {
+                e.id.clear();
+            }
+        });
+    }
+
+    fn is_expanded(self, index: usize) -> bool {
+        self.expanded
+            .with(|expanded| expanded.get(index).copied().unwrap_or(true))
+    }
+
+    /// Tracks the active section while scrolling (like the table of contents) and on
+    /// navigation (e.g. via the table of contents) to a section.
+    #[cfg(any(feature = "csr", feature = "hydrate"))]
+    fn listen(self) {
+        use leptos::ev;
+        fn top(id: &str) -> Option<f64> {
+            let rect = document().get_element_by_id(id)?.get_bounding_client_rect();
+            // collapsed
+            (rect.height() > 0.0 || rect.width() > 0.0).then(|| rect.top())
+        }
+
+        let on_scroll = move || {
+            // only sections that are currently displayed
+            let (indices, tops): (Vec<_>, Vec<_>) = self.sections.with_value(|sections| {
+                sections
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(i, e)| top(&e.id).map(|t| (i, Some(t))))
+                    .unzip()
+            });
+            let Some(active) = ftml_component_utils::active_anchor(tops).map(|i| indices[i]) else {
+                return;
+            };
+            if self.active.get_untracked() == Some(active) {
+                return;
+            }
+            // keep the new active section in place while the others collapse
+            let id = self
+                .sections
+                .with_value(|s| s.get(active).map(|e| e.id.clone()));
+            let Some(id) = id else { return };
+            let before = top(&id);
+            self.active.set(Some(active));
+            request_animation_frame(move || {
+                if let (Some(before), Some(after)) = (before, top(&id)) {
+                    window().scroll_by_with_x_and_y(0.0, after - before);
+                }
+            });
+        };
+        let cb =
+            ftml_component_utils::js::throttle(on_scroll, std::time::Duration::from_millis(200));
+        let scroll_handle = ftml_component_utils::js::add_event_listener_with_bool(
+            document(),
+            ev::scroll,
+            move |_| cb(),
+            true,
+        );
+        let hash_handle = window_event_listener(ev::hashchange, move |_| {
+            let Ok(hash) = window().location().hash() else {
+                return;
+            };
+            let id = hash.trim_start_matches('#').to_string();
+            let Some(index) = self
+                .sections
+                .with_value(|s| s.iter().position(|e| !id.is_empty() && e.id == id))
+            else {
+                return;
+            };
+            self.active.set(Some(index));
+            request_animation_frame(move || {
+                if let Some(el) = document().get_element_by_id(&id) {
+                    el.scroll_into_view();
+                }
+            });
+        });
+        on_cleanup(move || {
+            scroll_handle.remove();
+            hash_handle.remove();
+        });
+    }
+}
+
+/// Which of the sections (given by the indices of their closest enclosing sections, in
+/// document order) are expanded in focus mode, if `active` is the active one: the active
+/// section itself, its enclosing sections and its subsections. Without an active section,
+/// all are.
+fn expanded_sections(parents: &[Option<usize>], active: Option<usize>) -> Vec<bool> {
+    let Some(active) = active.filter(|a| *a < parents.len()) else {
+        return vec![true; parents.len()];
+    };
+    let mut expanded = vec![false; parents.len()];
+    let mut current = Some(active);
+    while let Some(i) = current {
+        expanded[i] = true;
+        current = parents[i];
+    }
+    // enclosing sections precede their subsections
+    for i in active + 1..parents.len() {
+        if let Some(p) = parents[i]
+            && (p == active || (p > active && expanded[p]))
+        {
+            expanded[i] = true;
+        }
+    }
+    expanded
+}
+
 pub fn section_title(class: &'static str, children: OriginalNode) -> impl IntoView {
     use leptos::either::Either::{Left, Right};
     let lvl = DocumentState::current_section_level();
@@ -129,3 +292,36 @@ pub fn position_marker(pos: &HtmlDivElement, marker: &HtmlDivElement) {
         ),
     );
 }
+
+#[cfg(test)]
+mod tests {
+    use super::expanded_sections;
+
+    #[test]
+    fn focused_sections() {
+        // 0: Chapter 1
+        //   1: Section 1.1
+        //     2: Subsection 1.1.1
+        //   3: Section 1.2
+        // 4: Chapter 2
+        //   5: Section 2.1
+        let parents = [None, Some(0), Some(1), Some(0), None, Some(4)];
+        assert_eq!(expanded_sections(&parents, None), [true; 6]);
+        assert_eq!(
+            expanded_sections(&parents, Some(1)),
+            [true, true, true, false, false, false]
+        );
+        assert_eq!(
+            expanded_sections(&parents, Some(2)),
+            [true, true, true, false, false, false]
+        );
+        assert_eq!(
+            expanded_sections(&parents, Some(4)),
+            [false, false, false, false, true, true]
+        );
+        assert_eq!(
+            expanded_sections(&parents, Some(3)),
+            [true, false, false, true, false, false]
+        );
+    }
+}
@@ -13,14 +13,27 @@ pub fn do_sidebar(
     pdf_link: bool,
     choose_highlight_style: bool,
     floating: bool,
+    rtl: bool,
     children: impl FnOnce() -> AnyView,
 ) -> AnyView {
     inject_css("ftml-sidebar", include_str!("./sidebar.css"));
 
     if floating {
-        floating_sidebar(show_content, pdf_link, choose_highlight_style, children)
+        floating_sidebar(
+            show_content,
+            pdf_link,
+            choose_highlight_style,
+            rtl,
+            children,
+        )
     } else {
-        flex_sidebar(show_content, pdf_link, choose_highlight_style, children)
+        flex_sidebar(
+            show_content,
+            pdf_link,
+            choose_highlight_style,
+            rtl,
+            children,
+        )
     }
 }
 
@@ -28,18 +41,21 @@ fn flex_sidebar(
     show_content: bool,
     pdf_link: bool,
     choose_highlight_style: bool,
+    rtl: bool,
     children: impl FnOnce() -> AnyView,
 ) -> AnyView {
     use ftml_component_utils::{Button, ButtonShape, ButtonSize, Flex};
 
     let visible = RwSignal::new(true);
+    let content = NodeRef::new();
     let body = fancy_collapsible(
         move || {
             view! {
                 {if choose_highlight_style {Some(select_highlighting())} else {None}}
                 <Flex>
-                    {if show_content {Some(content_drawer())} else {None}}
+                    {if show_content {Some(content_drawer(rtl))} else {None}}
                     {if pdf_link {Some(pdf())} else {None}}
+                    {export(content)}
                 </Flex>
                 {super::toc::toc()}
             }
@@ -49,7 +65,7 @@ fn flex_sidebar(
         "width:fit-content;",
     );
 
-    let children = view!(<div>{children()}</div>);
+    let children = view!(<div node_ref=content>{children()}</div>);
 
     view! {
         <Flex>
@@ -75,6 +91,7 @@ fn floating_sidebar(
     show_content: bool,
     pdf_link: bool,
     choose_highlight_style: bool,
+    rtl: bool,
     children: impl FnOnce() -> AnyView,
 ) -> AnyView {
     use ftml_component_utils::{Button, ButtonShape, ButtonSize, Flex};
@@ -85,7 +102,7 @@ fn floating_sidebar(
         if let Some(pos_ref) = pos_ref.get()
             && let Some(sidebar_ref) = sidebar_ref.get()
         {
-            position_sidebar(&pos_ref, &sidebar_ref);
+            position_sidebar(&pos_ref, &sidebar_ref, rtl);
         }
     });
     let visible = RwSignal::new(true);
@@ -94,8 +111,9 @@ fn floating_sidebar(
             view! {
                 {if choose_highlight_style {Some(select_highlighting())} else {None}}
                 <Flex>
-                    {if show_content {Some(content_drawer())} else {None}}
+                    {if show_content {Some(content_drawer(rtl))} else {None}}
                     {if pdf_link {Some(pdf())} else {None}}
+                    {export(pos_ref)}
                 </Flex>
                 {super::toc::toc()}
             }
@@ -125,7 +143,7 @@ fn floating_sidebar(
     .into_any()
 }
 
-fn position_sidebar(position: &HtmlDivElement, sidebar: &HtmlDivElement) {
+fn position_sidebar(position: &HtmlDivElement, sidebar: &HtmlDivElement, rtl: bool) {
     use leptos::wasm_bindgen::JsCast;
 
     // hacky: insert sidebar next to the first "reasonable" container:
@@ -141,12 +159,13 @@ fn position_sidebar(position: &HtmlDivElement, sidebar: &HtmlDivElement) {
         parent = fc;
     }
     // first, add it to the end; since width=100%, this will get us a reasonable actual width of
-    // the container, which we use as margin-left
+    // the container, which we use as margin-left (or margin-right, for right-to-left text)
     let _ = parent.append_child(sidebar);
     let _ = sidebar.set_attribute(
         "style",
         &format!(
-            "width:fit-content;margin-left:{}px",
+            "width:fit-content;margin-{}:{}px",
+            if rtl { "right" } else { "left" },
             parent.get_bounding_client_rect().width()/*rect.width()*/ + 50.0
         ),
     );
@@ -180,14 +199,14 @@ fn max_child(e: &leptos::web_sys::Element) -> Option<leptos::web_sys::Element> {
     curr
 }
 
-fn content_drawer() -> AnyView {
+fn content_drawer(rtl: bool) -> AnyView {
     use ftml_component_utils::{
         Button, ButtonAppearance, DrawerBody, DrawerHeader, DrawerHeaderTitle,
         DrawerHeaderTitleAction, DrawerPosition, OverlayDrawer, Popover, PopoverTrigger, Text,
     };
 
     let uri = DocumentState::document_uri();
-    if uri == *DocumentUri::no_doc() {
+    if uri.is_anonymous() {
         ().into_any();
     }
     let uricl = uri.clone();
@@ -195,6 +214,11 @@ fn content_drawer() -> AnyView {
     inject_css("ftml-content-drawer", include_str!("content.css"));
     let open = RwSignal::new(false);
     let title = RwSignal::new("...".to_string());
+    let position = if rtl {
+        DrawerPosition::Left
+    } else {
+        DrawerPosition::Right
+    };
 
     view! {
         <Button
@@ -207,7 +231,7 @@ fn content_drawer() -> AnyView {
                 width="1.5em"
             />
         </Button>
-        <OverlayDrawer class="ftml-drawer-absolute-wide" open position=DrawerPosition::Right>
+        <OverlayDrawer class="ftml-drawer-absolute-wide" open position>
             <DrawerHeader>
                 <DrawerHeaderTitle>
                     <DrawerHeaderTitleAction slot>
@@ -278,20 +302,61 @@ fn pdf() -> impl IntoView {
     use ftml_component_utils::{Button, ButtonAppearance};
 
     let uri = DocumentState::document_uri();
-    if uri == *DocumentUri::no_doc() {
+    if uri.is_anonymous() {
         return None;
     }
-    crate::backend().resource_link_url(&uri, "pdf").map(|url| {
-        view! {
-            <a target="_blank" href=url ><Button
-                attr:title="Download PDF"
-               appearance=ButtonAppearance::Subtle>
-               <ftml_component_utils::icons::PdfIcon
-                    height="1.5em"
-                    width="1.5em"
-                />
-            </Button></a>
-        }
+    // hide the button if the PDF does not actually exist
+    let link = LocalCache::resource(move |_| {
+        ftml_backend::available_resource_link(
+            crate::backend(),
+            &uri,
+            ftml_backend::ResourceKind::Pdf,
+        )
+    });
+    Some(move || {
+        link.with(|link| match link {
+            Some(Ok(Some(url))) => Some(url.clone()),
+            _ => None,
+        })
+        .map(|url| {
+            view! {
+                <a target="_blank" href=url ><Button
+                    attr:title="Download PDF"
+                   appearance=ButtonAppearance::Subtle>
+                   <ftml_component_utils::icons::PdfIcon
+                        height="1.5em"
+                        width="1.5em"
+                    />
+                </Button></a>
+            }
+        })
+    })
+}
+
+/// A button exporting the rendered document as a self-contained HTML file; see
+/// [`export`](crate::utils::export).
+fn export(content: NodeRef<leptos::html::Div>) -> impl IntoView {
+    use ftml_component_utils::{Button, ButtonAppearance};
+
+    let uri = DocumentState::document_uri();
+    if uri.is_anonymous() {
+        return None;
+    }
+    Some(view! {
+        <Button
+            attr:title="Export as HTML"
+            appearance=ButtonAppearance::Subtle
+            on_click=move |_| {
+                #[cfg(any(feature = "csr", feature = "hydrate"))]
+                if let Some(root) = content.get_untracked() {
+                    crate::utils::export::export_html(&root, uri.clone());
+                }
+                #[cfg(not(any(feature = "csr", feature = "hydrate")))]
+                let _ = (&content, &uri);
+            }
+        >
+            <ftml_component_utils::icons::HtmlIcon height="1.5em" width="1.5em"/>
+        </Button>
     })
 }
 
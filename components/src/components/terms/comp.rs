@@ -5,7 +5,7 @@ use ftml_dom::{
     utils::{ContextChain, css::inject_css},
 };
 use ftml_ontology::terms::{ArgumentMode, VarOrSym};
-use ftml_uris::SymbolUri;
+use ftml_uris::{LeafUri, SymbolUri};
 use leptos::prelude::*;
 
 use crate::{
@@ -59,6 +59,22 @@ pub fn comp_like<V: IntoView + 'static>(
     let on_click = move |_| {
         on_click.click(allow_formals, top_term.clone());
     };
+    // symbols are focusable (via a roving tabindex) iff the notation shortcut applies
+    let focused = RwSignal::new(false);
+    let tabindex = if let VarOrSym::Sym(uri) = &head
+        && FtmlConfig::allow_notation_changes()
+    {
+        crate::components::notations::notation_shortcut_target(
+            LeafUri::Symbol(uri.clone()),
+            Signal::derive(move || is_hovered.get() || focused.get()),
+        );
+        Some(crate::components::notations::roving_tabindex())
+    } else {
+        None
+    };
+    let on_focus = tabindex.map(|(_, on_focus)| on_focus);
+    let roving = tabindex.map(|_| "");
+    let tabindex = tabindex.map(|(tabindex, _)| tabindex);
     view! {
         <Popover
             class=top_class
@@ -69,12 +85,36 @@ pub fn comp_like<V: IntoView + 'static>(
         >
             <PopoverTrigger slot>{
                 children().attr("class",move || class)
+                .attr("tabindex",move || tabindex.map(|t| t.get()))
+                .attr(crate::components::notations::ROVING_ATTR,roving)
                 .add_any_attr(leptos::ev::on(
                     leptos::ev::click,
                     Box::new(on_click)
                 ))
+                .add_any_attr(leptos::ev::on(
+                    leptos::ev::focusin,
+                    Box::new(move |_| {
+                        focused.set(true);
+                        if let Some(on_focus) = on_focus {
+                            on_focus();
+                        }
+                    })
+                ))
+                .add_any_attr(leptos::ev::on(
+                    leptos::ev::keydown,
+                    Box::new(move |_e: leptos::web_sys::KeyboardEvent| {
+                        #[cfg(any(feature = "csr", feature = "hydrate"))]
+                        if roving.is_some() {
+                            crate::components::notations::on_roving_keydown(&_e);
+                        }
+                    })
+                ))
+                .add_any_attr(leptos::ev::on(
+                    leptos::ev::focusout,
+                    Box::new(move |_| focused.set(false))
+                ))
             }</PopoverTrigger>
-            {super::popover::term_popover(head)}
+            {FtmlConfig::nested_hover(move || super::popover::term_popover(head))}
         </Popover>
     }
     .into_any()
@@ -0,0 +1,95 @@
+use ftml_dom::utils::local_cache::LocalCache;
+use ftml_ontology::{
+    narrative::elements::{Notation, notations::NotationResolver},
+    terms::{IsTerm, Term, Variable},
+};
+use ftml_uris::{DocumentElementUri, Id, LeafUri};
+use leptos::prelude::GetUntracked;
+use rustc_hash::FxHashMap;
+
+use crate::utils::ReactiveStore;
+
+/// Macro names and (preferred) notations of all symbols/variables in a term,
+/// fetched beforehand.
+#[derive(Default)]
+struct Prefetched {
+    macros: FxHashMap<LeafUri, Id>,
+    notations: FxHashMap<LeafUri, Notation>,
+}
+impl NotationResolver for Prefetched {
+    #[inline]
+    fn notation(&self, leaf: &LeafUri, _pref: Option<Id>) -> Option<&Notation> {
+        self.notations.get(leaf)
+    }
+    #[inline]
+    fn macro_name(&self, leaf: &LeafUri) -> Option<&str> {
+        self.macros.get(leaf).map(AsRef::as_ref)
+    }
+}
+
+/// Renders `term` as LaTeX (see [`Term::to_latex`]) and copies the result to the clipboard.
+/// Notations the user chose (see [`FtmlConfig::notation_preference`](crate::config::FtmlConfig::notation_preference))
+/// are respected.
+pub(super) fn copy_as_latex(term: Term, store: Option<&ReactiveStore>) {
+    let leaves = term
+        .symbols()
+        .map(|s| LeafUri::Symbol(s.clone()))
+        .chain(term.variables().filter_map(|v| match v {
+            Variable::Ref { declaration, .. } => Some(LeafUri::Element(declaration.clone())),
+            Variable::Name { .. } => None,
+        }))
+        .collect::<rustc_hash::FxHashSet<_>>();
+    let leaves = leaves
+        .into_iter()
+        .map(|leaf| {
+            let pref = store.and_then(|s| {
+                s.with_value(|s| s.notations.get(&leaf).copied())
+                    .and_then(|sig| sig.get_untracked())
+            });
+            (leaf, pref)
+        })
+        .collect::<Vec<_>>();
+    leptos::task::spawn_local(async move {
+        let resolver = prefetch(leaves).await;
+        let Some(latex) = term.to_latex(&resolver) else {
+            tracing::warn!("term can not be converted to LaTeX");
+            return;
+        };
+        write_clipboard(&latex);
+    });
+}
+
+async fn prefetch(leaves: Vec<(LeafUri, Option<DocumentElementUri>)>) -> Prefetched {
+    let mut ret = Prefetched::default();
+    for (leaf, pref) in leaves {
+        if let LeafUri::Symbol(s) = &leaf
+            && let Ok(symbol) = LocalCache::get()
+                .get_symbol(crate::backend(), s.clone())
+                .await
+            && let Some(name) = ::either::for_both!(symbol, s => s.data.macroname.clone())
+        {
+            ret.macros.insert(leaf.clone(), name);
+        }
+        let notations = LocalCache::get()
+            .get_notations(crate::backend(), leaf.clone())
+            .await
+            .into_iter()
+            .collect::<Vec<_>>();
+        let chosen = pref
+            .and_then(|pref| notations.iter().position(|(uri, _)| *uri == pref))
+            .unwrap_or_default();
+        if let Some((_, n)) = notations.into_iter().nth(chosen) {
+            ret.notations.insert(leaf, n);
+        }
+    }
+    ret
+}
+
+fn write_clipboard(text: &str) {
+    #[cfg(any(feature = "csr", feature = "hydrate"))]
+    if let Some(window) = leptos::web_sys::window() {
+        let _ = window.navigator().clipboard().write_text(text);
+    }
+    #[cfg(not(any(feature = "csr", feature = "hydrate")))]
+    let _ = text;
+}
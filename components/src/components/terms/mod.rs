@@ -2,6 +2,7 @@
 
 pub mod comp;
 pub mod formals;
+mod latex;
 pub mod popover;
 pub mod subterms;
 pub mod symvars;
@@ -11,6 +12,8 @@ use ftml_component_utils::inject_css;
 use ftml_dom::{
     ClonableView, DocumentState, FtmlViews, terms::ReactiveApplication, utils::ContextChain,
 };
+#[cfg(feature = "ssr")]
+use ftml_ontology::narrative::elements::{DocumentTerm, notations::NotationResolver};
 use ftml_ontology::terms::{ArgumentMode, VarOrSym, Variable};
 use ftml_uris::{DocumentElementUri, LeafUri, SymbolUri};
 use leptos::prelude::*;
@@ -29,6 +32,17 @@ pub fn inject_comp_css() {
     inject_css("ftml-comp", include_str!("comp.css"));
 }
 
+/// Renders `term` as a static `<math>` element, using the given `notations` (see
+/// [`Term::to_mathml`](ftml_ontology::terms::Term::to_mathml)) - i.e. without the reactive
+/// machinery of [`ReactiveStore::render_term`](crate::utils::ReactiveStore::render_term).
+#[cfg(feature = "ssr")]
+pub fn render_document_term_static<R: NotationResolver + ?Sized>(
+    term: &DocumentTerm,
+    notations: &R,
+) -> String {
+    format!("<math>{}</math>", term.presentation().to_mathml(notations))
+}
+
 #[derive(Copy, Clone)]
 struct InBinder {
     hovered: RwSignal<bool>,
@@ -255,3 +269,59 @@ pub fn oma(
         children.into_view::<crate::Views>()
     }
 }
+
+#[cfg(all(test, feature = "ssr"))]
+mod tests {
+    use super::render_document_term_static;
+    use ftml_ontology::{
+        narrative::elements::{
+            DocumentTerm,
+            notations::{Notation, NotationComponent, NotationMap},
+        },
+        terms::{ArgumentMode, Term, helpers::IntoTerm},
+    };
+    use ftml_uris::{Id, LeafUri, SymbolUri};
+
+    #[test]
+    fn static_document_term() {
+        let plus: SymbolUri = "http://example.com?a=archive&m=module&s=plus"
+            .parse()
+            .expect("valid uri");
+        let var = |name: &str| -> Term { name.parse::<Id>().expect("valid id").into() };
+        let arg = |index| NotationComponent::Argument {
+            index,
+            mode: ArgumentMode::Simple,
+        };
+        let notations: NotationMap = [(
+            LeafUri::Symbol(plus.clone()),
+            Notation {
+                precedence: 100,
+                id: None,
+                argprecs: vec![100, 99],
+                component: NotationComponent::Node {
+                    tag: "mrow".parse().expect("valid id"),
+                    attributes: Box::default(),
+                    children: Box::new([
+                        arg(0),
+                        NotationComponent::Text { txt: "+".into() },
+                        arg(1),
+                    ]),
+                },
+                op: None,
+            },
+        )]
+        .into_iter()
+        .collect();
+        let term = DocumentTerm::new(
+            "http://example.com?a=archive&d=doc&l=en&e=term"
+                .parse()
+                .expect("valid uri"),
+            plus.apply_tms([var("x"), var("y")]),
+            None,
+        );
+        assert_eq!(
+            render_document_term_static(&term, &notations),
+            "<math><mrow><mi>x</mi>+<mi>y</mi></mrow></math>"
+        );
+    }
+}
@@ -64,8 +64,13 @@ pub fn resolved_var_popover(uri: DocumentElementUri, is_sequence: bool) -> AnyVi
             };
             let tp = v.data.tp.presentation();
             let df = v.data.df.presentation();
+            let record = df.as_ref().and_then(|df| {
+                crate::components::content::domain::structure_instance_view(df, tp.as_ref())
+            });
+            let is_record = record.is_some();
             view! {
-                {df.map(|df| {
+                {record}
+                {df.filter(|_| !is_record).map(|df| {
                     let v = view!{"defined as "
                         {
                             let t = df.into_view::<crate::Views>(crate::backend(),false);
@@ -20,6 +20,7 @@ ftml_js_utils::split! {
         let current = std::cell::Cell::new(None);
         let owner = Owner::current().expect("not in a reactive context");
         let dialog_open = RwSignal::new(false);
+        let store = use_context::<ReactiveStore>();
 
         let ownercl = owner.child();
         let ownerclcl = ownercl.clone();
@@ -31,6 +32,8 @@ ftml_js_utils::split! {
                     let id = uuid::Uuid::new_v4();
                     current.set(Some(id));
                     let body = {
+                        let term = t;
+                        let store = store.clone();
                         let t = move || {
                             t.get()
                                 .map(|t| t.into_view::<crate::Views>(crate::backend(), false))
@@ -43,6 +46,13 @@ ftml_js_utils::split! {
                                     size=ButtonSize::Small
                                     on_click=move |_| dialog_open.set(true)
                                 >"Details"</Button>
+                                <Button
+                                    shape=ButtonShape::Rounded
+                                    size=ButtonSize::Small
+                                    on_click=move |_| if let Some(t) = term.get_untracked() {
+                                        super::latex::copy_as_latex(t, store.as_ref());
+                                    }
+                                >"Copy LaTeX"</Button>
                             </div>
                         }
                     };
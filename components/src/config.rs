@@ -1,12 +1,17 @@
-use ftml_dom::{counters::LogicalLevel, toc::TocSource};
+use ftml_dom::{
+    DocumentState,
+    counters::LogicalLevel,
+    notations::{MaxRenderedSequence, OpaqueMath, ShowImplicits, ShowTypes},
+    toc::TocSource,
+};
 use ftml_js_utils::JsDisplay;
-use ftml_ontology::narrative::elements::SectionLevel;
+use ftml_ontology::{narrative::elements::SectionLevel, utils::NumberFormat};
 use ftml_uris::{DocumentElementUri, DocumentUri, LeafUri};
 use leptos::context::Provider;
 use leptos::prelude::*;
 
 use crate::{
-    components::{problems::ProblemStates, toc::TocProgresses},
+    components::{problems::ProblemStates, sections::FocusState, toc::TocProgresses},
     utils::ReactiveStore,
 };
 
@@ -30,9 +35,18 @@ export interface FtmlConfig {
     pdfLink?:boolean;
     documentUri?:DocumentUri;
     highlightStyle?:HighlightStyle;
+    loadingStyle?:LoadingStyle;
     toc?:TocSource;
     tocProgress?:TocProgress[];
     autoexpandLimit?:LogicalLevel;
+    maxRenderedSequence?:number;
+    maxHoverDepth?:number;
+    showImplicits?:boolean;
+    opaqueMath?:boolean;
+    persistNotationChoice?:boolean;
+    pointDecimals?:number;
+    focusMode?:boolean;
+    showTypes?:boolean;
     sectionWrap?:SectionWrap;
     paragraphWrap?:ParagraphWrap;
     slideWrap?:SlideWrap;
@@ -78,6 +92,11 @@ pub struct FtmlConfig {
     #[cfg_attr(feature = "csr", serde(default, rename = "highlightStyle"))]
     pub highlight_style: Option<HighlightStyle>,
 
+    /// The placeholder shown while content is loaded asynchronously (default:
+    /// [`LoadingStyle::Spinner`]; see [`LoadingStyle`] for the available styles).
+    #[cfg_attr(feature = "csr", serde(default, rename = "loadingStyle"))]
+    pub loading_style: Option<LoadingStyle>,
+
     #[cfg_attr(feature = "csr", serde(default))]
     pub toc: Option<TocSource>,
 
@@ -87,6 +106,50 @@ pub struct FtmlConfig {
     #[cfg_attr(feature = "csr", serde(default, rename = "autoexpandLimit"))]
     pub autoexpand_limit: Option<LogicalLevel>,
 
+    /// The maximum number of elements of a sequence argument to render before
+    /// truncating it with an expandable ellipsis (default:
+    /// [`MaxRenderedSequence::DEFAULT`]).
+    #[cfg_attr(feature = "csr", serde(default, rename = "maxRenderedSequence"))]
+    pub max_rendered_sequence: Option<usize>,
+
+    /// The maximum nesting depth of hover popups: symbols in a popup that is already nested
+    /// this deep are rendered as plain text, without a popup of their own (default:
+    /// [`MaxHoverDepth::DEFAULT`]; `0` disables hovers entirely).
+    #[cfg_attr(feature = "csr", serde(default, rename = "maxHoverDepth"))]
+    pub max_hover_depth: Option<usize>,
+
+    /// Whether to render implicit arguments (see [`ftml_dom::notations::is_implicit_argument`];
+    /// default: `false`).
+    #[cfg_attr(feature = "csr", serde(default, rename = "showImplicits"))]
+    pub show_implicits: Option<bool>,
+
+    /// Whether `$...$`-delimited spans in the text of opaque (informal) terms are rendered as
+    /// MathML identifiers (containing their TeX source) rather than literally (see
+    /// [`ftml_dom::notations::split_math_spans`]; default: `false`).
+    #[cfg_attr(feature = "csr", serde(default, rename = "opaqueMath"))]
+    pub opaque_math: Option<bool>,
+
+    /// Whether terms annotated with a type are rendered as `term : type` (see
+    /// [`Term::declared_type`](ftml_ontology::terms::Term::declared_type); default: `false`).
+    #[cfg_attr(feature = "csr", serde(default, rename = "showTypes"))]
+    pub show_types: Option<bool>,
+
+    /// Whether notation choices made by the user are persisted in `localStorage`
+    /// and restored on reload (default: `true`; see
+    /// [`FtmlConfig::notation_preference`]).
+    #[cfg_attr(feature = "csr", serde(default, rename = "persistNotationChoice"))]
+    pub persist_notation_choice: Option<bool>,
+
+    /// The maximum number of decimal places when displaying points and scores of problems
+    /// (default: [`NumberFormat::DEFAULT_DECIMALS`]; see [`FtmlConfig::format_points`]).
+    #[cfg_attr(feature = "csr", serde(default, rename = "pointDecimals"))]
+    pub point_decimals: Option<u8>,
+
+    /// Whether to collapse all sections but the one currently being read (default: `false`;
+    /// see [`FtmlConfig::focus_mode`]).
+    #[cfg_attr(feature = "csr", serde(default, rename = "focusMode"))]
+    pub focus_mode: Option<bool>,
+
     #[cfg(feature = "callbacks")]
     #[serde(skip)]
     pub section_wrap: Option<SectionWrap>,
@@ -147,9 +210,64 @@ impl HighlightStyle {
 }
 impl ftml_js_utils::conversion::FromWasmBindgen for HighlightStyle {}
 
+/// The placeholder shown while content (e.g. a fragment, a module or the paragraphs of a
+/// symbol) is loaded asynchronously; see [`FtmlConfig::loading_style`].
+#[wasm_bindgen::prelude::wasm_bindgen]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub enum LoadingStyle {
+    /// A spinner.
+    #[default]
+    Spinner,
+    /// A gray, pulsating block taking up the width of the content, so that the surrounding
+    /// content does not jump around as much when the actual content arrives.
+    Skeleton,
+    /// Nothing at all.
+    None,
+}
+impl LoadingStyle {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Spinner => "spinner",
+            Self::Skeleton => "skeleton",
+            Self::None => "none",
+        }
+    }
+
+    #[must_use]
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "spinner" => Some(Self::Spinner),
+            "skeleton" => Some(Self::Skeleton),
+            "none" => Some(Self::None),
+            _ => None,
+        }
+    }
+}
+impl ftml_js_utils::conversion::FromWasmBindgen for LoadingStyle {}
+
 #[derive(Copy, Clone, PartialEq, Eq)]
 pub struct AllowHovers(pub bool);
 
+/// The maximum nesting depth of hover popups; see [`FtmlConfig::max_hover_depth`].
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub struct MaxHoverDepth(pub usize);
+impl MaxHoverDepth {
+    pub const DEFAULT: usize = 3;
+}
+
+/// The number of hover popups the current view is nested in; see
+/// [`FtmlConfig::nested_hover`].
+#[derive(Copy, Clone, PartialEq, Eq, Default)]
+struct HoverDepth(usize);
+
+/// Whether a view nested in `depth` hover popups may open another one, given a maximum
+/// nesting depth of `max`.
+const fn hover_depth_allowed(depth: usize, max: usize) -> bool {
+    depth < max
+}
+
 #[derive(Copy, Clone, PartialEq, Eq)]
 pub struct AllowFullscreen(pub bool);
 
@@ -174,6 +292,15 @@ pub struct AllowNotationChanges(bool);
 #[derive(Copy, Clone)]
 pub struct AllowSubterms(pub bool);
 
+#[derive(Copy, Clone)]
+pub struct PersistNotationChoice(pub bool);
+
+#[derive(Copy, Clone)]
+pub struct PointDecimals(pub u8);
+
+#[derive(Copy, Clone)]
+pub struct FocusMode(pub bool);
+
 #[derive(thiserror::Error, Debug)]
 pub enum FtmlConfigParseError {
     #[error("not a javascript object")]
@@ -230,9 +357,30 @@ impl ftml_js_utils::conversion::FromJs for FtmlConfig {
         get!("chooseHighlightStyle"+choose_highlight_style:bool);
         get!("documentUri"+document_uri:DocumentUri);
         get!("highlightStyle"+highlight_style:HighlightStyle);
+        get!("loadingStyle"+loading_style:LoadingStyle);
         get!("toc"+toc:TocSource);
         get!("tocProgress"+toc_progress:TocProgresses);
         get!("autoexpandLimit"+autoexpand_limit:LogicalLevel);
+        match <f64 as FromJs>::from_field(&value, "maxRenderedSequence") {
+            Err(_) => errors.push(FtmlConfigParseError::InvalidValue("maxRenderedSequence")),
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            Ok(v) => config.max_rendered_sequence = v.map(|v| v.max(0.0) as usize),
+        }
+        match <f64 as FromJs>::from_field(&value, "maxHoverDepth") {
+            Err(_) => errors.push(FtmlConfigParseError::InvalidValue("maxHoverDepth")),
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            Ok(v) => config.max_hover_depth = v.map(|v| v.max(0.0) as usize),
+        }
+        get!("showImplicits"+show_implicits:bool);
+        get!("opaqueMath"+opaque_math:bool);
+        get!("showTypes"+show_types:bool);
+        get!("persistNotationChoice"+persist_notation_choice:bool);
+        match <f64 as FromJs>::from_field(&value, "pointDecimals") {
+            Err(_) => errors.push(FtmlConfigParseError::InvalidValue("pointDecimals")),
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            Ok(v) => config.point_decimals = v.map(|v| v.clamp(0.0, 255.0) as u8),
+        }
+        get!("focusMode"+focus_mode:bool);
         #[cfg(feature = "callbacks")]
         get!("sectionWrap"+section_wrap:SectionWrap);
         #[cfg(feature = "callbacks")]
@@ -264,6 +412,9 @@ impl FtmlConfig {
         if let Some(b) = self.show_content {
             provide_context(ShowContent(b));
         }
+        if let Some(style) = self.loading_style {
+            provide_context(style);
+        }
         if let Some(b) = self.allow_formals {
             provide_context(AllowFormals(b));
         }
@@ -298,6 +449,33 @@ impl FtmlConfig {
             provide_context(sig);
             provide_context(sig.read_only());
         }
+        if let Some(max) = self.max_rendered_sequence {
+            provide_context(MaxRenderedSequence(max));
+        }
+        if let Some(max) = self.max_hover_depth {
+            provide_context(MaxHoverDepth(max));
+        }
+        if let Some(b) = self.show_implicits {
+            provide_context(ShowImplicits(b));
+        }
+        if let Some(b) = self.opaque_math {
+            provide_context(OpaqueMath(b));
+        }
+        if let Some(b) = self.show_types {
+            provide_context(ShowTypes(b));
+        }
+        if let Some(b) = self.persist_notation_choice {
+            provide_context(PersistNotationChoice(b));
+        }
+        if let Some(d) = self.point_decimals {
+            provide_context(PointDecimals(d));
+        }
+        if let Some(b) = self.focus_mode {
+            provide_context(FocusMode(b));
+            if b {
+                provide_context(FocusState::new());
+            }
+        }
         #[cfg(feature = "callbacks")]
         if let Some(b) = self.section_wrap {
             provide_context(Some(b));
@@ -369,6 +547,18 @@ impl FtmlConfig {
     #[must_use]
     pub fn allow_hovers() -> bool {
         use_context::<AllowHovers>().is_none_or(|b| b.0)
+            && hover_depth_allowed(
+                use_context::<HoverDepth>().unwrap_or_default().0,
+                Self::max_hover_depth(),
+            )
+    }
+
+    /// The maximum nesting depth of hover popups (default: [`MaxHoverDepth::DEFAULT`]);
+    /// beyond it, [`allow_hovers`](Self::allow_hovers) is `false`.
+    #[inline]
+    #[must_use]
+    pub fn max_hover_depth() -> usize {
+        use_context::<MaxHoverDepth>().map_or(MaxHoverDepth::DEFAULT, |m| m.0)
     }
 
     #[inline]
@@ -395,6 +585,9 @@ impl FtmlConfig {
         use_context::<AllowFormals>().is_none_or(|b| b.0)
     }
 
+    /// Whether readers may change the notation of symbols; either via the selector in a
+    /// symbol's popover or via the keyboard shortcut
+    /// [`NOTATION_SHORTCUT_KEY`](crate::components::notations::NOTATION_SHORTCUT_KEY).
     #[inline]
     #[must_use]
     pub fn allow_notation_changes() -> bool {
@@ -413,6 +606,14 @@ impl FtmlConfig {
         expect_context()
     }
 
+    /// The placeholder shown while content is loaded asynchronously (see
+    /// [`wait_and_then`](crate::utils::wait_and_then)); defaults to [`LoadingStyle::Spinner`].
+    #[inline]
+    #[must_use]
+    pub fn loading_style() -> LoadingStyle {
+        use_context::<LoadingStyle>().unwrap_or_default()
+    }
+
     #[inline]
     #[must_use]
     pub fn choose_highlight_style() -> bool {
@@ -424,6 +625,72 @@ impl FtmlConfig {
     pub fn autoexpand_limit() -> ReadSignal<AutoexpandLimit> {
         expect_context()
     }
+
+    #[inline]
+    #[must_use]
+    pub fn max_rendered_sequence() -> usize {
+        MaxRenderedSequence::get()
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn show_implicits() -> bool {
+        ShowImplicits::get()
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn opaque_math() -> bool {
+        OpaqueMath::get()
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn show_types() -> bool {
+        ShowTypes::get()
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn persist_notation_choice() -> bool {
+        use_context::<PersistNotationChoice>().is_none_or(|b| b.0)
+    }
+
+    /// Whether focus mode is enabled (default: `false`): all sections other than the active
+    /// one - i.e. the one currently being read, as determined while scrolling (like the
+    /// highlighted entry of the [`toc`](crate::components::toc::toc); see
+    /// [`active_anchor`](ftml_component_utils::active_anchor)) - its enclosing sections
+    /// and its subsections are collapsed to their titles. Scrolling to the title of a
+    /// collapsed section makes it the active one.
+    ///
+    /// Clicking an entry of the table of contents navigates to its section, which switches
+    /// the focus to it - expanding it (and its enclosing sections) if it was collapsed - and
+    /// scrolls it into view. Sections can still be expanded and collapsed manually; such
+    /// changes last until the focus changes next.
+    #[inline]
+    #[must_use]
+    pub fn focus_mode() -> bool {
+        use_context::<FocusMode>().is_some_and(|b| b.0)
+    }
+
+    /// Formats points or scores of problems for display, using the current document's
+    /// language and the configured [`point_decimals`](Self::point_decimals) (see
+    /// [`NumberFormat`]).
+    #[must_use]
+    pub fn format_points(points: f32) -> String {
+        let decimals =
+            use_context::<PointDecimals>().map_or(NumberFormat::DEFAULT_DECIMALS, |d| d.0);
+        NumberFormat::new(DocumentState::document_uri().language)
+            .with_decimals(decimals)
+            .format(points)
+    }
+
+    /// Formats the estimated time (in minutes) of a problem for display, using the current
+    /// document's language (see [`NumberFormat`]).
+    #[must_use]
+    pub fn format_minutes(minutes: f32) -> String {
+        NumberFormat::new(DocumentState::document_uri().language).format(minutes)
+    }
     /*
     #[inline]
     pub fn set_toc_source(src: TocSource) {
@@ -449,6 +716,32 @@ impl FtmlConfig {
         leptos::tachys::reactive_graph::OwnedView::new_with_owner(children, owner).into_any()
     }
 
+    /// Renders `f` as the contents of a hover popup, i.e. one level deeper in the nesting
+    /// of popups (see [`max_hover_depth`](Self::max_hover_depth)).
+    ///
+    /// ### Panics
+    pub fn nested_hover<F: FnOnce() -> AnyView>(f: F) -> AnyView {
+        let depth = use_context::<HoverDepth>().unwrap_or_default().0;
+        let owner = leptos::prelude::Owner::current()
+            .expect("no current reactive Owner found")
+            .child();
+        let children = owner.with(move || {
+            provide_context(HoverDepth(depth + 1));
+            f()
+        });
+        leptos::tachys::reactive_graph::OwnedView::new_with_owner(children, owner).into_any()
+    }
+
+    /// The notation chosen for `uri`, if any.
+    ///
+    /// If [`persist_notation_choice`](Self::persist_notation_choice) is enabled, choices are
+    /// stored in `localStorage`, one entry per symbol/variable: the key is
+    /// `notation_<LeafUri>` and the value the JSON string of the [`DocumentElementUri`] of the
+    /// chosen notation. Resetting to the default notation removes the entry. Entries that
+    /// fail to parse are ignored. If storing fails (e.g. because the storage quota is
+    /// exceeded or storage is disabled), the error is logged and the choice remains in effect
+    /// for the current session only.
+    ///
     /// ### Panics
     #[must_use]
     pub fn notation_preference(uri: &LeafUri) -> ReadSignal<Option<DocumentElementUri>> {
@@ -464,11 +757,16 @@ impl FtmlConfig {
             if let Some(v) = s.with_value(|store| store.notations.get(uri).copied()) {
                 return v;
             }
+            #[cfg(any(feature = "csr", feature = "hydrate"))]
+            let persist = Self::persist_notation_choice();
             let value = {
                 #[cfg(any(feature = "csr", feature = "hydrate"))]
                 {
-                    use gloo_storage::Storage;
-                    gloo_storage::LocalStorage::get(format!("notation_{uri}")).ok()
+                    if persist {
+                        stored_notation::load(uri)
+                    } else {
+                        None
+                    }
                 }
                 #[cfg(not(any(feature = "csr", feature = "hydrate")))]
                 {
@@ -478,18 +776,10 @@ impl FtmlConfig {
             let ret = s.with(move || {
                 let r = RwSignal::new(value);
                 #[cfg(any(feature = "csr", feature = "hydrate"))]
-                {
+                if persist {
                     let uri = uri.clone();
                     Effect::new(move || {
-                        r.with(|s| {
-                            use gloo_storage::Storage;
-                            if let Some(s) = s.as_ref() {
-                                let _ =
-                                    gloo_storage::LocalStorage::set(format!("notation_{uri}"), s);
-                            } else {
-                                gloo_storage::LocalStorage::delete(format!("notation_{uri}"));
-                            }
-                        });
+                        r.with(|s| stored_notation::store(&uri, s.as_ref()));
                     });
                 }
                 r
@@ -514,3 +804,111 @@ impl FtmlConfig {
         })
     }
 }
+
+/// (De)serialization of persisted notation choices; see [`FtmlConfig::notation_preference`].
+#[cfg_attr(not(any(feature = "csr", feature = "hydrate")), allow(dead_code))]
+mod stored_notation {
+    use ftml_uris::{DocumentElementUri, LeafUri};
+
+    pub fn key(uri: &LeafUri) -> String {
+        format!("notation_{uri}")
+    }
+
+    pub fn encode(notation: &DocumentElementUri) -> String {
+        serde_json::Value::String(notation.to_string()).to_string()
+    }
+
+    pub fn decode(value: &str) -> Option<DocumentElementUri> {
+        serde_json::from_str::<String>(value).ok()?.parse().ok()
+    }
+
+    #[cfg(any(feature = "csr", feature = "hydrate"))]
+    pub fn load(uri: &LeafUri) -> Option<DocumentElementUri> {
+        use gloo_storage::Storage;
+        gloo_storage::LocalStorage::raw()
+            .get_item(&key(uri))
+            .ok()
+            .flatten()
+            .and_then(|v| decode(&v))
+    }
+
+    #[cfg(any(feature = "csr", feature = "hydrate"))]
+    pub fn store(uri: &LeafUri, notation: Option<&DocumentElementUri>) {
+        use gloo_storage::Storage;
+        let storage = gloo_storage::LocalStorage::raw();
+        let key = key(uri);
+        let result = match notation {
+            Some(n) => storage.set_item(&key, &encode(n)),
+            None => storage.remove_item(&key),
+        };
+        if let Err(e) = result {
+            tracing::warn!(
+                "failed to persist notation choice for {uri}: {}",
+                ftml_js_utils::JsDisplay(e)
+            );
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn notation_choice_roundtrip() {
+            let symbol: LeafUri = "http://example.com?a=archive&m=module&s=plus"
+                .parse()
+                .expect("valid uri");
+            let notation: DocumentElementUri =
+                "http://example.com?a=archive&d=doc&l=en&e=plus/notation"
+                    .parse()
+                    .expect("valid uri");
+            assert_eq!(
+                key(&symbol),
+                "notation_http://example.com?a=archive&m=module&s=plus"
+            );
+            let encoded = encode(&notation);
+            assert_eq!(
+                encoded,
+                "\"http://example.com?a=archive&d=doc&l=en&e=plus/notation\""
+            );
+            assert_eq!(decode(&encoded), Some(notation));
+            assert_eq!(decode("not json"), None);
+            assert_eq!(decode("\"not a uri\""), None);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn configured_loading_style() {
+        let owner = Owner::new();
+        owner.with(|| {
+            assert_eq!(FtmlConfig::loading_style(), LoadingStyle::Spinner);
+            let child = Owner::current().expect("owner is set").child();
+            child.with(|| {
+                provide_context(LoadingStyle::Skeleton);
+                assert_eq!(FtmlConfig::loading_style(), LoadingStyle::Skeleton);
+            });
+        });
+        for style in [
+            LoadingStyle::Spinner,
+            LoadingStyle::Skeleton,
+            LoadingStyle::None,
+        ] {
+            assert_eq!(LoadingStyle::from_str(style.as_str()), Some(style));
+        }
+    }
+
+    #[test]
+    fn hover_depth_gating() {
+        let max = MaxHoverDepth::DEFAULT;
+        assert!(hover_depth_allowed(0, max));
+        assert!(hover_depth_allowed(max - 1, max));
+        assert!(!hover_depth_allowed(max, max));
+        assert!(!hover_depth_allowed(max + 1, max));
+        assert!(!hover_depth_allowed(0, 0));
+    }
+}
@@ -19,8 +19,8 @@ use ftml_dom::{
     structure::DocumentStructure,
     toc::{TocSource, TocStyle},
 };
-use ftml_ontology::narrative::documents::Document;
-use ftml_uris::{DocumentUri, NarrativeUri};
+use ftml_ontology::narrative::{DocumentRange, documents::Document};
+use ftml_uris::{DocumentUri, ModuleUri, NarrativeUri, SymbolUri};
 use leptos::{
     IntoView,
     html::{ElementChild, div},
@@ -38,6 +38,10 @@ pub fn backend() -> &'static dyn DynBackend {
         .expect("Backend not set")
         .expect("Backend not set")
 }
+/// The backend set via [`set_backend`], if any.
+pub(crate) fn try_backend() -> Option<&'static dyn DynBackend> {
+    GLOBAL_BACKEND.read().ok().and_then(|b| *b)
+}
 /// #### Panics
 pub fn set_backend<Be: SendBackend>() {
     *GLOBAL_BACKEND.write().expect("error") = Some(Be::as_dyn());
@@ -62,6 +66,18 @@ pub enum SidebarPosition {
 #[derive(Copy, Clone)]
 struct InFtmlTop;
 
+/// The `dir` attribute to set on the root of a rendered document, if any: documents in a
+/// [right-to-left](ftml_uris::Language::is_rtl) language get `dir="rtl"` (which also flips the
+/// sidebar to the other side); all others inherit the direction of the surrounding page.
+#[inline]
+const fn root_dir(uri: &DocumentUri) -> Option<&'static str> {
+    if uri.language.is_rtl() {
+        Some("rtl")
+    } else {
+        None
+    }
+}
+
 pub trait ViewContinuations: Sync + 'static {
     fn document_drawer(&self, doc: &Document) -> AnyView;
 }
@@ -110,7 +126,9 @@ impl Views {
         children: impl FnOnce() -> AnyView + Send + 'static,
     ) -> AnyView {
         use leptos::prelude::*;
-        Self::maybe_top(move || {
+        let dir = root_dir(&uri);
+        let rtl = dir.is_some();
+        let inner = Self::maybe_top(move || {
             ftml_dom::setup_document(uri, is_stripped, toc, crate::backend(), move || {
                 let (v, s) = Slides::new();
                 provide_context(s);
@@ -132,6 +150,7 @@ impl Views {
                         pdf_link,
                         choose_highlight_style,
                         sidebar == SidebarPosition::Find,
+                        rtl,
                         children,
                     )
                 } else {
@@ -139,7 +158,16 @@ impl Views {
                 }
             })
         })
-        .into_any()
+        .into_any();
+        if let Some(dir) = dir {
+            div()
+                .dir(dir)
+                .style("display:contents;")
+                .child(inner)
+                .into_any()
+        } else {
+            inner
+        }
     }
 
     pub fn render_fragment(
@@ -169,4 +197,71 @@ impl Views {
             inner
         }
     }
+
+    /// Renders only those (top-level) elements of the document `uri` whose ranges intersect
+    /// `range`, leaving all others as (invisible) placeholders that still advance section,
+    /// paragraph and slide counters - i.e. the rendered elements are numbered as in the full
+    /// document; see [`range`](utils::range) for details.
+    #[must_use]
+    pub fn render_range(uri: DocumentUri, range: DocumentRange) -> AnyView {
+        use ftml_dom::utils::{css::CssExt, local_cache::LocalCache};
+        use utils::LocalCacheExt;
+        let uri2 = uri.clone();
+        LocalCache::with(
+            move |cache| {
+                let doc = cache.get_document(crate::backend(), uri2.clone());
+                let html = cache.get_document_html(crate::backend(), uri2, None);
+                async move { Ok::<_, ftml_backend::BackendError<_>>((doc.await?, html.await?)) }
+            },
+            move |(doc, (html, css, is_stripped))| {
+                for c in css {
+                    c.inject();
+                }
+                Self::setup_document(
+                    uri,
+                    SidebarPosition::None,
+                    is_stripped,
+                    TocSource::None,
+                    move || {
+                        let segments = utils::range::select_range(&doc.elements, range);
+                        utils::range::render_segments(&segments, &html)
+                    },
+                )
+            },
+        )
+    }
+
+    /// Renders the module `uri` as a lazily loaded, expandable tree of its imports and nested
+    /// modules; see [`module_tree`](components::content::domain::module_tree).
+    #[inline]
+    #[must_use]
+    pub fn module_tree(uri: ModuleUri) -> AnyView {
+        components::content::domain::module_tree(uri)
+    }
+
+    /// Renders the signature `name : A → B → C` of the symbol `uri` from its argument and
+    /// return types; see [`symbol_signature`](components::content::symbols::symbol_signature).
+    #[inline]
+    #[must_use]
+    pub fn symbol_signature(uri: SymbolUri) -> AnyView {
+        components::content::symbols::symbol_signature(uri)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::root_dir;
+    use ftml_uris::DocumentUri;
+
+    #[test]
+    fn rtl_root() {
+        let uri = |lang: &str| -> DocumentUri {
+            format!("http://example.com?a=archive&d=doc&l={lang}")
+                .parse()
+                .expect("valid uri")
+        };
+        assert_eq!(root_dir(&uri("ar")), Some("rtl"));
+        assert_eq!(root_dir(&uri("en")), None);
+        assert_eq!(root_dir(&uri("de")), None);
+    }
 }
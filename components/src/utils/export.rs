@@ -0,0 +1,228 @@
+//! Exporting the current document as a single, self-contained HTML file.
+//!
+//! What ends up in the file:
+//! - **inlined**: the rendered document (i.e. the current DOM, without the sidebar), the
+//!   document's own CSS and all `<style>` elements the components injected into the page.
+//!   Stylesheet [`Link`](Css::Link)s are fetched and inlined via [`Css::inline`].
+//! - **linked**: stylesheets that could not be fetched (e.g. due to CORS) remain `<link>`s;
+//!   resources referenced *from* the CSS or the document (fonts, images, ...) are not
+//!   embedded either.
+//! - **omitted**: all scripts; the exported document is static, i.e. without hovers,
+//!   popovers or any other interactivity.
+
+use ftml_ontology::utils::Css;
+
+/// Assembles a complete HTML document from the given `body` and `css`: [`Inline`](Css::Inline)
+/// and [`Class`](Css::Class) CSS becomes `<style>` elements (see [`escape_style`]),
+/// [`Link`](Css::Link)s (i.e. those that could not be inlined) become `<link>`s.
+#[must_use]
+pub fn assemble_html(title: &str, lang: &str, css: &[Css], body: &str) -> String {
+    use std::fmt::Write;
+    let mut html = String::with_capacity(body.len() + 256);
+    let _ = write!(
+        html,
+        "<!DOCTYPE html>\n<html lang=\"{}\">\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n",
+        escape(lang),
+        escape(title)
+    );
+    for c in css {
+        let _ = match c {
+            Css::Link(href) => {
+                writeln!(html, "<link rel=\"stylesheet\" href=\"{}\">", escape(href))
+            }
+            Css::Inline(css) | Css::Class { css, .. } => {
+                writeln!(html, "<style>{}</style>", escape_style(css))
+            }
+        };
+    }
+    let _ = write!(html, "</head>\n<body>\n{body}\n</body>\n</html>\n");
+    html
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Makes `css` safe to put into a `<style>` element, whose contents are not HTML-escaped
+/// but end at the first `</style`: every `</` becomes `<\/`, which in CSS (i.e. in strings
+/// and comments, the only places `<` can occur in valid CSS) denotes the same characters.
+fn escape_style(css: &str) -> std::borrow::Cow<'_, str> {
+    if css.contains("</") {
+        css.replace("</", r"<\/").into()
+    } else {
+        css.into()
+    }
+}
+
+/// Exports the document rendered in `root` as a self-contained HTML file (see the
+/// [module documentation](self)) and triggers a download of it in the browser.
+#[cfg(any(feature = "csr", feature = "hydrate"))]
+pub fn export_html(root: &leptos::web_sys::Element, uri: ftml_uris::DocumentUri) {
+    use ftml_dom::utils::local_cache::LocalCache;
+    use leptos::wasm_bindgen::JsCast;
+
+    let Ok(copy) = root.clone_node_with_deep(true) else {
+        return;
+    };
+    let Ok(copy) = copy.dyn_into::<leptos::web_sys::Element>() else {
+        return;
+    };
+    if let Ok(sidebars) = copy.query_selector_all(".ftml-sidebar") {
+        for i in 0..sidebars.length() {
+            if let Some(sidebar) = sidebars.item(i)
+                && let Some(parent) = sidebar.parent_node()
+            {
+                let _ = parent.remove_child(&sidebar);
+            }
+        }
+    }
+    let body = copy.outer_html();
+    let injected = injected_styles();
+
+    leptos::task::spawn_local(async move {
+        let mut css = match LocalCache::get()
+            .get_document_html(crate::backend(), uri.clone(), None)
+            .await
+        {
+            Ok((_, css, _)) => css.into_vec(),
+            Err(e) => {
+                tracing::warn!("error fetching css of {uri}: {e}");
+                Vec::new()
+            }
+        };
+        css.extend(injected);
+        let mut fetched = rustc_hash::FxHashMap::default();
+        for c in &css {
+            if let Css::Link(url) = c
+                && let Some(content) = fetch_css(url).await
+            {
+                fetched.insert(url.clone(), content);
+            }
+        }
+        let css = css
+            .iter()
+            .map(|c| c.inline(|url| fetched.get(url).cloned()))
+            .collect::<Vec<_>>();
+        let html = assemble_html(uri.name.as_ref(), uri.language.bcp47(), &css, &body);
+        download(&format!("{}.html", uri.name), &html);
+    });
+}
+
+/// The contents of all `<style>` elements in the page's `<head>`
+#[cfg(any(feature = "csr", feature = "hydrate"))]
+fn injected_styles() -> Vec<Css> {
+    let Some(head) = leptos::web_sys::window()
+        .and_then(|w| w.document())
+        .and_then(|d| d.head())
+    else {
+        return Vec::new();
+    };
+    let Ok(styles) = head.query_selector_all("style") else {
+        return Vec::new();
+    };
+    (0..styles.length())
+        .filter_map(|i| styles.item(i)?.text_content())
+        .filter(|s| !s.trim().is_empty())
+        .map(|s| Css::Inline(s.into_boxed_str()))
+        .collect()
+}
+
+#[cfg(any(feature = "csr", feature = "hydrate"))]
+async fn fetch_css(url: &str) -> Option<Box<str>> {
+    let res = gloo_net::http::Request::get(url).send().await.ok()?;
+    if !res.ok() {
+        return None;
+    }
+    res.text().await.ok().map(String::into_boxed_str)
+}
+
+#[cfg(any(feature = "csr", feature = "hydrate"))]
+fn download(filename: &str, content: &str) {
+    use leptos::wasm_bindgen::{JsCast, JsValue};
+    use leptos::web_sys::{Blob, BlobPropertyBag, HtmlAnchorElement, Url};
+
+    let parts = leptos::web_sys::js_sys::Array::of1(&JsValue::from_str(content));
+    let options = BlobPropertyBag::new();
+    options.set_type("text/html;charset=utf-8");
+    let Ok(blob) = Blob::new_with_str_sequence_and_options(&parts, &options) else {
+        return;
+    };
+    let Ok(url) = Url::create_object_url_with_blob(&blob) else {
+        return;
+    };
+    if let Some(anchor) = leptos::web_sys::window()
+        .and_then(|w| w.document())
+        .and_then(|d| d.create_element("a").ok())
+        .and_then(|a| a.dyn_into::<HtmlAnchorElement>().ok())
+    {
+        anchor.set_href(&url);
+        anchor.set_download(filename);
+        anchor.click();
+    }
+    // the download may only start after `click` returns; revoking the url right away could
+    // cancel it
+    leptos::prelude::set_timeout(
+        move || {
+            let _ = Url::revoke_object_url(&url);
+        },
+        REVOKE_DELAY,
+    );
+}
+
+/// How long the object url of a download stays valid.
+#[cfg(any(feature = "csr", feature = "hydrate"))]
+const REVOKE_DELAY: std::time::Duration = std::time::Duration::from_secs(60);
+
+#[cfg(test)]
+mod tests {
+    use super::assemble_html;
+    use ftml_ontology::utils::Css;
+
+    #[test]
+    fn complete_document() {
+        let html = assemble_html(
+            "My <Doc>",
+            "en",
+            &[
+                Css::Inline(".a{color:red}".into()),
+                Css::Class {
+                    name: "b".into(),
+                    css: ".b{color:blue}".into(),
+                },
+                Css::Link("https://example.com/unreachable.css".into()),
+            ],
+            "<div class=\"a\">content</div>",
+        );
+        assert_eq!(
+            html,
+            "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+             <title>My &lt;Doc&gt;</title>\n\
+             <style>.a{color:red}</style>\n\
+             <style>.b{color:blue}</style>\n\
+             <link rel=\"stylesheet\" href=\"https://example.com/unreachable.css\">\n\
+             </head>\n<body>\n<div class=\"a\">content</div>\n</body>\n</html>\n"
+        );
+    }
+
+    #[test]
+    fn style_breakout() {
+        let html = assemble_html(
+            "doc",
+            "en",
+            &[Css::Inline(
+                r#".a::after{content:"</style><script>alert(1)</script>"}"#.into(),
+            )],
+            "",
+        );
+        assert!(!html.contains("</style><script>"), "{html}");
+        assert!(
+            html.contains(
+                r#"<style>.a::after{content:"<\/style><script>alert(1)<\/script>"}</style>"#
+            ),
+            "{html}"
+        );
+    }
+}
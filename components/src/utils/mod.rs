@@ -1,3 +1,5 @@
+pub mod export;
+pub mod range;
 pub mod theming;
 
 use crate::components::terms::OnClickData;
@@ -36,6 +38,12 @@ pub struct ReactiveStore(std::sync::Arc<std::sync::Mutex<ReactiveStoreI>>);
 pub(crate) struct ReactiveStoreI {
     pub(crate) notations: Map<LeafUri, RwSignal<Option<DocumentElementUri>>>,
     pub(crate) on_clicks: Map<VarOrSym, OnClickData>,
+    /// The symbol the notation shortcut currently applies to; see
+    /// [`notation_shortcut_target`](crate::components::notations::notation_shortcut_target).
+    pub(crate) shortcut_target: Option<LeafUri>,
+    /// The state of the roving tabindex of symbols; see
+    /// [`roving_tabindex`](crate::components::notations::roving_tabindex).
+    pub(crate) tab_stops: crate::components::notations::TabStops,
     owner: Owner,
     term_owner: Owner,
 }
@@ -55,12 +63,23 @@ impl ReactiveStore {
             .child();
         owner.with(|| DocumentState::no_document(|| {}));
         let term_owner = owner.child();
-        Self(std::sync::Arc::new(std::sync::Mutex::new(ReactiveStoreI {
+        let slf = Self(std::sync::Arc::new(std::sync::Mutex::new(ReactiveStoreI {
             notations: Map::default(),
             on_clicks: Map::default(),
+            shortcut_target: None,
+            tab_stops: crate::components::notations::TabStops::new(),
             owner,
             term_owner,
-        })))
+        })));
+        #[cfg(any(feature = "csr", feature = "hydrate"))]
+        {
+            let store = slf.clone();
+            let handle = leptos::prelude::window_event_listener(leptos::ev::keydown, move |e| {
+                crate::components::notations::on_notation_shortcut(&store, &e);
+            });
+            leptos::prelude::on_cleanup(move || handle.remove());
+        }
+        slf
     }
     #[inline]
     /// ### Panics
@@ -182,20 +201,24 @@ impl LocalCacheExt for LocalCache {
         };
         use leptos::prelude::*;
         let toaster = ToasterInjection::expect_context();
-        Self::with_or_err(f, view, move |e| {
-            tracing::error!("{e:?}");
-            toaster.dispatch_toast(
-                move || {
-                    view! {
-                      <MessageBar intent=MessageBarIntent::Error>
-                        <MessageBarBody>{format!("{e:?}")}</MessageBarBody>
-                      </MessageBar>
-                    }
-                },
-                ToastOptions::default().with_position(ToastPosition::Top),
-            );
-            error()
-        })
+        wait_and_then_or_retry(
+            move || f(Self::get()),
+            view,
+            move |e, retry| {
+                tracing::error!("{e:?}");
+                toaster.dispatch_toast(
+                    move || {
+                        view! {
+                          <MessageBar intent=MessageBarIntent::Error>
+                            <MessageBarBody>{format!("{e:?}")}</MessageBarBody>
+                          </MessageBar>
+                        }
+                    },
+                    ToastOptions::default().with_position(ToastPosition::Top),
+                );
+                view!({error()}{retry.button()}).into_any()
+            },
+        )
     }
 
     fn with_or_err<R, E, Fut>(
@@ -212,6 +235,24 @@ impl LocalCacheExt for LocalCache {
     }
 }
 
+/// The placeholder for content that is still loading, in the given [`LoadingStyle`].
+///
+/// [`LoadingStyle`]: crate::config::LoadingStyle
+#[must_use]
+pub fn loading_placeholder(style: crate::config::LoadingStyle) -> AnyView {
+    use crate::config::LoadingStyle;
+    use ftml_component_utils::Spinner;
+    use leptos::prelude::*;
+    match style {
+        LoadingStyle::Spinner => view!(<Spinner/>).into_any(),
+        LoadingStyle::Skeleton => {
+            ftml_dom::utils::css::inject_css("ftml-loading", include_str!("loading.css"));
+            view!(<div class="ftml-loading-skeleton"/>).into_any()
+        }
+        LoadingStyle::None => ().into_any(),
+    }
+}
+
 pub fn wait_and_then<R, E: Send + Sync + 'static, Fut>(
     f: impl FnOnce() -> Fut + Send + Sync + 'static + Clone,
     view: impl FnOnce(R) -> AnyView + Clone + Send + 'static,
@@ -221,23 +262,162 @@ where
     R: Send + Sync + 'static + Clone,
     Fut: Future<Output = Result<R, E>> + Send + 'static,
 {
-    use ftml_component_utils::Spinner;
+    wait_and_then_or_retry(f, view, move |e, _| error(e))
+}
+
+/// Like [`wait_and_then`], but the `error` view additionally gets a [`Retry`] handle.
+///
+/// Retrying calls `f` *again* (which is why it needs to be [`Clone`]) and awaits the new
+/// future, showing the loading placeholder in the meantime; the errors cached by the
+/// [`backend`](crate::backend) are discarded first (see [`Retry::retry`]), so the request is
+/// actually sent again.
+pub fn wait_and_then_or_retry<R, E: Send + Sync + 'static, Fut>(
+    f: impl FnOnce() -> Fut + Send + Sync + 'static + Clone,
+    view: impl FnOnce(R) -> AnyView + Clone + Send + 'static,
+    error: impl FnOnce(E, Retry) -> AnyView + Clone + Send + 'static,
+) -> AnyView
+where
+    R: Send + Sync + 'static + Clone,
+    Fut: Future<Output = Result<R, E>> + Send + 'static,
+{
     use leptos::{
         either::Either::{Left, Right},
         prelude::*,
     };
+    let style = crate::config::FtmlConfig::loading_style();
+    let retry = Retry::default();
     view! {
-        <Suspense fallback = || view!(<Spinner/>)>{move || {
+        <Suspense fallback = move || loading_placeholder(style)>{move || {
             let v = view.clone();
             let err = error.clone();
-            let fut = (f.clone())();
+            let fut = retry.fetch(f.clone());
             Suspend::new(async move {
                 match fut.await {
                     Ok(r) => Left(v(r)),
-                    Err(e) => Right(err(e))
+                    Err(e) => Right(err(e, retry))
                 }
             })
         }}</Suspense>
     }
     .into_any()
 }
+
+/// Re-runs a failed fetch of [`wait_and_then_or_retry`].
+#[derive(Copy, Clone)]
+pub struct Retry(RwSignal<usize>);
+impl Default for Retry {
+    #[inline]
+    fn default() -> Self {
+        Self(RwSignal::new(0))
+    }
+}
+impl Retry {
+    /// Calls `fetch`, such that the current reactive context (e.g. the children of a
+    /// `Suspense`) is re-run on [`retry`](Self::retry).
+    #[inline]
+    pub fn fetch<R>(self, fetch: impl FnOnce() -> R) -> R {
+        use leptos::prelude::Track;
+        self.0.track();
+        fetch()
+    }
+
+    /// Re-runs all reactive contexts that called [`fetch`](Self::fetch), after discarding the
+    /// errors cached by the [`backend`](crate::backend) (if set; see
+    /// [`FtmlBackend::forget_errors`](ftml_backend::FtmlBackend::forget_errors)) - otherwise,
+    /// a caching backend would just return the previous error again.
+    #[inline]
+    pub fn retry(self) {
+        use leptos::prelude::Update;
+        if let Some(backend) = crate::try_backend() {
+            ftml_backend::dynbackend::DynBackend::forget_errors(backend);
+        }
+        self.0.update(|n| *n += 1);
+    }
+
+    /// A "Retry" button calling [`retry`](Self::retry).
+    #[must_use]
+    pub fn button(self) -> AnyView {
+        use ftml_component_utils::{Button, ButtonSize};
+        use leptos::prelude::*;
+        view!(<Button size=ButtonSize::Small on_click=move |_| self.retry()>"Retry"</Button>)
+            .into_any()
+    }
+}
+
+/// Truncates `s` to at most `max` (extended) grapheme clusters (i.e. user-perceived
+/// characters), replacing the excess by an ellipsis (`…`, counting as one of the `max`); `s` is
+/// returned unchanged if it is short enough.
+///
+/// The string is only ever cut *between* clusters, so multibyte characters are never split
+/// and combining marks (e.g. the accent of `e\u{301}`) always stay with their base character.
+#[must_use]
+pub fn truncate_display(s: &str, max: usize) -> String {
+    use unicode_segmentation::UnicodeSegmentation;
+    if max == 0 {
+        return String::new();
+    }
+    let mut starts = s.grapheme_indices(true).map(|(i, _)| i).skip(max - 1);
+    match (starts.next(), starts.next()) {
+        // more than `max` clusters
+        (Some(cut), Some(_)) => {
+            let mut ret = String::with_capacity(cut + '…'.len_utf8());
+            ret.push_str(&s[..cut]);
+            ret.push('…');
+            ret
+        }
+        _ => s.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Retry, truncate_display};
+    use leptos::prelude::{GetUntracked, Memo, Owner};
+    use std::sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    };
+
+    #[test]
+    fn retry_refetches() {
+        Owner::new().with(|| {
+            let calls = Arc::new(AtomicUsize::new(0));
+            let retry = Retry::default();
+            let counter = calls.clone();
+            let fetched =
+                Memo::new(move |_| retry.fetch(|| counter.fetch_add(1, Ordering::SeqCst) + 1));
+            assert_eq!(fetched.get_untracked(), 1);
+            assert_eq!(fetched.get_untracked(), 1);
+            retry.retry();
+            assert_eq!(fetched.get_untracked(), 2);
+            assert_eq!(calls.load(Ordering::SeqCst), 2);
+        });
+    }
+
+    #[test]
+    fn truncate_graphemes() {
+        assert_eq!(truncate_display("short", 10), "short");
+        assert_eq!(truncate_display("exactly", 7), "exactly");
+        assert_eq!(truncate_display("truncated", 6), "trunc…");
+        assert_eq!(truncate_display("anything", 0), "");
+        // "e" + combining acute accent is one cluster and never split
+        let combining = "e\u{301}e\u{301}e\u{301}e\u{301}";
+        assert_eq!(truncate_display(combining, 4), combining);
+        assert_eq!(truncate_display(combining, 3), "e\u{301}e\u{301}…");
+        assert_eq!(truncate_display(combining, 1), "…");
+        // multibyte characters
+        assert_eq!(truncate_display("Äpfel für Bäume", 5), "Äpfe…");
+        // a family emoji, joined by zero width joiners
+        let family = "\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}";
+        assert_eq!(
+            truncate_display(&format!("{family}{family}x"), 2),
+            format!("{family}…")
+        );
+        // flags are pairs of regional indicators
+        let flag = "\u{1F1E9}\u{1F1EA}";
+        assert_eq!(
+            truncate_display(&format!("{flag}{flag}x"), 2),
+            format!("{flag}…")
+        );
+    }
+}
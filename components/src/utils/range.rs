@@ -0,0 +1,157 @@
+//! Partial rendering of documents; see [`Views::render_range`](crate::Views::render_range).
+//!
+//! #### Counter consistency
+//! Section, paragraph and slide numbers are computed *while rendering*, by advancing the
+//! document's counters for every element encountered (see [`DocumentState`]). Simply
+//! omitting elements would therefore renumber everything after them. Instead, every element
+//! outside the requested range is replaced by a (empty) placeholder, which advances the
+//! counters exactly as the element (and its children) would have via
+//! [`DocumentState::advance_counters`] - using only the [`Document`](ftml_ontology::narrative::documents::Document)'s
+//! element tree, i.e. without fetching or parsing its HTML. Since the segments are rendered
+//! in document order, every rendered element sees the same counter values as in a full
+//! rendering.
+//!
+//! Elements are selected at the granularity of *top-level* elements: sections, paragraphs,
+//! problems, slides, figures etc. are rendered (or skipped) as a whole; only modules,
+//! structures and [`SkipSection`](DocumentElement::SkipSection)s are descended into (and
+//! skipped as a whole if none of their children intersect the range). HTML outside of any
+//! such element (e.g. plain text between sections) is not rendered.
+
+use ftml_dom::{DocumentState, FtmlViews};
+use ftml_ontology::narrative::{
+    DocumentRange,
+    elements::{DocumentElement, IsDocumentElement},
+};
+use leptos::prelude::*;
+
+/// A top-level element of a partially rendered document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RangeSegment<'d> {
+    /// The element intersects the requested range and its HTML (at the given range) is
+    /// rendered.
+    Render(&'d DocumentElement, DocumentRange),
+    /// The element lies outside the requested range and is replaced by a placeholder.
+    Placeholder(&'d DocumentElement),
+}
+
+/// Selects the top-level `elements` (in document order) and whether they intersect `range`
+/// (see the [module documentation](self)).
+#[must_use]
+pub fn select_range(elements: &[DocumentElement], range: DocumentRange) -> Vec<RangeSegment<'_>> {
+    fn collect<'d>(
+        elements: &'d [DocumentElement],
+        range: DocumentRange,
+        out: &mut Vec<RangeSegment<'d>>,
+    ) {
+        for e in elements {
+            match e {
+                DocumentElement::Module { children, .. }
+                | DocumentElement::MathStructure { children, .. }
+                | DocumentElement::Extension { children, .. }
+                | DocumentElement::Morphism { children, .. }
+                | DocumentElement::SkipSection(children) => {
+                    let start = out.len();
+                    collect(children, range, out);
+                    // entirely outside the range => the container itself is skipped, so that
+                    // its children advance the counters in its context
+                    if !out[start..]
+                        .iter()
+                        .any(|s| matches!(s, RangeSegment::Render(..)))
+                        && out.len() > start
+                    {
+                        out.truncate(start);
+                        out.push(RangeSegment::Placeholder(e));
+                    }
+                }
+                _ => match e.range() {
+                    Some(r) if r.intersects(&range) => out.push(RangeSegment::Render(e, r)),
+                    Some(_) => out.push(RangeSegment::Placeholder(e)),
+                    None => (),
+                },
+            }
+        }
+    }
+    let mut out = Vec::new();
+    collect(elements, range, &mut out);
+    out
+}
+
+/// Renders the given segments of the document `html`; needs to be called in a document
+/// context (see [`Views::setup_document`](crate::Views::setup_document)).
+pub(crate) fn render_segments(segments: &[RangeSegment<'_>], html: &str) -> AnyView {
+    segments
+        .iter()
+        .map(|s| match s {
+            RangeSegment::Render(_, r) => html.get(r.start..r.end).map_or_else(
+                || {
+                    tracing::warn!("range {}..{} out of bounds", r.start, r.end);
+                    ().into_any()
+                },
+                |html| crate::Views::render_ftml(html.to_string(), None).into_any(),
+            ),
+            RangeSegment::Placeholder(e) => {
+                DocumentState::advance_counters(e.as_ref());
+                view!(<div class="ftml-range-placeholder" style="display:none;"/>).into_any()
+            }
+        })
+        .collect_view()
+        .into_any()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{RangeSegment, select_range};
+    use ftml_ontology::{
+        narrative::{
+            DocumentRange,
+            elements::{DocumentElement, Section},
+        },
+        utils::SourceRange,
+    };
+
+    fn section(name: &str, start: usize, end: usize) -> DocumentElement {
+        DocumentElement::Section(Section {
+            range: DocumentRange { start, end },
+            uri: format!("http://example.com?a=archive&d=doc&l=en&e={name}")
+                .parse()
+                .expect("valid uri"),
+            title: None,
+            children: Box::default(),
+            source: SourceRange::DEFAULT,
+        })
+    }
+
+    #[test]
+    fn elements_in_range() {
+        let elements = [
+            section("one", 0, 10),
+            DocumentElement::SkipSection(Box::new([section("two", 10, 20)])),
+            section("three", 20, 30),
+            section("four", 30, 40),
+        ];
+        let selected = select_range(&elements, DocumentRange { start: 15, end: 25 });
+        let DocumentElement::SkipSection(skipped) = &elements[1] else {
+            unreachable!()
+        };
+        assert_eq!(
+            selected,
+            vec![
+                RangeSegment::Placeholder(&elements[0]),
+                RangeSegment::Render(&skipped[0], DocumentRange { start: 10, end: 20 }),
+                RangeSegment::Render(&elements[2], DocumentRange { start: 20, end: 30 }),
+                RangeSegment::Placeholder(&elements[3]),
+            ]
+        );
+        // ranges are half-open; skip sections are skipped as a whole
+        let selected = select_range(&elements, DocumentRange { start: 30, end: 35 });
+        assert_eq!(
+            selected,
+            vec![
+                RangeSegment::Placeholder(&elements[0]),
+                RangeSegment::Placeholder(&elements[1]),
+                RangeSegment::Placeholder(&elements[2]),
+                RangeSegment::Render(&elements[3], DocumentRange { start: 30, end: 40 }),
+            ]
+        );
+    }
+}
@@ -9,10 +9,16 @@ use crate::{
 };
 use ftml_backend::dynbackend::DynBackend;
 use ftml_ontology::{
-    narrative::elements::paragraphs::{ParagraphFormatting, ParagraphKind},
+    narrative::{
+        Narrative,
+        elements::{
+            DocumentElementRef,
+            paragraphs::{ParagraphFormatting, ParagraphKind},
+        },
+    },
     terms::VarOrSym,
 };
-use ftml_parser::extraction::ArgumentPosition;
+use ftml_parser::extraction::{ArgumentPosition, state::UnknownKeyPolicy};
 use ftml_uris::{DocumentElementUri, DocumentUri, Id, Language, NarrativeUri, SymbolUri};
 use leptos::prelude::*;
 use std::str::FromStr;
@@ -83,6 +89,30 @@ struct ContextUri(NarrativeUri);
 #[derive(Clone, PartialEq, Eq)]
 pub struct CurrentUri(pub NarrativeUri);
 
+/// Lifecycle events of the extraction of a document; see [`DocumentState::on_event`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExtractionEvent {
+    /// Extraction started, i.e. the first node of the document is being processed
+    Started,
+    /// The element with the given URI has been extracted completely
+    Element(DocumentElementUri),
+    /// Extraction is done, i.e. [`finished_parsing`](DocumentState::finished_parsing) is
+    /// about to be set
+    Finished,
+}
+
+/// What to do with content that would be recorded for the current document (e.g.
+/// definitions and examples for symbols) if the document is
+/// [anonymous](DocumentState::is_anonymous).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AnonymousContentPolicy {
+    /// Drop the content silently (logged at `debug` level only)
+    #[default]
+    Drop,
+    /// Drop the content and log a warning
+    Warn,
+}
+
 pub struct DocumentState;
 impl DocumentState {
     /// ### Panics
@@ -122,12 +152,63 @@ impl DocumentState {
         .expect("Not in a document context");
     }
 
+    /// Whether the current document is [anonymous](DocumentUri::is_anonymous), e.g.
+    /// [`DocumentUri::no_doc`] (see [`no_document`](Self::no_document)); its content is rendered, but not recorded (see
+    /// [`AnonymousContentPolicy`]).
+    ///
+    /// ### Panics
+    pub fn is_anonymous() -> bool {
+        with_context::<RwSignal<DomExtractor>, _>(|s| s.with_untracked(DomExtractor::is_anonymous))
+            .expect("Not in a document context")
+    }
+
+    /// Sets how content of the current document is handled if it is
+    /// [anonymous](Self::is_anonymous).
+    ///
+    /// ### Panics
+    pub fn set_anonymous_policy(policy: AnonymousContentPolicy) {
+        with_context::<RwSignal<DomExtractor>, _>(|s| {
+            s.update_untracked(|e| e.anonymous_policy = policy);
+        })
+        .expect("Not in a document context");
+    }
+
+    /// Sets how `data-ftml-*` attributes that are not known [`FtmlKey`](ftml_parser::FtmlKey)s
+    /// are handled while extracting the current document (default:
+    /// [`Warn`](UnknownKeyPolicy::Warn)); with [`Error`](UnknownKeyPolicy::Error), they are
+    /// logged as errors.
+    ///
+    /// ### Panics
+    pub fn set_unknown_key_policy(policy: UnknownKeyPolicy) {
+        with_context::<RwSignal<DomExtractor>, _>(|s| {
+            s.update_untracked(|e| e.state.unknown_keys = policy);
+        })
+        .expect("Not in a document context");
+    }
+
     /// ### Panics
     pub fn finished_parsing() -> ReadSignal<bool> {
         with_context::<RwSignal<DomExtractor>, _>(|s| s.with_untracked(|e| e.is_done_read))
             .expect("Not in a document context")
     }
 
+    /// Registers `callback` to be called with every [`ExtractionEvent`] of the current
+    /// document's extraction (during [`iterate`](crate::iterate)).
+    ///
+    /// Callbacks are called synchronously on the thread doing the extraction - i.e. in the
+    /// browser (`csr`/`hydrate`), on the (wasm) main thread while the DOM is being processed -
+    /// so they should return quickly. They are never called while the extraction state is
+    /// borrowed, and may hence use [`DocumentState`] themselves. (The `Send + Sync` bounds
+    /// are only required since the state lives in a reactive signal.)
+    ///
+    /// ### Panics
+    pub fn on_event(callback: impl Fn(&ExtractionEvent) + Send + Sync + 'static) {
+        with_context::<RwSignal<DomExtractor>, _>(|s| {
+            s.update_untracked(|e| e.listeners.push(std::sync::Arc::new(callback)));
+        })
+        .expect("Not in a document context");
+    }
+
     #[inline]
     pub fn in_inputref() -> bool {
         DocumentStructure::in_inputref()
@@ -239,4 +320,29 @@ impl DocumentState {
     pub(crate) fn skip_section() {
         DocumentStructure::skip_section();
     }
+
+    /// Advances all section, paragraph and slide counters as if `element` (including its
+    /// children) had been rendered, without rendering anything; e.g. for elements left out of
+    /// a partial rendering, so that subsequent elements are still numbered correctly.
+    ///
+    /// Every element is processed in its own child [`Owner`], so that the section levels it
+    /// introduces do not leak into its subsequent siblings.
+    pub fn advance_counters(element: DocumentElementRef<'_>) {
+        Owner::new().with(|| {
+            match element {
+                DocumentElementRef::Section(s) => {
+                    let _ = DocumentStructure::new_section(s.uri.clone());
+                }
+                DocumentElementRef::SkipSection(_) => DocumentStructure::skip_section(),
+                DocumentElementRef::Paragraph(p) => {
+                    let _ = DocumentStructure::get_para(p.kind, &p.styles);
+                }
+                DocumentElementRef::Slide(_) => DocumentStructure::slide_inc(),
+                _ => (),
+            }
+            for c in element.children() {
+                Self::advance_counters(c);
+            }
+        });
+    }
 }
@@ -1,17 +1,18 @@
-use crate::{extractor, markers::Marker};
+use crate::{AnonymousContentPolicy, ExtractionEvent, extractor, markers::Marker};
 use dashmap::Entry;
 use either::Either;
 use ftml_ontology::narrative::DocumentRange;
+use ftml_ontology::narrative::elements::DocumentElement;
 use ftml_parser::{
     FtmlKey,
     extraction::{
-        FtmlExtractionError, FtmlRuleSet, FtmlStateExtractor, KeyList,
+        FtmlExtractionError, FtmlExtractor, FtmlRuleSet, FtmlStateExtractor, KeyList,
         attributes::Attributes,
         nodes::FtmlNode,
-        state::{ExtractionResult, ExtractorState},
+        state::{ExtractionResult, ExtractorState, UnknownKeyPolicy},
     },
 };
-use ftml_uris::{DocumentUri, NarrativeUri};
+use ftml_uris::{DocumentElementUri, DocumentUri, NarrativeUri};
 use leptos::{
     prelude::ReadSignal,
     web_sys::{Element, NodeList, js_sys::JsString},
@@ -30,6 +31,8 @@ pub enum ExtractorMode {
     Done,
 }
 
+pub type EventListener = std::sync::Arc<dyn Fn(&ExtractionEvent) + Send + Sync>;
+
 pub struct DomExtractor {
     pub state: ExtractorState<FtmlDomElement>,
     pub context: NarrativeUri,
@@ -37,24 +40,68 @@ pub struct DomExtractor {
     pub is_done: RwSignal<bool>,
     pub is_done_read: ReadSignal<bool>,
     pub is_stripped: bool,
+    pub listeners: Vec<EventListener>,
+    pub last_element: Option<DocumentElementUri>,
+    pub anonymous_policy: AnonymousContentPolicy,
 }
 impl DomExtractor {
     #[inline]
     pub fn new(uri: DocumentUri, context: NarrativeUri, is_stripped: bool) -> Self {
         let is_done = RwSignal::new(false);
+        let mut state = ExtractorState::new(uri, false);
+        state.unknown_keys = UnknownKeyPolicy::Warn;
         Self {
-            state: ExtractorState::new(uri, false),
+            state,
             context,
             mode: ExtractorMode::Pending,
             is_done_read: is_done.read_only(),
             is_done,
             is_stripped,
+            listeners: Vec::new(),
+            last_element: None,
+            anonymous_policy: AnonymousContentPolicy::default(),
+        }
+    }
+
+    #[inline]
+    pub fn is_anonymous(&self) -> bool {
+        self.state.document.is_anonymous()
+    }
+
+    /// Whether `what` may be recorded for the current document, i.e. unless it is
+    /// [anonymous](Self::is_anonymous) - in which case it is dropped according to the
+    /// [`AnonymousContentPolicy`].
+    pub fn accepts(&self, what: &str) -> bool {
+        if !self.is_anonymous() {
+            return true;
         }
+        match self.anonymous_policy {
+            AnonymousContentPolicy::Drop => tracing::debug!("No document; {what} ignored"),
+            AnonymousContentPolicy::Warn => tracing::warn!("No document; {what} ignored"),
+        }
+        false
+    }
+
+    /// The URI of the most recently completed element, unless already reported (or nobody
+    /// is [listening](crate::DocumentState::on_event)).
+    pub fn completed_element(&mut self) -> Option<DocumentElementUri> {
+        if self.listeners.is_empty() {
+            return None;
+        }
+        let uri = self
+            .last_element()
+            .and_then(DocumentElement::element_uri)
+            .cloned()?;
+        if self.last_element.as_ref() == Some(&uri) {
+            return None;
+        }
+        self.last_element = Some(uri.clone());
+        Some(uri)
     }
 
     pub fn finish(&mut self) -> Option<WriteSignal<bool>> {
         tracing::info!("Finishing extraction for {}", self.state.document);
-        if self.state.document == *DocumentUri::no_doc() {
+        if self.is_anonymous() {
             return if self.mode == ExtractorMode::Done {
                 None
             } else {
@@ -247,7 +294,9 @@ impl NodeAttrs {
         Self { elem: elem.clone() }
     }
 
-    pub(crate) fn keys(&self) -> KeyList {
+    /// The known [`FtmlKey`]s of this node; other `data-ftml-` attributes are handled
+    /// according to the [`UnknownKeyPolicy`] of `state`.
+    pub(crate) fn keys(&self, state: &ExtractorState<FtmlDomElement>) -> KeyList {
         self.elem
             .get_attribute_names()
             .into_iter()
@@ -256,9 +305,11 @@ impl NodeAttrs {
                     if PREFIX.with(|p| s.slice(0, PREFIX_LEN) == **p) {
                         s.as_string().and_then(|str| {
                             FtmlKey::from_attr(&str).or_else(|| {
-                                tracing::warn!("Unknown ftml attribute: {}", str);
-                                #[cfg(any(feature = "csr", feature = "hydrate"))]
-                                web_sys::console::error_1(&self.elem);
+                                if let Err(e) = state.unknown_key(&str) {
+                                    tracing::error!("{e}");
+                                    #[cfg(any(feature = "csr", feature = "hydrate"))]
+                                    web_sys::console::error_1(&self.elem);
+                                }
                                 None
                             })
                         })
@@ -269,6 +320,22 @@ impl NodeAttrs {
             })
             .collect()
     }
+
+    /// Iterates over the FTML attributes of this node with their raw values, in the order
+    /// the DOM reports them (see [`FtmlKey::filter_attrs`]).
+    ///
+    /// This only reads attributes - it does *not* run any rules or validate the values, and
+    /// (unlike [`keys`](Self::keys)) silently skips `data-ftml-` attributes that are not
+    /// known [`FtmlKey`]s. Values are owned, since the DOM hands out fresh strings anyway.
+    pub fn iter_ftml(&self) -> impl Iterator<Item = (FtmlKey, String)> + '_ {
+        FtmlKey::filter_attrs(
+            self.elem
+                .get_attribute_names()
+                .into_iter()
+                .filter_map(JsValue::as_string)
+                .filter_map(|k| self.elem.get_attribute(&k).map(|v| (k, v))),
+        )
+    }
 }
 impl Attributes for NodeAttrs {
     /*type KeyIter<'a>
@@ -20,7 +20,6 @@ pub mod toc;
 pub mod utils;
 pub use clonable_views::ClonableView;
 use ftml_ontology::narrative::elements::{ParagraphOrProblemKind, paragraphs::ParagraphKind};
-use ftml_uris::DocumentUri;
 use smallvec::SmallVec;
 mod views;
 
@@ -30,7 +29,9 @@ use crate::{
     terms::ReactiveApplication,
     utils::local_cache::LOCAL_CACHE,
 };
-pub use document::{DocumentMeta, DocumentState, setup_document};
+pub use document::{
+    AnonymousContentPolicy, DocumentMeta, DocumentState, ExtractionEvent, setup_document,
+};
 use ftml_parser::extraction::{CloseFtmlElement, FtmlExtractor};
 use leptos::prelude::*;
 use leptos_posthoc::OriginalNode;
@@ -60,13 +61,7 @@ pub fn iterate<Views: FtmlViews + ?Sized>(
     let Some(sig) = use_context::<RwSignal<DomExtractor>>() else {
         return (None, None);
     };
-    let finish = sig.update_untracked(|ext| {
-        ext.mode == ExtractorMode::Pending && {
-            ext.mode = ExtractorMode::Extracting;
-            tracing::info!("Starting extracting {}", ext.state.document);
-            true
-        }
-    });
+    let finish = start_extraction(sig);
 
     tracing::trace!("iterating {}", e.outer_html());
     #[cfg(any(feature = "csr", feature = "hydrate"))]
@@ -82,7 +77,7 @@ pub fn iterate<Views: FtmlViews + ?Sized>(
     tracing::trace!("Has ftml attributes");
     let (mut markers, invisible, close) = sig.update_untracked(|extractor| {
         let mut attrs = NodeAttrs::new(e);
-        let rules = attrs.keys();
+        let rules = attrs.keys(&extractor.state);
         let mut markers = smallvec::SmallVec::<_, 4>::new();
         let mut close = smallvec::SmallVec::<_, 2>::new();
         for r in rules.apply(extractor, &mut attrs, &n) {
@@ -135,14 +130,18 @@ fn close_things(
     let mut closes = close.clone();
     closes.reverse();
     tracing::trace!("closing element: {close:?}");
-    sig.update_untracked(move |extractor| {
+    let completed = sig.update_untracked(move |extractor| {
         for c in close.into_iter().rev() {
             if let Err(e) = extractor.close(c, &n) {
                 tracing::error!("{e}");
                 leptos::web_sys::console::log_1(&n.0);
             }
         }
+        extractor.completed_element()
     });
+    if let Some(uri) = completed {
+        emit(sig, ExtractionEvent::Element(uri));
+    }
     if !invisible {
         for c in closes {
             #[allow(clippy::enum_glob_use)]
@@ -153,9 +152,7 @@ fn close_things(
                     ReactiveApplication::close(term);
                 }
                 Cl::Paragraph => {
-                    if sig.with_untracked(|r| r.state.document == *DocumentUri::no_doc()) {
-                        tracing::debug!("No document; paragraph ignored");
-                    } else {
+                    if sig.with_untracked(|r| r.accepts("paragraph")) {
                         add_paragraph(sig);
                     }
                 }
@@ -187,6 +184,8 @@ fn close_things(
                 | Cl::ParagraphTitle
                 | Cl::SlideTitle
                 | Cl::Slide
+                | Cl::Figure
+                | Cl::FigureCaption
                 | Cl::Definiendum
                 | Cl::MathStructure
                 | Cl::ComplexTerm
@@ -221,16 +220,45 @@ fn close_things(
         }
     }
     if finish {
-        let r = sig.update_untracked(|r| {
-            if r.is_stripped {
-                Some(r.is_done.write_only())
-            } else {
-                r.finish()
-            }
-        });
-        if let Some(r) = r {
-            r.set(true);
+        finish_extraction(sig);
+    }
+}
+
+/// Marks the start of the extraction, if it has not started yet; returns whether it did.
+fn start_extraction(sig: RwSignal<DomExtractor>) -> bool {
+    let started = sig.update_untracked(|ext| {
+        ext.mode == ExtractorMode::Pending && {
+            ext.mode = ExtractorMode::Extracting;
+            tracing::info!("Starting extracting {}", ext.state.document);
+            true
+        }
+    });
+    if started {
+        emit(sig, ExtractionEvent::Started);
+    }
+    started
+}
+
+fn finish_extraction(sig: RwSignal<DomExtractor>) {
+    let r = sig.update_untracked(|r| {
+        if r.is_stripped {
+            Some(r.is_done.write_only())
+        } else {
+            r.finish()
         }
+    });
+    if let Some(r) = r {
+        emit(sig, ExtractionEvent::Finished);
+        r.set(true);
+    }
+}
+
+/// Calls all [listeners](DocumentState::on_event); outside of the signal, so that they can
+/// access it themselves.
+fn emit(sig: RwSignal<DomExtractor>, event: ExtractionEvent) {
+    let listeners = sig.with_untracked(|ext| ext.listeners.clone());
+    for listener in listeners {
+        listener(&event);
     }
 }
 
@@ -314,3 +342,107 @@ mod client {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{AnonymousContentPolicy, DocumentState, ExtractionEvent, extractor::DomExtractor};
+    use ftml_uris::DocumentUri;
+    use leptos::prelude::*;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn extraction_events() {
+        Owner::new().with(|| {
+            let sig = RwSignal::new(DomExtractor::new(
+                DocumentUri::no_doc().clone(),
+                DocumentUri::no_doc().clone().into(),
+                true,
+            ));
+            provide_context(sig);
+            let events = Arc::new(Mutex::new(Vec::new()));
+            let evs = events.clone();
+            DocumentState::on_event(move |e| evs.lock().expect("not poisoned").push(e.clone()));
+
+            assert!(super::start_extraction(sig));
+            assert!(!super::start_extraction(sig));
+            super::finish_extraction(sig);
+            assert!(sig.with_untracked(|e| e.is_done_read.get_untracked()));
+            assert_eq!(
+                *events.lock().expect("not poisoned"),
+                [ExtractionEvent::Started, ExtractionEvent::Finished]
+            );
+        });
+    }
+
+    /// Counts `WARN` events
+    #[derive(Clone, Default)]
+    struct Warnings(Arc<Mutex<usize>>);
+    impl<S: tracing::Subscriber> tracing_subscriber::Layer<S> for Warnings {
+        fn on_event(
+            &self,
+            event: &tracing::Event<'_>,
+            _: tracing_subscriber::layer::Context<'_, S>,
+        ) {
+            if *event.metadata().level() == tracing::Level::WARN {
+                *self.0.lock().expect("not poisoned") += 1;
+            }
+        }
+    }
+
+    #[test]
+    fn anonymous_paragraphs() {
+        Owner::new().with(|| {
+            let extractor = |uri: &DocumentUri, policy| {
+                let mut e = DomExtractor::new(uri.clone(), uri.clone().into(), true);
+                e.anonymous_policy = policy;
+                e
+            };
+            let warnings = Warnings::default();
+            let accepts = |e: &DomExtractor| {
+                use tracing_subscriber::layer::SubscriberExt;
+                let subscriber = tracing_subscriber::registry().with(warnings.clone());
+                tracing::subscriber::with_default(subscriber, || e.accepts("paragraph"))
+            };
+            let warned = || *warnings.0.lock().expect("not poisoned");
+
+            let doc: DocumentUri = "http://example.com?a=archive&d=doc&l=en"
+                .parse()
+                .expect("valid uri");
+            let e = extractor(&doc, AnonymousContentPolicy::Warn);
+            assert!(!e.is_anonymous());
+            assert!(accepts(&e));
+            assert_eq!(warned(), 0);
+
+            let e = extractor(DocumentUri::no_doc(), AnonymousContentPolicy::Drop);
+            assert!(e.is_anonymous());
+            assert!(!accepts(&e));
+            assert_eq!(warned(), 0);
+
+            let e = extractor(DocumentUri::no_doc(), AnonymousContentPolicy::Warn);
+            assert!(!accepts(&e));
+            assert_eq!(warned(), 1);
+
+            provide_context(RwSignal::new(e));
+            assert!(DocumentState::is_anonymous());
+        });
+    }
+
+    #[test]
+    fn unknown_key_policy() {
+        use ftml_parser::extraction::state::UnknownKeyPolicy;
+        Owner::new().with(|| {
+            let sig = RwSignal::new(DomExtractor::new(
+                DocumentUri::no_doc().clone(),
+                DocumentUri::no_doc().clone().into(),
+                true,
+            ));
+            provide_context(sig);
+            assert_eq!(
+                sig.with_untracked(|e| e.state.unknown_keys),
+                UnknownKeyPolicy::Warn
+            );
+            DocumentState::set_unknown_key_policy(UnknownKeyPolicy::Error);
+            assert!(sig.with_untracked(|e| e.state.unknown_key("data-ftml-foo").is_err()));
+        });
+    }
+}
@@ -197,7 +197,7 @@ impl Marker {
                 fors,
             } => {
                 ModuleContext::barrier();
-                if *uri.document_uri() != *DocumentUri::no_doc() {
+                if !uri.document_uri().is_anonymous() {
                     LOCAL_CACHE
                         .paragraphs
                         .insert(uri.clone(), orig.html_string().into_boxed_str());
@@ -391,7 +391,7 @@ impl Marker {
                 DocumentStructure::set_max_level(*lvl);
                 None
             }
-            OpenFtmlElement::InputRef { target, uri } => Some(Self::InputRef {
+            OpenFtmlElement::InputRef { target, uri, .. } => Some(Self::InputRef {
                 target: target.clone(),
                 uri: uri.clone(),
             }),
@@ -414,7 +414,8 @@ impl Marker {
             OpenFtmlElement::IfInputref(b) => Some(Self::IfInputref(*b)),
             OpenFtmlElement::Comp => Some(Self::Comp),
             OpenFtmlElement::DefComp => Some(Self::DefComp(None)),
-            OpenFtmlElement::Definiendum(u) => Some(Self::DefComp(Some(u.clone()))),
+            // with several definienda, the component is highlighted for the first one
+            OpenFtmlElement::Definiendum(u) => Some(Self::DefComp(u.first().cloned())),
             OpenFtmlElement::SkipSection => Some(Self::SkipSection),
             OpenFtmlElement::SectionTitle => Some(Self::SectionTitle),
             OpenFtmlElement::ParagraphTitle => Some(Self::ParagraphTitle),
@@ -537,6 +538,8 @@ impl Marker {
             | OpenFtmlElement::ProofArgument(_)
             | OpenFtmlElement::ProofJustification
             | OpenFtmlElement::Rule(_)
+            | OpenFtmlElement::Figure { .. }
+            | OpenFtmlElement::FigureCaption
             | OpenFtmlElement::SeqRange => None,
         }
     }
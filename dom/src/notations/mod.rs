@@ -148,6 +148,12 @@ pub trait ArgumentRender: Clone + Send + Sync + 'static {
     }
 
     fn length_at(&self, index: u8) -> usize;
+
+    /// Whether the argument at `index` is *implicit* (see [`is_implicit_argument`]).
+    #[inline]
+    fn is_implicit(&self, _index: u8, _mode: ArgumentMode) -> bool {
+        false
+    }
 }
 
 fn render_arg_with_sep_default<Views: FtmlViews, Args: ArgumentRender>(
@@ -307,12 +313,211 @@ fn render_cv<Views: FtmlViews>(
     )
 }
 
+/// The maximum number of elements of a sequence argument that are rendered, if provided
+/// as context; longer sequences are rendered as their first elements, followed by an
+/// ellipsis that renders the full sequence when clicked. Defaults to
+/// [`DEFAULT`](Self::DEFAULT).
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct MaxRenderedSequence(pub usize);
+impl MaxRenderedSequence {
+    pub const DEFAULT: usize = 100;
+
+    #[inline]
+    #[must_use]
+    pub fn get() -> usize {
+        use_context::<Self>().map_or(Self::DEFAULT, |m| m.0)
+    }
+}
+impl Default for MaxRenderedSequence {
+    #[inline]
+    fn default() -> Self {
+        Self(Self::DEFAULT)
+    }
+}
+
+/// Whether implicit arguments (see [`is_implicit_argument`]) are rendered, if provided as
+/// context. Defaults to `false`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+pub struct ShowImplicits(pub bool);
+impl ShowImplicits {
+    #[inline]
+    #[must_use]
+    pub fn get() -> bool {
+        use_context::<Self>().is_some_and(|b| b.0)
+    }
+}
+
+/// Whether `$...$`-delimited spans in the text of opaque terms are rendered as math (see
+/// [`split_math_spans`]) rather than literally, if provided as context. Defaults to `false`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+pub struct OpaqueMath(pub bool);
+impl OpaqueMath {
+    #[inline]
+    #[must_use]
+    pub fn get() -> bool {
+        use_context::<Self>().is_some_and(|b| b.0)
+    }
+}
+
+/// Whether terms with a [declared type](Term::declared_type) are rendered as `term : type`, if
+/// provided as context. Defaults to `false`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+pub struct ShowTypes(pub bool);
+impl ShowTypes {
+    #[inline]
+    #[must_use]
+    pub fn get() -> bool {
+        use_context::<Self>().is_some_and(|b| b.0)
+    }
+}
+
+/// A span of the text of an opaque term; see [`split_math_spans`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TextSpan {
+    Text(String),
+    Math(String),
+}
+
+/// Splits `text` into prose and inline math, delimited by single dollar signs (`$...$`).
+///
+/// The only escape is `\$`, which denotes a literal dollar sign (in prose and math alike) that
+/// neither opens nor closes a math span; all other backslashes are kept as they are. A `$`
+/// without a closing counterpart is literal text, and empty math spans (`$$`) are dropped.
+#[must_use]
+pub fn split_math_spans(text: &str) -> Vec<TextSpan> {
+    let mut spans = Vec::new();
+    let mut current = String::new();
+    let mut in_math = false;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if chars.peek() == Some(&'$') => {
+                let _ = chars.next();
+                current.push('$');
+            }
+            '$' if in_math => {
+                if !current.is_empty() {
+                    spans.push(TextSpan::Math(std::mem::take(&mut current)));
+                }
+                in_math = false;
+            }
+            '$' => {
+                if !current.is_empty() {
+                    spans.push(TextSpan::Text(std::mem::take(&mut current)));
+                }
+                in_math = true;
+            }
+            c => current.push(c),
+        }
+    }
+    if in_math {
+        // unclosed: merge back into the preceding prose
+        let text = match spans.pop() {
+            Some(TextSpan::Text(t)) => t,
+            Some(m) => {
+                spans.push(m);
+                String::new()
+            }
+            None => String::new(),
+        };
+        spans.push(TextSpan::Text(format!("{text}${current}")));
+    } else if !current.is_empty() {
+        spans.push(TextSpan::Text(current));
+    }
+    spans
+}
+
+/// Whether an argument `term` in [`ArgumentMode`] `mode` counts as *implicit*, i.e. was
+/// inferred rather than given by the author, and is hence not rendered unless
+/// [`ShowImplicits`] is set.
+///
+/// Only [`Simple`](ArgumentMode::Simple) and [`Sequence`](ArgumentMode::Sequence) arguments
+/// can be implicit; bound variables ([`BoundVariable`](ArgumentMode::BoundVariable) and
+/// [`BoundVariableSequence`](ArgumentMode::BoundVariableSequence)) are always rendered,
+/// since hiding them would leave occurrences in the body unbound. Such an argument is
+/// implicit if it is marked by the metatheory, i.e. is an application of
+/// [`APPLY_IMPLICIT`](ftml_uris::metatheory::APPLY_IMPLICIT).
+#[must_use]
+pub fn is_implicit_argument(term: &Term, mode: ArgumentMode) -> bool {
+    if !matches!(mode, ArgumentMode::Simple | ArgumentMode::Sequence) {
+        return false;
+    }
+    let Term::Application(app) = term else {
+        return false;
+    };
+    app.head.is(&*ftml_uris::metatheory::APPLY_IMPLICIT)
+}
+
+fn is_implicit_sequence(seq: &MaybeSequence<Term>, mode: ArgumentMode) -> bool {
+    match seq {
+        MaybeSequence::One(t) => is_implicit_argument(t, mode),
+        MaybeSequence::Seq(v) => !v.is_empty() && v.iter().all(|t| is_implicit_argument(t, mode)),
+    }
+}
+
+/// Whether the argument at `index` is skipped when rendering a notation.
+#[inline]
+fn skip_argument<A: ArgumentRender>(
+    args: &A,
+    index: u8,
+    mode: ArgumentMode,
+    show_implicits: bool,
+) -> bool {
+    !show_implicits && args.is_implicit(index, mode)
+}
+
+/// Returns the number of leading elements to render of a sequence of length `len`, if it
+/// needs to be truncated to at most `max` elements (but always at least one).
+#[must_use]
+pub const fn truncate_sequence_at(len: usize, max: usize) -> Option<usize> {
+    let max = if max == 0 { 1 } else { max };
+    if len > max { Some(max) } else { None }
+}
+
 fn render_seq<Views: FtmlViews>(
     backend: &'static dyn DynBackend,
     index: u8,
     mode: ArgumentMode,
     argument_prec: i64,
     v: &[Term],
+) -> AnyView {
+    let len = v.len();
+    if len == 0 {
+        return ().into_any();
+    }
+    if let Some(max) = truncate_sequence_at(len, MaxRenderedSequence::get()) {
+        let v = v.to_vec();
+        let show_all = RwSignal::new(false);
+        return (move || {
+            if show_all.get() {
+                return render_seq_elems::<Views>(backend, index, mode, argument_prec, &v);
+            }
+            view! {
+                <mrow>
+                    {render_seq_elems::<Views>(backend, index, mode, argument_prec, &v[..max])}
+                    <mo>","</mo>
+                    {
+                        leptos::math::mtext()
+                            .title("show all")
+                            .style("cursor:pointer")
+                            .on(leptos::ev::click, move |_| show_all.set(true))
+                            .child("…")
+                    }
+                </mrow>
+            }
+            .into_any()
+        })
+        .into_any();
+    }
+    render_seq_elems::<Views>(backend, index, mode, argument_prec, v)
+}
+
+fn render_seq_elems<Views: FtmlViews>(
+    backend: &'static dyn DynBackend,
+    index: u8,
+    mode: ArgumentMode,
+    argument_prec: i64,
+    v: &[Term],
 ) -> AnyView {
     let len = v.len();
     if len == 0 {
@@ -460,6 +665,13 @@ impl ArgumentRender for Box<[Argument]> {
             }
         })
     }
+    fn is_implicit(&self, index: u8, mode: ArgumentMode) -> bool {
+        match self.get(index as usize) {
+            Some(Argument::Simple(t)) => is_implicit_argument(t, mode),
+            Some(Argument::Sequence(s)) => is_implicit_sequence(s, mode),
+            None => false,
+        }
+    }
 }
 
 impl ArgumentRender for Box<[BoundArgument]> {
@@ -625,6 +837,13 @@ impl ArgumentRender for Box<[BoundArgument]> {
             }
         })
     }
+    fn is_implicit(&self, index: u8, mode: ArgumentMode) -> bool {
+        match self.get(index as usize) {
+            Some(BoundArgument::Simple(t)) => is_implicit_argument(t, mode),
+            Some(BoundArgument::Sequence(s)) => is_implicit_sequence(s, mode),
+            Some(BoundArgument::Bound(_) | BoundArgument::BoundSeq(_)) | None => false,
+        }
+    }
 }
 
 impl ArgumentRender for Vec<Either<ClonableView, Vec<ClonableView>>> {
@@ -938,6 +1157,12 @@ pub(crate) fn view_component_with_args<Views: FtmlViews, A: ArgumentRender>(
                 view_node(&n, true)
             }
         }
+        NotationComponent::Argument { index, mode }
+        | NotationComponent::ArgSep { index, mode, .. }
+            if skip_argument(args, *index, *mode, ShowImplicits::get()) =>
+        {
+            ().into_any()
+        }
         NotationComponent::Argument { index, mode } => {
             let prec = argument_precs.get(*index as usize).copied().unwrap_or(prec);
             args.render_arg::<Views>(backend, *index, *mode, prec)
@@ -1098,3 +1323,57 @@ pub(crate) fn html_from_tag(id: &str, children: impl IntoView) -> AnyView {
         }*/
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        ArgumentMode, ArgumentRender, TextSpan, skip_argument, split_math_spans,
+        truncate_sequence_at,
+    };
+    use ftml_ontology::terms::{Argument, Term, helpers::IntoTerm};
+    use ftml_uris::{Id, metatheory};
+
+    #[test]
+    fn sequence_truncation() {
+        assert_eq!(truncate_sequence_at(3, 5), None);
+        assert_eq!(truncate_sequence_at(5, 5), None);
+        assert_eq!(truncate_sequence_at(1000, 5), Some(5));
+        assert_eq!(truncate_sequence_at(2, 0), Some(1));
+        assert_eq!(truncate_sequence_at(1, 0), None);
+    }
+
+    #[test]
+    fn implicit_arguments() {
+        let x: Term = "x".parse::<Id>().expect("valid id").into();
+        let implicit = metatheory::APPLY_IMPLICIT.clone().apply_tms([x.clone()]);
+        let args: Box<[Argument]> = Box::new([Argument::Simple(implicit), Argument::Simple(x)]);
+
+        assert!(args.is_implicit(0, ArgumentMode::Simple));
+        assert!(!args.is_implicit(0, ArgumentMode::BoundVariable));
+        assert!(!args.is_implicit(1, ArgumentMode::Simple));
+
+        // hidden by default...
+        assert!(skip_argument(&args, 0, ArgumentMode::Simple, false));
+        assert!(!skip_argument(&args, 1, ArgumentMode::Simple, false));
+        // ...and shown with `show_implicits`
+        assert!(!skip_argument(&args, 0, ArgumentMode::Simple, true));
+    }
+
+    #[test]
+    fn opaque_math_spans() {
+        let text = |s: &str| TextSpan::Text(s.to_string());
+        let math = |s: &str| TextSpan::Math(s.to_string());
+        assert_eq!(
+            split_math_spans("for all $x$ in A"),
+            [text("for all "), math("x"), text(" in A")]
+        );
+
+        assert_eq!(
+            split_math_spans(r"costs \$5, $2x+1 < \$$"),
+            [text("costs $5, "), math("2x+1 < $")]
+        );
+        assert_eq!(split_math_spans("a $b"), [text("a $b")]);
+        assert_eq!(split_math_spans("$x$ and $y"), [math("x"), text(" and $y")]);
+        assert!(split_math_spans("$$").is_empty());
+    }
+}
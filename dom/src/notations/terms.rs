@@ -3,7 +3,10 @@ use std::{hint::unreachable_unchecked};
 use crate::{
     ClonableView, DocumentState, FtmlViews,
     document::CurrentUri,
-    notations::{AnyMaybeAttr, ArgumentRender, NotationExt},
+    notations::{
+        AnyMaybeAttr, ArgumentRender, NotationExt, OpaqueMath, ShowTypes, TextSpan,
+        split_math_spans,
+    },
     terms::{ReactiveTerm, TopTerm},
     utils::{
         FutureExt,
@@ -198,6 +201,20 @@ impl TermExt for Term {
     ) -> AnyView {
         self = replace(self);
         tracing::trace!("Presenting {self:?}");
+        if ShowTypes::get()
+            && let Self::Label { name, df, tp: Some(tp) } = self
+        {
+            // rendered without its type, which is then appended
+            let tm = Self::Label { name, df, tp: None };
+            return view! {
+                <mrow>
+                    {tm.into_view_with_precedence::<Views>(backend, true, 0)}
+                    <mo>":"</mo>
+                    {(*tp).into_view_with_precedence::<Views>(backend, true, 0)}
+                </mrow>
+            }
+            .into_any();
+        }
         if self.as_sequence().is_some_and(|v| {
             !matches!(v,Sequence::Var(_)) &&
             v.is_concrete_or(&mut |v| matches!(v,Variable::Ref{..}))
@@ -835,7 +852,7 @@ fn do_opaque<F: FnOnce() -> AnyView>(
             .iter()
             .map(|e| match e {
                 AnyOpaque::Node(node) => A(do_opaque(node, terms)),
-                AnyOpaque::Text(t) => B(t.to_string()),
+                AnyOpaque::Text(t) => B(opaque_text(node.tag.as_ref(), t)),
                 AnyOpaque::Term(i) => {
                     let f = terms.get_mut(*i as usize).and_then(Option::take);
                     f.map_or_else(|| C(mtext().child("ERROR")), |f| D(f()))
@@ -856,6 +873,30 @@ fn do_opaque<F: FnOnce() -> AnyView>(
     }
 }
 
+/// Renders the text of an opaque term that is a child of a `parent` element; with
+/// [`OpaqueMath`] enabled, `$...$`-delimited spans (see [`split_math_spans`]) are rendered as
+/// `<mi>` elements containing their (escaped) TeX source - or as plain text without the
+/// delimiters, if `parent` is a MathML token element, which may only contain text.
+///
+/// Opaque terms are rendered inside `<math>` already, so no new `<math>` element is opened;
+/// TeX macros are not interpreted.
+fn opaque_text(parent: &str, text: &str) -> AnyView {
+    use leptos::either::Either::{Left, Right};
+    if !text.contains('$') || !OpaqueMath::get() {
+        return text.to_string().into_any();
+    }
+    let in_token = matches!(parent, "mi" | "mn" | "mo" | "ms" | "mtext");
+    split_math_spans(text)
+        .into_iter()
+        .map(|span| match span {
+            TextSpan::Text(t) => Left(t),
+            TextSpan::Math(m) if in_token => Left(m),
+            TextSpan::Math(m) => Right(mi().child(m)),
+        })
+        .collect_view()
+        .into_any()
+}
+
 pub fn do_cv<Views: FtmlViews>(backend:&'static dyn DynBackend,cv: ComponentVar,precedence:i64) -> AnyView {
     use leptos::either::Either::{Left, Right};
     match cv.var {
@@ -1012,3 +1053,80 @@ unsafe fn do_head(head: Term) -> (LeafUri, VarOrSym) {
         _ => unsafe { unreachable_unchecked() },
     }
 }
+
+#[cfg(all(test, feature = "ssr"))]
+mod tests {
+    use super::{TermExt, opaque_text};
+    use crate::{
+        ClonableView,
+        notations::{OpaqueMath, ShowTypes},
+        terms::ReactiveApplication,
+    };
+    use ftml_backend::{RemoteFlamsBackend, dynbackend::DynBackend};
+    use ftml_ontology::terms::{Numeric, Term};
+    use ftml_uris::{DocumentElementUri, Id};
+    use leptos::{prelude::*, tachys::view::RenderHtml};
+
+    /// Never actually asked; terms without symbols do not need a backend.
+    static BACKEND: RemoteFlamsBackend<&'static str> =
+        RemoteFlamsBackend::new("http://localhost:3000", false);
+
+    /// The default views, rendering applications as they are.
+    struct Views;
+    impl crate::TermTrackedViews for Views {
+        fn application(
+            _: ReadSignal<ReactiveApplication>,
+            _: Option<Id>,
+            _: Option<DocumentElementUri>,
+            then: ClonableView,
+        ) -> AnyView {
+            then.into_view::<Self>()
+        }
+        fn binder_application(
+            _: ReadSignal<ReactiveApplication>,
+            _: Option<Id>,
+            _: Option<DocumentElementUri>,
+            then: ClonableView,
+        ) -> AnyView {
+            then.into_view::<Self>()
+        }
+    }
+
+    #[test]
+    fn opaque_math_text() {
+        Owner::new().with(|| {
+            let text = "for all $x$ with $a<b$";
+            assert_eq!(opaque_text("mrow", text).to_html(), text.replace('<', "&lt;"));
+
+            provide_context(OpaqueMath(true));
+            let html = opaque_text("mrow", text).to_html();
+            assert!(!html.contains("<math"), "{html}");
+            assert!(html.contains("<mi>x</mi>"), "{html}");
+            assert!(html.contains("<mi>a&lt;b</mi>"), "{html}");
+            assert!(!html.contains('$'), "{html}");
+
+            let html = opaque_text("mtext", text).to_html();
+            assert!(!html.contains("<mi"), "{html}");
+            assert!(html.contains("a&lt;b"), "{html}");
+        });
+    }
+
+    #[test]
+    fn typed_label() {
+        let label = || Term::Label {
+            name: "x".parse().expect("valid name"),
+            df: None,
+            tp: Some(Box::new(Term::Number(Numeric::Int(1)))),
+        };
+        let backend: &'static dyn DynBackend = &BACKEND;
+        Owner::new().with(|| {
+            let html = label().into_view::<Views>(backend, false).to_html();
+            assert!(!html.contains("<mo>:</mo>"), "{html}");
+
+            provide_context(ShowTypes(true));
+            let html = label().into_view::<Views>(backend, false).to_html();
+            assert!(html.starts_with("<mrow>"), "{html}");
+            assert!(html.ends_with("<mo>:</mo><mn>1</mn></mrow>"), "{html}");
+        });
+    }
+}
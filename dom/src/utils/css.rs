@@ -47,13 +47,20 @@ fn do_css(css: Css) {
             }
             #[cfg(not(feature = "ssr"))]
             {
+                use ftml_component_utils::InjectedCss;
                 use leptos::prelude::document;
+                if InjectedCss::is_injected(&id) {
+                    return;
+                }
                 let Some(head) = document().head() else {
                     leptos::logging::log!("ERROR: head does not exist");
                     return;
                 };
                 match head.query_selector(&format!("link#{id}")) {
-                    Ok(Some(_)) => return,
+                    Ok(Some(_)) => {
+                        InjectedCss::with(|ids| ids.insert(id));
+                        return;
+                    }
                     Err(e) => {
                         leptos::logging::log!("ERROR: query link element error: {e:?}");
                         return;
@@ -68,6 +75,7 @@ fn do_css(css: Css) {
                 _ = style.set_attribute("rel", "stylesheet");
                 _ = style.set_attribute("href", &s);
                 _ = head.append_with_node_1(&style);
+                InjectedCss::with(|ids| ids.insert(id));
             }
         }
     }
@@ -12,7 +12,7 @@ use ftml_ontology::{
 };
 use ftml_parser::extraction::{
     FtmlExtractionError, FtmlStateExtractor, OpenFtmlElement,
-    state::{ExtractionResult, ExtractorState},
+    state::{ExtractionResult, ExtractionStats, ExtractorState, IdStrategy, UnknownKeyPolicy},
 };
 use ftml_uris::DocumentUri;
 
@@ -24,19 +24,70 @@ pub struct FtmlResult {
     pub css: Box<[Css]>,
     pub errors: Box<[FtmlExtractionError]>,
     pub doc: ExtractionResult,
+    /// Statistics about the extraction, including the time it took; see [`ExtractionStats`].
+    pub stats: ExtractionStats,
     pub body: DocumentRange,
     pub inner_offset: u32,
 }
 
 /// # Errors
+#[inline]
 pub fn run(
     ftml: &str,
     img: impl Fn(&str) -> Option<String>,
     css: impl Fn(&str) -> Option<Box<str>>,
     uri: DocumentUri,
     rdf: bool,
+) -> Result<FtmlResult, String> {
+    run_with_policy(ftml, img, css, uri, rdf, UnknownKeyPolicy::default())
+}
+
+/// Like [`run`], but with the given [`UnknownKeyPolicy`] for `data-ftml-*` attributes that are
+/// not known [`FtmlKey`](ftml_parser::FtmlKey)s; with [`UnknownKeyPolicy::Error`], these end
+/// up in [`FtmlResult::errors`].
+///
+/// # Errors
+pub fn run_with_policy(
+    ftml: &str,
+    img: impl Fn(&str) -> Option<String>,
+    css: impl Fn(&str) -> Option<Box<str>>,
+    uri: DocumentUri,
+    rdf: bool,
+    unknown_keys: UnknownKeyPolicy,
+) -> Result<FtmlResult, String> {
+    let mut state = ExtractorState::new(uri, rdf);
+    state.unknown_keys = unknown_keys;
+    run_with_state(ftml, img, css, state)
+}
+
+/// Like [`run`], but naming elements without explicit ids with the given [`IdStrategy`]
+/// instead of the default [`PrefixCounter`](ftml_parser::extraction::state::PrefixCounter).
+///
+/// # Errors
+pub fn run_with_id_strategy(
+    ftml: &str,
+    img: impl Fn(&str) -> Option<String>,
+    css: impl Fn(&str) -> Option<Box<str>>,
+    uri: DocumentUri,
+    rdf: bool,
+    ids: impl IdStrategy + 'static,
+) -> Result<FtmlResult, String> {
+    run_with_state(
+        ftml,
+        img,
+        css,
+        ExtractorState::with_id_strategy(uri, rdf, ids),
+    )
+}
+
+fn run_with_state(
+    ftml: &str,
+    img: impl Fn(&str) -> Option<String>,
+    css: impl Fn(&str) -> Option<Box<str>>,
+    state: ExtractorState<ever::NodeRef>,
 ) -> Result<FtmlResult, String> {
     use html5ever::tendril::{SliceExt, TendrilSink};
+    let start = std::time::Instant::now();
     let parser = parser::HtmlParser {
         document_node: ever::NodeRef::new_document(),
         body: std::cell::Cell::new((DocumentRange::default(), 0)),
@@ -46,12 +97,51 @@ pub fn run(
         extractor: std::cell::RefCell::new(HtmlExtractor {
             parse_errors: String::new(),
             css: Vec::new(),
-            state: ExtractorState::new(uri, rdf),
+            state,
+            dry_run: None,
+        }),
+    };
+    html5ever::parse_document(parser, html5ever::ParseOpts::default())
+        .from_utf8()
+        .one(ftml.as_bytes().to_tendril())
+        .map(|r| {
+            let mut r = r.left_or_else(|_| unreachable!("not a dry run"));
+            r.stats.duration = start.elapsed();
+            tracing::debug!("extraction stats for {}: {:?}", r.doc.document.uri, r.stats);
+            r
+        })
+}
+
+/// Runs the extraction rules over the given `ftml` without producing a document, returning
+/// the [`OpenFtmlElement`]s that would be added (in document order).
+///
+/// The rules still operate on a scratch [`ExtractorState`] (which is required for nested
+/// elements to be resolved correctly), so ids and [`Uri`](ftml_uris::Uri)s are assigned
+/// exactly like in [`run`]; the state is discarded afterwards without being finished, so no
+/// [`ExtractionResult`] (document, modules, notations,...) is produced. Errors of individual
+/// rules are skipped.
+///
+/// # Errors
+/// if the HTML is malformed.
+pub fn dry_run(ftml: &str, uri: DocumentUri) -> Result<Vec<OpenFtmlElement>, String> {
+    use html5ever::tendril::{SliceExt, TendrilSink};
+    let parser = parser::HtmlParser {
+        document_node: ever::NodeRef::new_document(),
+        body: std::cell::Cell::new((DocumentRange::default(), 0)),
+        errors: std::cell::RefCell::new(Vec::new()),
+        img: |_: &str| -> Option<String> { None },
+        css: |_: &str| -> Option<Box<str>> { None },
+        extractor: std::cell::RefCell::new(HtmlExtractor {
+            parse_errors: String::new(),
+            css: Vec::new(),
+            state: ExtractorState::new(uri, false),
+            dry_run: Some(Vec::new()),
         }),
     };
     html5ever::parse_document(parser, html5ever::ParseOpts::default())
         .from_utf8()
         .one(ftml.as_bytes().to_tendril())
+        .map(|r| r.right_or_else(|_| unreachable!("dry run")))
 }
 
 pub struct HtmlExtractor {
@@ -60,6 +150,7 @@ pub struct HtmlExtractor {
     //document:UncheckedDocument,
     //backend: &'a AnyBackend,
     state: ExtractorState<ever::NodeRef>,
+    dry_run: Option<Vec<OpenFtmlElement>>,
 }
 
 static RULES: ftml_parser::extraction::FtmlRuleSet<HtmlExtractor> =
@@ -84,7 +175,10 @@ impl FtmlStateExtractor for HtmlExtractor {
     }
     /// ### Errors
     #[inline]
-    fn on_add(&mut self, _: &OpenFtmlElement) -> Result<Self::Return, FtmlExtractionError> {
+    fn on_add(&mut self, elem: &OpenFtmlElement) -> Result<Self::Return, FtmlExtractionError> {
+        if let Some(elems) = &mut self.dry_run {
+            elems.push(elem.clone());
+        }
         Ok(())
     }
 }
@@ -120,3 +214,376 @@ static SOURCEREF_SPECS: [SourceRefSpec; 1] = [SourceRefSpec {
         })
     },
 }];
+
+#[cfg(test)]
+mod tests {
+    use ftml_ontology::narrative::elements::{DocumentElement, LogicalParagraph};
+    use ftml_parser::extraction::OpenFtmlElement;
+    use ftml_uris::DocumentUri;
+
+    fn uri() -> DocumentUri {
+        "http://example.com?a=archive&d=doc&l=en"
+            .parse()
+            .expect("valid uri")
+    }
+
+    /// Extracts `html` as the document [`uri`] and asserts that there were no errors.
+    fn extract(html: &str) -> super::FtmlResult {
+        let result = super::run(html, |_| None, |_| None, uri(), false).expect("valid html");
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+        result
+    }
+
+    fn single_paragraph(result: &super::FtmlResult) -> &LogicalParagraph {
+        let [DocumentElement::Paragraph(p)] = &*result.doc.document.elements else {
+            panic!(
+                "expected a single paragraph, got {:?}",
+                result.doc.document.elements
+            );
+        };
+        p
+    }
+
+    #[test]
+    fn dry_run_module() {
+        let elems = super::dry_run(
+            r#"<html><body><div data-ftml-module="mod"><span>content</span></div></body></html>"#,
+            uri(),
+        )
+        .expect("valid html");
+        let [OpenFtmlElement::Module { uri, .. }] = elems.as_slice() else {
+            panic!("expected a single module, got {elems:?}");
+        };
+        assert_eq!(uri.name.to_string(), "mod");
+    }
+
+    #[test]
+    fn unknown_key_policy() {
+        use ftml_parser::extraction::{FtmlExtractionError, state::UnknownKeyPolicy};
+        const HTML: &str = r#"<html><body><div data-ftml-not-a-key="foo"><span>content</span></div></body></html>"#;
+        let run = |policy| {
+            super::run_with_policy(HTML, |_| None, |_| None, uri(), false, policy)
+                .expect("valid html")
+        };
+
+        let ignored = run(UnknownKeyPolicy::Ignore);
+        assert!(ignored.errors.is_empty());
+
+        let errors = run(UnknownKeyPolicy::Error).errors;
+        let [FtmlExtractionError::UnknownKey(key)] = &*errors else {
+            panic!("expected a single unknown key error, got {errors:?}");
+        };
+        assert_eq!(key, "data-ftml-not-a-key");
+    }
+
+    #[test]
+    fn paragraph_style() {
+        let result = extract(
+            r#"<html><body><div data-ftml-paragraph="" data-ftml-id="p" data-ftml-styles="remark" data-ftml-paragraph-style="important"><span>content</span></div></body></html>"#,
+        );
+        let p = single_paragraph(&result);
+        let styles = p.styles.iter().map(AsRef::as_ref).collect::<Vec<&str>>();
+        assert_eq!(styles, ["remark", "important"]);
+    }
+
+    #[test]
+    fn multiple_definienda() {
+        let result = extract(
+            r#"<html><body><div data-ftml-definition="" data-ftml-id="d"><span data-ftml-definiendum="http://example.com?a=archive&amp;m=mod&amp;s=a, http://example.com?a=archive&amp;m=mod&amp;s=b">a and b</span></div></body></html>"#,
+        );
+        let p = single_paragraph(&result);
+        let fors = p
+            .fors
+            .iter()
+            .map(|(s, _)| s.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            fors,
+            [
+                "http://example.com?a=archive&m=mod&s=a",
+                "http://example.com?a=archive&m=mod&s=b"
+            ]
+        );
+        let definienda = p
+            .children
+            .iter()
+            .filter_map(|c| match c {
+                DocumentElement::Definiendum { uri, .. } => Some(uri.name.to_string()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(definienda, ["a", "b"]);
+    }
+
+    #[test]
+    fn figures_with_captions() {
+        use ftml_ontology::narrative::elements::FigureKind;
+        let result = extract(
+            r#"<html><body><div data-ftml-figure="figure" data-ftml-id="fig"><img src="a.png"/><span data-ftml-caption="">A picture</span></div><div data-ftml-figure="table" data-ftml-id="tab"><table></table></div><div data-ftml-figure="figure" data-ftml-id="fig2"></div></body></html>"#,
+        );
+        let [DocumentElement::Figure(figure), ..] = &*result.doc.document.elements else {
+            panic!("expected a figure, got {:?}", result.doc.document.elements);
+        };
+        assert_eq!(figure.kind, FigureKind::Figure);
+        assert_eq!(figure.caption.as_deref(), Some("A picture"));
+
+        let figures = result
+            .doc
+            .document
+            .list_of_figures()
+            .into_iter()
+            .map(|(i, f)| (i, f.uri.name().last().to_string()))
+            .collect::<Vec<_>>();
+        assert_eq!(figures, [(1, "fig".to_string()), (2, "fig2".to_string())]);
+        let tables = result.doc.document.list_of_tables();
+        let [(1, table)] = &*tables else {
+            panic!("expected a single table, got {tables:?}");
+        };
+        assert_eq!(table.kind, FigureKind::Table);
+        assert!(table.caption.is_none());
+    }
+
+    #[test]
+    fn two_step_proof() {
+        use ftml_ontology::{
+            narrative::elements::paragraphs::{ParagraphKind, ParagraphStepKind},
+            terms::IsTerm,
+        };
+        let result = extract(
+            r#"<html><body><div data-ftml-proof="" data-ftml-id="pf"><div data-ftml-proofbody=""><div data-ftml-spfstep="" data-ftml-stepname="one"><span data-ftml-proofterm="">A</span> by <span data-ftml-spfjust=""><span data-ftml-term="OMID" data-ftml-head="http://example.com?a=archive&m=mod&s=lemma">the lemma</span></span></div><div data-ftml-spfconclusion=""><span data-ftml-proofterm="">B</span></div></div></div></body></html>"#,
+        );
+        let proof = single_paragraph(&result);
+        assert_eq!(proof.kind, ParagraphKind::Proof);
+        let steps = proof.numbered_steps();
+        let [(one, first), (two, second)] = &*steps else {
+            panic!("expected two steps, got {steps:?}");
+        };
+        assert_eq!((one.as_str(), two.as_str()), ("1", "2"));
+        assert_eq!(first.kind(), ParagraphStepKind::ProofStep);
+        assert_eq!(first.var_name().map(|n| n.name().last()), Some("one"));
+        assert!(first.yields().is_some());
+        let justified = first
+            .justification()
+            .into_iter()
+            .flat_map(IsTerm::symbols)
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        assert_eq!(justified, ["http://example.com?a=archive&m=mod&s=lemma"]);
+        assert_eq!(second.kind(), ParagraphStepKind::Conclusion);
+        assert!(second.justification().is_none());
+    }
+
+    #[test]
+    fn extraction_stats() {
+        let result = super::run_with_policy(
+            r#"<html><body><div data-ftml-module="mod"><div data-ftml-paragraph="" data-ftml-id="p"><span data-ftml-frobnicate="">content</span></div></div></body></html>"#,
+            |_| None,
+            |_| None,
+            uri(),
+            false,
+            super::UnknownKeyPolicy::Error,
+        )
+        .expect("valid html");
+        assert_eq!(result.stats.elements, 2);
+        assert_eq!(result.stats.errors, 1);
+        assert_eq!(result.stats.errors, result.errors.len());
+        assert_eq!(result.stats.notations, 0);
+    }
+
+    #[test]
+    fn custom_id_strategy() {
+        use ftml_ontology::utils::RefTree;
+        use ftml_parser::extraction::state::IdStrategy;
+
+        /// `{prefix}-{n}`, counting across all prefixes
+        #[derive(Debug, Default, Clone)]
+        struct Global(u32);
+        impl IdStrategy for Global {
+            fn new_id(&mut self, prefix: &str) -> String {
+                self.0 += 1;
+                format!("{prefix}-{}", self.0)
+            }
+        }
+
+        let result = super::run_with_id_strategy(
+            r#"<html><body><div data-ftml-section=""><div data-ftml-paragraph=""></div><div data-ftml-paragraph="" data-ftml-id="given"></div></div><div data-ftml-section=""></div></body></html>"#,
+            |_| None,
+            |_| None,
+            uri(),
+            false,
+            Global::default(),
+        )
+        .expect("valid html");
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+        let names = result
+            .doc
+            .document
+            .dfs()
+            .filter_map(|e| e.element_uri().map(|u| u.name.to_string()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            [
+                "section-1",
+                "section-1/paragraph-2",
+                "section-1/given",
+                "section-3"
+            ]
+        );
+    }
+
+    #[test]
+    fn open_element_stacks() {
+        use ftml_parser::extraction::{
+            OpenDomainElement, OpenNarrativeElement, state::ExtractorState,
+        };
+        let module: ftml_uris::ModuleUri = "http://example.com?a=archive&m=mod"
+            .parse()
+            .expect("valid uri");
+        let section: ftml_uris::DocumentElementUri =
+            "http://example.com?a=archive&d=doc&l=en&e=sec"
+                .parse()
+                .expect("valid uri");
+        let node = crate::ever::NodeRef::new_document();
+        let mut state = ExtractorState::new(uri(), false);
+        state
+            .add(
+                OpenFtmlElement::Module {
+                    uri: module.clone(),
+                    meta: None,
+                    signature: None,
+                },
+                &node,
+            )
+            .expect("opens");
+        state
+            .add(OpenFtmlElement::Section(section.clone()), &node)
+            .expect("opens");
+
+        let domain = state.open_domain_elements().collect::<Vec<_>>();
+        assert!(
+            matches!(domain[..], [OpenDomainElement::Module { uri, .. }] if *uri == module),
+            "{domain:?}"
+        );
+        // innermost first
+        let narrative = state.open_narrative_elements().collect::<Vec<_>>();
+        assert!(
+            matches!(
+                narrative[..],
+                [
+                    OpenNarrativeElement::Section { uri: s, .. },
+                    OpenNarrativeElement::Module { uri: m, .. }
+                ] if *s == section && *m == module
+            ),
+            "{narrative:?}"
+        );
+    }
+
+    #[test]
+    fn extraction_result_roundtrip() {
+        use ftml_ontology::narrative::VersionHeader;
+        use ftml_parser::extraction::state::ExtractionResult;
+        let result = extract(
+            r#"<html><body><div data-ftml-module="mod"><div data-ftml-paragraph="" data-ftml-id="p"><span>content</span></div></div></body></html>"#,
+        )
+        .doc;
+        let bytes = result.encode_to_vec().expect("encodable");
+        let decoded = ExtractionResult::decode_from_slice(&bytes).expect("decodable");
+        assert_eq!(decoded.document, result.document);
+        assert_eq!(decoded.modules, result.modules);
+        assert_eq!(decoded.data, result.data);
+        assert_eq!(decoded.notations, result.notations);
+
+        // a different format version is rejected
+        let mut stale = bytes;
+        let version = VersionHeader::MAGIC.len();
+        stale[version] = stale[version].wrapping_add(1);
+        assert!(ExtractionResult::decode_from_slice(&stale).is_err());
+    }
+
+    #[test]
+    fn anchored_document_reference() {
+        let result = extract(
+            r#"<html><body><div data-ftml-inputref="http://example.com?a=archive&amp;d=other&amp;l=en" data-ftml-inputref-anchor="http://example.com?a=archive&amp;d=other&amp;l=en&amp;e=sec/def" data-ftml-id="ref"></div></body></html>"#,
+        );
+        let [
+            DocumentElement::DocumentReference {
+                target,
+                anchor: Some(anchor),
+                ..
+            },
+        ] = &*result.doc.document.elements
+        else {
+            panic!(
+                "expected a single anchored reference, got {:?}",
+                result.doc.document.elements
+            );
+        };
+        assert_eq!(
+            target.to_string(),
+            "http://example.com?a=archive&d=other&l=en"
+        );
+        assert_eq!(anchor.document, *target);
+        assert_eq!(anchor.name.to_string(), "sec/def");
+    }
+
+    #[test]
+    fn symbol_signature() {
+        use ftml_ontology::{domain::declarations::Declaration, terms::IsTerm};
+        let result = extract(
+            r#"<html><body><div data-ftml-module="mod"><span data-ftml-symdecl="f" data-ftml-args="2"><span data-ftml-argtypes=""><span data-ftml-type=""><span data-ftml-term="OMID" data-ftml-head="http://example.com?a=archive&m=mod&s=A">A</span></span><span data-ftml-type=""><span data-ftml-term="OMID" data-ftml-head="http://example.com?a=archive&m=mod&s=B">B</span></span></span><span data-ftml-returntype=""><span data-ftml-term="OMID" data-ftml-head="http://example.com?a=archive&m=mod&s=C">C</span></span></span></div></body></html>"#,
+        );
+        let [module] = &*result.doc.modules else {
+            panic!("expected a single module, got {:?}", result.doc.modules);
+        };
+        let [Declaration::Symbol(symbol)] = &*module.declarations else {
+            panic!("expected a single symbol, got {:?}", module.declarations);
+        };
+        let arg_types = symbol
+            .data
+            .arg_types
+            .iter()
+            .flatten()
+            .flat_map(IsTerm::symbols)
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        assert!(symbol.data.arg_types.iter().all(Option::is_some));
+        assert_eq!(
+            arg_types,
+            [
+                "http://example.com?a=archive&m=mod&s=A",
+                "http://example.com?a=archive&m=mod&s=B"
+            ]
+        );
+        let return_type = symbol
+            .data
+            .return_type
+            .iter()
+            .flat_map(IsTerm::symbols)
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        assert_eq!(return_type, ["http://example.com?a=archive&m=mod&s=C"]);
+        assert!(symbol.data.tp.is_none());
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn problem_minutes() {
+        let result = extract(
+            r#"<html><body><div data-ftml-problem="" data-ftml-id="timed" data-ftml-problemminutes="7.5" data-ftml-problempoints="4"><p>Solve</p></div><div data-ftml-problem="" data-ftml-id="untimed"><p>Solve</p></div></body></html>"#,
+        );
+        let [
+            DocumentElement::Problem(timed),
+            DocumentElement::Problem(untimed),
+        ] = &*result.doc.document.elements
+        else {
+            panic!(
+                "expected two problems, got {:?}",
+                result.doc.document.elements
+            );
+        };
+        assert_eq!(timed.data.minutes, Some(7.5));
+        assert_eq!(timed.data.points, Some(4.0));
+        assert_eq!(untimed.data.minutes, None);
+    }
+}
@@ -3,6 +3,7 @@ use std::hint::unreachable_unchecked;
 #[allow(clippy::wildcard_imports)]
 use super::ever::*;
 use crate::{FtmlResult, HtmlExtractor};
+use either::Either;
 use ftml_ontology::{
     narrative::DocumentRange,
     utils::{Css, SourceRange},
@@ -10,8 +11,8 @@ use ftml_ontology::{
 use ftml_parser::{
     FtmlKey,
     extraction::{
-        CloseFtmlElement, FtmlExtractionError, FtmlExtractor, KeyList, attributes::Attributes,
-        nodes::FtmlNode,
+        CloseFtmlElement, FtmlExtractionError, FtmlExtractor, KeyList, OpenFtmlElement,
+        attributes::Attributes, nodes::FtmlNode,
     },
 };
 use html5ever::{
@@ -34,7 +35,7 @@ impl<Img: Fn(&str) -> Option<String>, CS: Fn(&str) -> Option<Box<str>>> TreeSink
     for HtmlParser<Img, CS>
 {
     type Handle = NodeRef;
-    type Output = Result<FtmlResult, String>;
+    type Output = Result<Either<FtmlResult, Vec<OpenFtmlElement>>, String>;
     type ElemName<'a>
         = &'a QualName
     where
@@ -49,11 +50,14 @@ impl<Img: Fn(&str) -> Option<String>, CS: Fn(&str) -> Option<Box<str>>> TreeSink
             parse_errors,
             mut css,
             mut state,
-            ..
+            dry_run,
         } = self.extractor.into_inner();
         if !parse_errors.is_empty() {
             return Err(parse_errors);
         }
+        if let Some(elems) = dry_run {
+            return Ok(Either::Right(elems));
+        }
         css = Css::merge(std::mem::take(&mut css));
         let res = state.finish();
 
@@ -65,14 +69,15 @@ impl<Img: Fn(&str) -> Option<String>, CS: Fn(&str) -> Option<Box<str>>> TreeSink
         );
         let ftml = String::from_utf8_lossy(&html).into_owned().into_boxed_str();
         let (body, inner_offset) = self.body.get();
-        Ok(FtmlResult {
+        Ok(Either::Left(FtmlResult {
             ftml,
             css: css.into_boxed_slice(),
             errors: self.errors.take().into_boxed_slice(),
             doc: res,
+            stats: state.stats,
             body,
             inner_offset: inner_offset as _,
-        })
+        }))
     }
 
     #[inline]
@@ -195,17 +200,25 @@ impl<Img: Fn(&str) -> Option<String>, CS: Fn(&str) -> Option<Box<str>>> TreeSink
                                 .0
                                 .iter()
                                 .filter_map(|(k, _)| {
-                                    if k.local.starts_with(ftml_parser::PREFIX) {
-                                        FtmlKey::from_attr(&k.local)
-                                    } else {
-                                        None
+                                    if !k.local.starts_with(ftml_parser::PREFIX) {
+                                        return None;
+                                    }
+                                    let key = FtmlKey::from_attr(&k.local);
+                                    if key.is_none()
+                                        && let Err(e) = extractor.state.unknown_key(&k.local)
+                                    {
+                                        errors += 1;
+                                        extractor.state.stats.errors += 1;
+                                        self.errors.borrow_mut().push(e);
                                     }
+                                    key
                                 })
                                 .collect();
                             if !rules.is_empty() {
                                 let mut attrs = attributes.clone();
                                 drop(attributes);
                                 let mut closes = SmallVec::<_, 2>::new();
+                                let mut failed = 0;
                                 for r in rules.apply(&mut *extractor, &mut attrs, &child) {
                                     match r {
                                         Ok(((), c)) => {
@@ -214,11 +227,13 @@ impl<Img: Fn(&str) -> Option<String>, CS: Fn(&str) -> Option<Box<str>>> TreeSink
                                             }
                                         }
                                         Err(e) => {
-                                            errors += 1;
+                                            failed += 1;
                                             self.errors.borrow_mut().push(e);
                                         }
                                     }
                                 }
+                                errors += failed;
+                                extractor.state.stats.errors += failed;
                                 *child_elem.attributes.borrow_mut() = attrs;
                                 if !closes.is_empty() {
                                     closes.reverse();
@@ -358,6 +373,7 @@ impl<Img: Fn(&str) -> Option<String>, CS: Fn(&str) -> Option<Box<str>>> TreeSink
             if let Err(e) = extractor.close(c, node) {
                 let s = node.string();
                 tracing::error!("errors: {e}\n in: {s}");
+                extractor.state.stats.errors += 1;
                 self.errors.borrow_mut().push(e);
             }
         }
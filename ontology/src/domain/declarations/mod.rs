@@ -169,6 +169,38 @@ impl<'d> AnyDeclarationRef<'d> {
         }
     }
 }
+impl AnyDeclarationRef<'_> {
+    /// A short, human-readable name for the kind of this declaration (e.g. `"symbol"`)
+    #[must_use]
+    pub const fn kind_name(&self) -> &'static str {
+        match self {
+            Self::NestedModule(_) => "nested_module",
+            Self::Import { .. } => "import",
+            Self::Symbol(_) => "symbol",
+            Self::MathStructure(_) => "structure",
+            Self::Morphism(_) => "morphism",
+            Self::Extension(_) => "extension",
+            Self::Rule { .. } => "rule",
+        }
+    }
+
+    /// Compact JSON representation of this declaration and its children; see
+    /// [`ModuleData::debug_tree`](crate::domain::modules::ModuleData::debug_tree).
+    #[cfg(feature = "serde")]
+    #[must_use]
+    pub fn debug_tree(self) -> serde_json::Value {
+        let uri = match self {
+            Self::Import { uri, .. } => Some(uri.to_string()),
+            Self::Rule { id, .. } => Some(id.to_string()),
+            _ => self.uri().map(ToString::to_string),
+        };
+        serde_json::json!({
+            "kind": self.kind_name(),
+            "uri": uri,
+            "children": self.tree_children().map(Self::debug_tree).collect::<Vec<_>>(),
+        })
+    }
+}
 impl crate::Ftml for AnyDeclarationRef<'_> {
     #[cfg(feature = "rdf")]
     #[allow(clippy::enum_glob_use)]
@@ -34,6 +34,110 @@ pub struct Morphism {
     #[cfg_attr(any(feature = "serde", feature = "serde-lite"), serde(default))]
     pub source: SourceRange,
 }
+impl Morphism {
+    /// Elaborates this morphism (if that has not happened yet), fetching the modules of its
+    /// domain with `get`, and returns the elaborated declarations (which are then also the
+    /// [`declarations`](HasDeclarations::declarations) of `self`).
+    ///
+    /// Every symbol of the domain is elaborated to a symbol whose definiens is either the
+    /// definiens of its [`Assignment`] or - if there is none - the original definiens
+    /// mapped along this morphism.
+    ///
+    /// # Errors
+    /// if `get` fails; the elaboration then remains empty.
+    pub fn elaborate<E: std::fmt::Display>(
+        &self,
+        get: impl FnMut(&ModuleUri) -> Result<ModuleLike, E>,
+    ) -> Result<&[Declaration], E> {
+        Elaboration::initialize(self, get)?;
+        Ok(self.elaboration.get())
+    }
+
+    /// The composition `second ∘ self` of `self: A → B` and `second: B' → C`, where the
+    /// codomain `B` of a morphism is the module containing it; i.e. a morphism `A → C` named
+    /// `<second>/<self>` in `C`, which is [`total`](Self::total) iff both are.
+    ///
+    /// `domain` are the symbols of `A` and `translated` those of `B'` - each including the
+    /// symbols of everything they (transitively) include and of their nested modules and
+    /// structures. Whether `B` is included in `B'` is *not* checked here.
+    ///
+    /// For every symbol `s` in `domain`, the composition assigns
+    /// - the definiens (and refined type) `self` assigns to `s` with every symbol in
+    ///   `translated` substituted by its image under `second` - i.e. the definiens `second`
+    ///   assigns to it, or otherwise the symbol it elaborates to, or
+    /// - if `self` assigns no definiens to `s`, the definiens `second` assigns to the symbol
+    ///   `s` elaborates to under `self`, if any.
+    ///
+    /// Symbols without either are left unassigned (and elaborate to new symbols of the
+    /// composition). Names and macro names of the individual assignments are not carried over.
+    #[must_use]
+    pub fn compose(
+        &self,
+        second: &Self,
+        domain: impl IntoIterator<Item = SymbolUri>,
+        translated: &[SymbolUri],
+    ) -> Self {
+        fn image(morphism: &Morphism, symbol: &SymbolUri) -> Term {
+            match morphism.elements.iter().find(|a| a.original == *symbol) {
+                Some(Assignment {
+                    definiens: Some(df),
+                    ..
+                }) => df.clone(),
+                Some(a) => a.elaborated_uri().into(),
+                None => Assignment::default_uri(&morphism.uri, symbol).into(),
+            }
+        }
+        let translate = |t: &Term| {
+            t.replace_subterms(|t| match t {
+                Term::Symbol { uri, .. } if translated.contains(uri) => Some(image(second, uri)),
+                _ => None,
+            })
+            .into_owned()
+        };
+
+        let uri = Assignment::default_uri(&second.uri, &self.uri);
+        let elements = domain
+            .into_iter()
+            .filter_map(|original| {
+                let assignment = self.elements.iter().find(|a| a.original == original);
+                let definiens = match assignment.and_then(|a| a.definiens.as_ref()) {
+                    Some(df) => Some(translate(df)),
+                    None => {
+                        let elaborated = assignment.map_or_else(
+                            || Assignment::default_uri(&self.uri, &original),
+                            Assignment::elaborated_uri,
+                        );
+                        second
+                            .elements
+                            .iter()
+                            .find(|a| a.original == elaborated)
+                            .and_then(|a| a.definiens.clone())
+                    }
+                };
+                let refined_type = assignment
+                    .and_then(|a| a.refined_type.as_ref())
+                    .map(translate);
+                (definiens.is_some() || refined_type.is_some()).then(|| Assignment {
+                    original,
+                    morphism: uri.clone(),
+                    definiens,
+                    refined_type,
+                    new_name: None,
+                    macroname: None,
+                    source: SourceRange::DEFAULT,
+                })
+            })
+            .collect();
+        Self {
+            uri,
+            domain: self.domain.clone(),
+            total: self.total && second.total,
+            elements,
+            elaboration: Elaboration::default(),
+            source: SourceRange::DEFAULT,
+        }
+    }
+}
 
 impl crate::__private::Sealed for Morphism {}
 impl crate::Ftml for Morphism {
@@ -27,6 +27,23 @@ pub struct MathStructure {
     #[cfg_attr(any(feature = "serde", feature = "serde-lite"), serde(default))]
     pub source: SourceRange,
 }
+impl MathStructure {
+    /// The fields declared in this structure (see [`StructureDeclaration::is_field`]), in
+    /// declaration order.
+    ///
+    /// Fields inherited via imports are *not* included; see [`imports`](Self::imports).
+    pub fn fields(&self) -> impl DoubleEndedIterator<Item = &StructureDeclaration> {
+        self.elements.iter().filter(|d| d.is_field())
+    }
+
+    /// The modules/structures imported into this structure.
+    pub fn imports(&self) -> impl DoubleEndedIterator<Item = &ModuleUri> {
+        self.elements
+            .iter()
+            .filter_map(StructureDeclaration::import)
+    }
+}
+
 impl crate::__private::Sealed for MathStructure {}
 impl crate::Ftml for MathStructure {
     #[cfg(feature = "rdf")]
@@ -121,6 +138,30 @@ pub enum StructureDeclaration {
         source: SourceRange,
     },
 }
+impl StructureDeclaration {
+    /// Whether this declaration is a *field*, i.e. a [`Symbol`] or [`Morphism`] declared
+    /// (with its own [`SymbolUri`]) in the structure itself.
+    ///
+    /// [`Import`](Self::Import)s are not fields (the fields of the imported module or
+    /// structure are only *visible* in the importing structure), and neither are
+    /// [`Rule`](Self::Rule)s.
+    #[inline]
+    #[must_use]
+    pub const fn is_field(&self) -> bool {
+        matches!(self, Self::Symbol(_) | Self::Morphism(_))
+    }
+
+    /// The uri of the imported module/structure, if this is an [`Import`](Self::Import).
+    #[inline]
+    #[must_use]
+    pub const fn import(&self) -> Option<&ModuleUri> {
+        match self {
+            Self::Import { uri, .. } => Some(uri),
+            _ => None,
+        }
+    }
+}
+
 impl crate::__private::Sealed for StructureDeclaration {}
 impl crate::Ftml for StructureDeclaration {
     #[cfg(feature = "rdf")]
@@ -194,6 +235,21 @@ pub struct StructureExtension {
     #[cfg_attr(any(feature = "serde", feature = "serde-lite"), serde(default))]
     pub source: SourceRange,
 }
+impl StructureExtension {
+    /// The fields added to the [`target`](Self::target) structure by this extension (see
+    /// [`StructureDeclaration::is_field`]), in declaration order.
+    pub fn fields(&self) -> impl DoubleEndedIterator<Item = &StructureDeclaration> {
+        self.elements.iter().filter(|d| d.is_field())
+    }
+
+    /// The modules/structures imported into this extension.
+    pub fn imports(&self) -> impl DoubleEndedIterator<Item = &ModuleUri> {
+        self.elements
+            .iter()
+            .filter_map(StructureDeclaration::import)
+    }
+}
+
 impl crate::__private::Sealed for StructureExtension {}
 impl crate::Ftml for StructureExtension {
     #[cfg(feature = "rdf")]
@@ -287,3 +343,51 @@ impl deepsize::DeepSizeOf for StructureExtension {
             .sum::<usize>()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{MathStructure, StructureDeclaration};
+    use crate::{
+        domain::declarations::{IsDeclaration, symbols::Symbol},
+        utils::SourceRange,
+    };
+    use ftml_uris::ModuleUri;
+
+    fn symbol(name: &str) -> StructureDeclaration {
+        StructureDeclaration::Symbol(Symbol {
+            uri: format!("http://example.com?a=archive&m=module/monoid&s={name}")
+                .parse()
+                .expect("valid uri"),
+            data: Box::default(),
+        })
+    }
+
+    #[test]
+    fn structure_fields() {
+        let import: ModuleUri = "http://example.com?a=archive&m=module/semigroup"
+            .parse()
+            .expect("valid uri");
+        let structure = MathStructure {
+            uri: "http://example.com?a=archive&m=module&s=monoid"
+                .parse()
+                .expect("valid uri"),
+            elements: Box::new([
+                StructureDeclaration::Import {
+                    uri: import.clone(),
+                    source: SourceRange::default(),
+                },
+                symbol("unit"),
+                symbol("op"),
+            ]),
+            macroname: None,
+            source: SourceRange::default(),
+        };
+        let fields = structure
+            .fields()
+            .filter_map(IsDeclaration::uri)
+            .map(|u| u.name().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(fields, ["unit", "op"]);
+        assert_eq!(structure.imports().collect::<Vec<_>>(), [&import]);
+    }
+}
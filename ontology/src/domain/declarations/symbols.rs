@@ -45,10 +45,16 @@ pub struct SymbolData {
     pub tp: TermContainer,
     #[cfg_attr(any(feature = "serde", feature = "serde-lite"), serde(default))]
     pub df: TermContainer,
+    /// The type of the result of applying this symbol to its arguments; in conjunction with
+    /// [`arg_types`](Self::arg_types), an alternative to a full [`tp`](Self::tp).
     #[cfg_attr(any(feature = "serde", feature = "serde-lite"), serde(default))]
     pub return_type: Option<Term>,
+    /// The types of the arguments of this symbol, in the order of its [`arity`](Self::arity);
+    /// `None` for an argument whose type is unknown, and empty if none are given (arguments
+    /// beyond the end are hence unknown, too). Only extracted in the absence of a
+    /// [`tp`](Self::tp).
     #[cfg_attr(any(feature = "serde", feature = "serde-lite"), serde(default))]
-    pub argument_types: Box<[Term]>,
+    pub arg_types: Box<[Option<Term>]>,
     #[cfg_attr(any(feature = "serde", feature = "serde-lite"), serde(default))]
     pub assoctype: Option<AssocType>,
     #[cfg_attr(any(feature = "serde", feature = "serde-lite"), serde(default))]
@@ -306,9 +312,14 @@ impl deepsize::DeepSizeOf for SymbolData {
                 .map(|t| t.deep_size_of_children(context))
                 .unwrap_or_default()
             + self
-                .argument_types
+                .arg_types
                 .iter()
-                .map(|t| std::mem::size_of_val(t) + t.deep_size_of_children(context))
+                .map(|t| {
+                    std::mem::size_of_val(t)
+                        + t.as_ref()
+                            .map(|t| t.deep_size_of_children(context))
+                            .unwrap_or_default()
+                })
                 .sum::<usize>()
     }
 }
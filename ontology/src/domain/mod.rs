@@ -1,9 +1,14 @@
-use ftml_uris::{DomainUriRef, ModuleUri, UriName};
+use ftml_uris::{DomainUriRef, ModuleUri, SymbolUri, UriName};
 
 use crate::{
     domain::{
-        declarations::{AnyDeclarationRef, IsDeclaration},
-        modules::{Module, ModuleLike},
+        declarations::{
+            AnyDeclarationRef, IsDeclaration,
+            morphisms::Morphism,
+            structures::{MathStructure, StructureExtension},
+            symbols::Symbol,
+        },
+        modules::{Module, ModuleLike, NestedModule},
     },
     utils::SharedArc,
 };
@@ -20,6 +25,40 @@ impl<T: IsDeclaration> std::ops::Deref for SharedDeclaration<T> {
         &self.0
     }
 }
+/// A [`SharedDeclaration`] of any kind that has a [`SymbolUri`]; i.e. the shared counterpart
+/// of an [`AnyDeclarationRef`] that is neither an import nor a rule.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub enum AnyDeclaration {
+    NestedModule(SharedDeclaration<NestedModule>),
+    Symbol(SharedDeclaration<Symbol>),
+    MathStructure(SharedDeclaration<MathStructure>),
+    Morphism(SharedDeclaration<Morphism>),
+    Extension(SharedDeclaration<StructureExtension>),
+}
+impl AnyDeclaration {
+    #[must_use]
+    pub fn as_ref(&self) -> AnyDeclarationRef<'_> {
+        match self {
+            Self::NestedModule(m) => AnyDeclarationRef::NestedModule(m),
+            Self::Symbol(s) => AnyDeclarationRef::Symbol(s),
+            Self::MathStructure(s) => AnyDeclarationRef::MathStructure(s),
+            Self::Morphism(m) => AnyDeclarationRef::Morphism(m),
+            Self::Extension(e) => AnyDeclarationRef::Extension(e),
+        }
+    }
+
+    #[must_use]
+    pub fn uri(&self) -> &SymbolUri {
+        match self {
+            Self::NestedModule(m) => &m.uri,
+            Self::Symbol(s) => &s.uri,
+            Self::MathStructure(s) => &s.uri,
+            Self::Morphism(m) => &m.uri,
+            Self::Extension(e) => &e.uri,
+        }
+    }
+}
+
 impl Module {
     pub fn get_as<T: IsDeclaration>(&self, name: &UriName) -> Option<SharedDeclaration<T>> {
         SharedArc::opt_new(self, |m| &m.0, move |e| e.find(name.steps()).ok_or(()))
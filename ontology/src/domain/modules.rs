@@ -4,13 +4,14 @@ use ftml_uris::{Language, ModuleUri, SymbolUri};
 
 use crate::{
     domain::{
-        HasDeclarations, SharedDeclaration,
+        AnyDeclaration, HasDeclarations, SharedDeclaration,
         declarations::{
             AnyDeclarationRef, Declaration, IsDeclaration,
             morphisms::Morphism,
             structures::{MathStructure, StructureExtension},
         },
     },
+    terms::{StableHasher, Term},
     utils::{RefTree, SharedArc, SourceRange},
 };
 
@@ -51,6 +52,23 @@ impl ModuleLike {
             }
         }
     }
+
+    /// The declaration `name` (relative to this module), whatever its kind; [`None`] if there
+    /// is no such declaration.
+    #[must_use]
+    pub fn get_declaration(&self, name: &ftml_uris::UriName) -> Option<AnyDeclaration> {
+        // meh: this needs to find() twice
+        Some(match self.find_declaration(name.steps())? {
+            AnyDeclarationRef::NestedModule(_) => AnyDeclaration::NestedModule(self.get_as(name)?),
+            AnyDeclarationRef::Symbol(_) => AnyDeclaration::Symbol(self.get_as(name)?),
+            AnyDeclarationRef::MathStructure(_) => {
+                AnyDeclaration::MathStructure(self.get_as(name)?)
+            }
+            AnyDeclarationRef::Morphism(_) => AnyDeclaration::Morphism(self.get_as(name)?),
+            AnyDeclarationRef::Extension(_) => AnyDeclaration::Extension(self.get_as(name)?),
+            AnyDeclarationRef::Import { .. } | AnyDeclarationRef::Rule { .. } => return None,
+        })
+    }
 }
 impl From<Module> for ModuleLike {
     #[inline]
@@ -151,8 +169,186 @@ impl ModuleData {
     pub fn close(self) -> Module {
         Module(triomphe::Arc::new(self))
     }
+
+    /// A compact JSON tree of this module's declarations intended for debugging.
+    /// Every node has the form `{"kind": ..., "uri": ..., "children": [...]}`, where `kind`
+    /// is [`AnyDeclarationRef::kind_name`]; for [`Rule`](AnyDeclarationRef::Rule)s,
+    /// `uri` is the rule's id.
+    #[cfg(feature = "serde")]
+    #[must_use]
+    pub fn debug_tree(&self) -> serde_json::Value {
+        serde_json::json!({
+            "kind": "module",
+            "uri": self.uri.to_string(),
+            "children": self
+                .declarations()
+                .map(AnyDeclarationRef::debug_tree)
+                .collect::<Vec<_>>(),
+        })
+    }
+
+    /// Checks the imports of this module for self-imports (including nested modules
+    /// importing any module they are nested in) and duplicate imports within the same
+    /// declaration list.
+    ///
+    /// This only considers this module's own declarations; imported modules are
+    /// *not* followed, so cycles spanning several modules are not detected.
+    ///
+    /// # Errors
+    /// on the first offending import encountered.
+    pub fn validate_imports(&self) -> Result<(), ImportCycle> {
+        fn check<'a>(
+            current: &ModuleUri,
+            decls: impl Iterator<Item = AnyDeclarationRef<'a>>,
+            enclosing: &mut Vec<ModuleUri>,
+        ) -> Result<(), ImportCycle> {
+            let mut imported = rustc_hash::FxHashSet::default();
+            for d in decls {
+                match d {
+                    AnyDeclarationRef::Import { uri, .. } => {
+                        if enclosing.contains(uri) {
+                            return Err(ImportCycle::SelfImport(current.clone()));
+                        }
+                        if !imported.insert(uri) {
+                            return Err(ImportCycle::DuplicateImport {
+                                importer: current.clone(),
+                                imported: uri.clone(),
+                            });
+                        }
+                    }
+                    AnyDeclarationRef::NestedModule(n) => {
+                        let uri = n.uri.clone().into_module();
+                        enclosing.push(uri.clone());
+                        check(&uri, n.declarations(), enclosing)?;
+                        enclosing.pop();
+                    }
+                    _ => (),
+                }
+            }
+            Ok(())
+        }
+        check(&self.uri, self.declarations(), &mut vec![self.uri.clone()])
+    }
+
+    /// A hash over the *content* of this module that is stable across runs and processes
+    /// (for the same build of this crate), suitable e.g. as a cache key.
+    ///
+    /// Unlike the derived [`Hash`] implementation (and that of [`Module`], which only hashes
+    /// the [`ModuleUri`]), this is computed field by field from the module's uri, meta module,
+    /// signature and (recursively) its declarations - hashing uris, names and ids by their
+    /// string forms and terms by their [`stable_hash`](crate::terms::Term::stable_hash) - and
+    /// hence does not depend on interning or memory addresses. Source ranges, as well as
+    /// inferred, checked or elaborated data, are not taken into account.
+    #[must_use]
+    pub fn content_hash(&self) -> u64 {
+        let mut h = StableHasher::default();
+        h.display(&self.uri);
+        h.option(self.meta_module.as_ref(), |h, m| h.display(m));
+        h.option(self.signature.as_ref(), |h, l| h.display(l));
+        hash_declarations(&mut h, self.declarations());
+        h.finish()
+    }
+}
+
+/// Error returned by [`ModuleData::validate_imports`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ImportCycle {
+    /// A module imports itself, or a nested module imports itself or one of the
+    /// modules it is nested in.
+    #[error("module {0} imports itself")]
+    SelfImport(ModuleUri),
+    /// The same module is imported more than once in the same declaration list.
+    #[error("module {imported} is imported more than once in {importer}")]
+    DuplicateImport {
+        importer: ModuleUri,
+        imported: ModuleUri,
+    },
+}
+
+fn hash_declarations<'d>(h: &mut StableHasher, decls: impl Iterator<Item = AnyDeclarationRef<'d>>) {
+    let decls = decls.collect::<Vec<_>>();
+    h.len(decls.len());
+    for d in decls {
+        hash_declaration(h, d);
+    }
+}
+
+fn hash_declaration(h: &mut StableHasher, d: AnyDeclarationRef<'_>) {
+    fn terms<'t>(h: &mut StableHasher, terms: impl ExactSizeIterator<Item = &'t Term>) {
+        h.len(terms.len());
+        for t in terms {
+            h.term(t);
+        }
+    }
+    h.display(d.kind_name());
+    match d {
+        AnyDeclarationRef::Import { uri, .. } => h.display(uri),
+        AnyDeclarationRef::Rule { id, parameters, .. } => {
+            h.display(id);
+            terms(h, parameters.iter());
+        }
+        AnyDeclarationRef::Symbol(s) => {
+            let data = &s.data;
+            h.display(&s.uri);
+            h.len(data.arity.iter().len());
+            for m in data.arity.iter() {
+                h.display(m.as_char());
+            }
+            h.option(data.macroname.as_ref(), |h, m| h.display(m));
+            h.len(data.role.len());
+            for r in &data.role {
+                h.display(r);
+            }
+            h.option(data.tp.get_parsed(), StableHasher::term);
+            h.option(data.df.get_parsed(), StableHasher::term);
+            h.option(data.return_type.as_ref(), StableHasher::term);
+            h.len(data.arg_types.len());
+            for t in &data.arg_types {
+                h.option(t.as_ref(), StableHasher::term);
+            }
+            h.option(data.assoctype.as_ref(), |h, a| h.tag(*a as u8));
+            h.option(data.reordering.as_ref(), |h, p| {
+                h.len(p.len());
+                h.bytes(p.indices());
+            });
+        }
+        AnyDeclarationRef::Morphism(m) => {
+            h.display(&m.uri);
+            h.display(&m.domain);
+            h.tag(u8::from(m.total));
+            // the elaboration is derived from the assignments, so we skip it
+            h.len(m.elements.len());
+            for a in &m.elements {
+                h.display(&a.original);
+                h.display(&a.morphism);
+                h.option(a.definiens.as_ref(), StableHasher::term);
+                h.option(a.refined_type.as_ref(), StableHasher::term);
+                h.option(a.new_name.as_ref(), |h, n| h.display(n));
+                h.option(a.macroname.as_ref(), |h, m| h.display(m));
+            }
+        }
+        AnyDeclarationRef::MathStructure(s) => {
+            h.display(&s.uri);
+            h.option(s.macroname.as_ref(), |h, m| h.display(m));
+            hash_declarations(h, s.declarations());
+        }
+        AnyDeclarationRef::Extension(e) => {
+            h.display(&e.uri);
+            h.display(&e.target);
+            hash_declarations(h, e.declarations());
+        }
+        AnyDeclarationRef::NestedModule(n) => {
+            h.display(&n.uri);
+            hash_declarations(h, n.declarations());
+        }
+    }
 }
 
+/// A shared, immutable [`ModuleData`].
+///
+/// [`Hash`] only takes the [`ModuleUri`] into account (consistent with [`Borrow<ModuleUri>`]);
+/// its value is not stable across runs. Use [`ModuleData::content_hash`] for a hash
+/// of the module's content that is.
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub struct Module(pub(crate) triomphe::Arc<ModuleData>);
 impl std::ops::Deref for Module {
@@ -162,6 +358,39 @@ impl std::ops::Deref for Module {
         &self.0
     }
 }
+impl Module {
+    /// Encodes this module into `writer` like
+    /// [`Document::encode_to_writer`](crate::narrative::documents::Document::encode_to_writer),
+    /// i.e. framed by a [`VersionHeader`](crate::narrative::VersionHeader) and an
+    /// [`EncodingTrailer`](crate::narrative::EncodingTrailer). Returns the number of bytes
+    /// written.
+    ///
+    /// # Errors
+    /// if writing to `writer` fails.
+    #[cfg(feature = "serde")]
+    #[inline]
+    pub fn encode_to_writer(
+        &self,
+        writer: impl std::io::Write,
+    ) -> Result<usize, bincode::error::EncodeError> {
+        crate::narrative::encode_framed(self, writer)
+    }
+
+    /// Decodes a module from `reader` as written by
+    /// [`encode_to_writer`](Self::encode_to_writer).
+    ///
+    /// # Errors
+    /// [`Truncated`](crate::narrative::DecodeError::Truncated) if the data ends prematurely or
+    /// not with an [`EncodingTrailer`](crate::narrative::EncodingTrailer); otherwise, if
+    /// reading fails or the data is not a valid (current) encoding of a module.
+    #[cfg(feature = "serde")]
+    #[inline]
+    pub fn decode_from_reader(
+        reader: impl std::io::Read,
+    ) -> Result<Self, crate::narrative::DecodeError> {
+        crate::narrative::decode_framed(reader)
+    }
+}
 impl std::hash::Hash for Module {
     #[inline]
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
@@ -515,3 +744,76 @@ impl deepsize::DeepSizeOf for NestedModule {
             .sum::<usize>()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::ModuleData;
+    use crate::domain::declarations::{Declaration, symbols::Symbol};
+    use ftml_uris::{ModuleUri, SymbolUri};
+
+    fn module(name: &str) -> ModuleData {
+        let uri: ModuleUri = format!("http://example.com?a=archive&m={name}")
+            .parse()
+            .expect("valid module uri");
+        let symbol: SymbolUri = format!("http://example.com?a=archive&m={name}&s=symbol")
+            .parse()
+            .expect("valid symbol uri");
+        ModuleData {
+            uri,
+            meta_module: None,
+            signature: None,
+            declarations: vec![Declaration::Symbol(Symbol {
+                uri: symbol,
+                data: Box::default(),
+            })]
+            .into_boxed_slice(),
+            source: crate::utils::SourceRange::default(),
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn truncated_encoding() {
+        use crate::narrative::DecodeError;
+        let module = super::Module(triomphe::Arc::new(module("module")));
+        let mut bytes = Vec::new();
+        module.encode_to_writer(&mut bytes).expect("encoding works");
+        let decoded = super::Module::decode_from_reader(bytes.as_slice()).expect("decoding works");
+        assert_eq!(decoded, module);
+        for len in [0, 4, bytes.len() / 2, bytes.len() - 1, bytes.len() - 4] {
+            assert!(
+                matches!(
+                    super::Module::decode_from_reader(&bytes[..len]),
+                    Err(DecodeError::Truncated)
+                ),
+                "{len}"
+            );
+        }
+    }
+
+    #[test]
+    fn self_import() {
+        let mut m = module("module");
+        assert_eq!(m.validate_imports(), Ok(()));
+
+        let mut declarations = m.declarations.into_vec();
+        declarations.push(Declaration::Import {
+            uri: m.uri.clone(),
+            source: crate::utils::SourceRange::default(),
+        });
+        m.declarations = declarations.into_boxed_slice();
+        assert_eq!(
+            m.validate_imports(),
+            Err(super::ImportCycle::SelfImport(m.uri.clone()))
+        );
+    }
+
+    #[test]
+    fn content_hash_is_stable() {
+        // simulates building the same module in two separate sessions
+        let first = module("module").close();
+        let second = module("module").close();
+        assert_eq!(first.content_hash(), second.content_hash());
+        assert_ne!(first.content_hash(), module("other").content_hash());
+    }
+}
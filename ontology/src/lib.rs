@@ -11,6 +11,8 @@ use crate::utils::SourceRange;
 pub mod domain;
 pub mod narrative;
 pub mod terms;
+#[cfg(test)]
+mod test_utils;
 pub mod utils;
 pub(crate) mod __private {
     pub trait Sealed {}
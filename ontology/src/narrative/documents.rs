@@ -1,12 +1,15 @@
 use std::borrow::Borrow;
 
-use ftml_uris::{DocumentElementUri, DocumentUri, Id, NarrativeUriRef, errors::SegmentParseError};
+use ftml_uris::{
+    DocumentElementUri, DocumentUri, Id, NarrativeUriRef, SymbolUri, errors::SegmentParseError,
+};
 
 use crate::{
     narrative::{
         Narrative,
         elements::{
             DocumentElement, DocumentElementRef,
+            figures::{Figure, FigureKind},
             paragraphs::{InvalidParagraphKind, ParagraphKind},
             sections::SectionLevel,
         },
@@ -41,6 +44,60 @@ impl DocumentData {
     pub fn close(self) -> Document {
         Document(triomphe::Arc::new(self))
     }
+
+    /// Checks that no two elements in this document share the same [`DocumentElementUri`].
+    ///
+    /// # Errors
+    /// Returns every element URI that occurs more than once (each reported once, in
+    /// document order).
+    pub fn check_unique_uris(&self) -> Result<(), Vec<DocumentElementUri>> {
+        let mut seen = rustc_hash::FxHashSet::default();
+        let mut duplicates = Vec::new();
+        for uri in self.dfs().filter_map(DocumentElementRef::element_uri) {
+            if !seen.insert(uri) && !duplicates.contains(uri) {
+                duplicates.push(uri.clone());
+            }
+        }
+        if duplicates.is_empty() {
+            Ok(())
+        } else {
+            Err(duplicates)
+        }
+    }
+
+    /// Shifts the [`DocumentRange`](crate::narrative::DocumentRange)s of all elements in
+    /// this document by `delta` (see
+    /// [`DocumentRange::shifted`](crate::narrative::DocumentRange::shifted)), e.g. after
+    /// embedding the document's HTML at some offset into a larger page.
+    ///
+    /// Note that ranges stored in the document's data buffer (e.g. of problem solutions)
+    /// are not affected.
+    pub fn shift_ranges(&mut self, delta: isize) {
+        for e in &mut self.elements {
+            e.shift_ranges(delta);
+        }
+    }
+
+    /// A compact JSON tree of this document intended for debugging (e.g. of extraction).
+    /// Every node has the form `{"kind": ..., "uri": ..., "children": [...]}`, where `kind`
+    /// is [`DocumentElementRef::kind_name`], `uri` is `null` for elements without one, and
+    /// nodes with a [`DocumentRange`](crate::narrative::DocumentRange) additionally carry
+    /// `"range": {"start": ..., "end": ...}`.
+    ///
+    /// Unlike the [`Serialize`](serde::Serialize) implementation, this omits all
+    /// element-specific data (titles, terms, notations, ...).
+    #[cfg(feature = "serde")]
+    #[must_use]
+    pub fn debug_tree(&self) -> serde_json::Value {
+        serde_json::json!({
+            "kind": "document",
+            "uri": self.uri.to_string(),
+            "children": self
+                .children()
+                .map(DocumentElementRef::debug_tree)
+                .collect::<Vec<_>>(),
+        })
+    }
 }
 
 impl crate::__private::Sealed for DocumentData {}
@@ -125,6 +182,260 @@ impl std::ops::Deref for Document {
         &self.0
     }
 }
+impl Document {
+    /// This document with all [`DocumentRange`](crate::narrative::DocumentRange)s shifted
+    /// by `delta`; see [`DocumentData::shift_ranges`].
+    #[must_use]
+    pub fn shifted(&self, delta: isize) -> Self {
+        let mut data = (*self.0).clone();
+        data.shift_ranges(delta);
+        data.close()
+    }
+
+    /// Compares two documents structurally (e.g. for golden tests), i.e. like `==`, but
+    /// ignoring all positional information that may differ between two extractions of the
+    /// same content: the [`DocumentRange`](crate::narrative::DocumentRange)s of elements and
+    /// the [`SourceRange`]s of elements, problems, variable declarations and notations. (The
+    /// source ranges of modules, structures, extensions and morphisms are not part of the
+    /// document, but of the extracted [`Module`](crate::domain::modules::Module)s.)
+    ///
+    /// Everything else - uris, titles, kinds, styles, terms and the nesting of elements -
+    /// has to coincide. As with `==`, terms are compared without their source ranges and
+    /// [`Problem`](DocumentElement::Problem)s only by uri.
+    #[must_use]
+    pub fn semantically_eq(&self, other: &Self) -> bool {
+        fn cleared(doc: &Document) -> DocumentData {
+            let mut data = (*doc.0).clone();
+            for e in &mut data.elements {
+                e.clear_ranges();
+            }
+            data
+        }
+        triomphe::Arc::ptr_eq(&self.0, &other.0) || cleared(self) == cleared(other)
+    }
+
+    /// A copy of this document containing only its "meaningful" elements, e.g. for a
+    /// lightweight index.
+    ///
+    /// Drops (at any depth) all [`UseModule`](DocumentElement::UseModule),
+    /// [`ImportModule`](DocumentElement::ImportModule),
+    /// [`Definiendum`](DocumentElement::Definiendum),
+    /// [`SymbolReference`](DocumentElement::SymbolReference) and
+    /// [`VariableReference`](DocumentElement::VariableReference) elements (see
+    /// [`DocumentElement::is_prunable`]). All other elements - in particular sections,
+    /// paragraphs, problems, symbol declarations and inputrefs - are kept, with their
+    /// nesting preserved.
+    #[must_use]
+    pub fn pruned(&self) -> Self {
+        let mut data = (*self.0).clone();
+        DocumentElement::prune(&mut data.elements);
+        data.close()
+    }
+
+    /// Encodes this document into `writer` without buffering the whole encoding in memory,
+    /// e.g. to stream it to disk or a socket. Returns the number of bytes written.
+    ///
+    /// The output is a [`VersionHeader`](crate::narrative::VersionHeader), followed by the
+    /// document's [`bincode`] encoding (with the [standard](bincode::config::standard)
+    /// configuration) and an [`EncodingTrailer`](crate::narrative::EncodingTrailer) - i.e.
+    /// exactly the bytes obtained by [`push`](crate::narrative::DataBuffer::push)ing the
+    /// header, the document and the trailer into a [`DataBuffer`](crate::narrative::DataBuffer).
+    ///
+    /// Writes are issued piecemeal, so `writer` should be buffered (e.g. via
+    /// [`BufWriter`](std::io::BufWriter)).
+    ///
+    /// # Errors
+    /// if writing to `writer` fails.
+    #[cfg(feature = "serde")]
+    #[inline]
+    pub fn encode_to_writer(
+        &self,
+        writer: impl std::io::Write,
+    ) -> Result<usize, bincode::error::EncodeError> {
+        crate::narrative::encode_framed(self, writer)
+    }
+
+    /// Decodes a document from `reader` as written by
+    /// [`encode_to_writer`](Self::encode_to_writer), without reading the whole encoding
+    /// into memory first.
+    ///
+    /// Reads are issued piecemeal, so `reader` should be buffered (e.g. via
+    /// [`BufReader`](std::io::BufReader)).
+    ///
+    /// # Errors
+    /// [`Truncated`](crate::narrative::DecodeError::Truncated) if the data ends prematurely or
+    /// not with an [`EncodingTrailer`](crate::narrative::EncodingTrailer); otherwise, if
+    /// reading fails, the data does not start with a valid
+    /// [`VersionHeader`](crate::narrative::VersionHeader) of the current
+    /// [`VERSION`](crate::narrative::VersionHeader::VERSION), or is not a valid encoding
+    /// of a document.
+    #[cfg(feature = "serde")]
+    #[inline]
+    pub fn decode_from_reader(
+        reader: impl std::io::Read,
+    ) -> Result<Self, crate::narrative::DecodeError> {
+        crate::narrative::decode_framed(reader)
+    }
+
+    /// The uri of the innermost element whose [`DocumentRange`](crate::narrative::DocumentRange)
+    /// contains the byte `offset` (see
+    /// [`DocumentRange::contains`](crate::narrative::DocumentRange::contains)), e.g. to
+    /// synchronize an editor with a preview.
+    ///
+    /// Only elements with both a uri and a range are considered. If several such elements
+    /// contain `offset`, the innermost one wins; among elements with the same range, the most
+    /// deeply nested one.
+    #[must_use]
+    pub fn element_at_source(&self, offset: usize) -> Option<&DocumentElementUri> {
+        // in a pre-order traversal, nested elements come after their ancestors
+        self.dfs()
+            .filter(|e| e.range().is_some_and(|r| r.contains(offset)))
+            .filter_map(DocumentElementRef::element_uri)
+            .last()
+    }
+
+    /// The [`DocumentRange`](crate::narrative::DocumentRange) of the element `uri` in this
+    /// document (the inverse of [`element_at_source`](Self::element_at_source)), e.g. to
+    /// scroll an editor to the element currently shown in a preview.
+    ///
+    /// Returns `None` if there is no such element in this document, or if the element does
+    /// not have a range (e.g. [`Notation`](DocumentElementRef::Notation)s).
+    #[must_use]
+    pub fn source_range_of(
+        &self,
+        uri: &DocumentElementUri,
+    ) -> Option<crate::narrative::DocumentRange> {
+        if uri.document != self.uri {
+            return None;
+        }
+        self.dfs()
+            .find(|e| e.element_uri() == Some(uri))
+            .and_then(|e| e.range())
+    }
+
+    /// All [`Figure`]s of kind [`FigureKind::Figure`] in this document, in document order and
+    /// paired with their numbers.
+    ///
+    /// Figures and tables are numbered separately, consecutively from 1 and independently of
+    /// the section structure (i.e. `Figure 3`, not `Figure 2.1`). Figures in referenced
+    /// documents (see [`DocumentReference`](DocumentElementRef::DocumentReference)) are not
+    /// counted.
+    #[must_use]
+    pub fn list_of_figures(&self) -> Vec<(u32, &Figure)> {
+        self.numbered_figures(FigureKind::Figure)
+    }
+
+    /// All [`Figure`]s of kind [`FigureKind::Table`] in this document, numbered like
+    /// [`list_of_figures`](Self::list_of_figures).
+    #[must_use]
+    pub fn list_of_tables(&self) -> Vec<(u32, &Figure)> {
+        self.numbered_figures(FigureKind::Table)
+    }
+
+    fn numbered_figures(&self, kind: FigureKind) -> Vec<(u32, &Figure)> {
+        self.dfs()
+            .filter_map(|e| match e {
+                DocumentElementRef::Figure(f) if f.kind == kind => Some(f),
+                _ => None,
+            })
+            .zip(1..)
+            .map(|(f, i)| (i, f))
+            .collect()
+    }
+
+    /// Checks this document for accessibility problems (see [`A11yIssue`]), in document order:
+    /// - every [`Section`](DocumentElementRef::Section) should have a title,
+    /// - every [`Figure`] (and table) should have a caption - the extracted model does not
+    ///   retain the images themselves, so the caption is their only text alternative, and
+    /// - every [`Term`](DocumentElementRef::Term) without semantic structure (i.e. an
+    ///   [opaque](crate::terms::Term::Opaque) one, which can not be verbalized from its
+    ///   symbols) should carry an `alttext`, `aria-label` or `alt` attribute.
+    ///
+    /// Elements in referenced documents (see
+    /// [`DocumentReference`](DocumentElementRef::DocumentReference)) are not checked.
+    #[must_use]
+    pub fn accessibility_audit(&self) -> Vec<A11yIssue> {
+        const ALT_ATTRIBUTES: [&str; 3] = ["alttext", "aria-label", "alt"];
+        self.dfs()
+            .filter_map(|e| match e {
+                DocumentElementRef::Section(s) if s.title.is_none() => {
+                    Some(A11yIssue::UntitledSection(s.uri.clone()))
+                }
+                DocumentElementRef::Figure(f) if f.caption.is_none() => {
+                    Some(A11yIssue::UncaptionedFigure(f.uri.clone()))
+                }
+                DocumentElementRef::Term(t) => match t.term.presentation() {
+                    Some(crate::terms::Term::Opaque(o))
+                        if !o
+                            .node
+                            .attributes
+                            .iter()
+                            .any(|(k, _)| ALT_ATTRIBUTES.contains(&k.as_ref())) =>
+                    {
+                        Some(A11yIssue::MathWithoutAlt(t.uri.clone()))
+                    }
+                    _ => None,
+                },
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// A reverse index from symbols to the paragraphs in this document that are
+    /// [`for`](crate::narrative::elements::LogicalParagraph::fors) them - restricted to
+    /// [definitions and examples](crate::narrative::elements::LogicalParagraph::is_indexed_for),
+    /// i.e. the same data the reactive local cache of `ftml-dom` accumulates while rendering
+    /// the document, but computed from the static document in one pass.
+    ///
+    /// The paragraphs for each symbol are in document order (including paragraphs nested in
+    /// other elements); the order of the symbols themselves is unspecified. Paragraphs in
+    /// referenced documents (see [`DocumentReference`](DocumentElementRef::DocumentReference))
+    /// are not included.
+    #[must_use]
+    pub fn paragraphs_for(&self) -> rustc_hash::FxHashMap<SymbolUri, Vec<DocumentElementUri>> {
+        let mut index = rustc_hash::FxHashMap::<_, Vec<_>>::default();
+        for p in self.dfs().filter_map(|e| match e {
+            DocumentElementRef::Paragraph(p) if p.is_indexed_for() => Some(p),
+            _ => None,
+        }) {
+            for s in p.for_symbols() {
+                index.entry(s.clone()).or_default().push(p.uri.clone());
+            }
+        }
+        index
+    }
+}
+
+/// An accessibility problem found by [`Document::accessibility_audit`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize, bincode::Decode, bincode::Encode)
+)]
+#[cfg_attr(
+    feature = "serde-lite",
+    derive(serde_lite::Serialize, serde_lite::Deserialize)
+)]
+#[cfg_attr(feature = "typescript", derive(tsify::Tsify))]
+#[cfg_attr(feature = "typescript", tsify(into_wasm_abi, from_wasm_abi))]
+pub enum A11yIssue {
+    /// A section without a title, i.e. without a meaningful entry in the table of contents
+    /// and in screen reader navigation.
+    UntitledSection(DocumentElementUri),
+    /// A figure or table without a caption.
+    UncaptionedFigure(DocumentElementUri),
+    /// An opaque term without a text alternative.
+    MathWithoutAlt(DocumentElementUri),
+}
+impl std::fmt::Display for A11yIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UntitledSection(uri) => write!(f, "section {uri} has no title"),
+            Self::UncaptionedFigure(uri) => write!(f, "figure {uri} has no caption"),
+            Self::MathWithoutAlt(uri) => write!(f, "math {uri} has no text alternative"),
+        }
+    }
+}
 impl std::hash::Hash for Document {
     #[inline]
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
@@ -283,6 +594,11 @@ impl std::fmt::Display for DocumentKind {
 /// 2. an inputref to some other document; the URI is the one for the
 ///    inputref itself; not the referenced Document. For the TOC,
 ///    which document is inputrefed is actually irrelevant.
+///
+/// Serialized (and exported to TypeScript) as an internally tagged object, e.g.
+/// `{"type": "Section", "title": "...", "uri": "...", "id": "...", "children": [...]}`;
+/// `title` and `children` may be omitted. For a simpler schema restricted to the
+/// entries shown in a sidebar, see [`toc_to_json`].
 pub enum TocElem {
     /// A section; the title is assumed to be an HTML string
     Section {
@@ -317,6 +633,57 @@ pub enum TocElem {
     },
     Slide, //{uri:DocumentElementUri}
 }
+/// Exports a table of contents to a simple JSON tree for external navigation UIs, mirroring
+/// the entries shown in the TOC sidebar: Every node has the form
+/// `{"title": ..., "uri": ..., "id": ..., "children": [...]}`, where `id` is the HTML id of
+/// the node (i.e. `#id` navigates to it).
+///
+/// - [`Section`](TocElem::Section)s have their (HTML) title or `null`,
+/// - [`Inputref`](TocElem::Inputref)s have the title of the referenced document, falling back
+///   to its name,
+/// - the children of [`SkippedSection`](TocElem::SkippedSection)s are inlined into the parent,
+/// - [`Paragraph`](TocElem::Paragraph)s and [`Slide`](TocElem::Slide)s are omitted.
+#[cfg(feature = "serde")]
+#[must_use]
+pub fn toc_to_json(toc: &[TocElem]) -> serde_json::Value {
+    fn push(toc: &[TocElem], ret: &mut Vec<serde_json::Value>) {
+        use ftml_uris::IsNarrativeUri;
+        for e in toc {
+            match e {
+                TocElem::Section {
+                    title,
+                    uri,
+                    id,
+                    children,
+                } => ret.push(serde_json::json!({
+                    "title": title,
+                    "uri": uri.to_string(),
+                    "id": id,
+                    "children": toc_to_json(children),
+                })),
+                TocElem::Inputref {
+                    uri,
+                    title,
+                    id,
+                    children,
+                } => ret.push(serde_json::json!({
+                    "title": title
+                        .as_deref()
+                        .map_or_else(|| uri.document_name().to_string(), ToString::to_string),
+                    "uri": uri.to_string(),
+                    "id": id,
+                    "children": toc_to_json(children),
+                })),
+                TocElem::SkippedSection { children } => push(children, ret),
+                TocElem::Paragraph { .. } | TocElem::Slide => (),
+            }
+        }
+    }
+    let mut ret = Vec::new();
+    push(toc, &mut ret);
+    serde_json::Value::Array(ret)
+}
+
 /*
 impl TocElem {
     pub fn iter(v: &[Self]) -> impl Iterator<Item = &Self> {
@@ -475,3 +842,495 @@ impl deepsize::DeepSizeOf for Document {
         std::mem::size_of::<DocumentData>() + self.0.deep_size_of_children(context)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::narrative::{DocumentRange, elements::Section};
+
+    fn section(uri: &DocumentElementUri, children: Vec<DocumentElement>) -> DocumentElement {
+        DocumentElement::Section(Section {
+            range: DocumentRange::default(),
+            uri: uri.clone(),
+            title: None,
+            children: children.into_boxed_slice(),
+            source: SourceRange::DEFAULT,
+        })
+    }
+
+    #[test]
+    fn duplicate_element_uris() {
+        let uri: DocumentUri = "http://example.com?a=archive&d=doc&l=en"
+            .parse()
+            .expect("valid uri");
+        let a: DocumentElementUri = "http://example.com?a=archive&d=doc&l=en&e=a"
+            .parse()
+            .expect("valid uri");
+        let b: DocumentElementUri = "http://example.com?a=archive&d=doc&l=en&e=b"
+            .parse()
+            .expect("valid uri");
+        let doc = |elements: Vec<DocumentElement>| DocumentData {
+            uri: uri.clone(),
+            title: None,
+            elements: elements.into_boxed_slice(),
+            styles: DocumentStyles::default(),
+            top_section_level: SectionLevel::Section,
+            kind: DocumentKind::Article,
+        };
+
+        let unique = doc(vec![section(&a, vec![section(&b, Vec::new())])]);
+        assert_eq!(unique.check_unique_uris(), Ok(()));
+
+        let duplicated = doc(vec![
+            section(&a, vec![section(&b, Vec::new())]),
+            section(&b, vec![section(&b, Vec::new())]),
+        ]);
+        assert_eq!(duplicated.check_unique_uris(), Err(vec![b]));
+    }
+
+    #[test]
+    fn accessibility_issues() {
+        use crate::narrative::elements::{Figure, FigureKind};
+        let uri: DocumentUri = "http://example.com?a=archive&d=doc&l=en"
+            .parse()
+            .expect("valid uri");
+        let sec: DocumentElementUri = "http://example.com?a=archive&d=doc&l=en&e=sec"
+            .parse()
+            .expect("valid uri");
+        let fig: DocumentElementUri = "http://example.com?a=archive&d=doc&l=en&e=sec/fig"
+            .parse()
+            .expect("valid uri");
+        let figure = |uri: &DocumentElementUri, caption: Option<&str>| {
+            DocumentElement::Figure(Figure {
+                range: DocumentRange::default(),
+                uri: uri.clone(),
+                kind: FigureKind::Figure,
+                caption: caption.map(Into::into),
+                children: Box::new([]),
+                source: SourceRange::DEFAULT,
+            })
+        };
+        let captioned: DocumentElementUri = "http://example.com?a=archive&d=doc&l=en&e=sec/cap"
+            .parse()
+            .expect("valid uri");
+        let doc = DocumentData {
+            uri,
+            title: None,
+            elements: vec![section(
+                &sec,
+                vec![figure(&fig, None), figure(&captioned, Some("A picture"))],
+            )]
+            .into_boxed_slice(),
+            styles: DocumentStyles::default(),
+            top_section_level: SectionLevel::Section,
+            kind: DocumentKind::Article,
+        }
+        .close();
+        assert_eq!(
+            doc.accessibility_audit(),
+            [
+                A11yIssue::UntitledSection(sec),
+                A11yIssue::UncaptionedFigure(fig)
+            ]
+        );
+    }
+
+    #[test]
+    fn shifting_ranges() {
+        let range = DocumentRange { start: 10, end: 20 };
+        assert_eq!(range.shifted(5), DocumentRange { start: 15, end: 25 });
+        assert_eq!(range.shifted(-10), DocumentRange { start: 0, end: 10 });
+        assert_eq!(range.shifted(-15), DocumentRange { start: 0, end: 5 });
+
+        let uri: DocumentUri = "http://example.com?a=archive&d=doc&l=en"
+            .parse()
+            .expect("valid uri");
+        let a: DocumentElementUri = "http://example.com?a=archive&d=doc&l=en&e=a"
+            .parse()
+            .expect("valid uri");
+        let b: DocumentElementUri = "http://example.com?a=archive&d=doc&l=en&e=b"
+            .parse()
+            .expect("valid uri");
+        let ranged = |uri, start, end, children| {
+            let mut e = section(uri, children);
+            if let DocumentElement::Section(s) = &mut e {
+                s.range = DocumentRange { start, end };
+            }
+            e
+        };
+        let inner = ranged(&b, 5, 10, Vec::new());
+        let outer = ranged(
+            &a,
+            0,
+            20,
+            vec![DocumentElement::SkipSection(Box::new([inner]))],
+        );
+        let doc = DocumentData {
+            uri,
+            title: None,
+            elements: vec![outer].into_boxed_slice(),
+            styles: DocumentStyles::default(),
+            top_section_level: SectionLevel::Section,
+            kind: DocumentKind::Article,
+        }
+        .close();
+
+        let shifted = doc.shifted(100);
+        let ranges = |d: &Document| d.dfs().filter_map(|e| e.range()).collect::<Vec<_>>();
+        assert_eq!(
+            ranges(&shifted),
+            ranges(&doc)
+                .into_iter()
+                .map(|r| r.shifted(100))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            ranges(&shifted),
+            [
+                DocumentRange {
+                    start: 100,
+                    end: 120
+                },
+                DocumentRange {
+                    start: 105,
+                    end: 110
+                }
+            ]
+        );
+        assert_eq!(ranges(&shifted.shifted(-100)), ranges(&doc));
+    }
+
+    /// A section `sec` (with the given title) spanning `0..30` containing a paragraph `sec/par`
+    /// spanning `10..20`
+    fn nested_paragraph(title: Option<&str>) -> (Document, DocumentElementUri, DocumentElementUri) {
+        use crate::narrative::elements::{paragraphs::ParagraphKind, test_paragraph};
+        let uri: DocumentUri = "http://example.com?a=archive&d=doc&l=en"
+            .parse()
+            .expect("valid uri");
+        let section_uri: DocumentElementUri = "http://example.com?a=archive&d=doc&l=en&e=sec"
+            .parse()
+            .expect("valid uri");
+        let paragraph_uri: DocumentElementUri = "http://example.com?a=archive&d=doc&l=en&e=sec/par"
+            .parse()
+            .expect("valid uri");
+        let paragraph = test_paragraph(
+            paragraph_uri.clone(),
+            ParagraphKind::Definition,
+            DocumentRange { start: 10, end: 20 },
+            &[],
+        );
+        let section = Section {
+            range: DocumentRange { start: 0, end: 30 },
+            uri: section_uri.clone(),
+            title: title.map(Into::into),
+            children: vec![DocumentElement::Paragraph(paragraph)].into_boxed_slice(),
+            source: SourceRange::DEFAULT,
+        };
+        let doc = DocumentData {
+            uri,
+            title: None,
+            elements: vec![DocumentElement::Section(section)].into_boxed_slice(),
+            styles: DocumentStyles::default(),
+            top_section_level: SectionLevel::Section,
+            kind: DocumentKind::Article,
+        }
+        .close();
+        (doc, section_uri, paragraph_uri)
+    }
+
+    #[test]
+    fn semantic_equality() {
+        let (doc, _, _) = nested_paragraph(None);
+        let shifted = doc.shifted(7);
+        assert_ne!(shifted, doc);
+        assert!(shifted.semantically_eq(&doc));
+
+        let mut data = (*doc).clone();
+        if let DocumentElement::Section(s) = &mut data.elements[0] {
+            s.source = SourceRange {
+                start: crate::utils::SourcePos { line: 3, col: 1 },
+                end: crate::utils::SourcePos { line: 9, col: 4 },
+            };
+        }
+        let moved = data.clone().close();
+        assert_ne!(moved, doc);
+        assert!(moved.semantically_eq(&doc));
+
+        data.title = Some("other".into());
+        assert!(!data.close().semantically_eq(&doc));
+    }
+
+    #[test]
+    fn element_at_source_offset() {
+        let (doc, section, paragraph) = nested_paragraph(None);
+        assert_eq!(doc.element_at_source(15), Some(&paragraph));
+        assert_eq!(doc.element_at_source(10), Some(&paragraph));
+        assert_eq!(doc.element_at_source(20), Some(&section));
+        assert_eq!(doc.element_at_source(5), Some(&section));
+        assert_eq!(doc.element_at_source(30), None);
+    }
+
+    #[test]
+    fn source_range_of_element() {
+        let (doc, section, paragraph) = nested_paragraph(None);
+        assert_eq!(
+            doc.source_range_of(&paragraph),
+            Some(DocumentRange { start: 10, end: 20 })
+        );
+        assert_eq!(
+            doc.source_range_of(&section),
+            Some(DocumentRange { start: 0, end: 30 })
+        );
+        let unknown: DocumentElementUri = "http://example.com?a=archive&d=doc&l=en&e=sec/other"
+            .parse()
+            .expect("valid uri");
+        assert_eq!(doc.source_range_of(&unknown), None);
+        for offset in [0, 15, 29] {
+            let uri = doc.element_at_source(offset).expect("element exists");
+            assert!(
+                doc.source_range_of(uri)
+                    .is_some_and(|range| range.contains(offset))
+            );
+        }
+    }
+
+    #[test]
+    fn pruning() {
+        let (doc, _, _) = nested_paragraph(None);
+        let module: ftml_uris::ModuleUri = "http://example.com?a=archive&m=module"
+            .parse()
+            .expect("valid uri");
+        let symbol: ftml_uris::SymbolUri = "http://example.com?a=archive&m=module&s=symbol"
+            .parse()
+            .expect("valid uri");
+        let variable: DocumentElementUri = "http://example.com?a=archive&d=doc&l=en&e=sec/var"
+            .parse()
+            .expect("valid uri");
+        let mut data = (*doc).clone();
+        let DocumentElement::Section(section) = &mut data.elements[0] else {
+            panic!("not a section");
+        };
+        let mut children = vec![
+            DocumentElement::Definiendum {
+                range: DocumentRange::default(),
+                uri: symbol.clone(),
+                source: SourceRange::DEFAULT,
+            },
+            DocumentElement::SymbolReference {
+                range: DocumentRange::default(),
+                uri: symbol,
+                notation: None,
+                source: SourceRange::DEFAULT,
+            },
+        ];
+        children.extend(section.children.iter().cloned());
+        children.push(DocumentElement::VariableReference {
+            range: DocumentRange::default(),
+            uri: variable,
+            notation: None,
+            source: SourceRange::DEFAULT,
+        });
+        section.children = children.into_boxed_slice();
+        let mut elements = vec![
+            DocumentElement::UseModule {
+                uri: module.clone(),
+                source: SourceRange::DEFAULT,
+            },
+            DocumentElement::ImportModule(module),
+        ];
+        elements.extend(data.elements.iter().cloned());
+        data.elements = elements.into_boxed_slice();
+        let noisy = data.close();
+        assert_eq!(noisy.dfs().count(), 7);
+
+        let pruned = noisy.pruned();
+        assert_eq!(pruned, doc);
+        assert_eq!(
+            pruned.dfs().map(|e| e.kind_name()).collect::<Vec<_>>(),
+            ["section", "paragraph"]
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn streaming_encoding() {
+        use crate::narrative::{DataBuffer, EncodingTrailer, VersionHeader};
+        let (doc, _, _) = nested_paragraph(None);
+        let mut streamed = Vec::new();
+        let written = doc.encode_to_writer(&mut streamed).expect("encoding works");
+        assert_eq!(written, streamed.len());
+
+        let mut buffer = DataBuffer::default();
+        buffer.push(&VersionHeader).expect("encoding works");
+        buffer.push(&doc).expect("encoding works");
+        buffer.push(&EncodingTrailer).expect("encoding works");
+        assert_eq!(*buffer.take(), *streamed);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn streaming_roundtrip() {
+        use crate::narrative::{DecodeError, EncodingTrailer};
+        let (doc, _, _) = nested_paragraph(None);
+        let mut bytes = Vec::new();
+        doc.encode_to_writer(&mut bytes).expect("encoding works");
+        let decoded = Document::decode_from_reader(bytes.as_slice()).expect("decoding works");
+        assert_eq!(decoded, doc);
+
+        // wrong version
+        let mut wrong = bytes.clone();
+        wrong[4] = wrong[4].wrapping_add(1);
+        assert!(Document::decode_from_reader(wrong.as_slice()).is_err());
+        // no header
+        let mut headerless = Vec::new();
+        bincode::encode_into_std_write(&doc, &mut headerless, bincode::config::standard())
+            .expect("encoding works");
+        assert!(Document::decode_from_reader(headerless.as_slice()).is_err());
+        // truncated
+        assert!(matches!(
+            Document::decode_from_reader(&bytes[..bytes.len() / 2]),
+            Err(DecodeError::Truncated)
+        ));
+        // truncated right after the (complete) document
+        assert!(matches!(
+            Document::decode_from_reader(&bytes[..bytes.len() - EncodingTrailer::MAGIC.len()]),
+            Err(DecodeError::Truncated)
+        ));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn debug_tree_shape() {
+        let (doc, section_uri, paragraph_uri) = nested_paragraph(Some("Title"));
+        assert_eq!(
+            doc.debug_tree(),
+            serde_json::json!({
+                "kind": "document",
+                "uri": doc.uri.to_string(),
+                "children": [{
+                    "kind": "section",
+                    "uri": section_uri.to_string(),
+                    "range": { "start": 0, "end": 30 },
+                    "children": [{
+                        "kind": "paragraph",
+                        "uri": paragraph_uri.to_string(),
+                        "range": { "start": 10, "end": 20 },
+                        "children": []
+                    }]
+                }]
+            })
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn toc_json_shape() {
+        let section_uri: DocumentElementUri = "http://example.com?a=archive&d=doc&l=en&e=sec"
+            .parse()
+            .expect("valid uri");
+        let sub_uri: DocumentElementUri = "http://example.com?a=archive&d=doc&l=en&e=sec/sub"
+            .parse()
+            .expect("valid uri");
+        let input_uri: DocumentUri = "http://example.com?a=archive&d=other&l=en"
+            .parse()
+            .expect("valid uri");
+        let toc = vec![
+            TocElem::Section {
+                title: Some("Intro".into()),
+                uri: section_uri.clone(),
+                id: "sec".to_string(),
+                children: vec![
+                    TocElem::Slide,
+                    TocElem::SkippedSection {
+                        children: vec![TocElem::Section {
+                            title: None,
+                            uri: sub_uri.clone(),
+                            id: "sub".to_string(),
+                            children: Vec::new(),
+                        }],
+                    },
+                    TocElem::Paragraph {
+                        styles: Vec::new(),
+                        kind: ParagraphKind::Definition,
+                    },
+                ],
+            },
+            TocElem::Inputref {
+                uri: input_uri.clone(),
+                title: None,
+                id: "input".to_string(),
+                children: Vec::new(),
+            },
+        ];
+        assert_eq!(
+            toc_to_json(&toc),
+            serde_json::json!([
+                {
+                    "title": "Intro",
+                    "uri": section_uri.to_string(),
+                    "id": "sec",
+                    "children": [{
+                        "title": null,
+                        "uri": sub_uri.to_string(),
+                        "id": "sub",
+                        "children": []
+                    }]
+                },
+                {
+                    "title": "other",
+                    "uri": input_uri.to_string(),
+                    "id": "input",
+                    "children": []
+                }
+            ])
+        );
+    }
+
+    #[test]
+    fn paragraphs_for_symbols() {
+        use crate::{
+            narrative::elements::{paragraphs::ParagraphKind, test_paragraph},
+            test_utils::sym,
+        };
+        let elem = |name: &str| -> DocumentElementUri {
+            format!("http://example.com?a=archive&d=doc&l=en&e={name}")
+                .parse()
+                .expect("valid uri")
+        };
+        let paragraph = |name: &str, kind, fors: &[SymbolUri]| {
+            DocumentElement::Paragraph(test_paragraph(
+                elem(name),
+                kind,
+                DocumentRange::default(),
+                fors,
+            ))
+        };
+        let doc = DocumentData {
+            uri: "http://example.com?a=archive&d=doc&l=en"
+                .parse()
+                .expect("valid uri"),
+            title: None,
+            elements: vec![
+                paragraph("def1", ParagraphKind::Definition, &[sym("a"), sym("b")]),
+                section(
+                    &elem("sec"),
+                    vec![
+                        paragraph("sec/par", ParagraphKind::Paragraph, &[sym("a")]),
+                        paragraph("sec/def2", ParagraphKind::Definition, &[sym("a")]),
+                    ],
+                ),
+                paragraph("ex", ParagraphKind::Example, &[sym("b")]),
+            ]
+            .into_boxed_slice(),
+            styles: DocumentStyles::default(),
+            top_section_level: SectionLevel::Section,
+            kind: DocumentKind::Article,
+        }
+        .close();
+
+        let index = doc.paragraphs_for();
+        assert_eq!(index.len(), 2);
+        assert_eq!(index[&sym("a")], [elem("def1"), elem("sec/def2")]);
+        assert_eq!(index[&sym("b")], [elem("def1"), elem("ex")]);
+    }
+}
@@ -0,0 +1,138 @@
+use ftml_uris::DocumentElementUri;
+
+use crate::{
+    narrative::{
+        DocumentRange, Narrative,
+        elements::{DocumentElement, DocumentElementRef, IsDocumentElement},
+    },
+    utils::SourceRange,
+};
+
+/// Whether a [`Figure`] is a figure proper or a table. The two are numbered separately;
+/// see [`Document::list_of_figures`](crate::narrative::documents::Document::list_of_figures).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize, bincode::Decode, bincode::Encode)
+)]
+#[cfg_attr(
+    feature = "serde-lite",
+    derive(serde_lite::Serialize, serde_lite::Deserialize)
+)]
+#[cfg_attr(feature = "typescript", derive(tsify::Tsify))]
+#[cfg_attr(feature = "typescript", tsify(into_wasm_abi, from_wasm_abi))]
+pub enum FigureKind {
+    #[default]
+    Figure,
+    Table,
+}
+impl FigureKind {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Figure => "figure",
+            Self::Table => "table",
+        }
+    }
+}
+impl std::fmt::Display for FigureKind {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+#[error("invalid figure kind")]
+pub struct InvalidFigureKind;
+
+impl std::str::FromStr for FigureKind {
+    type Err = InvalidFigureKind;
+    #[inline]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "figure" => Ok(Self::Figure),
+            "table" => Ok(Self::Table),
+            _ => Err(InvalidFigureKind),
+        }
+    }
+}
+
+/// A (numbered) figure or table with an optional caption.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize, bincode::Decode, bincode::Encode)
+)]
+#[cfg_attr(
+    feature = "serde-lite",
+    derive(serde_lite::Serialize, serde_lite::Deserialize)
+)]
+#[cfg_attr(feature = "typescript", derive(tsify::Tsify))]
+#[cfg_attr(feature = "typescript", tsify(into_wasm_abi, from_wasm_abi))]
+pub struct Figure {
+    pub range: DocumentRange,
+    pub uri: DocumentElementUri,
+    pub kind: FigureKind,
+    #[cfg_attr(any(feature = "serde", feature = "serde-lite"), serde(default))]
+    pub caption: Option<Box<str>>,
+    #[cfg_attr(any(feature = "serde", feature = "serde-lite"), serde(default))]
+    pub children: Box<[DocumentElement]>,
+    #[cfg_attr(any(feature = "serde", feature = "serde-lite"), serde(default))]
+    pub source: SourceRange,
+}
+impl crate::__private::Sealed for Figure {}
+impl crate::Ftml for Figure {
+    #[cfg(feature = "rdf")]
+    fn triples(&self) -> impl IntoIterator<Item = ulo::rdf_types::Triple> {
+        self.contains_triples()
+    }
+    #[inline]
+    fn source_range(&self) -> SourceRange {
+        self.source
+    }
+}
+impl Narrative for Figure {
+    #[inline]
+    fn narrative_uri(&self) -> Option<ftml_uris::NarrativeUriRef<'_>> {
+        Some(ftml_uris::NarrativeUriRef::Element(&self.uri))
+    }
+    #[inline]
+    fn children(
+        &self,
+    ) -> impl ExactSizeIterator<Item = DocumentElementRef<'_>> + DoubleEndedIterator {
+        self.children.iter().map(DocumentElement::as_ref)
+    }
+}
+impl IsDocumentElement for Figure {
+    #[inline]
+    fn element_uri(&self) -> Option<&DocumentElementUri> {
+        Some(&self.uri)
+    }
+    #[inline]
+    fn as_ref(&self) -> DocumentElementRef<'_> {
+        DocumentElementRef::Figure(self)
+    }
+    #[inline]
+    fn from_element(e: DocumentElementRef<'_>) -> Option<&Self>
+    where
+        Self: Sized,
+    {
+        match e {
+            DocumentElementRef::Figure(p) => Some(p),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "deepsize")]
+impl deepsize::DeepSizeOf for Figure {
+    fn deep_size_of_children(&self, context: &mut deepsize::Context) -> usize {
+        self.caption.as_ref().map(|t| t.len()).unwrap_or_default()
+            + self
+                .children
+                .iter()
+                .map(|e| std::mem::size_of_val(e) + e.deep_size_of_children(context))
+                .sum::<usize>()
+    }
+}
@@ -1,3 +1,4 @@
+pub mod figures;
 pub mod notations;
 pub mod paragraphs;
 pub mod problems;
@@ -13,6 +14,7 @@ use crate::{
     terms::{Term, TermContainer},
     utils::SourceRange,
 };
+pub use figures::{Figure, FigureKind};
 use ftml_uris::{DocumentElementUri, DocumentUri, Id, ModuleUri, SymbolUri};
 pub use notations::Notation;
 pub use paragraphs::LogicalParagraph;
@@ -134,9 +136,13 @@ pub enum DocumentElement {
     Paragraph(LogicalParagraph),
     Problem(Problem),
     Slide(Slide),
+    Figure(Figure),
     DocumentReference {
         uri: DocumentElementUri,
         target: DocumentUri,
+        /// The element of `target` this reference links to, if any.
+        #[cfg_attr(any(feature = "serde", feature = "serde-lite"), serde(default))]
+        anchor: Option<DocumentElementUri>,
         #[cfg_attr(any(feature = "serde", feature = "serde-lite"), serde(default))]
         source: SourceRange,
     },
@@ -313,6 +319,7 @@ impl super::Narrative for DocumentElement {
             | Self::SkipSection(children) => B(children.iter().map(Self::as_ref)),
             Self::Section(s) => C(s.children()),
             Self::Slide(s) => D(s.children()),
+            Self::Figure(f) => B(f.children.iter().map(DocumentElement::as_ref)),
             Self::Paragraph(s) => E(s.children()),
             Self::Problem(s) => F(s.children()),
         }
@@ -339,6 +346,7 @@ impl Ftml for DocumentElement {
             }
             Self::Section(s) => RdfIterator::Section(s.triples().into_iter()),
             Self::Slide(s) => RdfIterator::Slide(s.triples().into_iter()),
+            Self::Figure(f) => RdfIterator::Children(Box::new(f.triples().into_iter())),
             Self::Paragraph(s) => RdfIterator::Paragraph(s.triples().into_iter()),
             Self::Problem(s) => RdfIterator::Problem(s.triples().into_iter()),
             Self::VariableDeclaration(v) => RdfIterator::Var(v.triples().into_iter()),
@@ -369,6 +377,7 @@ impl Ftml for DocumentElement {
             Self::VariableNotation(e) => e.source_range(),
             Self::Section(e) => e.source_range(),
             Self::Slide(e) => e.source_range(),
+            Self::Figure(e) => e.source_range(),
             Self::Paragraph(e) => e.source_range(),
             Self::Problem(e) => e.source_range(),
         }
@@ -397,10 +406,178 @@ impl DocumentElement {
             | Self::SkipSection(children) => Some(&**children),
             Self::Section(s) => Some(&*s.children),
             Self::Slide(s) => Some(&*s.children),
+            Self::Figure(s) => Some(&*s.children),
             Self::Paragraph(s) => Some(&*s.children),
             Self::Problem(s) => Some(&*s.children),
         }
     }
+
+    /// Shifts the [`DocumentRange`]s of this element and all its descendants by `delta`
+    /// (see [`DocumentRange::shifted`]).
+    pub fn shift_ranges(&mut self, delta: isize) {
+        let (range, children) = match self {
+            Self::UseModule { .. }
+            | Self::SymbolDeclaration(_)
+            | Self::ImportModule(_)
+            | Self::VariableDeclaration(_)
+            | Self::DocumentReference { .. }
+            | Self::Notation { .. }
+            | Self::VariableNotation { .. }
+            | Self::Term { .. } => return,
+            Self::Definiendum { range, .. }
+            | Self::SymbolReference { range, .. }
+            | Self::VariableReference { range, .. } => (Some(range), None),
+            Self::Module {
+                range, children, ..
+            }
+            | Self::MathStructure {
+                range, children, ..
+            }
+            | Self::Extension {
+                range, children, ..
+            }
+            | Self::Morphism {
+                range, children, ..
+            } => (Some(range), Some(children)),
+            Self::SkipSection(children) => (None, Some(children)),
+            Self::Section(s) => (Some(&mut s.range), Some(&mut s.children)),
+            Self::Slide(s) => (Some(&mut s.range), Some(&mut s.children)),
+            Self::Figure(s) => (Some(&mut s.range), Some(&mut s.children)),
+            Self::Paragraph(s) => (Some(&mut s.range), Some(&mut s.children)),
+            Self::Problem(s) => (Some(&mut s.range), Some(&mut s.children)),
+        };
+        if let Some(range) = range {
+            *range = range.shifted(delta);
+        }
+        for c in children.into_iter().flat_map(|c| c.iter_mut()) {
+            c.shift_ranges(delta);
+        }
+    }
+
+    /// Resets all [`DocumentRange`]s and [`SourceRange`]s of this element and its
+    /// descendants (including those of problems, variable declarations and notations) to their
+    /// defaults; see
+    /// [`Document::semantically_eq`](crate::narrative::documents::Document::semantically_eq).
+    pub(crate) fn clear_ranges(&mut self) {
+        let (range, source, children) = match self {
+            Self::SymbolDeclaration(_) | Self::ImportModule(_) | Self::Term(_) => return,
+            Self::UseModule { source, .. } | Self::DocumentReference { source, .. } => {
+                (None, Some(source), None)
+            }
+            Self::Definiendum { range, source, .. }
+            | Self::SymbolReference { range, source, .. }
+            | Self::VariableReference { range, source, .. } => (Some(range), Some(source), None),
+            Self::Notation(n) => (None, Some(&mut n.source), None),
+            Self::VariableNotation(n) => (None, Some(&mut n.source), None),
+            Self::VariableDeclaration(v) => (None, Some(&mut v.data.source), None),
+            Self::Module {
+                range, children, ..
+            }
+            | Self::MathStructure {
+                range, children, ..
+            }
+            | Self::Extension {
+                range, children, ..
+            }
+            | Self::Morphism {
+                range, children, ..
+            } => (Some(range), None, Some(children)),
+            Self::SkipSection(children) => (None, None, Some(children)),
+            Self::Section(s) => (
+                Some(&mut s.range),
+                Some(&mut s.source),
+                Some(&mut s.children),
+            ),
+            Self::Slide(s) => (
+                Some(&mut s.range),
+                Some(&mut s.source),
+                Some(&mut s.children),
+            ),
+            Self::Figure(s) => (
+                Some(&mut s.range),
+                Some(&mut s.source),
+                Some(&mut s.children),
+            ),
+            Self::Paragraph(s) => (
+                Some(&mut s.range),
+                Some(&mut s.source),
+                Some(&mut s.children),
+            ),
+            Self::Problem(s) => (
+                Some(&mut s.range),
+                Some(&mut s.data.source),
+                Some(&mut s.children),
+            ),
+        };
+        if let Some(range) = range {
+            *range = DocumentRange::default();
+        }
+        if let Some(source) = source {
+            *source = SourceRange::DEFAULT;
+        }
+        for c in children.into_iter().flat_map(|c| c.iter_mut()) {
+            c.clear_ranges();
+        }
+    }
+
+    /// Whether this element is dropped by [`Document::pruned`](crate::narrative::documents::Document::pruned):
+    /// [`UseModule`](Self::UseModule), [`ImportModule`](Self::ImportModule),
+    /// [`Definiendum`](Self::Definiendum), [`SymbolReference`](Self::SymbolReference) and
+    /// [`VariableReference`](Self::VariableReference). None of these have children.
+    #[must_use]
+    pub const fn is_prunable(&self) -> bool {
+        matches!(
+            self,
+            Self::UseModule { .. }
+                | Self::ImportModule(_)
+                | Self::Definiendum { .. }
+                | Self::SymbolReference { .. }
+                | Self::VariableReference { .. }
+        )
+    }
+
+    /// Removes all [prunable](Self::is_prunable) elements from `elements` and (recursively)
+    /// their children.
+    pub(crate) fn prune(elements: &mut Box<[Self]>) {
+        *elements = std::mem::take(elements)
+            .into_vec()
+            .into_iter()
+            .filter(|e| !e.is_prunable())
+            .map(|mut e| {
+                if let Some(children) = e.children_mut() {
+                    Self::prune(children);
+                }
+                e
+            })
+            .collect();
+    }
+
+    /// The children of this element, if it can have any.
+    pub(crate) const fn children_mut(&mut self) -> Option<&mut Box<[Self]>> {
+        match self {
+            Self::Module { children, .. }
+            | Self::MathStructure { children, .. }
+            | Self::Extension { children, .. }
+            | Self::Morphism { children, .. }
+            | Self::SkipSection(children) => Some(children),
+            Self::Section(s) => Some(&mut s.children),
+            Self::Slide(s) => Some(&mut s.children),
+            Self::Figure(s) => Some(&mut s.children),
+            Self::Paragraph(s) => Some(&mut s.children),
+            Self::Problem(s) => Some(&mut s.children),
+            Self::UseModule { .. }
+            | Self::SymbolDeclaration(_)
+            | Self::ImportModule(_)
+            | Self::DocumentReference { .. }
+            | Self::Notation(_)
+            | Self::VariableDeclaration(_)
+            | Self::VariableNotation(_)
+            | Self::Definiendum { .. }
+            | Self::SymbolReference { .. }
+            | Self::VariableReference { .. }
+            | Self::Term(_) => None,
+        }
+    }
 }
 
 #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
@@ -442,10 +619,13 @@ pub enum DocumentElementRef<'d> {
     Paragraph(&'d LogicalParagraph),
     Problem(&'d Problem),
     Slide(&'d Slide),
+    Figure(&'d Figure),
     DocumentReference {
         uri: &'d DocumentElementUri,
         target: &'d DocumentUri,
         #[cfg_attr(any(feature = "serde", feature = "serde-lite"), serde(default))]
+        anchor: Option<&'d DocumentElementUri>,
+        #[cfg_attr(any(feature = "serde", feature = "serde-lite"), serde(default))]
         source: SourceRange,
     },
     Notation(&'d NotationReference),
@@ -501,6 +681,7 @@ impl<'r> DocumentElementRef<'r> {
             | Self::SkipSection(children) => B(children.iter().map(DocumentElement::as_ref)),
             Self::Section(s) => C(s.children()),
             Self::Slide(s) => D(s.children()),
+            Self::Figure(f) => B(f.children.iter().map(DocumentElement::as_ref)),
             Self::Paragraph(s) => E(s.children()),
             Self::Problem(s) => F(s.children()),
         }
@@ -541,6 +722,7 @@ impl crate::Ftml for DocumentElementRef<'_> {
             }
             Self::Section(s) => RdfIterator::Section(s.triples().into_iter()),
             Self::Slide(s) => RdfIterator::Slide(s.triples().into_iter()),
+            Self::Figure(f) => RdfIterator::Children(Box::new(f.triples().into_iter())),
             Self::Paragraph(s) => RdfIterator::Paragraph(s.triples().into_iter()),
             Self::Problem(s) => RdfIterator::Problem(s.triples().into_iter()),
             Self::VariableDeclaration(v) => RdfIterator::Var(v.triples().into_iter()),
@@ -571,6 +753,7 @@ impl crate::Ftml for DocumentElementRef<'_> {
             Self::VariableNotation(e) => e.source_range(),
             Self::Section(e) => e.source_range(),
             Self::Slide(e) => e.source_range(),
+            Self::Figure(e) => e.source_range(),
             Self::Paragraph(e) => e.source_range(),
             Self::Problem(e) => e.source_range(),
         }
@@ -611,6 +794,7 @@ impl DocumentElement {
             | Self::Problem(_)
             | Self::VariableDeclaration(_)
             | Self::Slide { .. }
+            | Self::Figure(_)
             | Self::DocumentReference { .. }
             | Self::Notation { .. }
             | Self::VariableNotation { .. }
@@ -642,6 +826,7 @@ impl DocumentElement {
             Self::Problem(s) => &s.uri,
             Self::VariableDeclaration(s) => &s.uri,
             Self::Slide(s) => &s.uri,
+            Self::Figure(s) => &s.uri,
             Self::DocumentReference { uri, .. }
             | Self::Notation(NotationReference { uri, .. })
             | Self::VariableNotation(VariableNotationReference { uri, .. })
@@ -702,13 +887,16 @@ impl DocumentElement {
             Self::Paragraph(p) => DocumentElementRef::Paragraph(p),
             Self::Problem(p) => DocumentElementRef::Problem(p),
             Self::Slide(s) => DocumentElementRef::Slide(s),
+            Self::Figure(s) => DocumentElementRef::Figure(s),
             Self::DocumentReference {
                 uri,
                 target,
+                anchor,
                 source,
             } => DocumentElementRef::DocumentReference {
                 uri,
                 target,
+                anchor: anchor.as_ref(),
                 source: *source,
             },
             Self::Notation(n) => DocumentElementRef::Notation(n),
@@ -765,6 +953,7 @@ impl<'e> DocumentElementRef<'e> {
             | Self::Problem(_)
             | Self::VariableDeclaration(_)
             | Self::Slide { .. }
+            | Self::Figure(_)
             | Self::DocumentReference { .. }
             | Self::Notation { .. }
             | Self::VariableNotation { .. }
@@ -793,6 +982,7 @@ impl<'e> DocumentElementRef<'e> {
             | Self::VariableReference { .. } => return None,
             Self::Section(s) => &s.uri,
             Self::Slide(s) => &s.uri,
+            Self::Figure(s) => &s.uri,
             Self::Paragraph(s) => &s.uri,
             Self::Problem(s) => &s.uri,
             Self::VariableDeclaration(s) => &s.uri,
@@ -804,6 +994,274 @@ impl<'e> DocumentElementRef<'e> {
     }
 }
 
+impl DocumentElementRef<'_> {
+    /// A short, human-readable name for the kind of this element (e.g. `"section"`)
+    #[must_use]
+    pub const fn kind_name(&self) -> &'static str {
+        match self {
+            Self::UseModule { .. } => "use_module",
+            Self::Module { .. } => "module",
+            Self::MathStructure { .. } => "structure",
+            Self::Extension { .. } => "extension",
+            Self::Morphism { .. } => "morphism",
+            Self::SymbolDeclaration(_) => "symbol_declaration",
+            Self::ImportModule(_) => "import",
+            Self::Section(_) => "section",
+            Self::SkipSection(_) => "skip_section",
+            Self::Paragraph(_) => "paragraph",
+            Self::Problem(_) => "problem",
+            Self::Slide(_) => "slide",
+            Self::Figure(f) => f.kind.as_str(),
+            Self::DocumentReference { .. } => "document_reference",
+            Self::Notation(_) => "notation",
+            Self::VariableDeclaration(_) => "variable_declaration",
+            Self::VariableNotation(_) => "variable_notation",
+            Self::Definiendum { .. } => "definiendum",
+            Self::SymbolReference { .. } => "symbol_reference",
+            Self::VariableReference { .. } => "variable_reference",
+            Self::Term(_) => "term",
+        }
+    }
+
+    /// The [`DocumentRange`] of this element in the original HTML, if it has one
+    #[must_use]
+    pub const fn range(&self) -> Option<DocumentRange> {
+        match self {
+            Self::Module { range, .. }
+            | Self::MathStructure { range, .. }
+            | Self::Extension { range, .. }
+            | Self::Morphism { range, .. }
+            | Self::Definiendum { range, .. }
+            | Self::SymbolReference { range, .. }
+            | Self::VariableReference { range, .. } => Some(*range),
+            Self::Section(s) => Some(s.range),
+            Self::Paragraph(p) => Some(p.range),
+            Self::Problem(p) => Some(p.range),
+            Self::Slide(s) => Some(s.range),
+            Self::Figure(s) => Some(s.range),
+            Self::UseModule { .. }
+            | Self::SymbolDeclaration(_)
+            | Self::ImportModule(_)
+            | Self::SkipSection(_)
+            | Self::DocumentReference { .. }
+            | Self::Notation(_)
+            | Self::VariableDeclaration(_)
+            | Self::VariableNotation(_)
+            | Self::Term(_) => None,
+        }
+    }
+
+    /// Compact JSON representation of this element and its children; see
+    /// [`DocumentData::debug_tree`](crate::narrative::documents::DocumentData::debug_tree).
+    #[cfg(feature = "serde")]
+    #[must_use]
+    pub fn debug_tree(self) -> serde_json::Value {
+        let uri = match self {
+            Self::UseModule { uri, .. }
+            | Self::Module { module: uri, .. }
+            | Self::ImportModule(uri) => Some(uri.to_string()),
+            Self::MathStructure { structure: uri, .. }
+            | Self::Extension { extension: uri, .. }
+            | Self::Morphism { morphism: uri, .. }
+            | Self::SymbolDeclaration(uri)
+            | Self::Definiendum { uri, .. }
+            | Self::SymbolReference { uri, .. } => Some(uri.to_string()),
+            Self::VariableReference { uri, .. } => Some(uri.to_string()),
+            Self::SkipSection(_) => None,
+            _ => self.element_uri().map(ToString::to_string),
+        };
+        let mut ret = serde_json::Map::new();
+        ret.insert("kind".to_string(), self.kind_name().into());
+        ret.insert("uri".to_string(), uri.into());
+        if let Some(range) = self.range() {
+            ret.insert(
+                "range".to_string(),
+                serde_json::json!({ "start": range.start, "end": range.end }),
+            );
+        }
+        ret.insert(
+            "children".to_string(),
+            self.children_lt()
+                .map(Self::debug_tree)
+                .collect::<Vec<_>>()
+                .into(),
+        );
+        serde_json::Value::Object(ret)
+    }
+}
+
+/// Element types a [`DocumentElementRef`] can point to; see [`FilterKind::filter_kind`].
+pub trait TypedElement: crate::__private::Sealed {
+    /// The element `e` points to, if it is of this type.
+    fn from_element(e: DocumentElementRef<'_>) -> Option<&Self>;
+}
+macro_rules! typed_element {
+    ($($v:ident($t:ty)),*) => {$(
+        impl TypedElement for $t {
+            #[inline]
+            fn from_element(e: DocumentElementRef<'_>) -> Option<&Self> {
+                if let DocumentElementRef::$v(t) = e { Some(t) } else { None }
+            }
+        }
+    )*};
+}
+typed_element!(
+    Section(Section),
+    Paragraph(LogicalParagraph),
+    Problem(Problem),
+    Slide(Slide),
+    Figure(Figure),
+    Notation(NotationReference),
+    VariableDeclaration(VariableDeclaration),
+    VariableNotation(VariableNotationReference),
+    Term(DocumentTerm)
+);
+
+/// Restricts iterators over [`DocumentElementRef`]s (e.g. [`dfs`](crate::utils::RefTree::dfs))
+/// to elements of a single type, e.g. `document.dfs().filter_kind::<Section>()`.
+pub trait FilterKind<'d>: Iterator<Item = DocumentElementRef<'d>> + Sized {
+    /// Yields (in order) the elements of type `T`.
+    #[inline]
+    fn filter_kind<T: TypedElement + 'd>(self) -> impl Iterator<Item = &'d T> {
+        self.filter_map(T::from_element)
+    }
+}
+impl<'d, I: Iterator<Item = DocumentElementRef<'d>>> FilterKind<'d> for I {}
+
+/// An owned, deserializable counterpart of a [`DocumentElementRef`], e.g. for APIs that send
+/// element refs to a frontend and receive (edited) elements back.
+///
+/// This is a thin wrapper around the [`DocumentElement`] the ref borrows from (see
+/// [`into_inner`](Self::into_inner)); it (de)serializes exactly like the
+/// [`DocumentElementRef`] it was created from, i.e. with the same variant tags and fields
+/// (including all children).
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct DocumentElementDesc(pub DocumentElement);
+impl DocumentElementDesc {
+    #[inline]
+    #[must_use]
+    pub fn into_inner(self) -> DocumentElement {
+        self.0
+    }
+}
+impl std::ops::Deref for DocumentElementDesc {
+    type Target = DocumentElement;
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl From<DocumentElementDesc> for DocumentElement {
+    #[inline]
+    fn from(value: DocumentElementDesc) -> Self {
+        value.0
+    }
+}
+impl From<DocumentElementRef<'_>> for DocumentElementDesc {
+    fn from(value: DocumentElementRef<'_>) -> Self {
+        type E = DocumentElement;
+        Self(match value {
+            DocumentElementRef::UseModule { uri, source } => E::UseModule {
+                uri: uri.clone(),
+                source,
+            },
+            DocumentElementRef::Module {
+                range,
+                module,
+                children,
+            } => E::Module {
+                range,
+                module: module.clone(),
+                children: children.into(),
+            },
+            DocumentElementRef::MathStructure {
+                range,
+                structure,
+                children,
+            } => E::MathStructure {
+                range,
+                structure: structure.clone(),
+                children: children.into(),
+            },
+            DocumentElementRef::Extension {
+                range,
+                extension,
+                target,
+                children,
+            } => E::Extension {
+                range,
+                extension: extension.clone(),
+                target: target.clone(),
+                children: children.into(),
+            },
+            DocumentElementRef::Morphism {
+                range,
+                morphism,
+                children,
+            } => E::Morphism {
+                range,
+                morphism: morphism.clone(),
+                children: children.into(),
+            },
+            DocumentElementRef::SymbolDeclaration(uri) => E::SymbolDeclaration(uri.clone()),
+            DocumentElementRef::ImportModule(uri) => E::ImportModule(uri.clone()),
+            DocumentElementRef::Section(s) => E::Section(s.clone()),
+            DocumentElementRef::SkipSection(children) => E::SkipSection(children.into()),
+            DocumentElementRef::Paragraph(p) => E::Paragraph(p.clone()),
+            DocumentElementRef::Problem(p) => E::Problem(p.clone()),
+            DocumentElementRef::Slide(s) => E::Slide(s.clone()),
+            DocumentElementRef::Figure(s) => E::Figure(s.clone()),
+            DocumentElementRef::DocumentReference {
+                uri,
+                target,
+                anchor,
+                source,
+            } => E::DocumentReference {
+                uri: uri.clone(),
+                target: target.clone(),
+                anchor: anchor.cloned(),
+                source,
+            },
+            DocumentElementRef::Notation(n) => E::Notation(n.clone()),
+            DocumentElementRef::VariableDeclaration(v) => E::VariableDeclaration(v.clone()),
+            DocumentElementRef::VariableNotation(n) => E::VariableNotation(n.clone()),
+            DocumentElementRef::Definiendum { range, uri, source } => E::Definiendum {
+                range,
+                uri: uri.clone(),
+                source,
+            },
+            DocumentElementRef::SymbolReference {
+                range,
+                uri,
+                notation,
+                source,
+            } => E::SymbolReference {
+                range,
+                uri: uri.clone(),
+                notation: notation.cloned(),
+                source,
+            },
+            DocumentElementRef::VariableReference {
+                range,
+                uri,
+                notation,
+                source,
+            } => E::VariableReference {
+                range,
+                uri: uri.clone(),
+                notation: notation.cloned(),
+                source,
+            },
+            DocumentElementRef::Term(t) => E::Term(t.clone()),
+        })
+    }
+}
+
 #[cfg(feature = "rdf")]
 #[allow(clippy::large_enum_variant)]
 enum RdfIterator<
@@ -872,6 +1330,7 @@ impl deepsize::DeepSizeOf for DocumentElement {
                 .map(|c| std::mem::size_of_val(c) + c.deep_size_of_children(context))
                 .sum::<usize>(),
             Self::Slide(s) => s.deep_size_of_children(context),
+            Self::Figure(s) => s.deep_size_of_children(context),
             Self::Section(s) => s.deep_size_of_children(context),
             Self::Paragraph(s) => s.deep_size_of_children(context),
             Self::Problem(s) => s.deep_size_of_children(context),
@@ -881,3 +1340,134 @@ impl deepsize::DeepSizeOf for DocumentElement {
         }
     }
 }
+
+/// A [`Block`](paragraphs::ParagraphFormatting::Block) paragraph without title, styles,
+/// premises, children or steps, for use in tests.
+#[cfg(test)]
+pub(crate) fn test_paragraph(
+    uri: DocumentElementUri,
+    kind: paragraphs::ParagraphKind,
+    range: DocumentRange,
+    fors: &[SymbolUri],
+) -> LogicalParagraph {
+    LogicalParagraph {
+        kind,
+        uri,
+        formatting: paragraphs::ParagraphFormatting::Block,
+        range,
+        title: None,
+        styles: Box::default(),
+        premises: Box::default(),
+        binds_variables: Box::default(),
+        children: Box::default(),
+        fors: fors.iter().map(|s| (s.clone(), None)).collect(),
+        steps: Box::default(),
+        source: SourceRange::DEFAULT,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "serde")]
+    #[test]
+    fn element_desc_roundtrip() {
+        use super::{DocumentElement, DocumentElementDesc, Section};
+        use crate::narrative::DocumentRange;
+        let uri = |name: &str| {
+            format!("http://example.com?a=archive&d=doc&l=en&e={name}")
+                .parse()
+                .expect("valid uri")
+        };
+        let section = DocumentElement::Section(Section {
+            range: DocumentRange { start: 3, end: 42 },
+            uri: uri("intro"),
+            title: Some("Introduction".into()),
+            children: Box::new([DocumentElement::DocumentReference {
+                uri: uri("intro/ref"),
+                target: "http://example.com?a=archive&d=other&l=en"
+                    .parse()
+                    .expect("valid uri"),
+                anchor: None,
+                source: crate::utils::SourceRange::DEFAULT,
+            }]),
+            source: crate::utils::SourceRange::DEFAULT,
+        });
+
+        let desc = DocumentElementDesc::from(section.as_ref());
+        let json = serde_json::to_string(&desc).expect("serializable");
+        assert_eq!(
+            json,
+            serde_json::to_string(&section.as_ref()).expect("serializable")
+        );
+        let back: DocumentElementDesc = serde_json::from_str(&json).expect("deserializable");
+        assert_eq!(back, desc);
+        assert_eq!(back.into_inner(), section);
+    }
+
+    #[test]
+    fn filter_sections() {
+        use super::{DocumentElement, FilterKind, Section, test_paragraph};
+        use crate::{
+            narrative::{
+                DocumentRange,
+                documents::{DocumentData, DocumentKind, DocumentStyles},
+                elements::{SectionLevel, paragraphs::ParagraphKind},
+            },
+            utils::{RefTree, SourceRange},
+        };
+        use ftml_uris::DocumentElementUri;
+        let uri = |name: &str| -> DocumentElementUri {
+            format!("http://example.com?a=archive&d=doc&l=en&e={name}")
+                .parse()
+                .expect("valid uri")
+        };
+        let paragraph = |name: &str| {
+            DocumentElement::Paragraph(test_paragraph(
+                uri(name),
+                ParagraphKind::Definition,
+                DocumentRange::default(),
+                &[],
+            ))
+        };
+        let section = |name: &str, children: Vec<DocumentElement>| {
+            DocumentElement::Section(Section {
+                range: DocumentRange::default(),
+                uri: uri(name),
+                title: None,
+                children: children.into_boxed_slice(),
+                source: SourceRange::DEFAULT,
+            })
+        };
+        let doc = DocumentData {
+            uri: "http://example.com?a=archive&d=doc&l=en"
+                .parse()
+                .expect("valid uri"),
+            title: None,
+            elements: vec![
+                paragraph("a"),
+                section(
+                    "one",
+                    vec![
+                        paragraph("one/a"),
+                        section("one/two", vec![paragraph("one/two/a")]),
+                    ],
+                ),
+                DocumentElement::SkipSection(Box::new([section("three", Vec::new())])),
+            ]
+            .into_boxed_slice(),
+            styles: DocumentStyles::default(),
+            top_section_level: SectionLevel::Section,
+            kind: DocumentKind::Article,
+        }
+        .close();
+
+        let sections = doc
+            .dfs()
+            .filter_kind::<Section>()
+            .map(|s| &s.uri)
+            .collect::<Vec<_>>();
+        assert_eq!(sections, [&uri("one"), &uri("one/two"), &uri("three")]);
+        let paragraphs = doc.dfs().filter_kind::<LogicalParagraph>().count();
+        assert_eq!(paragraphs, 3);
+    }
+}
@@ -1,5 +1,6 @@
 use crate::{
     Ftml,
+    domain::declarations::symbols::ArgumentSpec,
     narrative::{
         DataRef, Narrative,
         elements::{DocumentElementRef, IsDocumentElement},
@@ -7,7 +8,7 @@ use crate::{
     terms::ArgumentMode,
     utils::{RefTree, SourceRange},
 };
-use ftml_uris::{DocumentElementUri, Id, NarrativeUriRef, SymbolUri};
+use ftml_uris::{DocumentElementUri, Id, LeafUri, NarrativeUriRef, SymbolUri};
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(
@@ -71,6 +72,106 @@ pub struct Notation {
     pub op: Option<NotationNode>,
 }
 impl Notation {
+    /// Synthesizes a default applicative notation for a symbol named `symbol_name` with
+    /// arguments `spec`, for symbols without authored notations.
+    ///
+    /// The generated layout is
+    /// ```text
+    /// <mrow>
+    ///   <mi data-ftml-maincomp>symbol_name</mi>
+    ///   <mo data-ftml-comp>(</mo> arg1 <mo data-ftml-comp>,</mo> ... argN <mo data-ftml-comp>)</mo>
+    /// </mrow>
+    /// ```
+    /// where sequence arguments (modes `a` and `B`) are rendered as `ArgSep`s separated by
+    /// `<mo data-ftml-comp>,</mo>`. Since the result is fully bracketed, the precedence is
+    /// [`i64::MIN`] and all argument precedences are [`i64::MAX`] (i.e. neither the notation
+    /// nor its arguments ever get parenthesized). The [`op`](Self::op) notation is just the
+    /// name. For an empty `spec`, the notation consists of the `<mi>` only.
+    #[must_use]
+    pub fn synthesize(symbol_name: &str, spec: &ArgumentSpec) -> Self {
+        // SAFETY: known to be valid Ids
+        let (mi, mo, mrow) = unsafe {
+            (
+                Id::new("mi").unwrap_unchecked(),
+                Id::new("mo").unwrap_unchecked(),
+                Id::new("mrow").unwrap_unchecked(),
+            )
+        };
+        let name = NotationNode {
+            tag: mi,
+            attributes: Box::default(),
+            children: Box::new([NodeOrText::Text(symbol_name.into())]),
+        };
+        if spec.num() == 0 {
+            return Self {
+                precedence: i64::MIN,
+                id: None,
+                argprecs: Vec::new(),
+                component: NotationComponent::MainComp { node: name },
+                op: None,
+            };
+        }
+        let op = |txt: &str| NotationComponent::Comp {
+            node: NotationNode {
+                tag: mo.clone(),
+                attributes: Box::default(),
+                children: Box::new([NodeOrText::Text(txt.into())]),
+            },
+        };
+        let mut children = Vec::with_capacity(2 * spec.num() as usize + 2);
+        children.push(NotationComponent::MainComp { node: name.clone() });
+        children.push(op("("));
+        for (index, mode) in (0..).zip(spec.iter()) {
+            if index > 0 {
+                children.push(op(","));
+            }
+            children.push(match mode {
+                ArgumentMode::Simple | ArgumentMode::BoundVariable => {
+                    NotationComponent::Argument { index, mode }
+                }
+                ArgumentMode::Sequence | ArgumentMode::BoundVariableSequence => {
+                    NotationComponent::ArgSep {
+                        index,
+                        mode,
+                        sep: Box::new([op(",")]),
+                    }
+                }
+            });
+        }
+        children.push(op(")"));
+        Self {
+            precedence: i64::MIN,
+            id: None,
+            argprecs: vec![i64::MAX; spec.num() as usize],
+            component: NotationComponent::Node {
+                tag: mrow,
+                attributes: Box::default(),
+                children: children.into_boxed_slice(),
+            },
+            op: Some(name),
+        }
+    }
+
+    /// The number of arguments this notation declares, i.e. one more than the highest
+    /// argument index occurring in its [`component`](Self::component) (a sequence argument
+    /// counts as one); `0` for notations without arguments.
+    #[must_use]
+    pub fn arity(&self) -> usize {
+        std::iter::once(&self.component)
+            .chain(self.component.dfs())
+            .filter_map(|c| match c {
+                NotationComponent::Argument { index, .. }
+                | NotationComponent::ArgSep { index, .. }
+                | NotationComponent::ArgMap { index, .. } => Some(usize::from(*index) + 1),
+                NotationComponent::Node { .. }
+                | NotationComponent::MainComp { .. }
+                | NotationComponent::Comp { .. }
+                | NotationComponent::Text { .. } => None,
+            })
+            .max()
+            .unwrap_or_default()
+    }
+
     #[must_use]
     pub fn is_op(&self) -> bool {
         self.op.is_some()
@@ -85,6 +186,80 @@ impl Notation {
     }
 }
 
+/// Provides [`Notation`]s (and macro names) for symbols and variables, e.g. for
+/// [`Term::to_latex`](crate::terms::Term::to_latex) and
+/// [`Term::to_mathml`](crate::terms::Term::to_mathml), independent of where they come from
+/// (see e.g. [`NotationMap`]).
+pub trait NotationResolver {
+    /// The notation to use for `leaf`.
+    ///
+    /// If `pref` is given and a notation with that [`id`](Notation::id) is known, that one
+    /// should be returned; otherwise (or if `pref` is `None`), implementations should fall
+    /// back to their default notation for `leaf` (e.g. the first one known) rather than
+    /// returning `None`.
+    fn notation(&self, leaf: &LeafUri, pref: Option<Id>) -> Option<&Notation>;
+
+    /// The notation to use for `leaf` applied to `arity` arguments, i.e. one with that
+    /// [`arity`](Notation::arity) - for symbols with several notations of different arities
+    /// (e.g. unary minus and binary subtraction). `None` if there is no such notation.
+    ///
+    /// If several notations have the given arity, the first one in the implementation's
+    /// order wins (for [`NotationMap`], the first one inserted). The default implementation
+    /// only considers the default [`notation`](Self::notation) for `leaf`.
+    fn notation_for_arity(&self, leaf: &LeafUri, arity: usize) -> Option<&Notation> {
+        self.notation(leaf, None).filter(|n| n.arity() == arity)
+    }
+
+    /// The (sTeX) macro name of `leaf`, if known.
+    #[inline]
+    fn macro_name(&self, _leaf: &LeafUri) -> Option<&str> {
+        None
+    }
+}
+
+/// A [`NotationResolver`] backed by preloaded notations, e.g. for static/offline rendering.
+///
+/// The first notation inserted for a leaf is its default; a preferred notation (by
+/// [`id`](Notation::id)) is returned if present.
+#[derive(Debug, Clone, Default)]
+pub struct NotationMap(rustc_hash::FxHashMap<LeafUri, Vec<Notation>>);
+impl NotationMap {
+    /// Adds `notation` as an (additional) notation for `leaf`.
+    pub fn insert(&mut self, leaf: LeafUri, notation: Notation) {
+        self.0.entry(leaf).or_default().push(notation);
+    }
+
+    /// All notations known for `leaf`, in insertion order.
+    #[must_use]
+    pub fn get(&self, leaf: &LeafUri) -> &[Notation] {
+        self.0.get(leaf).map_or(&[], Vec::as_slice)
+    }
+}
+impl NotationResolver for NotationMap {
+    fn notation(&self, leaf: &LeafUri, pref: Option<Id>) -> Option<&Notation> {
+        let notations = self.get(leaf);
+        pref.and_then(|pref| notations.iter().find(|n| n.id.as_ref() == Some(&pref)))
+            .or_else(|| notations.first())
+    }
+    fn notation_for_arity(&self, leaf: &LeafUri, arity: usize) -> Option<&Notation> {
+        self.get(leaf).iter().find(|n| n.arity() == arity)
+    }
+}
+impl Extend<(LeafUri, Notation)> for NotationMap {
+    fn extend<T: IntoIterator<Item = (LeafUri, Notation)>>(&mut self, iter: T) {
+        for (leaf, notation) in iter {
+            self.insert(leaf, notation);
+        }
+    }
+}
+impl FromIterator<(LeafUri, Notation)> for NotationMap {
+    fn from_iter<T: IntoIterator<Item = (LeafUri, Notation)>>(iter: T) -> Self {
+        let mut map = Self::default();
+        map.extend(iter);
+        map
+    }
+}
+
 #[derive(Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(
     feature = "serde",
@@ -445,3 +620,134 @@ impl deepsize::DeepSizeOf for Notation {
                 .unwrap_or_default()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Notation, NotationComponent, NotationMap, NotationResolver};
+    use crate::{
+        domain::declarations::symbols::ArgumentSpec,
+        terms::{ApplicationTerm, Argument, MaybeSequence, Term},
+        test_utils::var,
+        utils::RefTree,
+    };
+    use ftml_uris::{Id, LeafUri, SymbolUri};
+
+    struct Synthesized(Notation);
+    impl NotationResolver for Synthesized {
+        fn notation(&self, _leaf: &LeafUri, _pref: Option<Id>) -> Option<&Notation> {
+            Some(&self.0)
+        }
+    }
+
+    #[test]
+    fn synthesized_notation() {
+        let spec: ArgumentSpec = "ia".parse().expect("valid spec");
+        let notation = Notation::synthesize("f", &spec);
+        assert!(notation.op.is_some());
+        assert_eq!(notation.argprecs.len(), 2);
+        // only MathML elements the renderer knows
+        assert!(notation.component.dfs().all(|c| match c {
+            NotationComponent::Node { tag, .. } => ["mrow", "mi", "mo"].contains(&tag.as_ref()),
+            NotationComponent::Comp { node } | NotationComponent::MainComp { node } => {
+                ["mi", "mo"].contains(&node.tag.as_ref())
+            }
+            NotationComponent::Argument { .. }
+            | NotationComponent::ArgSep { .. }
+            | NotationComponent::Text { .. } => true,
+            NotationComponent::ArgMap { .. } => false,
+        }));
+
+        let f: SymbolUri = "http://example.com?a=archive&m=module&s=f"
+            .parse()
+            .expect("valid uri");
+        let term = Term::Application(ApplicationTerm::new(
+            f.clone().into(),
+            Box::new([
+                Argument::Simple(var("x")),
+                Argument::Sequence(MaybeSequence::Seq(Box::new([var("y"), var("z")]))),
+            ]),
+            None,
+        ));
+        let r = Synthesized(notation);
+        assert_eq!(term.to_latex(&r).as_deref(), Some("f(x,y,z)"));
+        // without arguments, the op notation is used
+        assert_eq!(Term::from(f).to_latex(&r).as_deref(), Some("f"));
+    }
+
+    #[test]
+    fn preferred_notation() {
+        let leaf = LeafUri::Symbol(
+            "http://example.com?a=archive&m=module&s=f"
+                .parse()
+                .expect("valid uri"),
+        );
+        let notation = |id: &str| {
+            let mut n = Notation::synthesize(id, &ArgumentSpec::default());
+            n.id = Some(id.parse().expect("valid id"));
+            n
+        };
+        let map = [notation("prefix"), notation("infix")]
+            .into_iter()
+            .map(|n| (leaf.clone(), n))
+            .collect::<NotationMap>();
+        let id = |leaf, pref: Option<&str>| {
+            map.notation(leaf, pref.map(|p| p.parse().expect("valid id")))
+                .and_then(|n| n.id.as_ref().map(ToString::to_string))
+        };
+
+        assert_eq!(id(&leaf, Some("infix")).as_deref(), Some("infix"));
+        assert_eq!(id(&leaf, None).as_deref(), Some("prefix"));
+        assert_eq!(id(&leaf, Some("missing")).as_deref(), Some("prefix"));
+        let other = LeafUri::Symbol(
+            "http://example.com?a=archive&m=module&s=g"
+                .parse()
+                .expect("valid uri"),
+        );
+        assert_eq!(id(&other, Some("infix")), None);
+    }
+
+    #[test]
+    fn notation_by_arity() {
+        let minus: SymbolUri = "http://example.com?a=archive&m=arith&s=minus"
+            .parse()
+            .expect("valid uri");
+        let leaf = LeafUri::Symbol(minus.clone());
+        let notation = |id: &str, spec: &str| {
+            let mut n = Notation::synthesize(id, &spec.parse().expect("valid spec"));
+            n.id = Some(id.parse().expect("valid id"));
+            n
+        };
+        let map = [
+            notation("neg", "i"),
+            notation("sub", "ii"),
+            notation("diff", "ii"),
+        ]
+        .into_iter()
+        .map(|n| (leaf.clone(), n))
+        .collect::<NotationMap>();
+        let id = |arity| {
+            map.notation_for_arity(&leaf, arity)
+                .and_then(|n| n.id.as_ref().map(ToString::to_string))
+        };
+        assert_eq!(id(1).as_deref(), Some("neg"));
+        // ties are broken by insertion order
+        assert_eq!(id(2).as_deref(), Some("sub"));
+        assert_eq!(id(3), None);
+
+        let apply = |args: Vec<Term>| {
+            Term::Application(ApplicationTerm::new(
+                minus.clone().into(),
+                args.into_iter().map(Argument::Simple).collect(),
+                None,
+            ))
+        };
+        assert_eq!(
+            apply(vec![var("x")]).to_latex(&map).as_deref(),
+            Some("\\mathrm{neg}(x)")
+        );
+        assert_eq!(
+            apply(vec![var("x"), var("y")]).to_latex(&map).as_deref(),
+            Some("\\mathrm{sub}(x,y)")
+        );
+    }
+}
@@ -260,6 +260,20 @@ impl std::str::FromStr for ParagraphKind {
     }
 }
 
+/// A step in a structured proof, i.e. a [`LogicalParagraph`] of [`ParagraphKind::Proof`]
+/// (or [`ParagraphKind::SubProof`]), collected in [`LogicalParagraph::steps`] in document order.
+///
+/// Every step (except [`EquationStep`](Self::EquationStep)s) optionally has
+/// - a `var_name` (from `data-ftml-stepname`), by which later steps can refer to it,
+/// - a `method` (`data-ftml-proofmethod`), e.g. "by contradiction",
+/// - a `justification` (`data-ftml-spfjust`), a term usually referencing the symbols
+///   (lemmata, rules, ...) the step relies on,
+/// - `arguments` (`data-ftml-spfarg`) for the justification, and
+/// - the proposition it `yields` (`data-ftml-proofterm`).
+///
+/// A [`Subproof`](Self::Subproof) additionally has its own `steps`, so the steps of a proof
+/// form a tree; [`LogicalParagraph::numbered_steps`] numbers them hierarchically
+/// (`1`, `2`, `2.1`, ...).
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
 #[cfg_attr(
     feature = "serde",
@@ -273,6 +287,7 @@ impl std::str::FromStr for ParagraphKind {
 #[cfg_attr(feature = "typescript", tsify(into_wasm_abi, from_wasm_abi))]
 #[cfg_attr(any(feature = "serde", feature = "serde-lite"), serde(tag = "type"))]
 pub enum ParagraphStep {
+    /// An assumption (`data-ftml-spfassumption`), e.g. "assume $x>0$".
     ProofAssumption {
         #[cfg_attr(any(feature = "serde", feature = "serde-lite"), serde(default))]
         var_name: Option<DocumentElementUri>,
@@ -285,6 +300,7 @@ pub enum ParagraphStep {
         #[cfg_attr(any(feature = "serde", feature = "serde-lite"), serde(default))]
         yields: Option<(Term, SourceRange)>,
     },
+    /// A regular step (`data-ftml-spfstep`).
     ProofStep {
         #[cfg_attr(any(feature = "serde", feature = "serde-lite"), serde(default))]
         var_name: Option<DocumentElementUri>,
@@ -297,6 +313,7 @@ pub enum ParagraphStep {
         #[cfg_attr(any(feature = "serde", feature = "serde-lite"), serde(default))]
         yields: Option<(Term, SourceRange)>,
     },
+    /// The conclusion of a (sub)proof (`data-ftml-spfconclusion`).
     ProofConclusion {
         #[cfg_attr(any(feature = "serde", feature = "serde-lite"), serde(default))]
         var_name: Option<DocumentElementUri>,
@@ -309,6 +326,7 @@ pub enum ParagraphStep {
         #[cfg_attr(any(feature = "serde", feature = "serde-lite"), serde(default))]
         yields: Option<(Term, SourceRange)>,
     },
+    /// A subproof (`data-ftml-subproof`), with its own steps.
     Subproof {
         uri: DocumentElementUri,
         #[cfg_attr(any(feature = "serde", feature = "serde-lite"), serde(default))]
@@ -324,9 +342,120 @@ pub enum ParagraphStep {
         #[cfg_attr(any(feature = "serde", feature = "serde-lite"), serde(default))]
         steps: Box<[Self]>,
     },
+    /// A step in an equational chain (`data-ftml-spfeqstep`); currently not extracted.
     EquationStep,
 }
 
+impl ParagraphStep {
+    #[must_use]
+    pub const fn kind(&self) -> ParagraphStepKind {
+        match self {
+            Self::ProofAssumption { .. } => ParagraphStepKind::Assumption,
+            Self::ProofStep { .. } => ParagraphStepKind::ProofStep,
+            Self::ProofConclusion { .. } => ParagraphStepKind::Conclusion,
+            Self::Subproof { .. } => ParagraphStepKind::SubProof,
+            Self::EquationStep => ParagraphStepKind::EquationStep,
+        }
+    }
+
+    #[must_use]
+    pub const fn var_name(&self) -> Option<&DocumentElementUri> {
+        match self {
+            Self::ProofAssumption { var_name, .. }
+            | Self::ProofStep { var_name, .. }
+            | Self::ProofConclusion { var_name, .. }
+            | Self::Subproof { var_name, .. } => var_name.as_ref(),
+            Self::EquationStep => None,
+        }
+    }
+
+    #[must_use]
+    pub fn method(&self) -> Option<&Term> {
+        match self {
+            Self::ProofAssumption { method, .. }
+            | Self::ProofStep { method, .. }
+            | Self::ProofConclusion { method, .. }
+            | Self::Subproof { method, .. } => method.as_ref().map(|(t, _)| t),
+            Self::EquationStep => None,
+        }
+    }
+
+    #[must_use]
+    pub fn justification(&self) -> Option<&Term> {
+        match self {
+            Self::ProofAssumption { justification, .. }
+            | Self::ProofStep { justification, .. }
+            | Self::ProofConclusion { justification, .. }
+            | Self::Subproof { justification, .. } => justification.as_ref().map(|(t, _)| t),
+            Self::EquationStep => None,
+        }
+    }
+
+    #[must_use]
+    pub fn yields(&self) -> Option<&Term> {
+        match self {
+            Self::ProofAssumption { yields, .. }
+            | Self::ProofStep { yields, .. }
+            | Self::ProofConclusion { yields, .. }
+            | Self::Subproof { yields, .. } => yields.as_ref().map(|(t, _)| t),
+            Self::EquationStep => None,
+        }
+    }
+
+    /// The steps of a [`Subproof`](Self::Subproof); empty for all other steps.
+    #[must_use]
+    pub fn substeps(&self) -> &[Self] {
+        match self {
+            Self::Subproof { steps, .. } => steps,
+            _ => &[],
+        }
+    }
+}
+
+impl LogicalParagraph {
+    /// The symbols this paragraph is about (see [`fors`](Self::fors)), in order and without
+    /// their (optional) definientia.
+    #[inline]
+    pub fn for_symbols(&self) -> impl ExactSizeIterator<Item = &SymbolUri> {
+        self.fors.iter().map(|(s, _)| s)
+    }
+
+    /// Whether this paragraph is indexed by the symbols it is [`for`](Self::fors), i.e. is a
+    /// definition(-like paragraph; see [`ParagraphKind::is_definition_like`]) or an example.
+    #[must_use]
+    pub fn is_indexed_for(&self) -> bool {
+        self.kind.is_definition_like(&self.styles) || self.kind == ParagraphKind::Example
+    }
+
+    /// The [steps](Self::steps) of this (sub)proof and all its subproofs in document order,
+    /// each with its hierarchical number (`1`, `2`, `2.1`, `2.2`, `3`, ...).
+    /// [`EquationStep`](ParagraphStep::EquationStep)s are skipped.
+    #[must_use]
+    pub fn numbered_steps(&self) -> Vec<(String, &ParagraphStep)> {
+        fn go<'s>(
+            prefix: &str,
+            steps: &'s [ParagraphStep],
+            out: &mut Vec<(String, &'s ParagraphStep)>,
+        ) {
+            let steps = steps
+                .iter()
+                .filter(|s| !matches!(s, ParagraphStep::EquationStep));
+            for (i, step) in (1..).zip(steps) {
+                let num = if prefix.is_empty() {
+                    format!("{i}")
+                } else {
+                    format!("{prefix}.{i}")
+                };
+                out.push((num.clone(), step));
+                go(&num, step.substeps(), out);
+            }
+        }
+        let mut out = Vec::new();
+        go("", &self.steps, &mut out);
+        out
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(
     feature = "serde",
@@ -47,7 +47,9 @@ pub struct Problem {
 pub struct ProblemData {
     pub sub_problem: bool,
     pub autogradable: bool,
+    /// The number of points this problem is worth, if given.
     pub points: Option<f32>,
+    /// The estimated time (in minutes) it takes to solve this problem, if given.
     pub minutes: Option<f32>,
     #[cfg_attr(feature = "typescript", tsify(type = "DataRef"))]
     pub solutions: DataRef<Solutions>, //State::Seq<SolutionData>,
@@ -71,6 +73,29 @@ pub struct ProblemData {
     pub source: SourceRange,
 }
 
+impl ProblemData {
+    /// A (top-level, not autogradable) problem with the given `solutions` and nothing else;
+    /// other fields can be set via struct update syntax.
+    #[must_use]
+    pub fn new(solutions: DataRef<Solutions>) -> Self {
+        Self {
+            sub_problem: false,
+            autogradable: false,
+            points: None,
+            minutes: None,
+            solutions,
+            gnotes: Box::default(),
+            hints: Box::default(),
+            notes: Box::default(),
+            title: None,
+            styles: Box::default(),
+            preconditions: Box::default(),
+            objectives: Box::default(),
+            source: SourceRange::default(),
+        }
+    }
+}
+
 impl crate::__private::Sealed for Problem {}
 impl crate::Ftml for Problem {
     #[cfg(feature = "rdf")]
@@ -143,6 +168,42 @@ impl IsDocumentElement for Problem {
     }
 }
 
+/// The total estimated time (in minutes) and the total points of the given problems, e.g. of
+/// a selected quiz.
+///
+/// Problems without [`minutes`](ProblemData::minutes) count as zero minutes. Problems
+/// without [`points`](ProblemData::points) do not contribute to the points; the total points
+/// are `None` iff none of the problems has points.
+///
+/// The values of a problem are assumed to account for its sub-problems: the children of the
+/// given problems are not inspected, and a given problem nested (at any depth) in another
+/// given problem is skipped. Hence passing a problem together with its sub-problems (e.g. all
+/// problems of a document) does not count the latter twice.
+#[must_use]
+pub fn quiz_totals(problems: &[Problem]) -> (f32, Option<f32>) {
+    use crate::utils::TreeIter;
+    let nested = problems
+        .iter()
+        .flat_map(|p| p.children().dfs())
+        .filter_map(|e| match e {
+            DocumentElementRef::Problem(p) => Some(&p.uri),
+            _ => None,
+        })
+        .collect::<rustc_hash::FxHashSet<_>>();
+    problems.iter().filter(|p| !nested.contains(&p.uri)).fold(
+        (0.0, None),
+        |(minutes, points), p| {
+            (
+                minutes + p.data.minutes.unwrap_or_default(),
+                match (points, p.data.points) {
+                    (Some(total), Some(points)) => Some(total + points),
+                    (total, points) => total.or(points),
+                },
+            )
+        },
+    )
+}
+
 impl Eq for Problem {}
 impl PartialEq for Problem {
     fn eq(&self, other: &Self) -> bool {
@@ -376,3 +437,56 @@ impl deepsize::DeepSizeOf for Problem {
             + self.data.deep_size_of_children(context)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn problem(
+        name: &str,
+        sub_problem: bool,
+        points: Option<f32>,
+        minutes: Option<f32>,
+        children: Vec<DocumentElement>,
+    ) -> Problem {
+        Problem {
+            uri: format!("http://example.com?a=archive&d=quiz&l=en&e={name}")
+                .parse()
+                .expect("valid uri"),
+            range: DocumentRange::default(),
+            children: children.into_boxed_slice(),
+            data: Box::new(ProblemData {
+                sub_problem,
+                points,
+                minutes,
+                ..ProblemData::new(DataRef {
+                    start: 0,
+                    end: 0,
+                    phantom_data: std::marker::PhantomData,
+                })
+            }),
+        }
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn quiz_totals_mixed() {
+        let sub = problem("p3/sub", true, Some(2.0), Some(1.5), Vec::new());
+        let quiz = [
+            problem("p1", false, Some(4.0), Some(10.0), Vec::new()),
+            problem("p2", false, None, Some(5.0), Vec::new()),
+            problem(
+                "p3",
+                false,
+                Some(3.0),
+                None,
+                vec![DocumentElement::Problem(sub.clone())],
+            ),
+            // already accounted for in `p3`
+            sub,
+        ];
+        assert_eq!(quiz_totals(&quiz), (15.0, Some(7.0)));
+        assert_eq!(quiz_totals(&quiz[1..2]), (5.0, None));
+        assert_eq!(quiz_totals(&[]), (0.0, None));
+    }
+}
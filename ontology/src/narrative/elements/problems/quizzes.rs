@@ -1,5 +1,5 @@
 use crate::{
-    narrative::elements::problems::{AnswerClass, CognitiveDimension},
+    narrative::elements::problems::{AnswerClass, CognitiveDimension, SolutionData, Solutions},
     utils::Css,
 };
 use ftml_uris::{DocumentElementUri, DocumentUri, SymbolUri};
@@ -77,6 +77,109 @@ pub struct QuizProblem {
     pub objectives: Box<[(CognitiveDimension, SymbolUri)]>,
 }
 
+/// A concrete presentation of a (randomized) quiz: which problems were selected, in which
+/// order they are shown, and in which order the choices of their
+/// [`ChoiceBlock`](super::ChoiceBlock)s are shown. Storing an instance alongside a submission
+/// allows for grading and reviewing it later, independent of subsequent randomizations.
+///
+/// Instances are computed deterministically from their [`seed`](Self::seed) (see
+/// [`QuizInstance::new`]): the same seed and the same candidate problems (in the same order,
+/// with the same numbers of choices) always yield the same instance - on every platform and
+/// across versions of this crate, since the pseudo-random generator (SplitMix64) is fixed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde-lite",
+    derive(serde_lite::Serialize, serde_lite::Deserialize)
+)]
+#[cfg_attr(feature = "typescript", derive(tsify::Tsify))]
+#[cfg_attr(feature = "typescript", tsify(into_wasm_abi, from_wasm_abi))]
+pub struct QuizInstance {
+    pub seed: u64,
+    /// The selected problems, in presentation order
+    pub problems: Box<[QuizProblemInstance]>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde-lite",
+    derive(serde_lite::Serialize, serde_lite::Deserialize)
+)]
+#[cfg_attr(feature = "typescript", derive(tsify::Tsify))]
+#[cfg_attr(feature = "typescript", tsify(into_wasm_abi, from_wasm_abi))]
+pub struct QuizProblemInstance {
+    pub uri: DocumentElementUri,
+    /// One entry per choice block of the problem's [`Solutions`] (in order); the `k`th choice
+    /// presented is the one with index `choice_orders[block][k]`.
+    #[cfg_attr(any(feature = "serde", feature = "serde-lite"), serde(default))]
+    pub choice_orders: Box<[Box<[u16]>]>,
+}
+
+impl QuizInstance {
+    /// Selects `count` (or all, if `None`) of the `candidates` in a random order, and shuffles
+    /// the choices of each of their choice blocks, using the pseudo-random sequence
+    /// determined by `seed`.
+    #[must_use]
+    pub fn new(
+        seed: u64,
+        candidates: &[(DocumentElementUri, &Solutions)],
+        count: Option<usize>,
+    ) -> Self {
+        let mut rng = SplitMix64(seed);
+        let mut order = (0..candidates.len()).collect::<Vec<_>>();
+        rng.shuffle(&mut order);
+        order.truncate(count.unwrap_or(usize::MAX));
+        let problems = order
+            .into_iter()
+            .map(|i| {
+                let (uri, solutions) = &candidates[i];
+                let choice_orders = solutions
+                    .inner()
+                    .iter()
+                    .filter_map(|s| match s {
+                        SolutionData::ChoiceBlock(block) => Some(block.choices.len()),
+                        _ => None,
+                    })
+                    .map(|len| {
+                        let mut choices =
+                            (0..u16::try_from(len).unwrap_or(u16::MAX)).collect::<Vec<_>>();
+                        rng.shuffle(&mut choices);
+                        choices.into_boxed_slice()
+                    })
+                    .collect();
+                QuizProblemInstance {
+                    uri: uri.clone(),
+                    choice_orders,
+                }
+            })
+            .collect();
+        Self { seed, problems }
+    }
+}
+
+/// SplitMix64; fixed here (rather than using some external PRNG) so that [`QuizInstance`]s
+/// remain reproducible from their seeds.
+struct SplitMix64(u64);
+impl SplitMix64 {
+    const fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Fisher-Yates
+    #[allow(clippy::cast_possible_truncation)]
+    fn shuffle<T>(&mut self, slice: &mut [T]) {
+        for i in (1..slice.len()).rev() {
+            let j = (self.next() % (i as u64 + 1)) as usize;
+            slice.swap(i, j);
+        }
+    }
+}
+
 #[cfg(feature = "serde")]
 mod document_quiz_impl {
     use std::hint::unreachable_unchecked;
@@ -245,6 +348,7 @@ mod document_quiz_impl {
                     }
                     DocumentElementRef::UseModule { .. }
                     | DocumentElementRef::Slide(_)
+                    | DocumentElementRef::Figure(_)
                     | DocumentElementRef::SymbolDeclaration(_)
                     | DocumentElementRef::ImportModule(_)
                     | DocumentElementRef::VariableDeclaration(_)
@@ -285,3 +389,62 @@ pub enum QuizError {
     #[error("invalid solutions for problem {0}")]
     InvalidSolutions(DocumentElementUri),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::narrative::{
+        DocumentRange,
+        elements::problems::{Choice, ChoiceBlock, ChoiceBlockStyle},
+    };
+
+    fn choices(n: usize) -> SolutionData {
+        SolutionData::ChoiceBlock(ChoiceBlock {
+            multiple: false,
+            block_style: ChoiceBlockStyle::Block,
+            range: DocumentRange::default(),
+            styles: Box::default(),
+            choices: (0..n)
+                .map(|i| Choice {
+                    correct: i == 0,
+                    verdict: "".into(),
+                    feedback: "".into(),
+                })
+                .collect(),
+        })
+    }
+
+    #[test]
+    fn seeded_instance() {
+        let solutions = Solutions::from_solutions(Box::new([choices(4), choices(3)]));
+        let candidates = (0..5)
+            .map(|i| {
+                let uri = format!("http://example.org?a=quiz&d=problems&l=en&e=problem{i}")
+                    .parse::<DocumentElementUri>()
+                    .expect("valid uri");
+                (uri, &solutions)
+            })
+            .collect::<Vec<_>>();
+
+        let instance = QuizInstance::new(42, &candidates, Some(3));
+        assert_eq!(instance.problems.len(), 3);
+        for problem in &instance.problems {
+            assert!(candidates.iter().any(|(uri, _)| *uri == problem.uri));
+            assert_eq!(problem.choice_orders.len(), 2);
+            for (order, len) in problem.choice_orders.iter().zip([4u16, 3]) {
+                let mut sorted = order.to_vec();
+                sorted.sort_unstable();
+                assert_eq!(sorted, (0..len).collect::<Vec<_>>());
+            }
+        }
+        assert_eq!(QuizInstance::new(42, &candidates, Some(3)), instance);
+        assert_eq!(QuizInstance::new(42, &candidates, None).problems.len(), 5);
+
+        #[cfg(feature = "serde")]
+        {
+            let json = serde_json::to_string(&instance).expect("serializable");
+            let back: QuizInstance = serde_json::from_str(&json).expect("deserializable");
+            assert_eq!(back, instance);
+        }
+    }
+}
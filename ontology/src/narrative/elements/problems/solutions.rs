@@ -114,6 +114,8 @@ pub struct Choice {
 #[cfg_attr(feature = "typescript", derive(tsify::Tsify))]
 #[cfg_attr(feature = "typescript", tsify(into_wasm_abi, from_wasm_abi))]
 pub struct FillInSol {
+    /// The width of the input field in pixels (`fillinsol-width`); see
+    /// [`render_width`](Self::render_width).
     #[cfg_attr(any(feature = "serde", feature = "serde-lite"), serde(default))]
     pub width: Option<f32>,
     #[cfg_attr(any(feature = "serde", feature = "serde-lite"), serde(default))]
@@ -156,6 +158,34 @@ pub enum FillInSolOption {
     },
 }
 
+impl FillInSol {
+    /// The width (in pixels) used for input fields without a (valid) width.
+    pub const DEFAULT_WIDTH: f32 = 150.0;
+    /// The minimal width (in pixels) of an input field.
+    pub const MIN_WIDTH: f32 = 20.0;
+    /// The maximal width (in pixels) of an input field.
+    pub const MAX_WIDTH: f32 = 1000.0;
+
+    /// The width (in pixels) the input field for this fill-in solution should be rendered
+    /// with; see [`clamp_width`](Self::clamp_width).
+    #[inline]
+    #[must_use]
+    pub fn render_width(&self) -> f32 {
+        Self::clamp_width(self.width)
+    }
+
+    /// Validates a width (in pixels) for a fill-in input field: missing, non-finite and
+    /// non-positive widths yield [`DEFAULT_WIDTH`](Self::DEFAULT_WIDTH); all others are
+    /// clamped to [`MIN_WIDTH`](Self::MIN_WIDTH)`..=`[`MAX_WIDTH`](Self::MAX_WIDTH).
+    #[must_use]
+    pub fn clamp_width(width: Option<f32>) -> f32 {
+        match width {
+            Some(w) if w.is_finite() && w > 0.0 => w.clamp(Self::MIN_WIDTH, Self::MAX_WIDTH),
+            _ => Self::DEFAULT_WIDTH,
+        }
+    }
+}
+
 impl FillInSolOption {
     pub fn add_feedback(&mut self, fb: Box<str>) {
         match self {
@@ -262,6 +292,36 @@ impl Solutions {
         &self.0
     }
 
+    /// Assembles the solutions of a (multi-part) problem from its individual parts, as the
+    /// extraction does for the [`Solution`](SolutionData::Solution), choice block and
+    /// fill-in-solution markers of a problem.
+    ///
+    /// The order of `parts` is significant and preserved: the `n`th [`ChoiceBlock`] or
+    /// [`FillInSol`] part is checked against the `n`th response (see [`check`](Self::check)),
+    /// and [`Solution`](SolutionData::Solution)s are shown in order. If several
+    /// [`Solution`](SolutionData::Solution)s share the same `answer_class`, the last one wins,
+    /// but stays at the position of the first one; solutions without an answer class never
+    /// conflict.
+    #[must_use]
+    pub fn merge(parts: Vec<SolutionData>) -> Self {
+        let mut merged: Vec<SolutionData> = Vec::with_capacity(parts.len());
+        for part in parts {
+            if let SolutionData::Solution {
+                answer_class: Some(class),
+                ..
+            } = &part
+                && let Some(existing) = merged.iter_mut().find(|s| {
+                    matches!(s, SolutionData::Solution { answer_class: Some(c), .. } if c == class)
+                })
+            {
+                *existing = part;
+            } else {
+                merged.push(part);
+            }
+        }
+        Self(merged.into_boxed_slice())
+    }
+
     #[must_use]
     pub fn default(&self) -> ProblemFeedback {
         let mut solutions = SmallVec::new();
@@ -564,3 +624,91 @@ impl Solutions {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn fillinsol_width() {
+        let sol = |width| FillInSol {
+            width,
+            opts: Vec::new(),
+        };
+        assert_eq!(sol(Some(80.0)).render_width(), 80.0);
+        assert_eq!(sol(None).render_width(), FillInSol::DEFAULT_WIDTH);
+        assert_eq!(sol(Some(-5.0)).render_width(), FillInSol::DEFAULT_WIDTH);
+        assert_eq!(sol(Some(f32::NAN)).render_width(), FillInSol::DEFAULT_WIDTH);
+        assert_eq!(sol(Some(1e9)).render_width(), FillInSol::MAX_WIDTH);
+        assert_eq!(sol(Some(1.0)).render_width(), FillInSol::MIN_WIDTH);
+    }
+
+    #[test]
+    fn merge_solutions() {
+        let class = || Some("ac".parse::<Id>().expect("valid id"));
+        let solutions = Solutions::merge(vec![
+            SolutionData::FillInSol(FillInSol {
+                width: None,
+                opts: vec![FillInSolOption::Exact {
+                    value: "42".into(),
+                    verdict: true,
+                    feedback: "".into(),
+                }],
+            }),
+            SolutionData::Solution {
+                html: "first".into(),
+                answer_class: class(),
+            },
+            SolutionData::ChoiceBlock(ChoiceBlock {
+                multiple: false,
+                block_style: ChoiceBlockStyle::Block,
+                range: DocumentRange::default(),
+                styles: Box::default(),
+                choices: vec![
+                    Choice {
+                        correct: false,
+                        verdict: "no".into(),
+                        feedback: "".into(),
+                    },
+                    Choice {
+                        correct: true,
+                        verdict: "yes".into(),
+                        feedback: "".into(),
+                    },
+                ]
+                .into_boxed_slice(),
+            }),
+            SolutionData::Solution {
+                html: "second".into(),
+                answer_class: class(),
+            },
+        ]);
+        assert!(matches!(
+            solutions.inner(),
+            [
+                SolutionData::FillInSol(_),
+                SolutionData::Solution { html, .. },
+                SolutionData::ChoiceBlock(_),
+            ] if &**html == "second"
+        ));
+
+        let feedback = solutions
+            .check(&ProblemResponse {
+                uri: "http://example.com?a=archive&d=doc&l=en&e=problem"
+                    .parse()
+                    .expect("valid uri"),
+                responses: [
+                    ProblemResponseType::Fillinsol {
+                        value: "42".to_string(),
+                    },
+                    ProblemResponseType::SingleChoice { value: Some(1) },
+                ]
+                .into_iter()
+                .collect(),
+            })
+            .expect("response matches solutions");
+        assert!(feedback.correct);
+        assert_eq!(feedback.solutions.as_slice(), [Box::<str>::from("second")]);
+    }
+}
@@ -1,5 +1,6 @@
 pub mod documents;
 pub mod elements;
+pub mod patch;
 
 use ftml_uris::{DocumentUri, NarrativeUriRef, UriName};
 use std::marker::PhantomData;
@@ -66,6 +67,7 @@ impl DocumentData {
                     DocumentElement::Section(elements::Section { uri, .. })
                     | DocumentElement::Paragraph(elements::LogicalParagraph { uri, .. })
                     | DocumentElement::Slide(elements::Slide{uri,..})
+                    | DocumentElement::Figure(elements::Figure{uri,..})
                     | DocumentElement::Problem(elements::Problem { uri, .. })
                         if uri.name().last() == step =>
                     {
@@ -106,6 +108,7 @@ impl DocumentData {
                     DocumentElement::Section(_)
                     | DocumentElement::Paragraph(_)
                     | DocumentElement::Slide(_)
+                    | DocumentElement::Figure(_)
                     | DocumentElement::Problem(_)
                     //| DocumentElementRef::SetSectionLevel(_)
                     | DocumentElement::SymbolDeclaration(_)
@@ -140,6 +143,7 @@ pub trait Narrative: crate::Ftml {
     fn contains_triples(&self) -> impl IntoIterator<Item = ulo::rdf_types::Triple> {
         use crate::narrative::elements::{
             LogicalParagraph, Problem, Section, VariableDeclaration,
+            figures::Figure,
             notations::{NotationReference, VariableNotationReference},
             sections::Slide,
         };
@@ -172,6 +176,7 @@ pub trait Narrative: crate::Ftml {
                     | DocumentElementRef::Paragraph(LogicalParagraph { uri, .. })
                     | DocumentElementRef::Problem(Problem { uri, .. })
                     | DocumentElementRef::Slide(Slide { uri, .. })
+                    | DocumentElementRef::Figure(Figure { uri, .. })
                     | DocumentElementRef::VariableDeclaration(VariableDeclaration {
                         uri, ..
                     })
@@ -226,6 +231,7 @@ pub trait Narrative: crate::Ftml {
                     | DocumentElementRef::Paragraph(elements::LogicalParagraph { uri, .. })
                     | DocumentElementRef::Problem(elements::Problem { uri, .. })
                     | DocumentElementRef::Slide(elements::Slide { uri, .. })
+                    | DocumentElementRef::Figure(elements::Figure { uri, .. })
                         if uri.name().last() == step =>
                     {
                         return if steps.peek().is_none() {
@@ -266,6 +272,7 @@ pub trait Narrative: crate::Ftml {
                     | DocumentElementRef::Paragraph(_)
                     | DocumentElementRef::Problem(_)
                     | DocumentElementRef::Slide(_)
+                    | DocumentElementRef::Figure(_)
                     | DocumentElementRef::SymbolDeclaration(_)
                     | DocumentElementRef::UseModule { .. }
                     | DocumentElementRef::ImportModule(_)
@@ -303,6 +310,59 @@ pub struct DocumentRange {
     pub start: usize,
     pub end: usize,
 }
+impl DocumentRange {
+    /// This range shifted by `delta` bytes, e.g. after embedding the HTML it refers to at
+    /// some offset into a larger page.
+    ///
+    /// Both ends saturate (at `0` and [`usize::MAX`], respectively) instead of overflowing;
+    /// i.e. shifting a range (partially) before the start of the HTML clamps it to `0`.
+    #[inline]
+    #[must_use]
+    pub const fn shifted(&self, delta: isize) -> Self {
+        Self {
+            start: self.start.saturating_add_signed(delta),
+            end: self.end.saturating_add_signed(delta),
+        }
+    }
+
+    /// The number of bytes in this range; `0` for inverted ranges (i.e. `start > end`).
+    #[inline]
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.end.saturating_sub(self.start)
+    }
+
+    /// Whether this range is empty, i.e. `start >= end`.
+    #[inline]
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.start >= self.end
+    }
+
+    /// Whether `offset` lies within this range (including `start`, excluding `end`).
+    /// Inverted ranges contain no offsets.
+    #[inline]
+    #[must_use]
+    pub const fn contains(&self, offset: usize) -> bool {
+        self.start <= offset && offset < self.end
+    }
+
+    /// Whether every offset in `other` lies within this range. Empty (or inverted) ranges
+    /// contain no offsets, and are hence contained in every range.
+    #[inline]
+    #[must_use]
+    pub const fn contains_range(&self, other: &Self) -> bool {
+        other.is_empty() || (self.start <= other.start && other.end <= self.end)
+    }
+
+    /// Whether this range and `other` share at least one offset; never true for empty (or
+    /// inverted) ranges.
+    #[inline]
+    #[must_use]
+    pub const fn intersects(&self, other: &Self) -> bool {
+        !self.is_empty() && !other.is_empty() && self.start < other.end && other.start < self.end
+    }
+}
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -376,7 +436,9 @@ impl<T> std::hash::Hash for DataRef<T> {
 }
 
 #[cfg(feature = "serde")]
-pub use serde_impl::DataBuffer;
+pub use serde_impl::{
+    DataBuffer, DecodeError, EncodingTrailer, VersionHeader, decode_framed, encode_framed,
+};
 
 #[cfg(feature = "serde")]
 mod serde_impl {
@@ -411,6 +473,136 @@ mod serde_impl {
         }
     }
 
+    /// A header for persisted binary (bincode) encodings, consisting of the magic bytes
+    /// [`MAGIC`](Self::MAGIC) and the format [`VERSION`](Self::VERSION).
+    ///
+    /// The binary encoding of ontology types is *not* stable across versions of this crate;
+    /// [`VERSION`](Self::VERSION) is bumped on every change to it, so that stale caches are
+    /// rejected on decoding (with a [`DecodeError::Other`](bincode::error::DecodeError::Other))
+    /// rather than misinterpreted.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct VersionHeader;
+    impl VersionHeader {
+        pub const MAGIC: [u8; 4] = *b"FTML";
+        pub const VERSION: u16 = 4;
+    }
+    impl bincode::Encode for VersionHeader {
+        fn encode<E: bincode::enc::Encoder>(
+            &self,
+            encoder: &mut E,
+        ) -> Result<(), bincode::error::EncodeError> {
+            bincode::Encode::encode(&Self::MAGIC, encoder)?;
+            bincode::Encode::encode(&Self::VERSION, encoder)
+        }
+    }
+    impl<Context> bincode::Decode<Context> for VersionHeader {
+        fn decode<D: bincode::de::Decoder<Context = Context>>(
+            decoder: &mut D,
+        ) -> Result<Self, bincode::error::DecodeError> {
+            let magic: [u8; 4] = bincode::Decode::<Context>::decode(decoder)?;
+            if magic != Self::MAGIC {
+                return Err(bincode::error::DecodeError::Other("not an FTML binary"));
+            }
+            let version: u16 = bincode::Decode::<Context>::decode(decoder)?;
+            if version != Self::VERSION {
+                return Err(bincode::error::DecodeError::Other(
+                    "unsupported FTML binary format version",
+                ));
+            }
+            Ok(Self)
+        }
+    }
+
+    /// A completeness marker terminating persisted binary (bincode) encodings (after the
+    /// [`VersionHeader`] and the encoded value), consisting of the magic bytes
+    /// [`MAGIC`](Self::MAGIC).
+    ///
+    /// Truncated data (e.g. from a network read cut short) may still decode to a valid, but
+    /// incomplete value; requiring the trailer after the value rejects it instead (with
+    /// [`DecodeError::Truncated`]).
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct EncodingTrailer;
+    impl EncodingTrailer {
+        pub const MAGIC: [u8; 4] = *b"LMTF";
+    }
+    impl bincode::Encode for EncodingTrailer {
+        fn encode<E: bincode::enc::Encoder>(
+            &self,
+            encoder: &mut E,
+        ) -> Result<(), bincode::error::EncodeError> {
+            bincode::Encode::encode(&Self::MAGIC, encoder)
+        }
+    }
+    impl<Context> bincode::Decode<Context> for EncodingTrailer {
+        fn decode<D: bincode::de::Decoder<Context = Context>>(
+            decoder: &mut D,
+        ) -> Result<Self, bincode::error::DecodeError> {
+            let magic: [u8; 4] = bincode::Decode::<Context>::decode(decoder)?;
+            if magic != Self::MAGIC {
+                return Err(bincode::error::DecodeError::Other("missing FTML trailer"));
+            }
+            Ok(Self)
+        }
+    }
+
+    /// Errors when decoding persisted binary encodings; see [`EncodingTrailer`].
+    #[derive(Debug, thiserror::Error)]
+    pub enum DecodeError {
+        /// The data ended prematurely, or did not end with the [`EncodingTrailer`].
+        #[error("truncated FTML binary")]
+        Truncated,
+        #[error(transparent)]
+        Bincode(bincode::error::DecodeError),
+    }
+    impl From<bincode::error::DecodeError> for DecodeError {
+        fn from(value: bincode::error::DecodeError) -> Self {
+            use bincode::error::DecodeError as E;
+            match value {
+                E::UnexpectedEnd { .. } => Self::Truncated,
+                E::Io { inner, .. } if inner.kind() == std::io::ErrorKind::UnexpectedEof => {
+                    Self::Truncated
+                }
+                e => Self::Bincode(e),
+            }
+        }
+    }
+
+    /// Encodes `value` into `writer`, framed by a [`VersionHeader`] and an [`EncodingTrailer`];
+    /// returns the number of bytes written.
+    ///
+    /// # Errors
+    /// if encoding or writing fails.
+    pub fn encode_framed<T: bincode::Encode>(
+        value: &T,
+        mut writer: impl std::io::Write,
+    ) -> Result<usize, bincode::error::EncodeError> {
+        let config = bincode::config::standard();
+        Ok(
+            bincode::encode_into_std_write(VersionHeader, &mut writer, config)?
+                + bincode::encode_into_std_write(value, &mut writer, config)?
+                + bincode::encode_into_std_write(EncodingTrailer, &mut writer, config)?,
+        )
+    }
+
+    /// Decodes a value from `reader` as written by [`encode_framed`].
+    ///
+    /// # Errors
+    /// [`Truncated`](DecodeError::Truncated) if `reader` ends prematurely or not with an
+    /// [`EncodingTrailer`]; otherwise, if it does not start with a [`VersionHeader`] of the
+    /// current [`VERSION`](VersionHeader::VERSION) or the value is invalid.
+    pub fn decode_framed<T: bincode::Decode<()>>(
+        mut reader: impl std::io::Read,
+    ) -> Result<T, DecodeError> {
+        let config = bincode::config::standard();
+        let _: VersionHeader = bincode::decode_from_std_read(&mut reader, config)?;
+        let value = bincode::decode_from_std_read(&mut reader, config)?;
+        let trailer: [u8; 4] = bincode::decode_from_std_read(&mut reader, config)?;
+        if trailer != EncodingTrailer::MAGIC {
+            return Err(DecodeError::Truncated);
+        }
+        Ok(value)
+    }
+
     impl<T> bincode::Encode for super::DataRef<T> {
         fn encode<E: bincode::enc::Encoder>(
             &self,
@@ -508,3 +700,50 @@ mod serde_lite_impl {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::DocumentRange;
+
+    const fn range(start: usize, end: usize) -> DocumentRange {
+        DocumentRange { start, end }
+    }
+
+    #[test]
+    fn containment() {
+        let r = range(10, 20);
+        assert_eq!(r.len(), 10);
+        assert!(r.contains(10) && r.contains(19));
+        assert!(!r.contains(9) && !r.contains(20));
+        assert!(r.contains_range(&r));
+        assert!(r.contains_range(&range(12, 15)));
+        assert!(!r.contains_range(&range(5, 15)));
+        assert!(!r.contains_range(&range(15, 25)));
+        assert!(r.contains_range(&range(100, 100)));
+    }
+
+    #[test]
+    fn intersection() {
+        let r = range(10, 20);
+        assert!(r.intersects(&range(15, 25)));
+        assert!(r.intersects(&range(0, 11)));
+        assert!(range(0, 100).intersects(&r));
+        // ranges are half-open
+        assert!(!r.intersects(&range(20, 30)));
+        assert!(!r.intersects(&range(0, 10)));
+        assert!(!r.intersects(&range(15, 15)));
+        assert!(range(0, usize::MAX).intersects(&range(usize::MAX - 1, usize::MAX)));
+    }
+
+    #[test]
+    fn inverted_range() {
+        let inverted = range(20, 10);
+        assert!(inverted.is_empty());
+        assert_eq!(inverted.len(), 0);
+        assert!(!inverted.contains(15));
+        assert!(!inverted.intersects(&range(0, 30)));
+        assert!(!range(0, 30).intersects(&inverted));
+        assert!(range(0, 5).contains_range(&inverted));
+        assert!(!inverted.contains_range(&range(12, 15)));
+    }
+}
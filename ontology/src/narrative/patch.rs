@@ -0,0 +1,357 @@
+//! Incremental updates of [`Document`]s; see [`Document::apply_patch`].
+
+use ftml_uris::{DocumentElementUri, DocumentUri};
+
+use crate::{
+    narrative::{
+        documents::Document,
+        elements::{DocumentElement, DocumentElementRef, IsDocumentElement},
+    },
+    utils::TreeIter,
+};
+
+/// A structural diff of a [`Document`], i.e. a sequence of [`PatchOperation`]s keyed by
+/// [`DocumentElementUri`]s.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize, bincode::Decode, bincode::Encode)
+)]
+#[cfg_attr(
+    feature = "serde-lite",
+    derive(serde_lite::Serialize, serde_lite::Deserialize)
+)]
+#[cfg_attr(feature = "typescript", derive(tsify::Tsify))]
+#[cfg_attr(feature = "typescript", tsify(into_wasm_abi, from_wasm_abi))]
+pub struct DocumentPatch {
+    /// The document this patch applies to.
+    pub document: DocumentUri,
+    #[cfg_attr(any(feature = "serde", feature = "serde-lite"), serde(default))]
+    pub operations: Vec<PatchOperation>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize, bincode::Decode, bincode::Encode)
+)]
+#[cfg_attr(
+    feature = "serde-lite",
+    derive(serde_lite::Serialize, serde_lite::Deserialize)
+)]
+#[cfg_attr(feature = "typescript", derive(tsify::Tsify))]
+#[cfg_attr(feature = "typescript", tsify(into_wasm_abi, from_wasm_abi))]
+pub enum PatchOperation {
+    /// Inserts `element` as a child of the element `parent` (or at the top level of the
+    /// document, if `None`) at position `index` (or after the last child, if `None`).
+    Add {
+        parent: Option<DocumentElementUri>,
+        index: Option<usize>,
+        element: DocumentElement,
+    },
+    /// Removes the element `uri` (and its children).
+    Remove { uri: DocumentElementUri },
+    /// Replaces the element `uri` (and its children) by `element`.
+    Replace {
+        uri: DocumentElementUri,
+        element: DocumentElement,
+    },
+}
+
+/// Conflicts between a [`DocumentPatch`] and the [`Document`] it is applied to; `operation`
+/// is the index of the offending [`PatchOperation`].
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+pub enum PatchError {
+    #[error("patch for {patch} can not be applied to {document}")]
+    WrongDocument {
+        patch: DocumentUri,
+        document: DocumentUri,
+    },
+    #[error("operation {operation}: no element {uri} in the document")]
+    NotFound {
+        operation: usize,
+        uri: DocumentElementUri,
+    },
+    #[error("operation {operation}: element {uri} can not have children")]
+    NotAContainer {
+        operation: usize,
+        uri: DocumentElementUri,
+    },
+    #[error("operation {operation}: index {index} out of bounds (length {len})")]
+    OutOfBounds {
+        operation: usize,
+        index: usize,
+        len: usize,
+    },
+    #[error("operation {operation}: element {uri} already exists")]
+    Duplicate {
+        operation: usize,
+        uri: DocumentElementUri,
+    },
+    #[error("operation {operation}: element {uri} does not belong to the document")]
+    ForeignElement {
+        operation: usize,
+        uri: DocumentElementUri,
+    },
+}
+
+impl Document {
+    /// Applies `patch` to this document, returning the updated one; `self` is left unchanged.
+    ///
+    /// The operations are applied in order, each to the result of the previous ones - so
+    /// e.g. an element added by one operation can be the `parent` of a subsequent one.
+    /// [`DocumentRange`](crate::narrative::DocumentRange)s are taken as given, i.e. *not*
+    /// adjusted to the inserted or removed elements.
+    ///
+    /// #### Conflict detection
+    /// A patch computed against an outdated version of the document (e.g. concurrently to
+    /// another one) may not fit the current version. The patch is applied atomically, i.e.
+    /// either all of its operations succeed or an error is returned, if
+    /// - the patch is for a different document, or any inserted element (at any depth) has a
+    ///   uri in a different document ([`WrongDocument`](PatchError::WrongDocument),
+    ///   [`ForeignElement`](PatchError::ForeignElement)),
+    /// - an element to remove or replace, or the `parent` of an added element, does not
+    ///   exist (anymore) ([`NotFound`](PatchError::NotFound)),
+    /// - the `parent` of an added element can not have children
+    ///   ([`NotAContainer`](PatchError::NotAContainer)),
+    /// - the `index` of an added element is out of bounds
+    ///   ([`OutOfBounds`](PatchError::OutOfBounds)), or
+    /// - an inserted element (at any depth) has the same uri as an element already in the
+    ///   document - other than the one it replaces ([`Duplicate`](PatchError::Duplicate)).
+    ///
+    /// # Errors
+    /// On the first conflicting operation; see above.
+    pub fn apply_patch(&self, patch: &DocumentPatch) -> Result<Self, PatchError> {
+        if patch.document != self.uri {
+            return Err(PatchError::WrongDocument {
+                patch: patch.document.clone(),
+                document: self.uri.clone(),
+            });
+        }
+        let mut data = (*self.0).clone();
+        for (operation, op) in patch.operations.iter().enumerate() {
+            let not_found = |uri: &DocumentElementUri| PatchError::NotFound {
+                operation,
+                uri: uri.clone(),
+            };
+            match op {
+                PatchOperation::Add {
+                    parent,
+                    index,
+                    element,
+                } => {
+                    check_new(&data.elements, None, element, &data.uri, operation)?;
+                    let children = match parent {
+                        None => &mut data.elements,
+                        Some(parent) => {
+                            let (elements, i) = locate(&mut data.elements, parent)
+                                .ok_or_else(|| not_found(parent))?;
+                            elements[i]
+                                .children_mut()
+                                .ok_or_else(|| PatchError::NotAContainer {
+                                    operation,
+                                    uri: parent.clone(),
+                                })?
+                        }
+                    };
+                    let index = index.unwrap_or(children.len());
+                    if index > children.len() {
+                        return Err(PatchError::OutOfBounds {
+                            operation,
+                            index,
+                            len: children.len(),
+                        });
+                    }
+                    let mut vec = std::mem::take(children).into_vec();
+                    vec.insert(index, element.clone());
+                    *children = vec.into_boxed_slice();
+                }
+                PatchOperation::Remove { uri } => {
+                    let (elements, i) =
+                        locate(&mut data.elements, uri).ok_or_else(|| not_found(uri))?;
+                    let mut vec = std::mem::take(elements).into_vec();
+                    vec.remove(i);
+                    *elements = vec.into_boxed_slice();
+                }
+                PatchOperation::Replace { uri, element } => {
+                    check_new(&data.elements, Some(uri), element, &data.uri, operation)?;
+                    let (elements, i) =
+                        locate(&mut data.elements, uri).ok_or_else(|| not_found(uri))?;
+                    elements[i] = element.clone();
+                }
+            }
+        }
+        Ok(data.close())
+    }
+}
+
+/// The slice containing the element `uri` and its index therein
+fn locate<'e>(
+    elements: &'e mut Box<[DocumentElement]>,
+    uri: &DocumentElementUri,
+) -> Option<(&'e mut Box<[DocumentElement]>, usize)> {
+    if let Some(i) = elements.iter().position(|e| e.element_uri() == Some(uri)) {
+        return Some((elements, i));
+    }
+    elements
+        .iter_mut()
+        .filter_map(DocumentElement::children_mut)
+        .find_map(|c| locate(c, uri))
+}
+
+/// Checks that the uris of `element` (and its children) are in `document` and do not occur in
+/// `elements` - except in the subtree of `replaced`.
+fn check_new(
+    elements: &[DocumentElement],
+    replaced: Option<&DocumentElementUri>,
+    element: &DocumentElement,
+    document: &DocumentUri,
+    operation: usize,
+) -> Result<(), PatchError> {
+    let replaced = replaced.and_then(|uri| {
+        elements
+            .iter()
+            .map(DocumentElement::as_ref)
+            .dfs()
+            .find(|e| e.element_uri() == Some(uri))
+    });
+    let existing = elements
+        .iter()
+        .map(DocumentElement::as_ref)
+        .dfs()
+        .filter_map(DocumentElementRef::element_uri)
+        .collect::<rustc_hash::FxHashSet<_>>();
+    let kept = replaced
+        .into_iter()
+        .dfs()
+        .filter_map(DocumentElementRef::element_uri)
+        .collect::<rustc_hash::FxHashSet<_>>();
+    for uri in std::iter::once(element.as_ref())
+        .dfs()
+        .filter_map(DocumentElementRef::element_uri)
+    {
+        if uri.document != *document {
+            return Err(PatchError::ForeignElement {
+                operation,
+                uri: uri.clone(),
+            });
+        }
+        if existing.contains(uri) && !kept.contains(uri) {
+            return Err(PatchError::Duplicate {
+                operation,
+                uri: uri.clone(),
+            });
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DocumentPatch, PatchError, PatchOperation};
+    use crate::{
+        narrative::{
+            DocumentRange,
+            documents::{DocumentData, DocumentKind, DocumentStyles},
+            elements::{
+                DocumentElement, Section, paragraphs::ParagraphKind, sections::SectionLevel,
+                test_paragraph,
+            },
+        },
+        utils::SourceRange,
+    };
+    use ftml_uris::{DocumentElementUri, DocumentUri};
+
+    fn uri(name: &str) -> DocumentElementUri {
+        format!("http://example.com?a=archive&d=doc&l=en&e={name}")
+            .parse()
+            .expect("valid uri")
+    }
+    fn paragraph(name: &str, start: usize) -> DocumentElement {
+        DocumentElement::Paragraph(test_paragraph(
+            uri(name),
+            ParagraphKind::Definition,
+            DocumentRange {
+                start,
+                end: start + 10,
+            },
+            &[],
+        ))
+    }
+
+    #[test]
+    fn add_paragraph() {
+        let document: DocumentUri = "http://example.com?a=archive&d=doc&l=en"
+            .parse()
+            .expect("valid uri");
+        let section = |children: Vec<DocumentElement>| {
+            DocumentElement::Section(Section {
+                range: DocumentRange { start: 0, end: 40 },
+                uri: uri("sec"),
+                title: None,
+                children: children.into_boxed_slice(),
+                source: SourceRange::DEFAULT,
+            })
+        };
+        let data = |children| DocumentData {
+            uri: document.clone(),
+            title: None,
+            elements: vec![section(children)].into_boxed_slice(),
+            styles: DocumentStyles::default(),
+            top_section_level: SectionLevel::Section,
+            kind: DocumentKind::Article,
+        };
+        let doc = data(vec![paragraph("sec/a", 0), paragraph("sec/c", 20)]).close();
+
+        let patch = DocumentPatch {
+            document: document.clone(),
+            operations: vec![PatchOperation::Add {
+                parent: Some(uri("sec")),
+                index: Some(1),
+                element: paragraph("sec/b", 10),
+            }],
+        };
+        let patched = doc.apply_patch(&patch).expect("applies");
+        assert_eq!(
+            patched,
+            data(vec![
+                paragraph("sec/a", 0),
+                paragraph("sec/b", 10),
+                paragraph("sec/c", 20)
+            ])
+            .close()
+        );
+        // the original is unchanged
+        assert_eq!(
+            doc,
+            data(vec![paragraph("sec/a", 0), paragraph("sec/c", 20)]).close()
+        );
+
+        // conflicts
+        assert_eq!(
+            patched.apply_patch(&patch),
+            Err(PatchError::Duplicate {
+                operation: 0,
+                uri: uri("sec/b")
+            })
+        );
+        let patch = DocumentPatch {
+            document,
+            operations: vec![
+                PatchOperation::Remove { uri: uri("sec/b") },
+                PatchOperation::Add {
+                    parent: Some(uri("sec/b")),
+                    index: None,
+                    element: paragraph("sec/b/x", 10),
+                },
+            ],
+        };
+        assert_eq!(
+            patched.apply_patch(&patch),
+            Err(PatchError::NotFound {
+                operation: 1,
+                uri: uri("sec/b")
+            })
+        );
+    }
+}
@@ -180,6 +180,41 @@ impl ArgumentMode {
             Self::BoundVariableSequence => 'B',
         }
     }
+
+    /// All argument modes.
+    pub const ALL: [Self; 4] = [
+        Self::Simple,
+        Self::Sequence,
+        Self::BoundVariable,
+        Self::BoundVariableSequence,
+    ];
+
+    /// Parses the value of an FTML argument mode attribute (one of `i`,`a`,`b`,`B`),
+    /// ignoring surrounding whitespace (unlike the [`FromStr`](std::str::FromStr)
+    /// implementation, which expects exactly one character).
+    #[must_use]
+    pub fn from_attr(s: &str) -> Option<Self> {
+        match s.trim() {
+            "i" => Some(Self::Simple),
+            "a" => Some(Self::Sequence),
+            "b" => Some(Self::BoundVariable),
+            "B" => Some(Self::BoundVariableSequence),
+            _ => None,
+        }
+    }
+
+    /// The value of this mode in FTML argument mode attributes; inverse of
+    /// [`from_attr`](Self::from_attr).
+    #[inline]
+    #[must_use]
+    pub const fn as_attr(self) -> &'static str {
+        match self {
+            Self::Simple => "i",
+            Self::Sequence => "a",
+            Self::BoundVariable => "b",
+            Self::BoundVariableSequence => "B",
+        }
+    }
 }
 impl std::fmt::Display for ArgumentMode {
     #[inline]
@@ -267,3 +302,25 @@ impl deepsize::DeepSizeOf for BoundArgument {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::ArgumentMode;
+
+    #[test]
+    fn argument_mode_attr_roundtrip() {
+        for mode in ArgumentMode::ALL {
+            assert_eq!(ArgumentMode::from_attr(mode.as_attr()), Some(mode));
+            assert_eq!(mode.as_attr(), mode.to_string());
+        }
+        assert_eq!(ArgumentMode::from_attr(" a "), Some(ArgumentMode::Sequence));
+        // unlike `from_attr`, `FromStr` does not trim
+        assert!(" a ".parse::<ArgumentMode>().is_err());
+        assert_eq!(
+            "a".parse::<ArgumentMode>().ok(),
+            Some(ArgumentMode::Sequence)
+        );
+        assert_eq!(ArgumentMode::from_attr("x"), None);
+        assert_eq!(ArgumentMode::from_attr("ab"), None);
+    }
+}
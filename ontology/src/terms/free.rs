@@ -1,4 +1,4 @@
-use ftml_uris::{DocumentUri, Id, IsDomainUri, IsNarrativeUri, ModuleUri};
+use ftml_uris::{DocumentUri, Id, IsDomainUri, IsNarrativeUri, ModuleUri, SymbolUri};
 use smallvec::SmallVec;
 
 use crate::{
@@ -42,6 +42,23 @@ impl Term {
         self.has_free_i(&mut smallvec::SmallVec::new(), &mut f)
     }
 
+    /// Whether the symbol `s` occurs anywhere in this term (as traversed by
+    /// [`symbols`](IsTerm::symbols)); stops at the first occurrence.
+    #[must_use]
+    pub fn contains_symbol(&self, s: &SymbolUri) -> bool {
+        self.symbols().any(|uri| uri == s)
+    }
+
+    /// Whether the variable `v` occurs *free* in this term; stops at the first occurrence.
+    ///
+    /// Binders are respected: an occurrence bound within this term (e.g. the `x` in `λx.x`) is
+    /// a different variable and does not count. Like [`free_variables`](Self::free_variables),
+    /// variables are compared by [`name`](Variable::name).
+    #[must_use]
+    pub fn contains_variable(&self, v: &Variable) -> bool {
+        self.has_free_such_that(|var| var.name() == v.name())
+    }
+
     #[must_use]
     pub fn all_variables(&self) -> smallvec::SmallVec<(&Variable, FreeOrBound), 4> {
         let mut vars = smallvec::SmallVec::new();
@@ -396,3 +413,36 @@ impl BoundArgument {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        terms::helpers::IntoTerm,
+        test_utils::{sym, var, variable},
+    };
+
+    #[test]
+    fn occurs_check() {
+        let x = var("x");
+        let y = var("y");
+        // f(x, λx.g(x, y))
+        let bound = sym("lambda").simple_bind(
+            variable("x"),
+            None,
+            None,
+            sym("g").apply_tms([x.clone(), y]),
+        );
+        let term = sym("f").apply_tms([x, bound.clone()]);
+
+        assert!(term.contains_symbol(&sym("f")));
+        assert!(term.contains_symbol(&sym("g")));
+        assert!(!term.contains_symbol(&sym("h")));
+
+        assert!(term.contains_variable(&variable("x")));
+        assert!(term.contains_variable(&variable("y")));
+        assert!(!term.contains_variable(&variable("z")));
+        // the x in the body is shadowed by the binder
+        assert!(!bound.contains_variable(&variable("x")));
+        assert!(bound.contains_variable(&variable("y")));
+    }
+}
@@ -0,0 +1,450 @@
+use ftml_uris::LeafUri;
+
+use crate::{
+    narrative::elements::notations::{
+        NodeOrText, Notation, NotationComponent, NotationNode, NotationResolver,
+    },
+    terms::{Argument, BoundArgument, MaybeSequence, Numeric, Term, Variable},
+};
+
+impl Term {
+    /// Renders this term as LaTeX, using the macro names and [`Notation`]s provided by
+    /// `notations`.
+    ///
+    /// Symbols and variables (and applications/bindings with such a head) are rendered
+    /// - as `\macro{arg1}{arg2}...` if `notations` knows a [macro name](NotationResolver::macro_name)
+    ///   (sequence arguments become a single comma-separated argument),
+    /// - otherwise by translating their [`Notation`] (presentation MathML) to LaTeX, with
+    ///   parentheses inserted according to the notation's precedences,
+    /// - otherwise as `\mathrm{name}` (symbols), `name` (variables) or `head(arg1, arg2, ...)`
+    ///   (applications).
+    ///
+    /// Returns `None` for unsupported constructs, namely bindings without macro name or
+    /// notation, record fields, labels, opaque terms, `ArgMap`s in notations and MathML
+    /// elements without LaTeX counterpart (e.g. tables).
+    #[must_use]
+    pub fn to_latex<R: NotationResolver + ?Sized>(&self, notations: &R) -> Option<String> {
+        let mut out = String::new();
+        Latex(notations).term(self, i64::MAX, &mut out)?;
+        Some(out.trim().to_string())
+    }
+}
+
+pub(super) enum Item<'a> {
+    Term(&'a Term),
+    Var(&'a Variable),
+}
+
+pub(super) enum Arg<'a> {
+    One(Item<'a>),
+    Seq(Vec<Item<'a>>),
+}
+
+impl<'a> From<&'a Argument> for Arg<'a> {
+    fn from(arg: &'a Argument) -> Self {
+        match arg {
+            Argument::Simple(t) | Argument::Sequence(MaybeSequence::One(t)) => {
+                Self::One(Item::Term(t))
+            }
+            Argument::Sequence(MaybeSequence::Seq(ts)) => {
+                Self::Seq(ts.iter().map(Item::Term).collect())
+            }
+        }
+    }
+}
+
+impl<'a> From<&'a BoundArgument> for Arg<'a> {
+    fn from(arg: &'a BoundArgument) -> Self {
+        match arg {
+            BoundArgument::Simple(t) | BoundArgument::Sequence(MaybeSequence::One(t)) => {
+                Self::One(Item::Term(t))
+            }
+            BoundArgument::Sequence(MaybeSequence::Seq(ts)) => {
+                Self::Seq(ts.iter().map(Item::Term).collect())
+            }
+            BoundArgument::Bound(cv) | BoundArgument::BoundSeq(MaybeSequence::One(cv)) => {
+                Self::One(Item::Var(&cv.var))
+            }
+            BoundArgument::BoundSeq(MaybeSequence::Seq(cvs)) => {
+                Self::Seq(cvs.iter().map(|cv| Item::Var(&cv.var)).collect())
+            }
+        }
+    }
+}
+
+struct Latex<'r, R: NotationResolver + ?Sized>(&'r R);
+
+impl<R: NotationResolver + ?Sized> Latex<'_, R> {
+    fn term(&self, term: &Term, prec: i64, out: &mut String) -> Option<()> {
+        match term {
+            Term::Symbol { uri, .. } => {
+                let leaf = LeafUri::Symbol(uri.clone());
+                self.leaf(&leaf, prec, out).unwrap_or_else(|| {
+                    out.push_str("\\mathrm{");
+                    escape(uri.name().last(), out);
+                    out.push('}');
+                    Some(())
+                })
+            }
+            Term::Var { variable, .. } => self.var(variable, prec, out),
+            Term::Number(Numeric::Int(i)) => {
+                out.push_str(&i.to_string());
+                Some(())
+            }
+            Term::Number(Numeric::Float(f)) => {
+                out.push_str(&f64::from(*f).to_string());
+                Some(())
+            }
+            Term::Application(app) => {
+                let args = app.arguments.iter().map(Arg::from).collect::<Vec<_>>();
+                if let Some(leaf) = head_leaf(&app.head)
+                    && let Some(r) = self.applied(&leaf, &args, prec, out)
+                {
+                    return r;
+                }
+                self.term(&app.head, 0, out)?;
+                out.push('(');
+                for (i, arg) in args.iter().enumerate() {
+                    if i > 0 {
+                        out.push_str(", ");
+                    }
+                    self.arg(arg, i64::MAX, out)?;
+                }
+                out.push(')');
+                Some(())
+            }
+            Term::Bound(b) => {
+                let args = b.arguments.iter().map(Arg::from).collect::<Vec<_>>();
+                self.applied(&head_leaf(&b.head)?, &args, prec, out)?
+            }
+            Term::Field(_) | Term::Label { .. } | Term::Opaque(_) => None,
+        }
+    }
+
+    fn var(&self, var: &Variable, prec: i64, out: &mut String) -> Option<()> {
+        let name = match var {
+            Variable::Ref { declaration, .. } => {
+                let leaf = LeafUri::Element(declaration.clone());
+                if let Some(r) = self.leaf(&leaf, prec, out) {
+                    return r;
+                }
+                declaration.name().last()
+            }
+            Variable::Name {
+                notated: Some(name),
+                ..
+            }
+            | Variable::Name { name, .. } => name.as_ref(),
+        };
+        if name.chars().count() == 1 {
+            escape(name, out);
+        } else {
+            out.push_str("\\mathit{");
+            escape(name, out);
+            out.push('}');
+        }
+        Some(())
+    }
+
+    fn item(&self, item: &Item<'_>, prec: i64, out: &mut String) -> Option<()> {
+        match item {
+            Item::Term(t) => self.term(t, prec, out),
+            Item::Var(v) => self.var(v, prec, out),
+        }
+    }
+
+    fn arg(&self, arg: &Arg<'_>, prec: i64, out: &mut String) -> Option<()> {
+        match arg {
+            Arg::One(item) => self.item(item, prec, out),
+            Arg::Seq(items) => {
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        out.push(',');
+                    }
+                    self.item(item, prec, out)?;
+                }
+                Some(())
+            }
+        }
+    }
+
+    /// `None` if neither a macro name nor a notation is known for `leaf`
+    fn leaf(&self, leaf: &LeafUri, prec: i64, out: &mut String) -> Option<Option<()>> {
+        if let Some(name) = self.0.macro_name(leaf) {
+            out.push('\\');
+            out.push_str(name);
+            out.push(' ');
+            return Some(Some(()));
+        }
+        let notation = self.0.notation(leaf, None)?;
+        let Some(op) = &notation.op else {
+            return Some(self.notation(notation, &[], prec, out));
+        };
+        Some(parenthesized(prec, notation.precedence, out, |out| {
+            notation_node(op, out)
+        }))
+    }
+
+    /// `None` if neither a macro name nor a notation is known for `leaf`
+    fn applied(
+        &self,
+        leaf: &LeafUri,
+        args: &[Arg<'_>],
+        prec: i64,
+        out: &mut String,
+    ) -> Option<Option<()>> {
+        if let Some(name) = self.0.macro_name(leaf) {
+            out.push('\\');
+            out.push_str(name);
+            for arg in args {
+                out.push('{');
+                if self.arg(arg, i64::MAX, out).is_none() {
+                    return Some(None);
+                }
+                out.push('}');
+            }
+            return Some(Some(()));
+        }
+        let notation = self
+            .0
+            .notation_for_arity(leaf, args.len())
+            .or_else(|| self.0.notation(leaf, None))?;
+        Some(self.notation(notation, args, prec, out))
+    }
+
+    fn notation(
+        &self,
+        notation: &Notation,
+        args: &[Arg<'_>],
+        prec: i64,
+        out: &mut String,
+    ) -> Option<()> {
+        parenthesized(prec, notation.precedence, out, |out| {
+            self.component(&notation.component, notation, args, out)
+        })
+    }
+
+    fn component(
+        &self,
+        comp: &NotationComponent,
+        notation: &Notation,
+        args: &[Arg<'_>],
+        out: &mut String,
+    ) -> Option<()> {
+        let arg_prec = |index: u8| {
+            notation
+                .argprecs
+                .get(index as usize)
+                .copied()
+                .unwrap_or(notation.precedence)
+        };
+        match comp {
+            NotationComponent::Text { txt } => {
+                escape(txt, out);
+                Some(())
+            }
+            NotationComponent::Argument { index, .. } => {
+                self.arg(args.get(*index as usize)?, arg_prec(*index), out)
+            }
+            NotationComponent::ArgSep { index, sep, .. } => {
+                let prec = arg_prec(*index);
+                match args.get(*index as usize)? {
+                    Arg::One(item) => self.item(item, prec, out),
+                    Arg::Seq(items) => {
+                        for (i, item) in items.iter().enumerate() {
+                            if i > 0 {
+                                for s in sep {
+                                    self.component(s, notation, args, out)?;
+                                }
+                            }
+                            self.item(item, prec, out)?;
+                        }
+                        Some(())
+                    }
+                }
+            }
+            NotationComponent::ArgMap { .. } => None,
+            NotationComponent::MainComp { node } | NotationComponent::Comp { node } => {
+                notation_node(node, out)
+            }
+            NotationComponent::Node { tag, children, .. } => {
+                mathml(tag.as_ref(), children, out, |c, out| {
+                    self.component(c, notation, args, out)
+                })
+            }
+        }
+    }
+}
+
+fn notation_node(node: &NotationNode, out: &mut String) -> Option<()> {
+    mathml(node.tag.as_ref(), &node.children, out, |c, out| match c {
+        NodeOrText::Text(t) => {
+            escape(t, out);
+            Some(())
+        }
+        NodeOrText::Node(n) => notation_node(n, out),
+    })
+}
+
+pub(super) fn head_leaf(head: &Term) -> Option<LeafUri> {
+    match head {
+        Term::Symbol { uri, .. } => Some(LeafUri::Symbol(uri.clone())),
+        Term::Var {
+            variable: Variable::Ref { declaration, .. },
+            ..
+        } => Some(LeafUri::Element(declaration.clone())),
+        _ => None,
+    }
+}
+
+fn parenthesized(
+    outer: i64,
+    inner: i64,
+    out: &mut String,
+    f: impl FnOnce(&mut String) -> Option<()>,
+) -> Option<()> {
+    if inner > outer {
+        out.push_str("\\left(");
+        f(out)?;
+        out.push_str("\\right)");
+        Some(())
+    } else {
+        f(out)
+    }
+}
+
+/// Translates the presentation MathML element `tag` with the given `children`.
+fn mathml<C>(
+    tag: &str,
+    children: &[C],
+    out: &mut String,
+    render: impl Fn(&C, &mut String) -> Option<()>,
+) -> Option<()> {
+    let mut parts = Vec::with_capacity(children.len());
+    for c in children {
+        let mut s = String::new();
+        render(c, &mut s)?;
+        parts.push(s);
+    }
+    let braced = |s: &str| format!("{{{}}}", s.trim());
+    let s = match (tag, parts.as_slice()) {
+        ("mi", [s]) if s.chars().count() > 1 && !s.starts_with('\\') => {
+            format!("\\mathrm{{{s}}}")
+        }
+        ("mtext", _) => format!("\\text{{{}}}", parts.concat()),
+        ("msub", [a, b]) => format!("{}_{}", braced(a), braced(b)),
+        ("msup", [a, b]) => format!("{}^{}", braced(a), braced(b)),
+        ("msubsup", [a, b, c]) => format!("{}_{}^{}", braced(a), braced(b), braced(c)),
+        ("mfrac", [a, b]) => format!("\\frac{}{}", braced(a), braced(b)),
+        ("msqrt", _) => format!("\\sqrt{}", braced(&parts.concat())),
+        ("mroot", [a, b]) => format!("\\sqrt[{}]{}", b.trim(), braced(a)),
+        ("mover", [a, b]) => format!("\\overset{}{}", braced(b), braced(a)),
+        ("munder", [a, b]) => format!("\\underset{}{}", braced(b), braced(a)),
+        ("munderover", [a, b, c]) => format!(
+            "\\underset{}{{\\overset{}{}}}",
+            braced(b),
+            braced(c),
+            braced(a)
+        ),
+        ("mspace", _) => "\\,".to_string(),
+        ("math" | "mrow" | "mstyle" | "mpadded" | "semantics" | "mi" | "mo" | "mn" | "span", _) => {
+            parts.concat()
+        }
+        _ => return None,
+    };
+    out.push_str(&s);
+    Some(())
+}
+
+fn escape(s: &str, out: &mut String) {
+    for c in s.chars() {
+        match c {
+            '\\' => out.push_str("\\backslash "),
+            '{' | '}' | '#' | '$' | '%' | '&' | '_' => {
+                out.push('\\');
+                out.push(c);
+            }
+            '^' => out.push_str("\\hat{}"),
+            '~' => out.push_str("\\sim "),
+            '\u{2061}'..='\u{2064}' => (),
+            c => {
+                if let Some((_, cmd)) = UNICODE_COMMANDS.iter().find(|(u, _)| *u == c) {
+                    out.push_str(cmd);
+                    out.push(' ');
+                    continue;
+                }
+                out.push(c);
+            }
+        }
+    }
+}
+
+/// Common unicode math characters and their LaTeX commands; all others are passed through
+/// (which works with `unicode-math`).
+const UNICODE_COMMANDS: [(char, &str); 24] = [
+    ('∀', "\\forall"),
+    ('∃', "\\exists"),
+    ('¬', "\\neg"),
+    ('∧', "\\wedge"),
+    ('∨', "\\vee"),
+    ('→', "\\rightarrow"),
+    ('⇒', "\\Rightarrow"),
+    ('⇔', "\\Leftrightarrow"),
+    ('↦', "\\mapsto"),
+    ('∈', "\\in"),
+    ('∉', "\\notin"),
+    ('⊆', "\\subseteq"),
+    ('∪', "\\cup"),
+    ('∩', "\\cap"),
+    ('∅', "\\emptyset"),
+    ('≤', "\\leq"),
+    ('≥', "\\geq"),
+    ('≠', "\\neq"),
+    ('×', "\\times"),
+    ('⋅', "\\cdot"),
+    ('∘', "\\circ"),
+    ('∞', "\\infty"),
+    ('λ', "\\lambda"),
+    ('…', "\\ldots"),
+];
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        narrative::elements::notations::NotationComponent,
+        terms::helpers::IntoTerm,
+        test_utils::{Resolver, infix, node, sym, var},
+    };
+    use ftml_uris::LeafUri;
+
+    /// `plus` has macro name `plus`; `times` only a (right-parenthesizing) notation `a ⋅ b`
+    fn resolver() -> Resolver {
+        let times = infix(
+            node("mo", vec![NotationComponent::Text { txt: "⋅".into() }]),
+            [99, 99],
+        );
+        Resolver {
+            notations: std::iter::once((LeafUri::Symbol(sym("times")), times)).collect(),
+            macros: vec![(sym("plus"), "plus")],
+        }
+    }
+
+    #[test]
+    fn application_to_latex() {
+        let r = resolver();
+        let term = sym("plus").apply_tms([var("x"), var("y")]);
+        assert_eq!(term.to_latex(&r).as_deref(), Some("\\plus{x}{y}"));
+
+        let term = sym("times").apply_tms([term, var("z")]);
+        assert_eq!(term.to_latex(&r).as_deref(), Some("\\plus{x}{y}\\cdot z"));
+
+        let term = sym("times").apply_tms([var("z"), term]);
+        assert_eq!(
+            term.to_latex(&r).as_deref(),
+            Some("z\\cdot \\left(\\plus{x}{y}\\cdot z\\right)")
+        );
+
+        let term = sym("f").apply_tms([var("x")]);
+        assert_eq!(term.to_latex(&r).as_deref(), Some("\\mathrm{f}(x)"));
+    }
+}
@@ -0,0 +1,346 @@
+use ftml_uris::LeafUri;
+
+use super::latex::{Arg, Item, head_leaf};
+use crate::{
+    narrative::elements::notations::{
+        NodeOrText, Notation, NotationComponent, NotationNode, NotationResolver,
+    },
+    terms::{Numeric, Term, Variable, opaque::AnyOpaque},
+};
+
+impl Term {
+    /// Renders this term as presentation MathML (without the enclosing `<math>` element),
+    /// using the [`Notation`]s provided by `notations` - i.e. statically, without any of the
+    /// interactive machinery of the (leptos) term components.
+    ///
+    /// Symbols and variables (and applications/bindings with such a head) are rendered using
+    /// their [`Notation`], with parentheses inserted according to the notation's precedences;
+    /// without a notation, they fall back to `<mi>name</mi>` and `head(arg1, arg2, ...)`
+    /// respectively. Record fields are rendered as `record.field`, labels by their name and
+    /// opaque terms by their original nodes. `ArgMap`s in notations are skipped.
+    #[must_use]
+    pub fn to_mathml<R: NotationResolver + ?Sized>(&self, notations: &R) -> String {
+        let mut out = String::new();
+        MathMl(notations).term(self, i64::MAX, &mut out);
+        out
+    }
+}
+
+struct MathMl<'r, R: NotationResolver + ?Sized>(&'r R);
+
+impl<R: NotationResolver + ?Sized> MathMl<'_, R> {
+    fn term(&self, term: &Term, prec: i64, out: &mut String) {
+        match term {
+            Term::Symbol { uri, .. } => {
+                if !self.leaf(&LeafUri::Symbol(uri.clone()), prec, out) {
+                    element("mi", uri.name().last(), out);
+                }
+            }
+            Term::Var { variable, .. } => self.var(variable, prec, out),
+            Term::Number(Numeric::Int(i)) => element("mn", &i.to_string(), out),
+            Term::Number(Numeric::Float(f)) => element("mn", &f64::from(*f).to_string(), out),
+            Term::Application(app) => {
+                let args = app.arguments.iter().map(Arg::from).collect::<Vec<_>>();
+                self.applied(&app.head, &args, prec, out);
+            }
+            Term::Bound(b) => {
+                let args = b.arguments.iter().map(Arg::from).collect::<Vec<_>>();
+                self.applied(&b.head, &args, prec, out);
+            }
+            Term::Field(f) => {
+                out.push_str("<mrow>");
+                self.term(&f.record, 0, out);
+                element("mo", ".", out);
+                element("mi", f.key.last(), out);
+                out.push_str("</mrow>");
+            }
+            Term::Label { name, .. } => element("mi", name.last(), out),
+            Term::Opaque(o) => {
+                out.push_str("<mrow>");
+                self.opaque(
+                    &o.node.tag,
+                    &o.node.attributes,
+                    &o.node.children,
+                    &o.terms,
+                    out,
+                );
+                out.push_str("</mrow>");
+            }
+        }
+    }
+
+    fn var(&self, var: &Variable, prec: i64, out: &mut String) {
+        let name = match var {
+            Variable::Ref { declaration, .. } => {
+                if self.leaf(&LeafUri::Element(declaration.clone()), prec, out) {
+                    return;
+                }
+                declaration.name().last()
+            }
+            Variable::Name {
+                notated: Some(name),
+                ..
+            }
+            | Variable::Name { name, .. } => name.as_ref(),
+        };
+        element("mi", name, out);
+    }
+
+    fn item(&self, item: &Item<'_>, prec: i64, out: &mut String) {
+        match item {
+            Item::Term(t) => self.term(t, prec, out),
+            Item::Var(v) => self.var(v, prec, out),
+        }
+    }
+
+    fn arg(&self, arg: &Arg<'_>, prec: i64, out: &mut String) {
+        match arg {
+            Arg::One(item) => self.item(item, prec, out),
+            Arg::Seq(items) => {
+                out.push_str("<mrow>");
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        element("mo", ",", out);
+                    }
+                    self.item(item, prec, out);
+                }
+                out.push_str("</mrow>");
+            }
+        }
+    }
+
+    /// `false` if no notation is known for `leaf`
+    fn leaf(&self, leaf: &LeafUri, prec: i64, out: &mut String) -> bool {
+        let Some(notation) = self.0.notation(leaf, None) else {
+            return false;
+        };
+        if let Some(op) = &notation.op {
+            parenthesized(prec, notation.precedence, out, |out| {
+                notation_node(op, None, out);
+            });
+        } else {
+            self.notation(notation, &[], prec, out);
+        }
+        true
+    }
+
+    fn applied(&self, head: &Term, args: &[Arg<'_>], prec: i64, out: &mut String) {
+        if let Some(leaf) = head_leaf(head)
+            && let Some(notation) = self
+                .0
+                .notation_for_arity(&leaf, args.len())
+                .or_else(|| self.0.notation(&leaf, None))
+        {
+            self.notation(notation, args, prec, out);
+            return;
+        }
+        out.push_str("<mrow>");
+        self.term(head, 0, out);
+        element("mo", "(", out);
+        for (i, arg) in args.iter().enumerate() {
+            if i > 0 {
+                element("mo", ",", out);
+            }
+            self.arg(arg, i64::MAX, out);
+        }
+        element("mo", ")", out);
+        out.push_str("</mrow>");
+    }
+
+    fn notation(&self, notation: &Notation, args: &[Arg<'_>], prec: i64, out: &mut String) {
+        parenthesized(prec, notation.precedence, out, |out| {
+            self.component(&notation.component, notation, args, out);
+        });
+    }
+
+    fn component(
+        &self,
+        comp: &NotationComponent,
+        notation: &Notation,
+        args: &[Arg<'_>],
+        out: &mut String,
+    ) {
+        let arg_prec = |index: u8| {
+            notation
+                .argprecs
+                .get(index as usize)
+                .copied()
+                .unwrap_or(notation.precedence)
+        };
+        match comp {
+            NotationComponent::Text { txt } => escape(txt, out),
+            NotationComponent::Argument { index, .. } => {
+                if let Some(arg) = args.get(*index as usize) {
+                    self.arg(arg, arg_prec(*index), out);
+                }
+            }
+            NotationComponent::ArgSep { index, sep, .. } => {
+                let prec = arg_prec(*index);
+                match args.get(*index as usize) {
+                    None => (),
+                    Some(Arg::One(item)) => self.item(item, prec, out),
+                    Some(Arg::Seq(items)) => {
+                        for (i, item) in items.iter().enumerate() {
+                            if i > 0 {
+                                for s in sep {
+                                    self.component(s, notation, args, out);
+                                }
+                            }
+                            self.item(item, prec, out);
+                        }
+                    }
+                }
+            }
+            NotationComponent::ArgMap { .. } => (),
+            NotationComponent::MainComp { node } => {
+                notation_node(node, Some("data-ftml-maincomp"), out);
+            }
+            NotationComponent::Comp { node } => notation_node(node, Some("data-ftml-comp"), out),
+            NotationComponent::Node {
+                tag,
+                attributes,
+                children,
+            } => {
+                open(tag.as_ref(), attributes, None, out);
+                for c in children {
+                    self.component(c, notation, args, out);
+                }
+                close(tag.as_ref(), out);
+            }
+        }
+    }
+
+    fn opaque(
+        &self,
+        tag: &ftml_uris::Id,
+        attributes: &[(ftml_uris::Id, Box<str>)],
+        children: &[AnyOpaque],
+        terms: &[Term],
+        out: &mut String,
+    ) {
+        open(tag.as_ref(), attributes, None, out);
+        for c in children {
+            match c {
+                AnyOpaque::Text(t) => escape(t, out),
+                AnyOpaque::Term(i) => {
+                    if let Some(t) = terms.get(*i as usize) {
+                        self.term(t, i64::MAX, out);
+                    }
+                }
+                AnyOpaque::Node(n) => self.opaque(&n.tag, &n.attributes, &n.children, terms, out),
+            }
+        }
+        close(tag.as_ref(), out);
+    }
+}
+
+fn notation_node(node: &NotationNode, marker: Option<&str>, out: &mut String) {
+    open(node.tag.as_ref(), &node.attributes, marker, out);
+    for c in &node.children {
+        match c {
+            NodeOrText::Text(t) => escape(t, out),
+            NodeOrText::Node(n) => notation_node(n, None, out),
+        }
+    }
+    close(node.tag.as_ref(), out);
+}
+
+fn parenthesized(outer: i64, inner: i64, out: &mut String, f: impl FnOnce(&mut String)) {
+    if inner > outer {
+        out.push_str("<mrow>");
+        element("mo", "(", out);
+        f(out);
+        element("mo", ")", out);
+        out.push_str("</mrow>");
+    } else {
+        f(out);
+    }
+}
+
+fn open(
+    tag: &str,
+    attributes: &[(ftml_uris::Id, Box<str>)],
+    marker: Option<&str>,
+    out: &mut String,
+) {
+    out.push('<');
+    out.push_str(tag);
+    for (k, v) in attributes {
+        out.push(' ');
+        out.push_str(k.as_ref());
+        out.push_str("=\"");
+        escape(v, out);
+        out.push('"');
+    }
+    if let Some(marker) = marker {
+        out.push(' ');
+        out.push_str(marker);
+    }
+    out.push('>');
+}
+
+fn close(tag: &str, out: &mut String) {
+    out.push_str("</");
+    out.push_str(tag);
+    out.push('>');
+}
+
+fn element(tag: &str, txt: &str, out: &mut String) {
+    open(tag, &[], None, out);
+    escape(txt, out);
+    close(tag, out);
+}
+
+fn escape(s: &str, out: &mut String) {
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            c => out.push(c),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        narrative::elements::{DocumentTerm, notations::NotationComponent},
+        terms::helpers::IntoTerm,
+        test_utils::{Resolver, infix, sym, var},
+    };
+    use ftml_uris::LeafUri;
+
+    /// `plus` has the (left-associative) notation `a + b`
+    fn resolver() -> Resolver {
+        let plus = infix(NotationComponent::Text { txt: "+".into() }, [100, 99]);
+        Resolver {
+            notations: std::iter::once((LeafUri::Symbol(sym("plus")), plus)).collect(),
+            ..Resolver::default()
+        }
+    }
+
+    #[test]
+    fn document_term_to_mathml() {
+        let r = resolver();
+        let term = sym("plus").apply_tms([var("x"), sym("f").apply_tms([var("y")])]);
+        let term = DocumentTerm::new(
+            "http://example.com?a=archive&d=doc&l=en&e=term"
+                .parse()
+                .expect("valid uri"),
+            term,
+            None,
+        );
+        assert_eq!(
+            term.presentation().to_mathml(&r),
+            "<mrow><mi>x</mi>+<mrow><mi>f</mi><mo>(</mo><mi>y</mi><mo>)</mo></mrow></mrow>"
+        );
+
+        let term = sym("plus").apply_tms([var("z"), sym("plus").apply_tms([var("x"), var("y")])]);
+        assert_eq!(
+            term.to_mathml(&r),
+            "<mrow><mi>z</mi>+<mrow><mo>(</mo><mrow><mi>x</mi>+<mi>y</mi></mrow><mo>)</mo></mrow></mrow>"
+        );
+    }
+}
@@ -8,6 +8,7 @@ mod macros;
 pub mod om;
 pub mod opaque;
 pub mod records;
+pub mod rewrite;
 pub mod sequences;
 pub mod simplify;
 pub mod subst;
@@ -16,6 +17,9 @@ pub mod termpaths;
 pub mod termpaths2;
 //pub mod traverser;
 mod debug;
+mod latex;
+mod mathml;
+mod stable_hash;
 pub mod patterns;
 mod variables;
 
@@ -30,6 +34,8 @@ pub use term::{
 };
 pub use variables::Variable;
 
+pub(crate) use stable_hash::StableHasher;
+
 use crate::utils::SourceRange;
 
 pub trait IsTerm: Clone + std::hash::Hash + PartialEq + Eq {
@@ -0,0 +1,200 @@
+use ftml_uris::SymbolUri;
+
+use crate::terms::{Argument, Term};
+
+type RuleFn = dyn Fn(&[Argument]) -> Option<Term> + Send + Sync;
+
+/// A registry of rewrite rules, keyed on the [`SymbolUri`] of the head of an application
+/// and its number of arguments.
+///
+/// A rule registered for `(head, arity)` is only tried on [`Term::Application`]s whose head
+/// is [`Term::Symbol`] with uri `head` and which have exactly `arity` [`Argument`]s (counting
+/// sequence arguments as one). The rule receives the arguments and may still decline by
+/// returning `None`; rules for the same key are tried in the order they were added.
+pub struct RewriteRules {
+    rules: rustc_hash::FxHashMap<(SymbolUri, usize), Vec<Box<RuleFn>>>,
+    max_steps: usize,
+}
+impl Default for RewriteRules {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            rules: rustc_hash::FxHashMap::default(),
+            max_steps: Self::DEFAULT_MAX_STEPS,
+        }
+    }
+}
+impl std::fmt::Debug for RewriteRules {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RewriteRules")
+            .field("rules", &self.rules.keys().collect::<Vec<_>>())
+            .field("max_steps", &self.max_steps)
+            .finish()
+    }
+}
+
+impl RewriteRules {
+    /// The default maximum number of rule applications in a single [`Term::rewrite`].
+    pub const DEFAULT_MAX_STEPS: usize = 1024;
+
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum number of rule applications in a single [`Term::rewrite`].
+    #[inline]
+    #[must_use]
+    pub const fn with_max_steps(mut self, max_steps: usize) -> Self {
+        self.max_steps = max_steps;
+        self
+    }
+
+    /// Registers a rule for applications of `head` to exactly `arity` arguments.
+    pub fn add(
+        &mut self,
+        head: SymbolUri,
+        arity: usize,
+        rule: impl Fn(&[Argument]) -> Option<Term> + Send + Sync + 'static,
+    ) {
+        self.rules
+            .entry((head, arity))
+            .or_default()
+            .push(Box::new(rule));
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    fn apply(&self, term: &Term) -> Option<Term> {
+        let Term::Application(app) = term else {
+            return None;
+        };
+        let Term::Symbol { uri, .. } = &app.head else {
+            return None;
+        };
+        self.rules
+            .get(&(uri.clone(), app.arguments.len()))?
+            .iter()
+            .find_map(|rule| rule(&app.arguments))
+    }
+}
+
+impl Term {
+    /// Rewrites this term with the given [`RewriteRules`] until no rule applies anymore.
+    ///
+    /// Rewriting is bottom-up (innermost first) and proceeds in passes over the term (via
+    /// [`Term::modify_bottom_up`]): within a pass, the subterms of a node are rewritten
+    /// before the rules are tried on the node itself (with its rewritten subterms). The
+    /// term returned by a rule is not descended into within the same pass; instead, passes
+    /// are repeated until one of them changes nothing. To guarantee termination (e.g. for
+    /// non-terminating rule sets like `f(x) ~> f(f(x))`), at most
+    /// [`max_steps`](RewriteRules::with_max_steps) rule applications happen in total
+    /// (default: [`RewriteRules::DEFAULT_MAX_STEPS`]); once exceeded, the current
+    /// (partially rewritten) term is returned.
+    #[must_use]
+    pub fn rewrite(&self, rules: &RewriteRules) -> Self {
+        let mut current = self.clone();
+        if rules.is_empty() {
+            return current;
+        }
+        let mut steps = 0;
+        while steps < rules.max_steps {
+            let next = current.modify_bottom_up(|t| {
+                if steps >= rules.max_steps {
+                    return None;
+                }
+                let r = rules.apply(t);
+                if r.is_some() {
+                    steps += 1;
+                }
+                r
+            });
+            match next {
+                std::borrow::Cow::Borrowed(_) => break,
+                std::borrow::Cow::Owned(t) => current = t,
+            }
+        }
+        current
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RewriteRules;
+    use crate::{
+        terms::{Argument, Term, helpers::IntoTerm},
+        test_utils::{sym, var},
+    };
+    use ftml_uris::SymbolUri;
+
+    fn neg() -> SymbolUri {
+        sym("neg")
+    }
+
+    fn double_negation() -> RewriteRules {
+        let mut rules = RewriteRules::new();
+        rules.add(neg(), 1, |args| match args {
+            [Argument::Simple(inner)] => match inner.unapply(&neg())? {
+                [Argument::Simple(x)] => Some(x.clone()),
+                _ => None,
+            },
+            _ => None,
+        });
+        rules
+    }
+
+    #[test]
+    fn rewrite_double_negation() {
+        let x = var("x");
+        let rules = double_negation();
+
+        let term = neg().apply_tms([neg().apply_tms([x.clone()])]);
+        assert_eq!(term.rewrite(&rules), x);
+
+        let term =
+            neg().apply_tms([neg().apply_tms([neg().apply_tms([neg().apply_tms([x.clone()])])])]);
+        assert_eq!(term.rewrite(&rules), x);
+
+        let term = neg().apply_tms([x.clone()]);
+        assert_eq!(term.rewrite(&rules), term);
+    }
+
+    #[test]
+    fn rewrite_innermost_first() {
+        let constant = |name: &str| Term::Symbol {
+            uri: sym(name),
+            presentation: None,
+        };
+        let mut rules = RewriteRules::new().with_max_steps(1);
+        // g(_) ~> d and f(a) ~> b
+        rules.add(sym("g"), 1, move |_| Some(constant("d")));
+        rules.add(sym("f"), 1, move |args| match args {
+            [Argument::Simple(Term::Symbol { uri, .. })] if *uri == sym("a") => Some(constant("b")),
+            _ => None,
+        });
+        // the single step rewrites the inner application first
+        let term = sym("g").apply_tms([sym("f").apply_tms([constant("a")])]);
+        assert_eq!(term.rewrite(&rules), sym("g").apply_tms([constant("b")]));
+    }
+
+    #[test]
+    fn rewrite_step_limit() {
+        let mut rules = RewriteRules::new().with_max_steps(5);
+        rules.add(neg(), 1, |args| match args {
+            [Argument::Simple(x)] => Some(neg().apply_tms([neg().apply_tms([x.clone()])])),
+            _ => None,
+        });
+        let x = var("x");
+        // terminates despite the rule set not being terminating; every step adds one `neg`
+        let mut expected = x.clone();
+        for _ in 0..6 {
+            expected = neg().apply_tms([expected]);
+        }
+        assert_eq!(neg().apply_tms([x]).rewrite(&rules), expected);
+    }
+}
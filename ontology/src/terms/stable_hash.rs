@@ -0,0 +1,282 @@
+use std::fmt::Write;
+
+use crate::terms::{
+    Argument, BoundArgument, ComponentVar, MaybeSequence, Term, VarOrSym, Variable,
+    opaque::{AnyOpaque, OpaqueNode},
+};
+
+impl Term {
+    /// A hash of this term that is stable across processes (and interner sessions), e.g. for
+    /// persistent caches keyed by term content.
+    ///
+    /// The derived [`Hash`](std::hash::Hash) is cheap, but depends on the interned
+    /// representations of [`Uri`](ftml_uris::Uri)s and the (per-process) term cache, so its
+    /// values must not leave the process. This method instead hashes the canonical structure
+    /// of the term - the string forms of symbols, variables and names, argument modes,
+    /// numbers and opaque nodes - with a fixed hash function (64-bit FNV-1a), and is
+    /// consequently much slower.
+    ///
+    /// Terms that are equal (`==`) have the same stable hash.
+    #[must_use]
+    pub fn stable_hash(&self) -> u64 {
+        let mut hasher = StableHasher::default();
+        hasher.term(self);
+        hasher.finish()
+    }
+}
+
+/// 64-bit FNV-1a; also used for
+/// [`ModuleData::content_hash`](crate::domain::modules::ModuleData::content_hash).
+pub(crate) struct StableHasher(u64);
+impl Default for StableHasher {
+    #[inline]
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+impl Write for StableHasher {
+    #[inline]
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        self.bytes(s.as_bytes());
+        Ok(())
+    }
+}
+
+impl StableHasher {
+    #[inline]
+    pub(crate) const fn finish(&self) -> u64 {
+        self.0
+    }
+    pub(crate) fn bytes(&mut self, bytes: &[u8]) {
+        for b in bytes {
+            self.0 ^= u64::from(*b);
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+    #[inline]
+    pub(crate) fn tag(&mut self, tag: u8) {
+        self.bytes(&[tag]);
+    }
+    #[inline]
+    pub(crate) fn len(&mut self, len: usize) {
+        self.bytes(&(len as u64).to_le_bytes());
+    }
+    /// strings are terminated by `0xff`, which never occurs in UTF-8
+    pub(crate) fn display(&mut self, d: impl std::fmt::Display) {
+        // writing into the hasher is infallible
+        let _ = write!(self, "{d}");
+        self.tag(0xff);
+    }
+    pub(crate) fn option<T>(&mut self, o: Option<&T>, f: impl FnOnce(&mut Self, &T)) {
+        match o {
+            None => self.tag(0),
+            Some(t) => {
+                self.tag(1);
+                f(self, t);
+            }
+        }
+    }
+
+    pub(crate) fn term(&mut self, term: &Term) {
+        match term {
+            Term::Symbol { uri, presentation } => {
+                self.tag(0);
+                self.display(uri);
+                self.option(presentation.as_ref(), Self::var_or_sym);
+            }
+            Term::Var {
+                variable,
+                presentation,
+            } => {
+                self.tag(1);
+                self.variable(variable);
+                self.option(presentation.as_ref(), Self::var_or_sym);
+            }
+            Term::Application(app) => {
+                self.tag(2);
+                self.term(&app.head);
+                self.len(app.arguments.len());
+                for a in &app.arguments {
+                    self.argument(a);
+                }
+                self.option(app.presentation.as_ref(), Self::var_or_sym);
+            }
+            Term::Bound(b) => {
+                self.tag(3);
+                self.term(&b.head);
+                self.len(b.arguments.len());
+                for a in &b.arguments {
+                    self.bound_argument(a);
+                }
+                self.option(b.presentation.as_ref(), Self::var_or_sym);
+            }
+            Term::Field(f) => {
+                self.tag(4);
+                self.term(&f.record);
+                self.display(&f.key);
+                self.option(f.record_type.as_ref(), Self::term);
+                self.option(f.presentation.as_ref(), Self::var_or_sym);
+            }
+            Term::Label { name, df, tp } => {
+                self.tag(5);
+                self.display(name);
+                self.option(df.as_deref(), Self::term);
+                self.option(tp.as_deref(), Self::term);
+            }
+            Term::Opaque(o) => {
+                self.tag(6);
+                self.opaque_node(&o.node);
+                self.len(o.terms.len());
+                for t in &o.terms {
+                    self.term(t);
+                }
+            }
+            Term::Number(n) => {
+                self.tag(7);
+                // consistent with `==`, which compares numbers as (ordered) floats
+                let f = n.as_float();
+                let f = if f == 0.0 {
+                    0.0
+                } else if f.is_nan() {
+                    f64::NAN
+                } else {
+                    f
+                };
+                self.bytes(&f.to_bits().to_le_bytes());
+            }
+        }
+    }
+
+    fn var_or_sym(&mut self, vos: &VarOrSym) {
+        match vos {
+            VarOrSym::Sym(uri) => {
+                self.tag(0);
+                self.display(uri);
+            }
+            VarOrSym::Var(v) => {
+                self.tag(1);
+                self.variable(v);
+            }
+        }
+    }
+
+    fn variable(&mut self, var: &Variable) {
+        match var {
+            Variable::Name { name, notated } => {
+                self.tag(0);
+                self.display(name);
+                self.option(notated.as_ref(), |s, n| s.display(n));
+            }
+            Variable::Ref {
+                declaration,
+                is_sequence,
+            } => {
+                self.tag(1);
+                self.display(declaration);
+                self.option(is_sequence.as_ref(), |s, b| s.tag(u8::from(*b)));
+            }
+        }
+    }
+
+    fn sequence<T>(&mut self, seq: &MaybeSequence<T>, f: impl Fn(&mut Self, &T)) {
+        match seq {
+            MaybeSequence::One(t) => {
+                self.tag(0);
+                f(self, t);
+            }
+            MaybeSequence::Seq(ts) => {
+                self.tag(1);
+                self.len(ts.len());
+                for t in ts {
+                    f(self, t);
+                }
+            }
+        }
+    }
+
+    fn argument(&mut self, arg: &Argument) {
+        self.display(arg.mode().as_char());
+        match arg {
+            Argument::Simple(t) => self.term(t),
+            Argument::Sequence(s) => self.sequence(s, Self::term),
+        }
+    }
+
+    fn bound_argument(&mut self, arg: &BoundArgument) {
+        self.display(arg.mode().as_char());
+        match arg {
+            BoundArgument::Simple(t) => self.term(t),
+            BoundArgument::Sequence(s) => self.sequence(s, Self::term),
+            BoundArgument::Bound(v) => self.component_var(v),
+            BoundArgument::BoundSeq(s) => self.sequence(s, Self::component_var),
+        }
+    }
+
+    fn component_var(&mut self, var: &ComponentVar) {
+        self.variable(&var.var);
+        self.option(var.tp.as_ref(), Self::term);
+        self.option(var.df.as_ref(), Self::term);
+    }
+
+    fn opaque_node(&mut self, node: &OpaqueNode) {
+        self.display(&node.tag);
+        self.len(node.attributes.len());
+        for (k, v) in &node.attributes {
+            self.display(k);
+            self.display(v);
+        }
+        self.len(node.children.len());
+        for c in &node.children {
+            match c {
+                AnyOpaque::Term(i) => {
+                    self.tag(0);
+                    self.bytes(&i.to_le_bytes());
+                }
+                AnyOpaque::Node(n) => {
+                    self.tag(1);
+                    self.opaque_node(n);
+                }
+                AnyOpaque::Text(t) => {
+                    self.tag(2);
+                    self.display(t);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        terms::{ApplicationTerm, Argument, Term, clear_term_cache, helpers::IntoTerm},
+        test_utils::{sym, var},
+    };
+
+    #[test]
+    fn stable_across_constructions() {
+        let x = var("x");
+        let helper = sym("plus").apply_tms([x.clone(), sym("zero").into()]);
+        let hash = helper.stable_hash();
+        // simulate a fresh session: the term cache is emptied and all terms rebuilt
+        drop(helper);
+        clear_term_cache();
+        let manual = Term::Application(ApplicationTerm::new(
+            Term::Symbol {
+                uri: sym("plus"),
+                presentation: None,
+            },
+            Box::new([
+                Argument::Simple(x),
+                Argument::Simple(Term::Symbol {
+                    uri: sym("zero"),
+                    presentation: None,
+                }),
+            ]),
+            None,
+        ));
+        assert_eq!(manual.stable_hash(), hash);
+
+        let other = sym("plus").apply_tms([sym("zero").into(), var("x")]);
+        assert_ne!(other.stable_hash(), hash);
+    }
+}
@@ -10,21 +10,42 @@ use crate::terms::{
 
 impl Term {
     pub fn modify(&self, mut f: impl FnMut(&Self) -> Option<Self>) -> Cow<'_, Self> {
-        self.modify_i(&mut f)
+        self.modify_i::<true, false>(&mut f)
     }
-    fn modify_i<'s>(&'s self, f: &mut impl FnMut(&Self) -> Option<Self>) -> Cow<'s, Self> {
-        let t = f(self).map_or(Cow::Borrowed(self), Cow::Owned);
-        match &*t {
+    /// Like [`modify`](Self::modify), but does not descend into the terms returned by `f`;
+    /// e.g. for replacing symbols by terms that may themselves contain replaced symbols.
+    pub fn replace_subterms(&self, mut f: impl FnMut(&Self) -> Option<Self>) -> Cow<'_, Self> {
+        self.modify_i::<false, false>(&mut f)
+    }
+    /// Like [`modify`](Self::modify), but bottom-up: `f` is called on a node only *after*
+    /// its subterms have been modified (i.e. on the node with the modified subterms), and
+    /// the terms returned by `f` are not descended into.
+    pub fn modify_bottom_up(&self, mut f: impl FnMut(&Self) -> Option<Self>) -> Cow<'_, Self> {
+        self.modify_i::<false, true>(&mut f)
+    }
+    fn modify_i<'s, const DEEP: bool, const POST: bool>(
+        &'s self,
+        f: &mut impl FnMut(&Self) -> Option<Self>,
+    ) -> Cow<'s, Self> {
+        let t = if POST {
+            Cow::Borrowed(self)
+        } else {
+            match f(self) {
+                Some(t) if !DEEP => return Cow::Owned(t),
+                t => t.map_or(Cow::Borrowed(self), Cow::Owned),
+            }
+        };
+        let t = match &*t {
             Self::Var { .. } | Self::Symbol { .. } | Self::Number(_) => t,
-            Self::Application(app) => match app.modify_i(f) {
+            Self::Application(app) => match app.modify_i::<DEEP, POST>(f) {
                 Cow::Owned(t) => Cow::Owned(Self::Application(t)),
                 Cow::Borrowed(_) => t,
             },
-            Self::Bound(app) => match app.modify_i(f) {
+            Self::Bound(app) => match app.modify_i::<DEEP, POST>(f) {
                 Cow::Owned(t) => Cow::Owned(Self::Bound(t)),
                 Cow::Borrowed(_) => t,
             },
-            Self::Field(fld) => match fld.record.modify_i(f) {
+            Self::Field(fld) => match fld.record.modify_i::<DEEP, POST>(f) {
                 Cow::Owned(t) => Cow::Owned(Self::Field(RecordFieldTerm::new(
                     t,
                     fld.key.clone(),
@@ -34,8 +55,8 @@ impl Term {
                 Cow::Borrowed(_) => t,
             },
             Self::Label { name, df, tp } => {
-                let ndf = df.as_ref().map(|t| t.modify_i(f));
-                let ntp = tp.as_ref().map(|t| t.modify_i(f));
+                let ndf = df.as_ref().map(|t| t.modify_i::<DEEP, POST>(f));
+                let ntp = tp.as_ref().map(|t| t.modify_i::<DEEP, POST>(f));
                 if ndf.as_ref().is_some_and(|t| matches!(t, Cow::Owned(_)))
                     || ntp.as_ref().is_some_and(|t| matches!(t, Cow::Owned(_)))
                 {
@@ -54,7 +75,7 @@ impl Term {
                     .terms
                     .iter()
                     .map(|t| {
-                        let r = t.modify_i(f);
+                        let r = t.modify_i::<DEEP, POST>(f);
                         if matches!(r, Cow::Owned(_)) {
                             changed = true;
                         }
@@ -70,16 +91,23 @@ impl Term {
                     t
                 }
             }
+        };
+        if POST && let Some(n) = f(&t) {
+            return Cow::Owned(n);
         }
+        t
     }
 }
 impl ApplicationTerm {
-    fn modify_i<'s>(&'s self, f: &mut impl FnMut(&Term) -> Option<Term>) -> Cow<'s, Self> {
+    fn modify_i<'s, const DEEP: bool, const POST: bool>(
+        &'s self,
+        f: &mut impl FnMut(&Term) -> Option<Term>,
+    ) -> Cow<'s, Self> {
         let mut changed = false;
-        let head = self.head.modify_i(f);
+        let head = self.head.modify_i::<DEEP, POST>(f);
         macro_rules! ch {
             ($e:expr) => {
-                match $e.modify_i(f) {
+                match $e.modify_i::<DEEP, POST>(f) {
                     Cow::Owned(t) => {
                         changed = true;
                         t
@@ -116,12 +144,15 @@ impl ApplicationTerm {
     }
 }
 impl BindingTerm {
-    fn modify_i<'s>(&'s self, f: &mut impl FnMut(&Term) -> Option<Term>) -> Cow<'s, Self> {
+    fn modify_i<'s, const DEEP: bool, const POST: bool>(
+        &'s self,
+        f: &mut impl FnMut(&Term) -> Option<Term>,
+    ) -> Cow<'s, Self> {
         let mut changed = false;
-        let head = self.head.modify_i(f);
+        let head = self.head.modify_i::<DEEP, POST>(f);
         macro_rules! ch {
             ($e:expr) => {
-                match $e.modify_i(f) {
+                match $e.modify_i::<DEEP, POST>(f) {
                     Cow::Owned(t) => {
                         changed = true;
                         t
@@ -166,9 +197,12 @@ impl BindingTerm {
     }
 }
 impl ComponentVar {
-    fn modify_i<'s>(&'s self, f: &mut impl FnMut(&Term) -> Option<Term>) -> Cow<'s, Self> {
-        let ndf = self.df.as_ref().map(|t| t.modify_i(f));
-        let ntp = self.tp.as_ref().map(|t| t.modify_i(f));
+    fn modify_i<'s, const DEEP: bool, const POST: bool>(
+        &'s self,
+        f: &mut impl FnMut(&Term) -> Option<Term>,
+    ) -> Cow<'s, Self> {
+        let ndf = self.df.as_ref().map(|t| t.modify_i::<DEEP, POST>(f));
+        let ntp = self.tp.as_ref().map(|t| t.modify_i::<DEEP, POST>(f));
         if ndf.as_ref().is_some_and(|t| matches!(t, Cow::Owned(_)))
             || ntp.as_ref().is_some_and(|t| matches!(t, Cow::Owned(_)))
         {
@@ -565,6 +565,55 @@ impl Term {
         super::debug::Short(self)
     }
 
+    /// The number of nodes in this term, i.e. this term and all its (transitive) subterms.
+    ///
+    /// Every [`Symbol`](Term::Symbol), [`Var`](Term::Var), [`Number`](Term::Number) etc. counts
+    /// as one, as does every [`Application`](Term::Application), [`Bound`](Term::Bound) etc.
+    /// *in addition to* its head and arguments (each element of a sequence argument counting
+    /// separately). Variables *bound* by a [`Bound`](Term::Bound) term are not terms and hence
+    /// not counted. Computed without recursion, so safe on arbitrarily deep terms.
+    #[must_use]
+    pub fn size(&self) -> usize {
+        use crate::utils::RefTree;
+        1 + self.dfs().count()
+    }
+
+    /// The depth of this term, where terms without subterms have depth `1` (counting as in
+    /// [`size`](Self::size)). Computed without recursion, so safe on arbitrarily deep terms.
+    #[must_use]
+    pub fn depth(&self) -> usize {
+        use crate::utils::RefTree;
+        let mut max = 0;
+        let mut stack = vec![(self, 1)];
+        while let Some((t, depth)) = stack.pop() {
+            max = max.max(depth);
+            stack.extend(t.tree_children().map(|c| (c, depth + 1)));
+        }
+        max
+    }
+
+    /// The type this term is annotated with, if any.
+    ///
+    /// Only [`Label`](Term::Label)s carry a type annotation of their own - extracted from a
+    /// `data-ftml-type` child of the `OML` node. The types of symbols and variables are part
+    /// of their *declarations* (see [`Symbol`](crate::domain::declarations::symbols::Symbol)
+    /// and [`VariableDeclaration`](crate::narrative::elements::VariableDeclaration)), so
+    /// references to them return `None`.
+    #[inline]
+    #[must_use]
+    pub fn declared_type(&self) -> Option<&Self> {
+        match self {
+            Self::Label { tp, .. } => tp.as_deref(),
+            Self::Symbol { .. }
+            | Self::Var { .. }
+            | Self::Application(_)
+            | Self::Bound(_)
+            | Self::Field(_)
+            | Self::Opaque(_)
+            | Self::Number(_) => None,
+        }
+    }
+
     #[must_use]
     pub fn with_presentation(self, pres: VarOrSym) -> Self {
         match self {
@@ -814,3 +863,45 @@ impl deepsize::DeepSizeOf for Term {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::terms::{Term, helpers::IntoTerm};
+    use ftml_uris::{Id, SymbolUri};
+
+    #[test]
+    fn term_size_and_depth() {
+        let f: SymbolUri = "http://example.com?a=archive&m=module&s=f"
+            .parse()
+            .expect("valid uri");
+        let x: Term = "x".parse::<Id>().expect("valid id").into();
+        assert_eq!(x.size(), 1);
+        assert_eq!(x.depth(), 1);
+
+        // f(f(x), x)
+        let term = f.clone().apply_tms([f.apply_tms([x.clone()]), x]);
+        assert_eq!(term.size(), 6);
+        assert_eq!(term.depth(), 3);
+    }
+
+    #[test]
+    fn declared_type() {
+        let nat: SymbolUri = "http://example.com?a=archive&m=module&s=Nat"
+            .parse()
+            .expect("valid uri");
+        let label = Term::Label {
+            name: "n".parse().expect("valid name"),
+            df: None,
+            tp: Some(Box::new(nat.clone().into())),
+        };
+        assert_eq!(label.declared_type(), Some(&Term::from(nat.clone())));
+
+        let untyped = Term::Label {
+            name: "m".parse().expect("valid name"),
+            df: None,
+            tp: None,
+        };
+        assert_eq!(untyped.declared_type(), None);
+        assert_eq!(Term::from(nat).declared_type(), None);
+    }
+}
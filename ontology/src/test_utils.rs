@@ -0,0 +1,69 @@
+//! Fixtures shared by the tests in this crate.
+
+use crate::{
+    narrative::elements::notations::{Notation, NotationComponent, NotationMap, NotationResolver},
+    terms::{ArgumentMode, Term, Variable},
+};
+use ftml_uris::{Id, LeafUri, SymbolUri};
+
+/// The symbol `name` in `http://example.com?a=archive&m=module`
+pub fn sym(name: &str) -> SymbolUri {
+    format!("http://example.com?a=archive&m=module&s={name}")
+        .parse()
+        .expect("valid uri")
+}
+
+/// The variable `name`
+pub fn variable(name: &str) -> Variable {
+    name.parse::<Id>().expect("valid id").into()
+}
+
+/// The variable `name` as a [`Term`]
+pub fn var(name: &str) -> Term {
+    variable(name).into()
+}
+
+/// The node `<tag>children</tag>`
+pub fn node(tag: &str, children: Vec<NotationComponent>) -> NotationComponent {
+    NotationComponent::Node {
+        tag: tag.parse().expect("valid id"),
+        attributes: Box::default(),
+        children: children.into_boxed_slice(),
+    }
+}
+
+/// The binary notation `<mrow>a op b</mrow>` with precedence `100`
+pub fn infix(op: NotationComponent, argprecs: [i64; 2]) -> Notation {
+    let arg = |index| NotationComponent::Argument {
+        index,
+        mode: ArgumentMode::Simple,
+    };
+    Notation {
+        precedence: 100,
+        id: None,
+        argprecs: argprecs.to_vec(),
+        component: node("mrow", vec![arg(0), op, arg(1)]),
+        op: None,
+    }
+}
+
+/// A [`NotationResolver`] with fixed notations and (sTeX) macro names
+#[derive(Default)]
+pub struct Resolver {
+    pub notations: NotationMap,
+    pub macros: Vec<(SymbolUri, &'static str)>,
+}
+impl NotationResolver for Resolver {
+    fn notation(&self, leaf: &LeafUri, pref: Option<Id>) -> Option<&Notation> {
+        self.notations.notation(leaf, pref)
+    }
+    fn notation_for_arity(&self, leaf: &LeafUri, arity: usize) -> Option<&Notation> {
+        self.notations.notation_for_arity(leaf, arity)
+    }
+    fn macro_name(&self, leaf: &LeafUri) -> Option<&str> {
+        self.macros
+            .iter()
+            .find(|(s, _)| matches!(leaf, LeafUri::Symbol(l) if l == s))
+            .map(|(_, m)| *m)
+    }
+}
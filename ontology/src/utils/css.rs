@@ -51,6 +51,20 @@ impl Ord for Css {
     }
 }
 impl Css {
+    /// Resolves a [`Link`](Self::Link) to [`Inline`](Self::Inline) CSS by `fetch`ing the
+    /// stylesheet at its URL, e.g. for self-contained exports; [`Inline`](Self::Inline) and
+    /// [`Class`](Self::Class) CSS is returned unchanged.
+    ///
+    /// If `fetch` returns `None` (i.e. the stylesheet could not be retrieved), the link is
+    /// kept as is.
+    #[must_use]
+    pub fn inline(&self, fetch: impl Fn(&str) -> Option<Box<str>>) -> Self {
+        match self {
+            Self::Link(url) => fetch(url).map_or_else(|| self.clone(), Self::Inline),
+            _ => self.clone(),
+        }
+    }
+
     #[cfg(feature = "css_normalize")]
     pub fn merge(v: Vec<Self>) -> Vec<Self> {
         use lightningcss::traits::ToCss;
@@ -195,3 +209,25 @@ impl Css {
     }
      */
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Css;
+
+    #[test]
+    fn inlining_links() {
+        let fetch = |url: &str| (url == "https://example.com/a.css").then(|| ".a{}".into());
+        assert_eq!(
+            Css::Link("https://example.com/a.css".into()).inline(fetch),
+            Css::Inline(".a{}".into())
+        );
+        assert_eq!(
+            Css::Link("https://example.com/b.css".into()).inline(fetch),
+            Css::Link("https://example.com/b.css".into())
+        );
+        assert_eq!(
+            Css::Inline(".b{}".into()).inline(fetch),
+            Css::Inline(".b{}".into())
+        );
+    }
+}
@@ -12,6 +12,8 @@ mod hexable;
 pub use hexable::*;
 mod css;
 pub use css::*;
+mod numbers;
+pub use numbers::NumberFormat;
 
 use crate::domain::declarations::symbols::ArgumentSpec;
 //pub mod awaitable;
@@ -329,6 +331,11 @@ impl Permutation {
         Ok(unsafe { self.apply_unchecked(arguments) })
     }
 
+    #[inline]
+    pub(crate) fn indices(&self) -> &[u8] {
+        &self.0
+    }
+
     #[allow(clippy::cast_possible_truncation)]
     #[must_use]
     pub fn of(&self, num: u8) -> Option<u8> {
@@ -0,0 +1,90 @@
+use ftml_uris::Language;
+
+/// Formats (non-integral) numbers such as [`Problem::points`](crate::narrative::elements::Problem::points)
+/// or scores for display.
+///
+/// Numbers are rounded to at most [`decimals`](Self::decimals) decimal places (default:
+/// [`NumberFormat::DEFAULT_DECIMALS`]), with trailing zeros removed - so `1.0` is displayed as
+/// `1` and `0.3f32` as `0.3` (rather than `0.30000001`). The decimal separator depends on the
+/// [`Language`] (see [`NumberFormat::decimal_separator`]); digits are always Latin.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NumberFormat {
+    pub language: Language,
+    pub decimals: u8,
+}
+impl Default for NumberFormat {
+    #[inline]
+    fn default() -> Self {
+        Self::new(Language::default())
+    }
+}
+impl NumberFormat {
+    /// The default number of decimal places
+    pub const DEFAULT_DECIMALS: u8 = 2;
+
+    #[inline]
+    #[must_use]
+    pub const fn new(language: Language) -> Self {
+        Self {
+            language,
+            decimals: Self::DEFAULT_DECIMALS,
+        }
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn with_decimals(mut self, decimals: u8) -> Self {
+        self.decimals = decimals;
+        self
+    }
+
+    /// The decimal separator used for the given language (following the
+    /// [CLDR](https://cldr.unicode.org/) data for Latin digits): `.` for English and
+    /// Arabic, `,` for all other supported languages.
+    #[must_use]
+    pub const fn decimal_separator(language: Language) -> char {
+        match language {
+            Language::English | Language::Arabic => '.',
+            _ => ',',
+        }
+    }
+
+    #[must_use]
+    pub fn format(&self, value: f32) -> String {
+        // the detour via f64 avoids f32-specific rounding artifacts in the last
+        // displayed digit
+        let mut s = format!("{:.*}", self.decimals as usize, f64::from(value));
+        if s.contains('.') {
+            let len = s.trim_end_matches('0').trim_end_matches('.').len();
+            s.truncate(len);
+        }
+        if s == "-0" {
+            s.remove(0);
+        }
+        let sep = Self::decimal_separator(self.language);
+        if sep != '.' {
+            s = s.replace('.', sep.encode_utf8(&mut [0; 4]));
+        }
+        s
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::NumberFormat;
+    use ftml_uris::Language;
+
+    #[test]
+    fn number_format() {
+        let en = NumberFormat::default();
+        assert_eq!(en.format(0.3), "0.3");
+        assert_eq!(en.format(1.0), "1");
+        assert_eq!(en.format(0.5), "0.5");
+        assert_eq!(en.format(2.0 / 3.0), "0.67");
+        assert_eq!(en.format(-0.001), "0");
+        assert_eq!(en.with_decimals(0).format(2.4), "2");
+        assert_eq!(en.with_decimals(4).format(0.1 + 0.2), "0.3");
+        assert_eq!(NumberFormat::new(Language::German).format(1.25), "1,25");
+        assert_eq!(NumberFormat::new(Language::German).format(3.0), "3");
+    }
+}
@@ -146,6 +146,7 @@ pub trait FtmlExtractor: 'static + Sized {
                 | OpenNarrativeElement::Problem { uri, .. }
                 | OpenNarrativeElement::VariableDeclaration { uri, .. }
                 | OpenNarrativeElement::Slide { uri, .. }
+                | OpenNarrativeElement::Figure { uri, .. }
                 | OpenNarrativeElement::Paragraph { uri, .. } => Some(uri),
             })
             .map_or_else(
@@ -172,6 +173,7 @@ pub trait FtmlExtractor: 'static + Sized {
                 | OpenNarrativeElement::Paragraph { .. }
                 | OpenNarrativeElement::Definiendum(_)
                 | OpenNarrativeElement::Slide { .. }
+                | OpenNarrativeElement::Figure { .. }
                 | OpenNarrativeElement::FillinSol { .. }
                 | OpenNarrativeElement::ProblemHint
                 | OpenNarrativeElement::ProblemExNote
@@ -240,15 +242,13 @@ pub trait FtmlExtractor: 'static + Sized {
     }
 
     fn resolve_variable_name(&self, name: Id) -> Variable {
-        fn ew(a: &UriName, b: &Id) -> bool {
-            let mut steps = a.steps().rev();
-            for s in b.as_ref().split('/').rev() {
-                if steps.next() != Some(s) {
-                    return false;
-                }
-            }
-            true
-        }
+        // an id that is not a valid name can not be the suffix of one either
+        let Ok(suffix) = name.as_ref().parse::<UriName>() else {
+            return Variable::Name {
+                name,
+                notated: None,
+            };
+        };
         for n in self.iterate_narrative() {
             let ch = match n {
                 OpenNarrativeElement::Module { children, .. }
@@ -257,6 +257,7 @@ pub trait FtmlExtractor: 'static + Sized {
                 | OpenNarrativeElement::Section { children, .. }
                 | OpenNarrativeElement::SkipSection { children }
                 | OpenNarrativeElement::Slide { children, .. }
+                | OpenNarrativeElement::Figure { children, .. }
                 | OpenNarrativeElement::Problem { children, .. }
                 | OpenNarrativeElement::Paragraph { children, .. } => children,
                 OpenNarrativeElement::Invisible
@@ -284,7 +285,7 @@ pub trait FtmlExtractor: 'static + Sized {
             for c in ch.iter().rev() {
                 match c {
                     DocumentElement::VariableDeclaration(VariableDeclaration { uri, data })
-                        if ew(uri.name(), &name) =>
+                        if uri.name().ends_with(&suffix) =>
                     {
                         return Variable::Ref {
                             declaration: uri.clone(),
@@ -298,7 +299,7 @@ pub trait FtmlExtractor: 'static + Sized {
         for c in self.iterate_dones().rev() {
             match c {
                 DocumentElement::VariableDeclaration(VariableDeclaration { uri, data })
-                    if ew(uri.name(), &name) =>
+                    if uri.name().ends_with(&suffix) =>
                 {
                     return Variable::Ref {
                         declaration: uri.clone(),
@@ -314,6 +315,46 @@ pub trait FtmlExtractor: 'static + Sized {
         }
     }
 
+    /// The most recently completed narrative element, i.e. the last child of the innermost
+    /// open narrative container (or the last top-level element, if there is none).
+    fn last_element(&self) -> Option<&DocumentElement> {
+        for e in self.iterate_narrative() {
+            match e {
+                OpenNarrativeElement::Invisible
+                | OpenNarrativeElement::Definiendum(_)
+                | OpenNarrativeElement::FoldExpr(_)
+                | OpenNarrativeElement::FoldExprShort
+                | OpenNarrativeElement::ProofStep { .. } => (),
+                OpenNarrativeElement::Module { children, .. }
+                | OpenNarrativeElement::MathStructure { children, .. }
+                | OpenNarrativeElement::Morphism { children, .. }
+                | OpenNarrativeElement::Section { children, .. }
+                | OpenNarrativeElement::Paragraph { children, .. }
+                | OpenNarrativeElement::Slide { children, .. }
+                | OpenNarrativeElement::Figure { children, .. }
+                | OpenNarrativeElement::Problem { children, .. }
+                | OpenNarrativeElement::SkipSection { children } => return children.last(),
+                OpenNarrativeElement::Notation { .. }
+                | OpenNarrativeElement::Solution(..)
+                | OpenNarrativeElement::NotationComp { .. }
+                | OpenNarrativeElement::ArgSep { .. }
+                | OpenNarrativeElement::VariableDeclaration { .. }
+                | OpenNarrativeElement::FillinSol { .. }
+                | OpenNarrativeElement::ProblemHint
+                | OpenNarrativeElement::ProblemExNote
+                | OpenNarrativeElement::ProblemGradingNote(_)
+                | OpenNarrativeElement::AnswerClass { .. }
+                | OpenNarrativeElement::ChoiceBlock { .. }
+                | OpenNarrativeElement::ProblemChoice { .. }
+                | OpenNarrativeElement::ProblemChoiceVerdict
+                | OpenNarrativeElement::ProblemChoiceFeedback
+                | OpenNarrativeElement::FillinSolCase(_)
+                | OpenNarrativeElement::NotationArg(_) => return None,
+            }
+        }
+        self.iterate_dones().next_back()
+    }
+
     fn last_paragraph(&self) -> Option<&LogicalParagraph> {
         for e in self.iterate_narrative() {
             match e {
@@ -328,6 +369,7 @@ pub trait FtmlExtractor: 'static + Sized {
                 | OpenNarrativeElement::Section { children, .. }
                 | OpenNarrativeElement::Paragraph { children, .. }
                 | OpenNarrativeElement::Slide { children, .. }
+                | OpenNarrativeElement::Figure { children, .. }
                 | OpenNarrativeElement::Problem { children, .. }
                 | OpenNarrativeElement::SkipSection { children } => match children.last() {
                     Some(DocumentElement::Paragraph(p)) => return Some(p),
@@ -633,6 +675,8 @@ pub enum FtmlExtractionError {
     InvalidNotationComponent(String),
     #[error("error encoding data for {0}: {1}")]
     EncodingError(FtmlKey, String),
+    #[error("unknown ftml attribute: `{0}`")]
+    UnknownKey(String),
 }
 impl From<(FtmlKey, Self)> for FtmlExtractionError {
     #[inline]
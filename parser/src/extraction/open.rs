@@ -10,6 +10,7 @@ use ftml_ontology::{
         documents::{DocumentCounter, DocumentKind, DocumentStyle},
         elements::{
             DocumentElement,
+            figures::FigureKind,
             notations::{NotationComponent, NotationNode},
             paragraphs::{ParagraphFormatting, ParagraphKind, ParagraphStep, ParagraphStepKind},
             problems::{
@@ -70,6 +71,8 @@ pub enum CloseFtmlElement {
     Morphism,
     Assign,
     Slide,
+    Figure,
+    FigureCaption,
     Problem,
     Solution,
     FillinSol,
@@ -301,12 +304,18 @@ pub enum OpenNarrativeElement<N: FtmlNode> {
     Solution(Option<Id>),
     NotationArg(ArgumentPosition),
     Invisible,
-    Definiendum(SymbolUri),
+    Definiendum(Vec<SymbolUri>),
     Slide {
         uri: DocumentElementUri,
         children: Vec<DocumentElement>,
         title: Option<Box<str>>,
     },
+    Figure {
+        uri: DocumentElementUri,
+        kind: FigureKind,
+        children: Vec<DocumentElement>,
+        caption: Option<Box<str>>,
+    },
     FillinSol {
         width: Option<f32>,
         cases: Vec<FillInSolOption>,
@@ -345,6 +354,7 @@ pub enum MetaDatum {
     InputRef {
         target: DocumentUri,
         uri: DocumentElementUri,
+        anchor: Option<DocumentElementUri>,
     },
     IfInputref(bool),
     SetSectionLevel(SectionLevel),
@@ -952,6 +962,15 @@ impl OpenFtmlElement {
                     title: None,
                 }),
             },
+            Self::Figure { kind, uri } => AnyOpen::Open {
+                domain: None,
+                narrative: Some(OpenNarrativeElement::Figure {
+                    uri,
+                    kind,
+                    children: Vec::new(),
+                    caption: None,
+                }),
+            },
             Self::Invisible => AnyOpen::Open {
                 domain: None,
                 narrative: Some(OpenNarrativeElement::Invisible),
@@ -1180,9 +1199,11 @@ impl OpenFtmlElement {
             Self::InputRef {
                 target: uri,
                 uri: id,
+                anchor,
             } => AnyOpen::Meta(MetaDatum::InputRef {
                 target: uri,
                 uri: id,
+                anchor,
             }),
             Self::ProofBody => AnyOpen::Meta(MetaDatum::ProofBody),
             Self::AnswerClassFeedback => AnyOpen::Meta(MetaDatum::AnswerClassFeedback),
@@ -1208,6 +1229,7 @@ impl OpenFtmlElement {
             | Self::ProblemTitle
             | Self::SlideTitle
             | Self::SlideNumber
+            | Self::FigureCaption
             | Self::CurrentSectionLevel(_) => AnyOpen::None,
         }
     }
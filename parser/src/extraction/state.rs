@@ -24,6 +24,7 @@ use ftml_ontology::{
         elements::{
             DocumentElement, DocumentTerm, LogicalParagraph, Notation, Problem, Section,
             SectionLevel, Slide, VariableDeclaration,
+            figures::{Figure, FigureKind},
             notations::{
                 NotationComponent, NotationNode, NotationReference, VariableNotationReference,
             },
@@ -47,78 +48,127 @@ use ftml_uris::{
 };
 use std::{borrow::Cow, hint::unreachable_unchecked, mem::MaybeUninit, num::NonZeroU8};
 
-#[derive(Debug)]
-pub struct IdCounter {
+/// Generates the names of elements (sections, paragraphs, notations, ...) that are not given
+/// an explicit id (via [`FtmlKey::Id`] et al.) in the document.
+///
+/// #### Contract
+/// - [`new_id`](Self::new_id) must return a valid single name step, i.e. a non-empty string
+///   that parses as an [`Id`] and contains no `/` (otherwise extracting the element fails
+///   with an [`FtmlExtractionError`]).
+/// - The ids returned for the same `prefix` must be distinct from each other *and* from all
+///   ids previously passed to [`update`](Self::update) for that prefix, since they become
+///   the (last step of the) [`DocumentElementUri`]s of siblings.
+/// - The `prefix` `EXTSTRUCT` is used for conservative structure extensions, which are
+///   recognized by their name starting with `EXTSTRUCT`; ids for it must keep that prefix.
+///
+/// Everything else - e.g. whether ids are deterministic (like the default
+/// [`PrefixCounter`]) or globally unique - is up to the implementation.
+///
+/// Strategies need to be [`Clone`] (which provides [`CloneIdStrategy`]), so that [`IdCounter`]
+/// is.
+pub trait IdStrategy: std::fmt::Debug + Send + Sync + CloneIdStrategy {
+    /// A fresh id for an element of the kind `prefix` (e.g. `section`, `notation`, or the
+    /// name of the [`ParagraphKind`]).
+    fn new_id(&mut self, prefix: &str) -> String;
+    /// Informs the strategy that the document explicitly uses the id `value` for an element
+    /// of the kind `prefix`. Does nothing by default.
+    #[allow(unused_variables)]
+    fn update(&mut self, prefix: &str, value: &Id) {}
+}
+
+/// Object-safe [`Clone`] for [`IdStrategy`]s; implemented for all that are [`Clone`].
+pub trait CloneIdStrategy {
+    fn clone_box(&self) -> Box<dyn IdStrategy>;
+}
+impl<S: IdStrategy + Clone + 'static> CloneIdStrategy for S {
+    #[inline]
+    fn clone_box(&self) -> Box<dyn IdStrategy> {
+        Box::new(self.clone())
+    }
+}
+
+/// The default [`IdStrategy`]: the first element of each kind `prefix` is named `prefix`,
+/// subsequent ones `prefix_1`, `prefix_2` etc. - skipping past explicitly given ids of the
+/// form `prefix_N`.
+#[derive(Debug, Clone)]
+pub struct PrefixCounter {
     inner: rustc_hash::FxHashMap<Cow<'static, str>, u32>,
-    forced: std::sync::Mutex<Option<DocumentElementUri>>,
 }
-impl Default for IdCounter {
+impl Default for PrefixCounter {
     fn default() -> Self {
         let mut inner = rustc_hash::FxHashMap::default();
         inner.insert("EXTSTRUCT".into(), 0);
-        Self {
-            inner,
-            forced: std::sync::Mutex::new(None),
+        Self { inner }
+    }
+}
+impl IdStrategy for PrefixCounter {
+    fn update(&mut self, prefix: &str, value: &Id) {
+        let value = value.as_ref();
+        if let Some(rest) = value.strip_prefix(prefix) {
+            if rest.is_empty() {
+                // should be fine if already present
+                self.inner.entry(prefix.to_string().into()).or_insert(0);
+            }
+        } else if let Some(rest) = value.strip_prefix('_')
+            && let Ok(value) = rest.parse::<u32>()
+        {
+            let v = self.inner.entry(prefix.to_string().into()).or_insert(value);
+            *v = (*v).max(value);
         }
     }
+    fn new_id(&mut self, prefix: &str) -> String {
+        use std::collections::hash_map::Entry;
+        match self.inner.entry(prefix.to_string().into()) {
+            Entry::Occupied(mut e) => {
+                *e.get_mut() += 1;
+                format!("{}_{}", e.key(), e.get())
+            }
+            Entry::Vacant(e) => {
+                let r = e.key().to_string();
+                e.insert(0);
+                r
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct IdCounter {
+    strategy: Box<dyn IdStrategy>,
+    forced: std::sync::Mutex<Option<DocumentElementUri>>,
+}
+impl Default for IdCounter {
+    #[inline]
+    fn default() -> Self {
+        Self::new(PrefixCounter::default())
+    }
 }
 impl Clone for IdCounter {
     fn clone(&self) -> Self {
         Self {
-            inner: self.inner.clone(),
+            strategy: self.strategy.clone_box(),
             forced: std::sync::Mutex::new(self.forced.lock().ok().and_then(|mut e| e.take())),
         }
     }
 }
 impl IdCounter {
+    #[must_use]
+    pub fn new(strategy: impl IdStrategy + 'static) -> Self {
+        Self {
+            strategy: Box::new(strategy),
+            forced: std::sync::Mutex::new(None),
+        }
+    }
     pub fn forced(&mut self) -> Option<DocumentElementUri> {
         self.forced.lock().ok().and_then(|mut e| e.take())
     }
+    #[inline]
     pub fn update(&mut self, prefix: impl Into<Cow<'static, str>>, value: &Id) {
-        fn update_i(slf: &mut IdCounter, prefix: Cow<'static, str>, value: &Id) {
-            use std::collections::hash_map::Entry;
-            let value = value.as_ref();
-            if let Some(rest) = value.strip_prefix(&*prefix) {
-                if rest.is_empty() {
-                    match slf.inner.entry(prefix) {
-                        Entry::Occupied(_) => {
-                            // should be fine
-                        }
-                        Entry::Vacant(e) => {
-                            e.insert(0);
-                        }
-                    }
-                }
-            } else if let Some(rest) = value.strip_prefix('_')
-                && let Ok(value) = rest.parse::<u32>()
-            {
-                match slf.inner.entry(prefix) {
-                    Entry::Occupied(mut e) => {
-                        let v = e.get_mut();
-                        *v = (*v).max(value);
-                    }
-                    Entry::Vacant(e) => {
-                        e.insert(value);
-                    }
-                }
-            }
-        }
-        update_i(self, prefix.into(), value);
+        self.strategy.update(&prefix.into(), value);
     }
+    #[inline]
     pub fn new_id(&mut self, prefix: impl Into<Cow<'static, str>>) -> String {
-        use std::collections::hash_map::Entry;
-        let prefix = prefix.into();
-        match self.inner.entry(prefix) {
-            Entry::Occupied(mut e) => {
-                *e.get_mut() += 1;
-                format!("{}_{}", e.key(), e.get())
-            }
-            Entry::Vacant(e) => {
-                let r = e.key().to_string();
-                e.insert(0);
-                r
-            }
-        }
+        self.strategy.new_id(&prefix.into())
     }
 }
 
@@ -140,12 +190,11 @@ impl<T> StackVec<T> {
     const fn last_mut(&mut self) -> Option<&mut T> {
         self.last.as_mut()
     }
+    /// Iterates over the elements from the top of the stack (i.e. the most recently pushed
+    /// element) to the bottom.
+    #[inline]
     pub fn iter(&self) -> impl Iterator<Item = &T> {
-        use either::Either::{Left, Right};
-        self.last.as_ref().map_or_else(
-            || Right(std::iter::empty()),
-            |l| Left(std::iter::once(l).chain(self.rest.iter().rev())),
-        )
+        self.into_iter()
     }
     pub(crate) fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
         use either::Either::{Left, Right};
@@ -156,7 +205,8 @@ impl<T> StackVec<T> {
         }
     }
     #[inline]
-    const fn is_empty(&self) -> bool {
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
         self.last.is_none()
     }
 
@@ -169,6 +219,52 @@ impl<T> StackVec<T> {
     pub fn pop(&mut self) -> Option<T> {
         std::mem::replace(&mut self.last, self.rest.pop())
     }
+
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.rest.len() + usize::from(self.last.is_some())
+    }
+}
+impl<'a, T> IntoIterator for &'a StackVec<T> {
+    type Item = &'a T;
+    type IntoIter =
+        std::iter::Chain<std::option::Iter<'a, T>, std::iter::Rev<std::slice::Iter<'a, T>>>;
+    /// See [`StackVec::iter`].
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.last.iter().chain(self.rest.iter().rev())
+    }
+}
+
+/// What to do with `data-ftml-*` attributes that are not known [`FtmlKey`]s (e.g. because
+/// the document was produced by a newer version).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum UnknownKeyPolicy {
+    /// Silently ignore the attribute.
+    #[default]
+    Ignore,
+    /// Log a warning and otherwise ignore the attribute.
+    Warn,
+    /// Report an [`UnknownKey`](FtmlExtractionError::UnknownKey) error.
+    Error,
+}
+
+/// Statistics about the extraction of a document, accumulated in
+/// [`ExtractorState::stats`]; e.g. for logging or monitoring build times.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct ExtractionStats {
+    /// The number of FTML elements added to the state, i.e. all successfully applied
+    /// `data-ftml-*` rules that have any effect (including those that only set metadata).
+    pub elements: usize,
+    /// The number of errors reported by the driver of the extraction (unknown keys,
+    /// failing rules and unexpected or invalid closing elements).
+    pub errors: usize,
+    /// The number of notations found (for symbols and variables).
+    pub notations: usize,
+    /// The (wall-clock) time the extraction took; only measured by drivers that can do so
+    /// (e.g. `ftml5ever::run`), and zero otherwise.
+    pub duration: std::time::Duration,
 }
 
 pub struct ExtractorState<N: FtmlNode + std::fmt::Debug> {
@@ -185,6 +281,8 @@ pub struct ExtractorState<N: FtmlNode + std::fmt::Debug> {
     pub narrative: StackVec<OpenNarrativeElement<N>>,
     pub kind: DocumentKind,
     pub current_source_range: SourceRange,
+    pub unknown_keys: UnknownKeyPolicy,
+    pub stats: ExtractionStats,
     top_section_level: Option<SectionLevel>,
     pub(crate) last_term: Option<Term>,
     pub(crate) ids: IdCounter,
@@ -194,6 +292,16 @@ pub struct ExtractorState<N: FtmlNode + std::fmt::Debug> {
     rdf: Vec<ulo::rdf_types::Triple>,
 }
 
+/// The result of extracting a document.
+///
+/// Implements [`bincode::Encode`]/[`bincode::Decode`] (prefixed with a
+/// [`VersionHeader`](ftml_ontology::narrative::VersionHeader)), so that build tools can cache
+/// extraction results instead of re-parsing; see [`encode_to_vec`](Self::encode_to_vec) and
+/// [`decode_from_slice`](Self::decode_from_slice). The encoding is only stable within the
+/// same [`VersionHeader::VERSION`](ftml_ontology::narrative::VersionHeader::VERSION);
+/// decoding a result encoded with a different version fails. RDF triples are *not*
+/// persisted (they can be recomputed from the document and modules) and are empty after
+/// decoding.
 #[derive(Debug)]
 pub struct ExtractionResult {
     pub document: Document,
@@ -203,19 +311,73 @@ pub struct ExtractionResult {
     pub triples: Vec<ulo::rdf_types::Triple>,
     pub notations: Vec<(LeafUri, DocumentElementUri, Notation)>,
 }
+impl ExtractionResult {
+    /// ### Errors
+    #[inline]
+    pub fn encode_to_vec(&self) -> Result<Vec<u8>, bincode::error::EncodeError> {
+        bincode::encode_to_vec(self, bincode::config::standard())
+    }
+
+    /// ### Errors
+    /// if `bytes` is not an encoded extraction result of the current
+    /// [`VersionHeader::VERSION`](ftml_ontology::narrative::VersionHeader::VERSION).
+    #[inline]
+    pub fn decode_from_slice(bytes: &[u8]) -> Result<Self, bincode::error::DecodeError> {
+        bincode::decode_from_slice(bytes, bincode::config::standard()).map(|(r, _)| r)
+    }
+}
+impl bincode::Encode for ExtractionResult {
+    fn encode<E: bincode::enc::Encoder>(
+        &self,
+        encoder: &mut E,
+    ) -> Result<(), bincode::error::EncodeError> {
+        use bincode::Encode;
+        ftml_ontology::narrative::VersionHeader.encode(encoder)?;
+        self.document.encode(encoder)?;
+        self.modules.encode(encoder)?;
+        self.data.encode(encoder)?;
+        self.notations.encode(encoder)
+    }
+}
+impl<Context> bincode::Decode<Context> for ExtractionResult {
+    fn decode<D: bincode::de::Decoder<Context = Context>>(
+        decoder: &mut D,
+    ) -> Result<Self, bincode::error::DecodeError> {
+        use bincode::Decode;
+        let _: ftml_ontology::narrative::VersionHeader = Decode::decode(decoder)?;
+        Ok(Self {
+            document: Decode::decode(decoder)?,
+            modules: Decode::decode(decoder)?,
+            data: Decode::decode(decoder)?,
+            #[cfg(feature = "rdf")]
+            triples: Vec::new(),
+            notations: Decode::decode(decoder)?,
+        })
+    }
+}
 
 #[allow(unused_variables)]
 impl<N: FtmlNode + std::fmt::Debug> ExtractorState<N> {
     #[inline]
     #[must_use]
-    #[allow(clippy::missing_const_for_fn)]
     pub fn new(document: DocumentUri, do_rdf: bool) -> Self {
+        Self::with_id_strategy(document, do_rdf, PrefixCounter::default())
+    }
+
+    /// Like [`new`](Self::new), but generating the names of elements without explicit ids
+    /// with the given [`IdStrategy`] instead of the default [`PrefixCounter`].
+    #[must_use]
+    pub fn with_id_strategy(
+        document: DocumentUri,
+        do_rdf: bool,
+        ids: impl IdStrategy + 'static,
+    ) -> Self {
         Self {
             do_rdf,
             document,
             top_section_level: None,
             title: None,
-            ids: IdCounter::default(),
+            ids: IdCounter::new(ids),
             counters: Vec::new(),
             styles: Vec::new(),
             notations: Vec::new(),
@@ -228,11 +390,45 @@ impl<N: FtmlNode + std::fmt::Debug> ExtractorState<N> {
             narrative: StackVec::default(),
             last_term: None,
             current_source_range: SourceRange::DEFAULT,
+            unknown_keys: UnknownKeyPolicy::Ignore,
+            stats: ExtractionStats::default(),
             #[cfg(feature = "rdf")]
             rdf: Vec::new(),
         }
     }
 
+    /// The currently open [`OpenDomainElement`]s, innermost first.
+    ///
+    /// For diagnostics only (e.g. to report the nesting when an element is closed in an
+    /// unexpected place); the extraction itself does not depend on them being inspected.
+    #[inline]
+    pub fn open_domain_elements(&self) -> impl Iterator<Item = &OpenDomainElement<N>> {
+        self.domain.iter()
+    }
+
+    /// The currently open [`OpenNarrativeElement`]s, innermost first; for diagnostics only
+    /// (see [`open_domain_elements`](Self::open_domain_elements)).
+    #[inline]
+    pub fn open_narrative_elements(&self) -> impl Iterator<Item = &OpenNarrativeElement<N>> {
+        self.narrative.iter()
+    }
+
+    /// Handles a `data-ftml-*` attribute `attr` that is not a known [`FtmlKey`] according
+    /// to [`unknown_keys`](Self::unknown_keys).
+    ///
+    /// ### Errors
+    /// iff the policy is [`UnknownKeyPolicy::Error`].
+    pub fn unknown_key(&self, attr: &str) -> Result<(), FtmlExtractionError> {
+        match self.unknown_keys {
+            UnknownKeyPolicy::Ignore => Ok(()),
+            UnknownKeyPolicy::Warn => {
+                tracing::warn!("Unknown ftml attribute: {attr}");
+                Ok(())
+            }
+            UnknownKeyPolicy::Error => Err(FtmlExtractionError::UnknownKey(attr.to_string())),
+        }
+    }
+
     pub fn set_next_uri(&mut self, uri: DocumentElementUri) {
         if let Ok(mut e) = self.ids.forced.lock() {
             *e = Some(uri);
@@ -269,6 +465,7 @@ impl<N: FtmlNode + std::fmt::Debug> ExtractorState<N> {
             })
             .collect();
         let data = take(&mut self.buffer).take();
+        self.stats.notations = self.notations.len();
         #[cfg(feature = "rdf")]
         let triples = take(&mut self.rdf);
         ExtractionResult {
@@ -312,10 +509,15 @@ impl<N: FtmlNode + std::fmt::Debug> ExtractorState<N> {
             MetaDatum::DocumentKind(k) => self.kind = k,
             MetaDatum::Style(s) => self.styles.push(s),
             MetaDatum::Counter(c) => self.counters.push(c),
-            MetaDatum::InputRef { target, uri } => {
+            MetaDatum::InputRef {
+                target,
+                uri,
+                anchor,
+            } => {
                 self.push_elem(DocumentElement::DocumentReference {
                     uri,
                     target,
+                    anchor,
                     source: self.current_source_range,
                 });
             }
@@ -417,6 +619,7 @@ impl<N: FtmlNode + std::fmt::Debug> ExtractorState<N> {
     pub fn add(&mut self, e: OpenFtmlElement, node: &N) -> Result<(), FtmlExtractionError> {
         match e.split(node) {
             AnyOpen::Open { domain, narrative } => {
+                self.stats.elements += 1;
                 if let Some(dom) = domain {
                     self.domain.push(dom);
                 }
@@ -442,7 +645,10 @@ impl<N: FtmlNode + std::fmt::Debug> ExtractorState<N> {
                     self.narrative.push(narr);
                 }
             }
-            AnyOpen::Meta(m) => self.do_meta(m, node)?,
+            AnyOpen::Meta(m) => {
+                self.stats.elements += 1;
+                self.do_meta(m, node)?;
+            }
             AnyOpen::None => (),
         }
         Ok(())
@@ -579,8 +785,8 @@ impl<N: FtmlNode + std::fmt::Debug> ExtractorState<N> {
                 _ => Err(FtmlExtractionError::UnexpectedEndOf(FtmlKey::Vardef)),
             },
             CloseFtmlElement::Definiendum => match self.narrative.pop() {
-                Some(OpenNarrativeElement::Definiendum(uri)) => {
-                    self.close_definiendum(uri, node.range())
+                Some(OpenNarrativeElement::Definiendum(uris)) => {
+                    self.close_definiendum(uris, node.range())
                 }
                 _ => Err(FtmlExtractionError::UnexpectedEndOf(FtmlKey::Definiendum)),
             },
@@ -651,6 +857,18 @@ impl<N: FtmlNode + std::fmt::Debug> ExtractorState<N> {
                 }
                 _ => Err(FtmlExtractionError::UnexpectedEndOf(FtmlKey::Slide)),
             },
+            CloseFtmlElement::Figure => match self.narrative.pop() {
+                Some(OpenNarrativeElement::Figure {
+                    uri,
+                    kind,
+                    children,
+                    caption,
+                }) => {
+                    self.close_figure(uri, kind, children, caption, node.range());
+                    Ok(())
+                }
+                _ => Err(FtmlExtractionError::UnexpectedEndOf(FtmlKey::Figure)),
+            },
             CloseFtmlElement::Assign => match self.domain.pop() {
                 Some(OpenDomainElement::Assign {
                     source,
@@ -1053,6 +1271,7 @@ impl<N: FtmlNode + std::fmt::Debug> ExtractorState<N> {
             CloseFtmlElement::ParagraphTitle => self.close_paragraph_title(node),
             CloseFtmlElement::ProblemTitle => self.close_problem_title(node),
             CloseFtmlElement::SlideTitle => self.close_slide_title(node),
+            CloseFtmlElement::FigureCaption => self.close_figure_caption(node),
             CloseFtmlElement::DocTitle => {
                 self.title = Some(node.inner_string().into_owned().into_boxed_str());
                 Ok(())
@@ -1160,6 +1379,7 @@ impl<N: FtmlNode + std::fmt::Debug> ExtractorState<N> {
                 | OpenNarrativeElement::Section { children, .. }
                 | OpenNarrativeElement::Paragraph { children, .. }
                 | OpenNarrativeElement::Slide { children, .. }
+                | OpenNarrativeElement::Figure { children, .. }
                 | OpenNarrativeElement::Problem { children, .. }
                 | OpenNarrativeElement::SkipSection { children } => {
                     children.push(e);
@@ -1460,21 +1680,25 @@ impl<N: FtmlNode + std::fmt::Debug> ExtractorState<N> {
 
     fn close_definiendum(
         &mut self,
-        uri: SymbolUri,
+        uris: Vec<SymbolUri>,
         range: DocumentRange,
     ) -> Result<(), FtmlExtractionError> {
         let mut iter = self.narrative.iter_mut();
         while let Some(e) = iter.next() {
             if let OpenNarrativeElement::Paragraph { fors, .. } = e {
-                if !fors.iter().any(|p| p.0 == uri) {
-                    fors.push((uri.clone(), None));
+                for uri in &uris {
+                    if !fors.iter().any(|p| p.0 == *uri) {
+                        fors.push((uri.clone(), None));
+                    }
                 }
                 drop(iter);
-                self.push_elem(DocumentElement::Definiendum {
-                    range,
-                    uri,
-                    source: self.current_source_range,
-                });
+                for uri in uris {
+                    self.push_elem(DocumentElement::Definiendum {
+                        range,
+                        uri,
+                        source: self.current_source_range,
+                    });
+                }
                 return Ok(());
             }
         }
@@ -1765,6 +1989,26 @@ impl<N: FtmlNode + std::fmt::Debug> ExtractorState<N> {
         self.push_elem(p);
     }
 
+    fn close_figure(
+        &mut self,
+        uri: DocumentElementUri,
+        kind: FigureKind,
+        children: Vec<DocumentElement>,
+        caption: Option<Box<str>>,
+        range: DocumentRange,
+    ) {
+        let p = DocumentElement::Figure(Figure {
+            uri,
+            kind,
+            caption,
+            range,
+            children: children.into_boxed_slice(),
+            source: self.current_source_range,
+        });
+        tracing::info!("Adding figure {p:#?}");
+        self.push_elem(p);
+    }
+
     #[allow(clippy::too_many_arguments)]
     fn close_notation(
         &mut self,
@@ -1967,6 +2211,7 @@ impl<N: FtmlNode + std::fmt::Debug> ExtractorState<N> {
                 | OpenNarrativeElement::Notation { .. }
                 | OpenNarrativeElement::Section { .. }
                 | OpenNarrativeElement::Slide { .. }
+                | OpenNarrativeElement::Figure { .. }
                 | OpenNarrativeElement::NotationComp { .. }
                 | OpenNarrativeElement::ArgSep { .. }
                 | OpenNarrativeElement::VariableDeclaration { .. }
@@ -2015,6 +2260,7 @@ impl<N: FtmlNode + std::fmt::Debug> ExtractorState<N> {
                 | OpenNarrativeElement::Notation { .. }
                 | OpenNarrativeElement::Paragraph { .. }
                 | OpenNarrativeElement::Slide { .. }
+                | OpenNarrativeElement::Figure { .. }
                 | OpenNarrativeElement::NotationComp { .. }
                 | OpenNarrativeElement::ArgSep { .. }
                 | OpenNarrativeElement::VariableDeclaration { .. }
@@ -2071,6 +2317,7 @@ impl<N: FtmlNode + std::fmt::Debug> ExtractorState<N> {
                 | OpenNarrativeElement::Notation { .. }
                 | OpenNarrativeElement::Section { .. }
                 | OpenNarrativeElement::Slide { .. }
+                | OpenNarrativeElement::Figure { .. }
                 | OpenNarrativeElement::NotationComp { .. }
                 | OpenNarrativeElement::ArgSep { .. }
                 | OpenNarrativeElement::VariableDeclaration { .. }
@@ -2110,6 +2357,7 @@ impl<N: FtmlNode + std::fmt::Debug> ExtractorState<N> {
                     return Err(FtmlExtractionError::DuplicateValue(FtmlKey::Title));
                 }
                 OpenNarrativeElement::SkipSection { .. }
+                | OpenNarrativeElement::Figure { .. }
                 | OpenNarrativeElement::Problem { .. }
                 | OpenNarrativeElement::Solution(..)
                 | OpenNarrativeElement::Notation { .. }
@@ -2144,6 +2392,55 @@ impl<N: FtmlNode + std::fmt::Debug> ExtractorState<N> {
         Err(FtmlExtractionError::UnexpectedEndOf(FtmlKey::Title))
     }
 
+    fn close_figure_caption(&mut self, node: &N) -> super::Result<()> {
+        for e in self.narrative.iter_mut() {
+            match e {
+                OpenNarrativeElement::Figure { caption, .. } if caption.is_none() => {
+                    let str = node.inner_string();
+                    if !str.is_empty() {
+                        *caption = Some(str.into_owned().into_boxed_str());
+                    }
+                    return Ok(());
+                }
+                OpenNarrativeElement::Figure { .. } => {
+                    return Err(FtmlExtractionError::DuplicateValue(FtmlKey::FigureCaption));
+                }
+                OpenNarrativeElement::SkipSection { .. }
+                | OpenNarrativeElement::Problem { .. }
+                | OpenNarrativeElement::Solution(..)
+                | OpenNarrativeElement::Notation { .. }
+                | OpenNarrativeElement::Section { .. }
+                | OpenNarrativeElement::Paragraph { .. }
+                | OpenNarrativeElement::Slide { .. }
+                | OpenNarrativeElement::NotationComp { .. }
+                | OpenNarrativeElement::ArgSep { .. }
+                | OpenNarrativeElement::VariableDeclaration { .. }
+                | OpenNarrativeElement::Definiendum(_)
+                | OpenNarrativeElement::FillinSol { .. }
+                | OpenNarrativeElement::ProblemHint
+                | OpenNarrativeElement::ProblemExNote
+                | OpenNarrativeElement::ProblemGradingNote(_)
+                | OpenNarrativeElement::AnswerClass { .. }
+                | OpenNarrativeElement::ChoiceBlock { .. }
+                | OpenNarrativeElement::ProblemChoice { .. }
+                | OpenNarrativeElement::ProblemChoiceVerdict
+                | OpenNarrativeElement::ProblemChoiceFeedback
+                | OpenNarrativeElement::FillinSolCase(_)
+                | OpenNarrativeElement::FoldExprShort
+                | OpenNarrativeElement::FoldExpr(_)
+                | OpenNarrativeElement::ProofStep { .. }
+                | OpenNarrativeElement::NotationArg(_) => {
+                    return Err(FtmlExtractionError::UnexpectedEndOf(FtmlKey::FigureCaption));
+                }
+                OpenNarrativeElement::Module { .. }
+                | OpenNarrativeElement::MathStructure { .. }
+                | OpenNarrativeElement::Morphism { .. }
+                | OpenNarrativeElement::Invisible => (),
+            }
+        }
+        Err(FtmlExtractionError::UnexpectedEndOf(FtmlKey::FigureCaption))
+    }
+
     fn close_seq_range(
         &mut self,
         mut terms: Vec<(Term, crate::NodePath)>,
@@ -2270,7 +2567,7 @@ impl<N: FtmlNode + std::fmt::Debug> ExtractorState<N> {
     fn close_argtypes(&mut self, terms: Vec<Term>, node: &N) -> super::Result<()> {
         match self.domain.last_mut() {
             Some(OpenDomainElement::SymbolDeclaration { uri, data }) if data.tp.is_none() => {
-                data.argument_types = terms.into_boxed_slice();
+                data.arg_types = terms.into_iter().map(Some).collect();
                 return Ok(());
             }
             _ => (),
@@ -2352,6 +2649,7 @@ impl<N: FtmlNode + std::fmt::Debug> ExtractorState<N> {
                 | OpenNarrativeElement::VariableDeclaration { .. }
                 | OpenNarrativeElement::SkipSection { .. }
                 | OpenNarrativeElement::Slide { .. }
+                | OpenNarrativeElement::Figure { .. }
                 | OpenNarrativeElement::Notation { .. }
                 | OpenNarrativeElement::NotationComp { .. }
                 | OpenNarrativeElement::ArgSep { .. }
@@ -2436,6 +2734,7 @@ impl<N: FtmlNode + std::fmt::Debug> ExtractorState<N> {
                 | OpenNarrativeElement::Solution(..)
                 | OpenNarrativeElement::Paragraph { .. }
                 | OpenNarrativeElement::Slide { .. }
+                | OpenNarrativeElement::Figure { .. }
                 | OpenNarrativeElement::VariableDeclaration { .. }
                 | OpenNarrativeElement::SkipSection { .. }
                 | OpenNarrativeElement::Notation { .. }
@@ -2697,6 +2996,7 @@ impl<N: FtmlNode + std::fmt::Debug> ExtractorState<N> {
                 | OpenNarrativeElement::Problem { .. }
                 | OpenNarrativeElement::Solution(..)
                 | OpenNarrativeElement::Slide { .. }
+                | OpenNarrativeElement::Figure { .. }
                 | OpenNarrativeElement::SkipSection { .. }
                 | OpenNarrativeElement::Notation { .. }
                 | OpenNarrativeElement::NotationComp { .. }
@@ -2753,6 +3053,7 @@ impl<N: FtmlNode + std::fmt::Debug> ExtractorState<N> {
                 | OpenNarrativeElement::Problem { .. }
                 | OpenNarrativeElement::Solution(..)
                 | OpenNarrativeElement::Slide { .. }
+                | OpenNarrativeElement::Figure { .. }
                 | OpenNarrativeElement::SkipSection { .. }
                 | OpenNarrativeElement::Notation { .. }
                 | OpenNarrativeElement::NotationComp { .. }
@@ -2833,6 +3134,7 @@ impl<N: FtmlNode + std::fmt::Debug> ExtractorState<N> {
                 | OpenNarrativeElement::Problem { .. }
                 | OpenNarrativeElement::Solution(..)
                 | OpenNarrativeElement::Slide { .. }
+                | OpenNarrativeElement::Figure { .. }
                 | OpenNarrativeElement::SkipSection { .. }
                 | OpenNarrativeElement::Notation { .. }
                 | OpenNarrativeElement::NotationComp { .. }
@@ -2882,6 +3184,7 @@ impl<N: FtmlNode + std::fmt::Debug> ExtractorState<N> {
                 | OpenNarrativeElement::Problem { .. }
                 | OpenNarrativeElement::Solution(..)
                 | OpenNarrativeElement::Slide { .. }
+                | OpenNarrativeElement::Figure { .. }
                 | OpenNarrativeElement::SkipSection { .. }
                 | OpenNarrativeElement::Notation { .. }
                 | OpenNarrativeElement::NotationComp { .. }
@@ -2931,6 +3234,7 @@ impl<N: FtmlNode + std::fmt::Debug> ExtractorState<N> {
                 | OpenNarrativeElement::Problem { .. }
                 | OpenNarrativeElement::Solution(..)
                 | OpenNarrativeElement::Slide { .. }
+                | OpenNarrativeElement::Figure { .. }
                 | OpenNarrativeElement::SkipSection { .. }
                 | OpenNarrativeElement::Notation { .. }
                 | OpenNarrativeElement::NotationComp { .. }
@@ -2982,6 +3286,7 @@ impl<N: FtmlNode + std::fmt::Debug> ExtractorState<N> {
                 | OpenNarrativeElement::Problem { .. }
                 | OpenNarrativeElement::Solution(..)
                 | OpenNarrativeElement::Slide { .. }
+                | OpenNarrativeElement::Figure { .. }
                 | OpenNarrativeElement::SkipSection { .. }
                 | OpenNarrativeElement::Notation { .. }
                 | OpenNarrativeElement::NotationComp { .. }
@@ -3051,6 +3356,7 @@ impl<N: FtmlNode + std::fmt::Debug> ExtractorState<N> {
                 | OpenNarrativeElement::Problem { .. }
                 | OpenNarrativeElement::Solution(..)
                 | OpenNarrativeElement::Slide { .. }
+                | OpenNarrativeElement::Figure { .. }
                 | OpenNarrativeElement::SkipSection { .. }
                 | OpenNarrativeElement::Notation { .. }
                 | OpenNarrativeElement::NotationComp { .. }
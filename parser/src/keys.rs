@@ -10,6 +10,7 @@ use ftml_ontology::{
         documents::{DocumentCounter, DocumentKind, DocumentStyle},
         elements::{
             SectionLevel,
+            figures::FigureKind,
             paragraphs::{ParagraphFormatting, ParagraphKind, ParagraphStepKind},
             problems::{AnswerKind, ChoiceBlockStyle, CognitiveDimension, FillInSolOption},
             variables::VariableData,
@@ -36,7 +37,6 @@ macro_rules! ftml {
     };
 }
 pub const PREFIX: &str = "data-ftml-";
-pub const NUM_KEYS: u8 = 130;
 /*
 pub struct FtmlRuleSet<E: crate::extraction::FtmlExtractor>(
     pub(crate)  [fn(
@@ -54,6 +54,21 @@ pub struct KeyList(pub(crate) smallvec::SmallVec<FtmlKey, 4>);
  */
 use crate::extraction::{FtmlRuleSet, KeyList};
 
+impl FtmlKey {
+    /// Picks the FTML attributes out of a list of `(name, value)` pairs of attributes of
+    /// some node, in the given order.
+    ///
+    /// This does not run any rules and does not validate the values; attributes that are not
+    /// (known) FTML keys are skipped.
+    pub fn filter_attrs<K: AsRef<str>, V>(
+        attrs: impl IntoIterator<Item = (K, V)>,
+    ) -> impl Iterator<Item = (Self, V)> {
+        attrs
+            .into_iter()
+            .filter_map(|(k, v)| Self::from_attr(k.as_ref()).map(|k| (k, v)))
+    }
+}
+
 #[allow(clippy::unnecessary_wraps)]
 fn todo<E: crate::extraction::FtmlExtractor>(
     key: FtmlKey,
@@ -240,6 +255,10 @@ macro_rules! do_keys {
                 $(+ $close:ident => $closeb:block   )?
             )?
     ),* $(,)? ) => {
+        /// The number of [`FtmlKey`]s
+        #[allow(clippy::cast_possible_truncation)]
+        pub const NUM_KEYS: u8 = [$($key),*].len() as u8;
+
         #[allow(clippy::unsafe_derive_deserialize)]
         #[derive(Copy,Clone,PartialEq, Eq,Hash)]//,serde::Serialize, serde::Deserialize)]
         //#[cfg_attr(feature = "typescript", wasm_bindgen::prelude::wasm_bindgen)]
@@ -291,6 +310,7 @@ macro_rules! do_keys {
                 ),*])
             }
         }
+        #[derive(Debug, Clone)]
         pub enum OpenFtmlElement {
             $($($(
                 $open $({$($f:$ft),*})? $( ( $($t),*) )? ,
@@ -506,13 +526,29 @@ do_keys! {
     /// A [`DocumentReference`](DocumentElement::DocumentReference); Inserts the referenced [`Document`]
     /// here; loosely analogous to an iframe, but the referenced
     /// [`Document`] is adapted to the current document in various ways (e.g. wrt [`SectionLevel`]s).
+    ///
+    /// Optionally, [`InputRefAnchor`](FtmlKey::InputRefAnchor) points to an element of the
+    /// referenced document, e.g.
+    /// `<div data-ftml-inputref="<document>" data-ftml-inputref-anchor="<element>"></div>`,
+    /// in which case the reference links directly to that element.
     InputRef = "inputref"
-        { ="[DocumentUri]" +(Id) }
-        := (ext,attrs,_keys,node) => {
+        { ="[DocumentUri]" +(Id,InputRefAnchor) }
+        := (ext,attrs,keys,node) => {
             let target = attrs.get_document_uri(FtmlKey::InputRef)?;
             let uri = attrs.get_elem_uri_from_id(ext, Cow::Owned(target.document_name().to_string()))?;
-            ret!(ext,node <- InputRef{uri,target})
-        } => InputRef{uri:DocumentElementUri,target:DocumentUri},
+            let anchor = opt!(attrs.get_typed(FtmlKey::InputRefAnchor, DocumentElementUri::from_str));
+            if anchor.as_ref().is_some_and(|a| a.document != target) {
+                return Err(FtmlExtractionError::InvalidValue(FtmlKey::InputRefAnchor));
+            }
+            del!(keys - InputRefAnchor);
+            ret!(ext,node <- InputRef{uri,target,anchor})
+        } => InputRef{uri:DocumentElementUri,target:DocumentUri,anchor:Option<DocumentElementUri>},
+
+    /// The element of the referenced [`Document`] an [`InputRef`](FtmlKey::InputRef) links to;
+    /// has to be an element of the [`InputRef`](FtmlKey::InputRef)'s target document.
+    InputRefAnchor = "inputref-anchor"
+        {="[DocumentElementUri]" -(InputRef)}
+        := noop,
 
     /// If `true`, shows the node iff the current document is being rendered as an
     /// [`InputRef`](FtmlKey::InputRef) in some other (top-level) document;
@@ -989,7 +1025,7 @@ do_keys! {
             ret!(ext,node <- FillinSol(val) + FillinSol)
         } => FillinSol(width:Option<f32>),
 
-    /// Thw width of the text input field for a fill-in-the-blanks element
+    /// The width (in pixels) of the text input field for a fill-in-the-blanks element
     ProblemFillinsolWidth = "fillinsol-width"
         { -(ProblemFillinsol) }
         := noop,
@@ -1051,6 +1087,7 @@ do_keys! {
                     | OpenNarrativeElement::ProofStep{kind:ParagraphStepKind::SubProof,..}
                     | OpenNarrativeElement::Invisible => (),
                     OpenNarrativeElement::SkipSection { .. }
+                    | OpenNarrativeElement::Figure { .. }
                     | OpenNarrativeElement::Notation { .. }
                     | OpenNarrativeElement::NotationComp { .. }
                     | OpenNarrativeElement::ArgSep { .. }
@@ -1101,6 +1138,25 @@ do_keys! {
             ret!(ext,node <- SlideNumber)
         } => SlideNumber,
 
+    /// Denotes a [`Figure`](ftml_ontology::narrative::elements::Figure) or table, depending
+    /// on the value. Figures and tables are numbered (separately) in document order; see
+    /// [`Document::list_of_figures`].
+    Figure = "figure"
+        {="`figure` or `table`" +(Id) &(FigureCaption) }
+        := (ext,attrs,keys,node) => {
+            let kind = attrs.get_typed(FtmlKey::Figure, |s| FigureKind::from_str(s).map_err(|_| ()))?;
+            let uri = attrs.get_elem_uri_from_id(ext, kind.as_str())?;
+            del!(keys - Id);
+            ret!(ext,node <- Figure{kind,uri} + Figure)
+        } => Figure{kind:FigureKind,uri:DocumentElementUri},
+
+    /// The caption of the current [`Figure`](ftml_ontology::narrative::elements::Figure).
+    FigureCaption = "caption"
+        { <=(Figure) }
+        := (ext,_attrs,_keys,node) => {
+            ret!(ext,node <- FigureCaption + FigureCaption)
+        } => FigureCaption,
+
 
     /// Inserts a reference to a paragraph; possibly in a different document ("Section 3.1 in the Documentation")
     SRef = "sref"
@@ -1124,6 +1180,14 @@ do_keys! {
         {-(Definition, Paragraph, Assertion, Example, Problem, SubProblem, Proof, SubProof, ProblemSingleChoiceBlock, ProblemMultipleChoiceBlock) }
         := noop,
 
+    /// A single explicit CSS style for this paragraph. It is appended to the
+    /// [`Styles`](FtmlKey::Styles) (unless already among them), and hence has lower priority
+    /// than those; like all named styles, it takes priority over the default style for the
+    /// paragraph's [`ParagraphKind`].
+    ParagraphStyle = "paragraph-style"
+        {="[Id]" -(Definition, Paragraph, Assertion, Example, Proof, SubProof) }
+        := noop,
+
     // ------------------------------------------------------------------------------------
 
     /// Denotes a new [`Module`] (or [`NestedModule`], iff already in a module) with the given
@@ -1258,7 +1322,7 @@ do_keys! {
                     assoctype,
                     reordering,
                     return_type:None,
-                    argument_types:Box::new([]),
+                    arg_types:Box::new([]),
                     tp: TermContainer::default(),
                     df: TermContainer::default(),
                     source
@@ -1424,13 +1488,15 @@ do_keys! {
         } => ReturnType,
 
     /// Denotes the *types* of the arguments for the current [`Symbol`] or [`Variable`], in absence of a
-    /// [`Type`](FtmlKey::Type). In conjunction with [`ArgTypes`](FtmlKey::ArgTypes),
+    /// [`Type`](FtmlKey::Type). In conjunction with [`ReturnType`](FtmlKey::ReturnType),
     /// the full type is assembled by binding the argument types.
+    ///
+    /// Every [`Type`](FtmlKey::Type) child denotes the type of the next argument, in order.
     ArgTypes = "argtypes"
         {<=(Symdecl, Vardef, Varseq) &(Type) }
         := (ext,_attrs,_keys,node) => {
             if ext.in_term() {
-                return Err(FtmlExtractionError::InvalidIn(FtmlKey::ReturnType, "terms"));
+                return Err(FtmlExtractionError::InvalidIn(FtmlKey::ArgTypes, "terms"));
             }
             ret!(ext,node <- ArgTypes + ArgTypes)
         } => ArgTypes,
@@ -1650,8 +1716,8 @@ do_keys! {
                 return Err(FtmlExtractionError::MissingKey(FtmlKey::Arg));
             };
             let mode: Option<ArgumentMode> = opt!(attrs.get_typed(FtmlKey::ArgMode, |s| {
-                s.parse()
-                    .map_err(|_| FtmlExtractionError::InvalidValue(FtmlKey::ArgMode))
+                ArgumentMode::from_attr(s)
+                    .ok_or(FtmlExtractionError::InvalidValue(FtmlKey::ArgMode))
             }));
             let Some(argument) = ArgumentPosition::from_strs(index.as_ref(), mode) else {
                 return Err(FtmlExtractionError::InvalidValue(FtmlKey::Arg));
@@ -1944,12 +2010,24 @@ do_keys! {
         } => DefComp,
 
     /// The *definiendum* in a Definition, i.e. a [`Symbol`] being defined here.
+    ///
+    /// Like [`Fors`](FtmlKey::Fors), the value may also be a comma-separated list of
+    /// [`Symbol`]s, e.g. `data-ftml-definiendum="<uri1>,<uri2>"`, if the marked node defines
+    /// several symbols at once; the enclosing paragraph then counts as a definition of
+    /// each of them, and a [`DocumentElement::Definiendum`] is recorded for every symbol.
     Definiendum = "definiendum"
-        {="[SymbolUri]" <=(Definition, Paragraph, Assertion) !"in definition-like [`LogicalParagraph`]s"}
+        {="[SymbolUri]+" <=(Definition, Paragraph, Assertion) !"in definition-like [`LogicalParagraph`]s"}
         := (ext,attrs,_keys,node) => {
-            let s = attrs.get_symbol_uri(FtmlKey::Definiendum)?;
+            let mut s: Vec<SymbolUri> = Vec::new();
+            for uri in attrs.get_typed_vec::<FtmlExtractionError, _>(FtmlKey::Definiendum, |s| {
+                SymbolUri::from_str(s).map_err(|e| (FtmlKey::Definiendum, e).into())
+            })? {
+                if !s.contains(&uri) {
+                    s.push(uri);
+                }
+            }
             ret!(ext,node <- Definiendum(s) + Definiendum)
-        } => Definiendum(s:SymbolUri),
+        } => Definiendum(s:Vec<SymbolUri>),
 
     // --------------------------------------------------------------------------
 
@@ -2136,7 +2214,7 @@ fn do_paragraph<E: FtmlExtractor>(
     (E::Return, Option<crate::extraction::CloseFtmlElement>),
     crate::extraction::FtmlExtractionError,
 > {
-    let uri = attrs.get_elem_uri_from_id(ext, Cow::Borrowed(kind.as_str()))?;
+    let uri = attrs.get_elem_uri_from_id(ext, kind.as_str())?;
     let inline = attrs.get_bool(FtmlKey::Inline);
     let mut fors: Vec<SymbolUri> = Vec::new();
     if let Some(f) = attrs.get(FtmlKey::Fors) {
@@ -2150,7 +2228,7 @@ fn do_paragraph<E: FtmlExtractor>(
             }
         }
     }
-    let styles = opt!(
+    let mut styles = opt!(
         attrs.get_typed_vec::<FtmlExtractionError, _>(FtmlKey::Styles, |s| {
             s.trim()
                 .parse()
@@ -2158,6 +2236,14 @@ fn do_paragraph<E: FtmlExtractor>(
         })
     )
     .unwrap_or_default();
+    if let Some(style) = opt!(attrs.get_typed(FtmlKey::ParagraphStyle, |s| {
+        s.trim()
+            .parse::<Id>()
+            .map_err(|_| FtmlExtractionError::InvalidValue(FtmlKey::ParagraphStyle))
+    })) && !styles.contains(&style)
+    {
+        styles.push(style);
+    }
 
     let (formatting, varname) = if inline {
         (ParagraphFormatting::Inline, None)
@@ -2179,6 +2265,7 @@ fn do_paragraph<E: FtmlExtractor>(
     };
 
     del!(keys - Id, Inline, Fors, Styles, ProofHide, ProofStepName);
+    del!(keys - ParagraphStyle);
     ret!(ext,node <- Paragraph{
         kind,
         formatting,
@@ -2189,7 +2276,15 @@ fn do_paragraph<E: FtmlExtractor>(
     } + Paragraph)
 }
 
+/// Parses the value of [`FtmlKey::ProblemPoints`] or [`FtmlKey::ProblemMinutes`]
+/// (`[f32]|[i32]`).
 #[allow(clippy::cast_precision_loss)]
+fn parse_problem_number(s: &str) -> Result<f32, ()> {
+    s.parse::<f32>()
+        .map_err(|_| ())
+        .or_else(|()| s.parse::<i32>().map_err(|_| ()).map(|i| i as f32))
+}
+
 fn do_problem<E: crate::extraction::FtmlExtractor>(
     _: FtmlKey,
     ext: &mut E,
@@ -2211,17 +2306,8 @@ fn do_problem<E: crate::extraction::FtmlExtractor>(
     )
     .unwrap_or_default();
     let autogradable = attrs.take_bool(FtmlKey::Autogradable);
-    let points = opt!(attrs.take_typed(FtmlKey::ProblemPoints, |s| {
-        s.parse::<f32>()
-            .map_err(|_| ())
-            .or_else(|()| s.parse::<i32>().map_err(|_| ()).map(|i| i as f32))
-    }));
-
-    let minutes = opt!(attrs.take_typed(FtmlKey::ProblemMinutes, |s| {
-        s.parse::<f32>()
-            .map_err(|_| ())
-            .or_else(|()| s.parse::<i32>().map_err(|_| ()).map(|i| i as f32))
-    }));
+    let points = opt!(attrs.take_typed(FtmlKey::ProblemPoints, parse_problem_number));
+    let minutes = opt!(attrs.take_typed(FtmlKey::ProblemMinutes, parse_problem_number));
 
     del!(
         keys - Id,
@@ -2250,3 +2336,53 @@ fn do_problem<E: crate::extraction::FtmlExtractor>(
         })
     */
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{FtmlKey, parse_problem_number};
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn problem_minutes() {
+        assert_eq!(parse_problem_number("2.5"), Ok(2.5));
+        assert_eq!(parse_problem_number("10"), Ok(10.0));
+        assert_eq!(parse_problem_number("ten"), Err(()));
+
+        // the extracted value survives (de)serialization of the problem
+        let data = ftml_ontology::narrative::elements::problems::ProblemData {
+            points: parse_problem_number("4").ok(),
+            minutes: parse_problem_number("7.5").ok(),
+            ..ftml_ontology::narrative::elements::problems::ProblemData::new(
+                ftml_ontology::narrative::DataBuffer::default()
+                    .push(&ftml_ontology::narrative::elements::problems::Solutions::default())
+                    .expect("encodable"),
+            )
+        };
+        let bytes = bincode::encode_to_vec(&data, bincode::config::standard()).expect("encodable");
+        let (decoded, _): (ftml_ontology::narrative::elements::problems::ProblemData, _) =
+            bincode::decode_from_slice(&bytes, bincode::config::standard()).expect("decodable");
+        assert_eq!(decoded.minutes, Some(7.5));
+        assert_eq!(decoded.points, Some(4.0));
+    }
+
+    #[test]
+    fn filter_ftml_attrs() {
+        let attrs = [
+            ("class", "foo"),
+            (
+                FtmlKey::Module.attr_name(),
+                "http://example.com?a=archive&m=mod",
+            ),
+            ("data-ftml-not-a-key", "bar"),
+            (FtmlKey::Language.attr_name(), "de"),
+        ];
+        let keys = FtmlKey::filter_attrs(attrs).collect::<Vec<_>>();
+        assert_eq!(
+            keys,
+            [
+                (FtmlKey::Module, "http://example.com?a=archive&m=mod"),
+                (FtmlKey::Language, "de")
+            ]
+        );
+    }
+}
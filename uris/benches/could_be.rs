@@ -0,0 +1,29 @@
+//! `cargo bench -p ftml-uris --bench could_be`
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use ftml_uris::{FtmlUri, Uri};
+use std::hint::black_box;
+
+const CASES: [&str; 8] = [
+    "http://example.com",
+    "http://example.com?a=some/archive",
+    "http://example.com?a=some/archive&p=some/path",
+    "http://example.com?a=some/archive&p=some/path&m=some/module",
+    "http://example.com?a=some/archive&p=some/path&m=some/module&s=some/symbol",
+    "http://example.com?a=some/archive&p=some/path&d=document&l=en",
+    "http://example.com?a=some/archive&p=some/path&d=document&l=en&e=some/element",
+    "not a uri at all, but a reasonably long string of text",
+];
+
+fn could_be(c: &mut Criterion) {
+    c.bench_function("Uri::could_be", |b| {
+        b.iter(|| {
+            for case in CASES {
+                black_box(Uri::could_be(black_box(case)));
+            }
+        });
+    });
+}
+
+criterion_group!(benches, could_be);
+criterion_main!(benches);
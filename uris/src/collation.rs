@@ -0,0 +1,237 @@
+//! Language-aware sorting of names and titles; see [`Language::collation_key`].
+//!
+//! #### Supported locales
+//! Strings are compared on three levels: first by their *base letters* (case- and
+//! accent-insensitively), then by their accents, then by case (lowercase first); remaining
+//! ties are broken by the byte-wise order. The base letters are determined by a small
+//! table of (mostly Latin) accented letters, e.g. `é` → `e`, `ß` → `ss`, `æ` → `ae`, which
+//! is then tailored per language:
+//!
+//! | Language | Tailoring |
+//! |----------|-----------|
+//! | [German](Language::German) | none (DIN 5007-1: umlauts sort as their base letters, `ß` as `ss`) |
+//! | [Finnish](Language::Finnish) | `å`, `ä`, `ö` (and `æ`, `ø`) are letters of their own after `z` |
+//! | [Turkish](Language::Turkish) | `ç`, `ğ`, `ö`, `ş`, `ü` follow their base letters; dotless `ı` precedes `i`; `I`/`İ` are the uppercase forms of `ı`/`i` |
+//! | [Romanian](Language::Romanian) | `ă`, `â` follow `a`; `î` follows `i`; `ș`, `ț` follow `s`, `t` |
+//! | [Slovenian](Language::Slovenian) | `č`, `š`, `ž` follow their base letters |
+//! | [Russian](Language::Russian) | `ё` sorts as `е` |
+//!
+//! #### Fallback
+//! All other languages ([English](Language::English), [French](Language::French),
+//! [Bulgarian](Language::Bulgarian), [Arabic](Language::Arabic)) use the untailored table.
+//! Characters not covered by the table (e.g. Cyrillic or Arabic letters, digits,
+//! punctuation) are compared by their (lowercased) code points, which coincides with the
+//! alphabetical order for the basic Cyrillic and Arabic alphabets.
+
+use crate::Language;
+
+/// A key for sorting strings in some [`Language`]; see
+/// [`Language::collation_key`].
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CollationKey {
+    primary: Vec<u32>,
+    secondary: Vec<u8>,
+    tertiary: Vec<u8>,
+    raw: Box<str>,
+}
+
+impl Language {
+    /// The key by which to sort `s` in this language; see the
+    /// [module documentation](crate::collation) for the supported locales.
+    #[must_use]
+    pub fn collation_key(self, s: &str) -> CollationKey {
+        let mut key = CollationKey {
+            primary: Vec::with_capacity(s.len()),
+            secondary: Vec::with_capacity(s.len()),
+            tertiary: Vec::with_capacity(s.len()),
+            raw: s.into(),
+        };
+        for c in s.chars() {
+            let (lower, upper) = self.to_lower(c);
+            let tertiary = u8::from(upper);
+            if let Some(primary) = self.tailored(lower) {
+                key.primary.push(primary);
+                key.secondary.push(0);
+                key.tertiary.push(tertiary);
+            } else if let Some((base, accent)) = fold(lower) {
+                for b in base.chars() {
+                    key.primary.push(weight(b));
+                    key.secondary.push(accent);
+                    key.tertiary.push(tertiary);
+                }
+            } else {
+                key.primary.push(weight(lower));
+                key.secondary.push(0);
+                key.tertiary.push(tertiary);
+            }
+        }
+        key
+    }
+
+    /// Compares `a` and `b` according to this language's collation; see
+    /// [`collation_key`](Self::collation_key).
+    #[must_use]
+    pub fn collate(self, a: &str, b: &str) -> std::cmp::Ordering {
+        self.collation_key(a).cmp(&self.collation_key(b))
+    }
+
+    /// Sorts `items` by the strings returned by `key` (e.g. [`UriName`](crate::UriName)s or
+    /// titles) according to this language's collation.
+    pub fn sort_by_collation<T>(self, items: &mut [T], key: impl Fn(&T) -> &str) {
+        items.sort_by_cached_key(|t| self.collation_key(key(t)));
+    }
+
+    fn to_lower(self, c: char) -> (char, bool) {
+        match (self, c) {
+            (Self::Turkish, 'I') => ('ı', true),
+            (Self::Turkish, 'İ') => ('i', true),
+            _ if c.is_uppercase() => (c.to_lowercase().next().unwrap_or(c), true),
+            _ => (c, false),
+        }
+    }
+
+    /// primary weights of the letters of this language's alphabet that are not just
+    /// accented variants of their base letters
+    fn tailored(self, c: char) -> Option<u32> {
+        let after = |base: char, offset: u32| Some(weight(base) + offset);
+        match (self, c) {
+            (Self::Finnish, 'å') => after('z', 1),
+            (Self::Finnish, 'ä' | 'æ') => after('z', 2),
+            (Self::Finnish, 'ö' | 'ø') => after('z', 3),
+            (Self::Turkish, 'ç') => after('c', 1),
+            (Self::Turkish, 'ğ') => after('g', 1),
+            (Self::Turkish, 'ı') => Some(weight('i') - 1),
+            (Self::Turkish, 'ö') => after('o', 1),
+            (Self::Turkish, 'ş') => after('s', 1),
+            (Self::Turkish, 'ü') => after('u', 1),
+            (Self::Romanian, 'ă') => after('a', 1),
+            (Self::Romanian, 'â') => after('a', 2),
+            (Self::Romanian, 'î') => after('i', 1),
+            (Self::Romanian, 'ș' | 'ş') => after('s', 1),
+            (Self::Romanian, 'ț' | 'ţ') => after('t', 1),
+            (Self::Slovenian, 'č') => after('c', 1),
+            (Self::Slovenian, 'š') => after('s', 1),
+            (Self::Slovenian, 'ž') => after('z', 1),
+            (Self::Russian, 'ё') => Some(weight('е')),
+            _ => None,
+        }
+    }
+}
+
+/// leaves room for (up to 7) tailored letters after every base letter
+#[inline]
+const fn weight(c: char) -> u32 {
+    (c as u32) << 3
+}
+
+/// base letters and accent of (lowercase) accented letters
+const fn fold(c: char) -> Option<(&'static str, u8)> {
+    const GRAVE: u8 = 1;
+    const ACUTE: u8 = 2;
+    const CIRCUMFLEX: u8 = 3;
+    const TILDE: u8 = 4;
+    const DIAERESIS: u8 = 5;
+    const RING: u8 = 6;
+    const CEDILLA: u8 = 7;
+    const CARON: u8 = 8;
+    const BREVE: u8 = 9;
+    const MACRON: u8 = 10;
+    const OGONEK: u8 = 11;
+    const DOT: u8 = 12;
+    const STROKE: u8 = 13;
+    const DOUBLE_ACUTE: u8 = 14;
+    const LIGATURE: u8 = 15;
+    Some(match c {
+        'à' => ("a", GRAVE),
+        'á' => ("a", ACUTE),
+        'â' => ("a", CIRCUMFLEX),
+        'ã' => ("a", TILDE),
+        'ä' => ("a", DIAERESIS),
+        'å' => ("a", RING),
+        'ā' => ("a", MACRON),
+        'ă' => ("a", BREVE),
+        'ą' => ("a", OGONEK),
+        'æ' => ("ae", LIGATURE),
+        'ç' => ("c", CEDILLA),
+        'ć' => ("c", ACUTE),
+        'č' => ("c", CARON),
+        'ď' => ("d", CARON),
+        'đ' => ("d", STROKE),
+        'è' => ("e", GRAVE),
+        'é' => ("e", ACUTE),
+        'ê' => ("e", CIRCUMFLEX),
+        'ë' => ("e", DIAERESIS),
+        'ē' => ("e", MACRON),
+        'ė' => ("e", DOT),
+        'ę' => ("e", OGONEK),
+        'ě' => ("e", CARON),
+        'ğ' => ("g", BREVE),
+        'ì' => ("i", GRAVE),
+        'í' => ("i", ACUTE),
+        'î' => ("i", CIRCUMFLEX),
+        'ï' => ("i", DIAERESIS),
+        'ī' => ("i", MACRON),
+        'į' => ("i", OGONEK),
+        'ı' => ("i", DOT),
+        'ł' => ("l", STROKE),
+        'ñ' => ("n", TILDE),
+        'ń' => ("n", ACUTE),
+        'ň' => ("n", CARON),
+        'ò' => ("o", GRAVE),
+        'ó' => ("o", ACUTE),
+        'ô' => ("o", CIRCUMFLEX),
+        'õ' => ("o", TILDE),
+        'ö' => ("o", DIAERESIS),
+        'ø' => ("o", STROKE),
+        'ō' => ("o", MACRON),
+        'ő' => ("o", DOUBLE_ACUTE),
+        'œ' => ("oe", LIGATURE),
+        'ŕ' => ("r", ACUTE),
+        'ř' => ("r", CARON),
+        'ß' => ("ss", LIGATURE),
+        'ś' => ("s", ACUTE),
+        'š' => ("s", CARON),
+        'ş' | 'ș' => ("s", CEDILLA),
+        'ť' => ("t", CARON),
+        'ţ' | 'ț' => ("t", CEDILLA),
+        'ù' => ("u", GRAVE),
+        'ú' => ("u", ACUTE),
+        'û' => ("u", CIRCUMFLEX),
+        'ü' => ("u", DIAERESIS),
+        'ū' => ("u", MACRON),
+        'ů' => ("u", RING),
+        'ű' => ("u", DOUBLE_ACUTE),
+        'ý' => ("y", ACUTE),
+        'ÿ' => ("y", DIAERESIS),
+        'ź' => ("z", ACUTE),
+        'ż' => ("z", DOT),
+        'ž' => ("z", CARON),
+        'ё' => ("е", DIAERESIS),
+        _ => return None,
+    })
+}
+
+crate::tests! {
+    german_collation {
+        let mut words = vec!["Zebra", "Mutter", "Müller", "Äpfel", "Muller", "Straße", "Strasse"];
+        Language::German.sort_by_collation(&mut words, |w| *w);
+        assert_eq!(
+            words,
+            ["Äpfel", "Muller", "Müller", "Mutter", "Strasse", "Straße", "Zebra"]
+        );
+        // byte-wise, "Ä" > "Z"
+        assert_eq!(Language::German.collate("Äpfel", "Zebra"), std::cmp::Ordering::Less);
+    };
+    tailored_collation {
+        use std::cmp::Ordering;
+        // in Finnish, "ä" is a letter of its own after "z"
+        assert_eq!(Language::Finnish.collate("äiti", "zeta"), Ordering::Greater);
+        assert_eq!(Language::German.collate("äiti", "zeta"), Ordering::Less);
+        // Turkish dotless ı precedes i
+        assert_eq!(Language::Turkish.collate("ılık", "iki"), Ordering::Less);
+        assert_eq!(Language::Turkish.collate("Işık", "ılık"), Ordering::Greater);
+        // case only matters as a last resort
+        assert_eq!(Language::English.collate("apple", "Banana"), Ordering::Less);
+        assert_eq!(Language::English.collate("apple", "Apple"), Ordering::Less);
+    }
+}
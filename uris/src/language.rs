@@ -230,6 +230,20 @@ pub enum Language {
 impl Language {
     pub(crate) const SEPARATOR: char = 'l';
 
+    /// All supported languages.
+    pub const ALL: [Self; 10] = [
+        Self::English,
+        Self::German,
+        Self::French,
+        Self::Romanian,
+        Self::Arabic,
+        Self::Bulgarian,
+        Self::Russian,
+        Self::Finnish,
+        Self::Turkish,
+        Self::Slovenian,
+    ];
+
     /// Returns the Unicode flag emoji for this language.
     ///
     /// # Examples
@@ -257,6 +271,80 @@ impl Language {
         unsafe { self.get_str("svg").unwrap_unchecked() }
     }
 
+    /// Whether this language is written right-to-left.
+    ///
+    /// Of the supported languages, only [`Arabic`](Self::Arabic) is; all others are
+    /// written left-to-right.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ftml_uris::prelude::*;
+    /// assert!(Language::Arabic.is_rtl());
+    /// assert!(!Language::English.is_rtl());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn is_rtl(self) -> bool {
+        matches!(self, Self::Arabic)
+    }
+
+    /// The canonical [BCP 47](https://www.rfc-editor.org/info/bcp47) tag of this language, as
+    /// registered in the [IANA Language Subtag Registry](https://www.iana.org/assignments/language-subtag-registry).
+    ///
+    /// For all supported languages, this is the primary language subtag only, i.e. coincides
+    /// with the [ISO 639-1](https://en.wikipedia.org/wiki/ISO_639) code used in
+    /// [`DocumentUri`](crate::DocumentUri)s (see [`Display`](std::fmt::Display)).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ftml_uris::prelude::*;
+    /// assert_eq!(Language::Slovenian.bcp47(), "sl");
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn bcp47(self) -> &'static str {
+        match self {
+            Self::English => "en",
+            Self::German => "de",
+            Self::French => "fr",
+            Self::Romanian => "ro",
+            Self::Arabic => "ar",
+            Self::Bulgarian => "bg",
+            Self::Russian => "ru",
+            Self::Finnish => "fi",
+            Self::Turkish => "tr",
+            Self::Slovenian => "sl",
+        }
+    }
+
+    /// The English name of this language, as given in the "Description" field of the
+    /// [IANA Language Subtag Registry](https://www.iana.org/assignments/language-subtag-registry).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ftml_uris::prelude::*;
+    /// assert_eq!(Language::German.english_name(), "German");
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn english_name(self) -> &'static str {
+        match self {
+            Self::English => "English",
+            Self::German => "German",
+            Self::French => "French",
+            Self::Romanian => "Romanian",
+            Self::Arabic => "Arabic",
+            Self::Bulgarian => "Bulgarian",
+            Self::Russian => "Russian",
+            Self::Finnish => "Finnish",
+            Self::Turkish => "Turkish",
+            Self::Slovenian => "Slovenian",
+        }
+    }
+
     /// Extracts language from a relative file path.
     ///
     /// This method parses file paths to extract language codes, particularly
@@ -343,5 +431,25 @@ crate::tests! {
         assert_eq!(Language::from(Path::new("file.tex")), Language::English); // No language -> default
         assert_eq!(Language::from(Path::new("file")), Language::English); // No extension -> default
         assert_eq!(Language::from(Path::new("")), Language::English); // Empty -> default
+    };
+    text_direction {
+        let rtl = Language::ALL
+            .into_iter()
+            .filter(|l| l.is_rtl())
+            .collect::<Vec<_>>();
+        assert_eq!(rtl, [Language::Arabic]);
+    };
+    locale_metadata {
+        assert_eq!(Language::English.bcp47(), "en");
+        assert_eq!(Language::Arabic.bcp47(), "ar");
+        assert_eq!(Language::Finnish.bcp47(), "fi");
+        assert_eq!(Language::Arabic.english_name(), "Arabic");
+        assert!(Language::Arabic.is_rtl());
+        assert!(!Language::Russian.is_rtl());
+        assert!(!Language::Turkish.is_rtl());
+        for lang in Language::ALL {
+            assert_eq!(lang.bcp47(), lang.to_string());
+            assert_eq!(lang.bcp47().parse::<Language>(), Ok(lang));
+        }
     }
 }
@@ -42,7 +42,9 @@ mod uris {
     pub mod paths;
     pub mod symbol;
 }
+pub mod collation;
 mod language;
+pub mod set;
 mod utils;
 #[allow(clippy::wildcard_imports)]
 pub(crate) use uris::*;
@@ -74,6 +76,7 @@ pub mod prelude {
     pub use crate::doc_element::DocumentElementUri;
     pub use crate::document::{DocumentUri, SimpleUriName};
     pub use crate::module::{ModuleUri, UriName};
+    pub use crate::set::{SymbolSet, UriSet};
     pub use crate::traits::{
         FtmlUri, IsDomainUri, IsNarrativeUri, NamedUri, UriWithArchive, UriWithPath,
     };
@@ -114,7 +117,12 @@ pub(crate) mod sealed {
 /// ```
 #[allow(clippy::unsafe_derive_deserialize)]
 #[derive(Clone, PartialEq, Eq, Hash, strum::EnumDiscriminants)]
-#[strum_discriminants(vis(pub), name(UriKind), derive(strum::Display))]
+#[strum_discriminants(
+    vis(pub),
+    name(UriKind),
+    derive(strum::Display, strum::EnumString, strum::VariantArray),
+    strum(ascii_case_insensitive)
+)]
 #[cfg_attr(
     feature = "serde",
     strum_discriminants(derive(
@@ -169,6 +177,206 @@ impl Uri {
             Self::DocumentElement(_) => UriKind::DocumentElement,
         }
     }
+
+    /// Whether `self` and `other` are of the same [`UriKind`]; cheaper than comparing
+    /// their [`kind`](Uri::kind)s.
+    #[inline]
+    #[must_use]
+    pub fn same_kind(&self, other: &Self) -> bool {
+        std::mem::discriminant(self) == std::mem::discriminant(other)
+    }
+
+    /// Returns the canonical representative of this [`Uri`], suitable e.g. as a cache key.
+    ///
+    /// Parsing already resolves most redundancies: trailing separators of the [`BaseUri`]
+    /// are removed, and an omitted language (`l=`) is resolved to [`Language::default()`],
+    /// so `…&d=doc` and `…&d=doc&l=en` parse to the same value. What remains is that
+    /// a [`PathUri`] without path component denotes the same resource as its
+    /// [`ArchiveUri`] (and has the same string representation), but is a different
+    /// variant; the canonical form is [`Uri::Archive`] in that case.
+    ///
+    /// Two [`Uri`]s denote the same resource iff their normalized forms are equal.
+    #[must_use]
+    pub fn normalized(&self) -> Self {
+        match self {
+            Self::Path(PathUri {
+                archive,
+                path: None,
+            }) => Self::Archive(archive.clone()),
+            o => o.clone(),
+        }
+    }
+
+    /// Truncates this [`Uri`] to its ancestor of the given [`UriKind`] (or itself, if it is
+    /// of that kind); i.e. the dynamic counterpart of the `From` conversions between the
+    /// uri types, for when the target kind is only known at runtime.
+    ///
+    /// The ancestors are: [`Base`](UriKind::Base) < [`Archive`](UriKind::Archive) <
+    /// [`Path`](UriKind::Path) < [`Module`](UriKind::Module) < [`Symbol`](UriKind::Symbol)
+    /// and [`Path`](UriKind::Path) < [`Document`](UriKind::Document) <
+    /// [`DocumentElement`](UriKind::DocumentElement). Truncating to a finer or unrelated
+    /// kind (e.g. a [`SymbolUri`] to a [`Document`](UriKind::Document), or an [`ArchiveUri`]
+    /// to a [`Path`](UriKind::Path)) returns `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ftml_uris::prelude::*;
+    /// # use ftml_uris::UriKind;
+    /// # use std::str::FromStr;
+    /// let uri = Uri::from_str("http://example.com?a=archive&m=module&s=symbol").unwrap();
+    /// let module = uri.truncate_to(UriKind::Module).unwrap();
+    /// assert_eq!(module.to_string(), "http://example.com?a=archive&m=module");
+    /// assert!(uri.truncate_to(UriKind::Document).is_none());
+    /// ```
+    #[must_use]
+    pub fn truncate_to(&self, kind: UriKind) -> Option<Self> {
+        match kind {
+            UriKind::Base => Some(Self::Base(self.base().clone())),
+            UriKind::Archive => match self {
+                Self::Base(_) => None,
+                Self::Archive(a) => Some(a),
+                Self::Path(p) => Some(p.archive_uri()),
+                Self::Module(m) => Some(m.archive_uri()),
+                Self::Symbol(s) => Some(s.archive_uri()),
+                Self::Document(d) => Some(d.archive_uri()),
+                Self::DocumentElement(e) => Some(e.archive_uri()),
+            }
+            .cloned()
+            .map(Self::Archive),
+            UriKind::Path => match self {
+                Self::Base(_) | Self::Archive(_) => None,
+                Self::Path(p) => Some(p),
+                Self::Module(m) => Some(m.path_uri()),
+                Self::Symbol(s) => Some(s.path_uri()),
+                Self::Document(d) => Some(d.path_uri()),
+                Self::DocumentElement(e) => Some(e.path_uri()),
+            }
+            .cloned()
+            .map(Self::Path),
+            UriKind::Module => match self {
+                Self::Module(m) => Some(m),
+                Self::Symbol(s) => Some(s.module_uri()),
+                _ => None,
+            }
+            .cloned()
+            .map(Self::Module),
+            UriKind::Document => match self {
+                Self::Document(d) => Some(d),
+                Self::DocumentElement(e) => Some(e.document_uri()),
+                _ => None,
+            }
+            .cloned()
+            .map(Self::Document),
+            UriKind::Symbol | UriKind::DocumentElement => {
+                (self.kind() == kind).then(|| self.clone())
+            }
+        }
+    }
+
+    /// Returns a short human-readable description of this [`Uri`], e.g. for tooltips (as
+    /// opposed to the machine-readable [`Display`](std::fmt::Display) representation).
+    ///
+    /// The format depends on the kind of [`Uri`]:
+    /// - [`Base`](Uri::Base): ``Base URI `http://example.com` ``
+    /// - [`Archive`](Uri::Archive) (and [`Path`](Uri::Path) without path): ``Archive `A` ``
+    /// - [`Path`](Uri::Path): ``Path `p` (archive `A`)``
+    /// - [`Module`](Uri::Module): ``Module `M` (archive `A`)``
+    /// - [`Symbol`](Uri::Symbol): ``Symbol `x` in module `M` (archive `A`)``
+    /// - [`Document`](Uri::Document): ``Document `d` [en] (archive `A`)``
+    /// - [`DocumentElement`](Uri::DocumentElement): ``Element `e` in document `d` (archive `A`)``
+    ///
+    /// Paths within the archive are only mentioned for [`Path`](Uri::Path)s themselves.
+    #[must_use]
+    pub fn describe(&self) -> String {
+        match self {
+            Self::Base(b) => format!("Base URI `{b}`"),
+            Self::Archive(a)
+            | Self::Path(PathUri {
+                archive: a,
+                path: None,
+            }) => format!("Archive `{}`", a.id),
+            Self::Path(PathUri {
+                archive,
+                path: Some(p),
+            }) => format!("Path `{p}` (archive `{}`)", archive.id),
+            Self::Module(m) => format!("Module `{}` (archive `{}`)", m.name, m.archive_id()),
+            Self::Symbol(s) => format!(
+                "Symbol `{}` in module `{}` (archive `{}`)",
+                s.name,
+                s.module.name,
+                s.archive_id()
+            ),
+            Self::Document(d) => format!(
+                "Document `{}` [{}] (archive `{}`)",
+                d.name,
+                d.language,
+                d.archive_id()
+            ),
+            Self::DocumentElement(e) => format!(
+                "Element `{}` in document `{}` (archive `{}`)",
+                e.name,
+                e.document.name,
+                e.archive_id()
+            ),
+        }
+    }
+
+    /// Displays this [`Uri`] in the given [`DisplayStyle`]; the [`Display`](std::fmt::Display)
+    /// implementation of [`Uri`] itself is always [`Canonical`](DisplayStyle::Canonical).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ftml_uris::{prelude::*, DisplayStyle};
+    /// # use std::str::FromStr;
+    /// let uri = Uri::from_str("http://example.com?a=archive&d=Example%203&l=en").unwrap();
+    /// assert_eq!(
+    ///     uri.display_with(DisplayStyle::Pretty).to_string(),
+    ///     "http://example.com?a=archive&d=Example 3&l=en"
+    /// );
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn display_with(&self, style: DisplayStyle) -> UriDisplay<'_> {
+        UriDisplay { uri: self, style }
+    }
+}
+
+/// How to [display](Uri::display_with) a [`Uri`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum DisplayStyle {
+    /// The machine-readable string representation, with all components exactly as stored
+    /// (i.e. percent-encoded where applicable); parses back to the same [`Uri`]. Compact
+    /// and unambiguous, e.g. for logs and cache keys.
+    #[default]
+    Canonical,
+    /// For presenting to users: percent-encoded sequences (e.g. `%20`) are decoded. The
+    /// result is not necessarily a valid [`Uri`] anymore; sequences that do not decode to
+    /// valid UTF-8 are displayed [canonically](Self::Canonical).
+    Pretty,
+}
+
+/// A [`Uri`] [displayed](std::fmt::Display) in some [`DisplayStyle`]; see
+/// [`Uri::display_with`].
+#[derive(Copy, Clone, Debug)]
+pub struct UriDisplay<'u> {
+    uri: &'u Uri,
+    style: DisplayStyle,
+}
+impl std::fmt::Display for UriDisplay<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.style {
+            DisplayStyle::Canonical => self.uri.fmt(f),
+            DisplayStyle::Pretty => {
+                let canonical = self.uri.to_string();
+                match urlencoding::decode(&canonical) {
+                    Ok(decoded) => f.write_str(&decoded),
+                    Err(_) => f.write_str(&canonical),
+                }
+            }
+        }
+    }
 }
 
 #[cfg_attr(feature = "typescript", wasm_bindgen::prelude::wasm_bindgen)]
@@ -187,7 +395,16 @@ pub fn rdf_encode(s: &str) -> Option<String> {
 }
 
 /// Like [Uri], but on references rather than owned values
+///
+/// Serializes exactly like the [`Uri`] it refers to (i.e. as its string representation), but
+/// can not be deserialized, since it borrows; deserialize a [`Uri`] instead (e.g. on the
+/// receiving end of an API taking a [`UriRef`]), and use [`owned`](Self::owned) to obtain
+/// one from a [`UriRef`] where both are needed.
 #[derive(Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_with::SerializeDisplay, bincode::Encode)
+)]
 pub enum UriRef<'u> {
     /// A base URI with no additional components.
     Base(&'u BaseUri),
@@ -321,6 +538,9 @@ crate::debugdisplay!(DomainUri);
 impl crate::sealed::Sealed for DomainUri {}
 
 /// Like [`DomainUri`] but wrapping around references
+///
+/// Serializes exactly like the [`DomainUri`] it refers to, but can not be deserialized, since
+/// it borrows; deserialize a [`DomainUri`] instead (see [`owned`](Self::owned)).
 #[derive(Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(
     feature = "serde",
@@ -335,6 +555,9 @@ pub enum DomainUriRef<'u> {
 impl crate::sealed::Sealed for DomainUriRef<'_> {}
 
 /// Like [`NarrativeUri`] but wrapping around references
+///
+/// Serializes exactly like the [`NarrativeUri`] it refers to, but can not be deserialized,
+/// since it borrows; deserialize a [`NarrativeUri`] instead (see [`owned`](Self::owned)).
 #[derive(Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(
     feature = "serde",
@@ -347,6 +570,7 @@ pub enum NarrativeUriRef<'u> {
     Element(&'u DocumentElementUri),
 }
 impl NarrativeUriRef<'_> {
+    /// convert this reference into an owned [`NarrativeUri`]
     #[must_use]
     pub fn owned(self) -> NarrativeUri {
         match self {
@@ -600,6 +824,38 @@ impl FromStr for Uri {
     }
 }
 
+/// Parses (and, with the `interned` feature, interns) many [`Uri`]s at once.
+///
+/// Equivalent to `strings.map(Uri::from_str).collect()`, except that the locks of all
+/// interning stores (ids, archive ids, names, paths) are acquired *once* for the
+/// whole batch rather than once per newly interned segment. Other threads interning
+/// *new* strings block until the batch is done; looking up already interned ones
+/// does not. Base URIs are still looked up per string, under their own lock.
+///
+/// The resulting [`Uri`]s are identical (in particular pointer-equal) to those
+/// obtained by parsing the strings individually.
+#[must_use]
+pub fn intern_bulk<'a>(strings: impl Iterator<Item = &'a str>) -> Vec<Result<Uri, UriParseError>> {
+    #[cfg(feature = "interned")]
+    let _guards = utils::interned::lock_stores();
+    strings.map(Uri::from_str).collect()
+}
+
+/// Parses (and, with the `interned` feature, interns) many [`Uri`]s in parallel
+/// using [`rayon`]; the results are in the same order as `strings`.
+///
+/// The interning stores are shared between all threads: equal segments are interned
+/// exactly once, so parsing equal strings - on the same or on different threads -
+/// yields pointer-equal [`Uri`]s, which are moreover equal to those obtained via
+/// [`Uri::from_str`]. Lookups of already interned strings proceed concurrently;
+/// only the insertion of new strings into a store is serialized.
+#[cfg(feature = "rayon")]
+#[must_use]
+pub fn parse_many_parallel(strings: &[&str]) -> Vec<Result<Uri, UriParseError>> {
+    use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+    strings.par_iter().map(|s| Uri::from_str(s)).collect()
+}
+
 impl FromStr for DomainUri {
     type Err = errors::UriParseError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
@@ -771,6 +1027,45 @@ impl std::fmt::Display for Uri {
         }
     }
 }
+/// The markers [`Uri::could_be`] dispatches on, collected in a single pass over the string
+/// (rather than one [`str::contains`] per marker).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct QueryMarkers(u8);
+impl QueryMarkers {
+    /// `?a`
+    const QUERY_ARCHIVE: u8 = 1;
+    /// `?d`
+    const QUERY_DOCUMENT: u8 = 1 << 1;
+    /// `?m`
+    const QUERY_MODULE: u8 = 1 << 2;
+    /// `&s`
+    const SYMBOL: u8 = 1 << 3;
+    /// `&e`
+    const ELEMENT: u8 = 1 << 4;
+
+    fn scan(s: &str) -> Self {
+        let mut flags = 0;
+        let mut previous = 0;
+        for b in s.bytes() {
+            flags |= match (previous, b) {
+                (b'?', b'a') => Self::QUERY_ARCHIVE,
+                (b'?', b'd') => Self::QUERY_DOCUMENT,
+                (b'?', b'm') => Self::QUERY_MODULE,
+                (b'&', b's') => Self::SYMBOL,
+                (b'&', b'e') => Self::ELEMENT,
+                _ => 0,
+            };
+            previous = b;
+        }
+        Self(flags)
+    }
+
+    #[inline]
+    const fn has(self, flag: u8) -> bool {
+        self.0 & flag != 0
+    }
+}
+
 impl FtmlUri for Uri {
     fn url_encoded(&self) -> impl std::fmt::Display {
         #[allow(clippy::enum_glob_use)]
@@ -824,13 +1119,21 @@ impl FtmlUri for Uri {
     }
 
     fn could_be(maybe_uri: &str) -> bool {
-        if !maybe_uri.contains("?a") {
-            return BaseUri::could_be(maybe_uri);
-        }
-        if maybe_uri.contains("?d") {
-            NarrativeUri::could_be(maybe_uri)
-        } else if maybe_uri.contains("?m") {
-            DomainUri::could_be(maybe_uri)
+        let markers = QueryMarkers::scan(maybe_uri);
+        if !markers.has(QueryMarkers::QUERY_ARCHIVE) {
+            BaseUri::could_be(maybe_uri)
+        } else if markers.has(QueryMarkers::QUERY_DOCUMENT) {
+            if markers.has(QueryMarkers::ELEMENT) {
+                DocumentElementUri::could_be(maybe_uri)
+            } else {
+                DocumentUri::could_be(maybe_uri)
+            }
+        } else if markers.has(QueryMarkers::QUERY_MODULE) {
+            if markers.has(QueryMarkers::SYMBOL) {
+                SymbolUri::could_be(maybe_uri)
+            } else {
+                ModuleUri::could_be(maybe_uri)
+            }
         } else {
             PathUri::could_be(maybe_uri)
         }
@@ -1102,6 +1405,15 @@ impl std::fmt::Debug for DomainUriRef<'_> {
 }
 
 impl<'u> DomainUriRef<'u> {
+    /// convert this reference into an owned [`DomainUri`]
+    #[must_use]
+    pub fn owned(self) -> DomainUri {
+        match self {
+            Self::Module(m) => DomainUri::Module(m.clone()),
+            Self::Symbol(s) => DomainUri::Symbol(s.clone()),
+        }
+    }
+
     #[inline]
     #[must_use]
     pub fn base(self) -> &'u BaseUri {
@@ -1417,6 +1729,18 @@ impl From<LeafUri> for Uri {
     }
 }
 
+impl TryFrom<Uri> for LeafUri {
+    type Error = errors::NotALeafUri;
+    #[inline]
+    fn try_from(value: Uri) -> Result<Self, Self::Error> {
+        match value {
+            Uri::Symbol(s) => Ok(Self::Symbol(s)),
+            Uri::DocumentElement(e) => Ok(Self::Element(e)),
+            uri => Err(errors::NotALeafUri(uri)),
+        }
+    }
+}
+
 impl PartialEq<str> for LeafUri {
     fn eq(&self, other: &str) -> bool {
         match self {
@@ -1628,7 +1952,102 @@ fn trace() {
     let _ = tracing_subscriber::fmt().try_init();
 }
 
+/// The symbol `name` in `http://example.com?a=archive&m=module`
+#[cfg(test)]
+fn sym(name: &str) -> SymbolUri {
+    format!("http://example.com?a=archive&m=module&s={name}")
+        .parse()
+        .expect("works")
+}
+
 crate::tests! {
+    uri_kind_from_str {
+        use strum::VariantArray;
+        for kind in UriKind::VARIANTS {
+            let s = kind.to_string();
+            assert_eq!(s.parse::<UriKind>().expect("works"), *kind);
+            assert_eq!(s.to_lowercase().parse::<UriKind>().expect("works"), *kind);
+            assert_eq!(s.to_uppercase().parse::<UriKind>().expect("works"), *kind);
+        }
+        assert_eq!("symbol".parse::<UriKind>().expect("works"), UriKind::Symbol);
+        assert!("document_element".parse::<UriKind>().is_err());
+    };
+    display_styles {
+        use std::str::FromStr;
+        let uri = Uri::from_str("http://example.com?a=archive&p=some%20path&m=my%20module&s=x%2By")
+            .expect("works");
+        assert_eq!(
+            uri.display_with(DisplayStyle::Canonical).to_string(),
+            "http://example.com?a=archive&p=some%20path&m=my%20module&s=x%2By"
+        );
+        assert_eq!(uri.display_with(DisplayStyle::Canonical).to_string(), uri.to_string());
+        assert_eq!(
+            uri.display_with(DisplayStyle::Pretty).to_string(),
+            "http://example.com?a=archive&p=some path&m=my module&s=x+y"
+        );
+        let labeled = Uri::Document(DocumentUri::anonymous_with_label("Example 3"));
+        assert!(labeled.display_with(DisplayStyle::Pretty).to_string().contains("d=Example 3&"));
+    };
+    #[cfg(feature = "serde")]
+    refs_deserialize_as_owned {
+        use std::str::FromStr;
+        let document = DocumentUri::from_str("http://example.com?a=archive&p=path&d=doc&l=en").expect("works");
+        let element = DocumentElementUri::from_str("http://example.com?a=archive&d=doc&l=en&e=sec/par").expect("works");
+        for narrative in [NarrativeUriRef::Document(&document), NarrativeUriRef::Element(&element)] {
+            let json = serde_json::to_string(&narrative).expect("works");
+            let owned: NarrativeUri = serde_json::from_str(&json).expect("works");
+            assert_eq!(owned, narrative.clone().owned());
+            let uri: Uri = serde_json::from_str(&json).expect("works");
+            assert_eq!(uri, narrative.as_uri().owned());
+        }
+        let symbol = SymbolUri::from_str("http://example.com?a=archive&m=module&s=symbol").expect("works");
+        let json = serde_json::to_string(&DomainUriRef::Symbol(&symbol)).expect("works");
+        assert_eq!(serde_json::from_str::<DomainUri>(&json).expect("works"), DomainUri::Symbol(symbol.clone()));
+        assert_eq!(json, serde_json::to_string(&UriRef::Symbol(&symbol)).expect("works"));
+    };
+    could_be_single_pass {
+        // the previous implementation, scanning once per marker
+        fn reference(maybe_uri: &str) -> bool {
+            if !maybe_uri.contains("?a") {
+                return BaseUri::could_be(maybe_uri);
+            }
+            if maybe_uri.contains("?d") {
+                NarrativeUri::could_be(maybe_uri)
+            } else if maybe_uri.contains("?m") {
+                DomainUri::could_be(maybe_uri)
+            } else {
+                PathUri::could_be(maybe_uri)
+            }
+        }
+        let cases = [
+            "",
+            "http://example.com",
+            "http://example.com?a=archive",
+            "http://example.com?a=archive&p=path",
+            "http://example.com?a=archive&m=module",
+            "http://example.com?a=archive&p=path&m=module&s=symbol",
+            "http://example.com?a=archive&d=doc&l=en",
+            "http://example.com?a=archive&p=path&d=doc&l=en&e=elem",
+            "http://example.com?d=doc&l=en&e=elem",
+            "http://example.com?m=module&s=symbol",
+            "http://example.com?d=doc?a=archive&e=elem",
+            "http://example.com?m=mod?a=archive&s=sym",
+            "http://example.com?m=mod?a=archive",
+            "http://example.com?d=doc?a=archive",
+            "?a=archive",
+            "?a",
+            "&e&s?d?m?a",
+            "not a uri",
+            "http://example.com?a=archive&s=symbol&e=elem",
+            "http://ex\\ample.com?a=archive",
+            "ünï://cödé?a=ärchive&p=päth",
+        ];
+        for case in cases {
+            assert_eq!(Uri::could_be(case), reference(case), "{case}");
+        }
+        assert_eq!(QueryMarkers::scan("?a&s?d&e?m"), QueryMarkers(0b1_1111));
+        assert_eq!(QueryMarkers::scan("a?s&a&d&m"), QueryMarkers(0));
+    };
     uri_enum {
         use std::str::FromStr;
 
@@ -1751,6 +2170,64 @@ crate::tests! {
         let uri_from_narrative: Uri = NarrativeUri::Document(document_uri).into();
         assert_eq!(uri_from_narrative.to_string(),  "http://example.com?a=archive&p=path&d=document&l=de");
     };
+    leaf_uri_conversion {
+        use std::str::FromStr;
+
+        let symbol = Uri::from_str("http://example.com?a=archive&m=module&s=symbol").expect("works");
+        let LeafUri::Symbol(symbol_uri) = LeafUri::try_from(symbol.clone()).expect("works") else {
+            panic!("Didn't work!")
+        };
+        assert_eq!(Uri::from(LeafUri::Symbol(symbol_uri)), symbol);
+
+        let element = Uri::from_str("http://example.com?a=archive&d=document&l=en&e=elem").expect("works");
+        let LeafUri::Element(element_uri) = LeafUri::try_from(element.clone()).expect("works") else {
+            panic!("Didn't work!")
+        };
+        assert_eq!(Uri::from(LeafUri::Element(element_uri)), element);
+
+        let module = Uri::from_str("http://example.com?a=archive&m=module").expect("works");
+        let err = LeafUri::try_from(module.clone()).expect_err("module uris are not leaves");
+        assert_eq!(err.0, module);
+        assert_eq!(
+            err.to_string(),
+            "Module uri http://example.com?a=archive&m=module is not a leaf (symbol or document element) uri"
+        );
+    };
+    uri_normalization {
+        use std::hash::BuildHasher;
+        use std::str::FromStr;
+
+        let explicit = Uri::from_str("http://example.com/?a=archive&d=doc&l=en").expect("works");
+        let elided = Uri::from_str("http://example.com?a=archive&d=doc").expect("works");
+        assert_eq!(explicit.normalized(), elided.normalized());
+        assert_eq!(explicit.normalized().to_string(), elided.normalized().to_string());
+        let hasher = std::hash::RandomState::new();
+        assert_eq!(hasher.hash_one(explicit.normalized()), hasher.hash_one(elided.normalized()));
+
+        let archive = ArchiveUri::from_str("http://example.com?a=archive").expect("works");
+        let path = Uri::Path(PathUri::from(archive.clone()));
+        assert_ne!(path, Uri::Archive(archive.clone()));
+        assert_eq!(path.normalized(), Uri::Archive(archive));
+        assert_eq!(explicit.normalized(), explicit);
+    };
+    uri_description {
+        use std::str::FromStr;
+
+        let symbol = Uri::from_str("http://example.com?a=math/core&p=algebra&m=groups&s=inverse")
+            .expect("works");
+        assert_eq!(
+            symbol.describe(),
+            "Symbol `inverse` in module `groups` (archive `math/core`)"
+        );
+        let base = Uri::from_str("http://example.com").expect("works");
+        assert_eq!(base.describe(), "Base URI `http://example.com`");
+        let element = Uri::from_str("http://example.com?a=archive&d=doc&l=de&e=sec/par")
+            .expect("works");
+        assert_eq!(
+            element.describe(),
+            "Element `sec/par` in document `doc` (archive `archive`)"
+        );
+    };
     trait_implementations {
         use std::str::FromStr;
 
@@ -1768,6 +2245,100 @@ crate::tests! {
         assert_eq!(module_uri.path().expect("works").to_string(), "path");
         assert_eq!(module_uri.module_name().to_string(), "math/algebra");
     };
+    bulk_interning {
+        let strings = [
+            "http://example.com?a=some/archive/identifier&p=some/longer/path&m=some_module_name&s=some_symbol_name",
+            "http://example.com?a=some/archive/identifier&p=some/longer/path&d=some_document_name&l=en",
+            "http://example.com?a=some/archive/identifier&p=some/longer/path&m=some_module_name",
+            "http://example.com?a=another/archive/identifier",
+            "http://example.com?a=some/archive/identifier&x=unknown",
+        ];
+        let bulk = intern_bulk(strings.iter().copied());
+        assert_eq!(bulk.len(), strings.len());
+        for (s, b) in strings.iter().zip(bulk) {
+            match (Uri::from_str(s), b) {
+                (Ok(single), Ok(bulk)) => {
+                    assert_eq!(single, bulk);
+                    assert_eq!(bulk.to_string(), *s);
+                }
+                (Err(_), Err(_)) => (),
+                (single, bulk) => panic!("{s}: {single:?} != {bulk:?}"),
+            }
+        }
+    };
+    #[cfg(feature = "rayon")]
+    parallel_parsing {
+        let strings = (0..256)
+            .map(|i| format!("http://example.com?a=parallel/archive&p=parallel/path&m=parallel_module_{}&s=parallel_symbol_{}", i % 4, i % 8))
+            .collect::<Vec<_>>();
+        let refs = strings.iter().map(String::as_str).collect::<Vec<_>>();
+        let uris = parse_many_parallel(&refs)
+            .into_iter()
+            .collect::<Result<Vec<_>, _>>()
+            .expect("works");
+        assert_eq!(uris.len(), strings.len());
+        for (i, uri) in uris.iter().enumerate() {
+            assert_eq!(uri.to_string(), strings[i]);
+            assert_eq!(*uri, uris[i % 8]);
+            assert_eq!(*uri, Uri::from_str(&strings[i]).expect("works"));
+            if i % 8 != 0 {
+                assert_ne!(*uri, uris[0]);
+            }
+        }
+    };
+    cross_kind_equality {
+        let module = Uri::from_str("http://example.com?a=archive&p=path&m=module").expect("works");
+        let symbol = Uri::from_str("http://example.com?a=archive&p=path&m=module&s=symbol").expect("works");
+        let document = Uri::from_str("http://example.com?a=archive&p=path&d=module&l=en").expect("works");
+        let archive = Uri::from_str("http://example.com?a=archive").expect("works");
+        let Uri::Archive(a) = archive.clone() else { panic!("Didn't work!") };
+        // same string representation, but different kinds
+        let path = Uri::Path(PathUri { archive: a, path: None });
+        assert_eq!(archive.to_string(), path.to_string());
+
+        let all = [&module, &symbol, &document, &archive, &path];
+        for (i, a) in all.iter().enumerate() {
+            for (j, b) in all.iter().enumerate() {
+                assert_eq!(a.same_kind(b), i == j);
+                assert_eq!(*a == *b, i == j);
+            }
+        }
+        let symbol2 = Uri::from_str("http://example.com?a=archive&p=path&m=module&s=other").expect("works");
+        assert!(symbol.same_kind(&symbol2));
+        assert_ne!(symbol, symbol2);
+        assert_eq!(path.normalized(), archive);
+    };
+    uri_truncation {
+        let symbol = Uri::from_str("http://example.com?a=archive&p=path&m=module&s=symbol").expect("works");
+        let truncated = |kind| symbol.truncate_to(kind).map(|u| u.to_string());
+        assert_eq!(truncated(UriKind::Symbol).as_deref(), Some("http://example.com?a=archive&p=path&m=module&s=symbol"));
+        assert_eq!(truncated(UriKind::Module).as_deref(), Some("http://example.com?a=archive&p=path&m=module"));
+        assert_eq!(truncated(UriKind::Path).as_deref(), Some("http://example.com?a=archive&p=path"));
+        assert_eq!(truncated(UriKind::Archive).as_deref(), Some("http://example.com?a=archive"));
+        assert_eq!(truncated(UriKind::Base).as_deref(), Some("http://example.com"));
+        assert_eq!(symbol.truncate_to(UriKind::Path).map(|u| u.kind()), Some(UriKind::Path));
+        assert!(symbol.truncate_to(UriKind::Document).is_none());
+        assert!(symbol.truncate_to(UriKind::DocumentElement).is_none());
+
+        let archive = Uri::from_str("http://example.com?a=archive").expect("works");
+        assert!(archive.truncate_to(UriKind::Path).is_none());
+        assert!(archive.truncate_to(UriKind::Module).is_none());
+    };
+    name_normalization {
+        use std::str::FromStr;
+        let name = |s| UriName::from_str(s).expect("works");
+        let redundant = name("math/./algebra");
+        assert_eq!(redundant.normalized(), name("math/algebra"));
+        assert_eq!(redundant.to_string(), "math/algebra");
+        assert_eq!(name("math/algebra").normalized(), name("math/algebra"));
+        assert_eq!(name("./math/algebra/.").normalized(), name("math/./algebra").normalized());
+        assert_eq!(name("./.").normalized(), name("."));
+        assert_eq!(name("../algebra").normalized(), name("../algebra"));
+        // parsing normalizes, so equivalent names are equal
+        assert_eq!(name("./math/algebra/."), name("math/algebra"));
+        assert_eq!(name("math/./algebra").as_ref(), "math/algebra");
+        assert!(UriName::from_str("math/./").is_err());
+    };
     uri_sizes {
         tracing::info!("Size of Uri: {}", std::mem::size_of::<Uri>());
         tracing::info!("Size of DomainUri: {}", std::mem::size_of::<DomainUri>());
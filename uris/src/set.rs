@@ -0,0 +1,344 @@
+//! Sets of [`Uri`]s keyed by the identity of their interned components.
+//!
+//! #### Invariant
+//! With the `interned` feature, all URI components ([`ArchiveId`]s, [`UriName`]s,
+//! [`UriPath`]s, [`BaseUri`]s) are interned: for any two *alive* values with the same
+//! contents, the interner hands out the same pointer (or, for short strings, the same
+//! inlined bytes). Equality of URIs is therefore already a comparison of pointers, and
+//! [`UriSet`] additionally *hashes* the pointers instead of the string contents, which makes
+//! both hashing and membership checks independent of the lengths of the URIs.
+//!
+//! Since every element of a [`UriSet`] keeps its components alive, they can not be dropped
+//! from (and later re-interned at a different address in) the interner while they are in
+//! the set - so membership coincides with `==`. The hash values themselves are meaningless
+//! across processes, and a set must not be kept across [`clear_memory`](crate::clear_memory)
+//! (which breaks the invariant for URIs in general).
+//!
+//! Without the `interned` feature, [`UriSet`] falls back to hashing the contents.
+
+use crate::{
+    ArchiveId, ArchiveUri, BaseUri, DocumentElementUri, DocumentUri, DomainUri, Language, LeafUri,
+    ModuleUri, NarrativeUri, PathUri, SimpleUriName, SymbolUri, Uri, UriName, UriPath,
+};
+use std::hash::{Hash, Hasher};
+
+/// Types that can be hashed by the identity of their interned components; see the
+/// [module documentation](self).
+pub trait PtrHash: Eq + private::Sealed {
+    /// Hashes `self` consistently with `==`.
+    fn ptr_hash<H: Hasher>(&self, state: &mut H);
+}
+mod private {
+    pub trait Sealed {}
+}
+
+macro_rules! ptr_hash {
+    (@hash $($t:ty),*) => {$(
+        impl private::Sealed for $t {}
+        impl PtrHash for $t {
+            #[inline]
+            fn ptr_hash<H: Hasher>(&self, state: &mut H) {
+                self.hash(state);
+            }
+        }
+    )*};
+    (@str $($t:ty),*) => {$(
+        impl private::Sealed for $t {}
+        impl PtrHash for $t {
+            #[inline]
+            fn ptr_hash<H: Hasher>(&self, state: &mut H) {
+                self.0.ptr_hash(state);
+            }
+        }
+    )*};
+    (@struct $($t:ty { $($f:ident),* }),*) => {$(
+        impl private::Sealed for $t {}
+        impl PtrHash for $t {
+            #[inline]
+            fn ptr_hash<H: Hasher>(&self, state: &mut H) {
+                $( self.$f.ptr_hash(state); )*
+            }
+        }
+    )*};
+    (@enum $($t:ident { $($v:ident),* }),*) => {$(
+        impl private::Sealed for $t {}
+        impl PtrHash for $t {
+            #[inline]
+            fn ptr_hash<H: Hasher>(&self, state: &mut H) {
+                std::mem::discriminant(self).hash(state);
+                match self {
+                    $( Self::$v(u) => u.ptr_hash(state), )*
+                }
+            }
+        }
+    )*};
+}
+// `BaseUri` already hashes its pointer
+ptr_hash!(@hash BaseUri, Language);
+ptr_hash!(@str ArchiveId, UriName, UriPath, SimpleUriName);
+ptr_hash!(@struct
+    ArchiveUri { base, id },
+    ModuleUri { path, name },
+    SymbolUri { module, name },
+    DocumentUri { path, name, language },
+    DocumentElementUri { document, name }
+);
+ptr_hash!(@enum
+    Uri { Base, Archive, Path, Module, Symbol, Document, DocumentElement },
+    DomainUri { Module, Symbol },
+    NarrativeUri { Document, Element },
+    LeafUri { Symbol, Element }
+);
+impl private::Sealed for PathUri {}
+impl PtrHash for PathUri {
+    #[inline]
+    fn ptr_hash<H: Hasher>(&self, state: &mut H) {
+        self.archive.ptr_hash(state);
+        match &self.path {
+            None => state.write_u8(0),
+            Some(p) => {
+                state.write_u8(1);
+                p.ptr_hash(state);
+            }
+        }
+    }
+}
+
+#[repr(transparent)]
+struct ByPtr<U>(U);
+impl<U> ByPtr<U> {
+    #[inline]
+    const fn from_ref(u: &U) -> &Self {
+        // SAFETY: ByPtr is repr(transparent)
+        unsafe { &*std::ptr::from_ref(u).cast::<Self>() }
+    }
+}
+impl<U: PtrHash> Hash for ByPtr<U> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.ptr_hash(state);
+    }
+}
+impl<U: PartialEq> PartialEq for ByPtr<U> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+impl<U: Eq> Eq for ByPtr<U> {}
+impl<U: Clone> Clone for ByPtr<U> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+/// A set of URIs, hashed by the identity of their interned components (see the
+/// [module documentation](self) for the invariant this relies on).
+pub struct UriSet<U: PtrHash>(std::collections::HashSet<ByPtr<U>, rustc_hash::FxBuildHasher>);
+
+/// A [`UriSet`] of [`SymbolUri`]s
+pub type SymbolSet = UriSet<SymbolUri>;
+
+impl<U: PtrHash> UriSet<U> {
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self(std::collections::HashSet::with_capacity_and_hasher(
+            capacity,
+            rustc_hash::FxBuildHasher,
+        ))
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Adds `uri` to the set; returns `false` if it was already present.
+    #[inline]
+    pub fn insert(&mut self, uri: U) -> bool {
+        self.0.insert(ByPtr(uri))
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn contains(&self, uri: &U) -> bool {
+        self.0.contains(ByPtr::from_ref(uri))
+    }
+
+    /// Removes `uri` from the set; returns `false` if it was not present.
+    #[inline]
+    pub fn remove(&mut self, uri: &U) -> bool {
+        self.0.remove(ByPtr::from_ref(uri))
+    }
+
+    #[inline]
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = &U> {
+        self.0.iter().map(|u| &u.0)
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn is_subset(&self, other: &Self) -> bool {
+        self.0.is_subset(&other.0)
+    }
+
+    /// The URIs in `self` or `other`.
+    #[must_use]
+    pub fn union(&self, other: &Self) -> Self
+    where
+        U: Clone,
+    {
+        let (larger, smaller) = if self.len() >= other.len() {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        let mut ret = larger.clone();
+        ret.0.extend(smaller.0.iter().cloned());
+        ret
+    }
+
+    /// The URIs in both `self` and `other`.
+    #[must_use]
+    pub fn intersection(&self, other: &Self) -> Self
+    where
+        U: Clone,
+    {
+        Self(self.0.intersection(&other.0).cloned().collect())
+    }
+
+    /// The URIs in `self`, but not in `other`.
+    #[must_use]
+    pub fn difference(&self, other: &Self) -> Self
+    where
+        U: Clone,
+    {
+        Self(self.0.difference(&other.0).cloned().collect())
+    }
+}
+
+impl<U: PtrHash> Default for UriSet<U> {
+    #[inline]
+    fn default() -> Self {
+        Self(std::collections::HashSet::default())
+    }
+}
+impl<U: PtrHash + Clone> Clone for UriSet<U> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+impl<U: PtrHash> PartialEq for UriSet<U> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+impl<U: PtrHash> Eq for UriSet<U> {}
+impl<U: PtrHash + std::fmt::Debug> std::fmt::Debug for UriSet<U> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+impl<U: PtrHash> FromIterator<U> for UriSet<U> {
+    #[inline]
+    fn from_iter<T: IntoIterator<Item = U>>(iter: T) -> Self {
+        Self(iter.into_iter().map(ByPtr).collect())
+    }
+}
+impl<U: PtrHash> Extend<U> for UriSet<U> {
+    #[inline]
+    fn extend<T: IntoIterator<Item = U>>(&mut self, iter: T) {
+        self.0.extend(iter.into_iter().map(ByPtr));
+    }
+}
+impl<U: PtrHash> IntoIterator for UriSet<U> {
+    type Item = U;
+    type IntoIter = IntoIter<U>;
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter(self.0.into_iter())
+    }
+}
+
+/// An owning iterator over the elements of a [`UriSet`]
+pub struct IntoIter<U>(std::collections::hash_set::IntoIter<ByPtr<U>>);
+impl<U> Iterator for IntoIter<U> {
+    type Item = U;
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|u| u.0)
+    }
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+impl<U> ExactSizeIterator for IntoIter<U> {}
+
+crate::tests! {
+    set_operations {
+        let a: SymbolSet = [sym("a"), sym("b"), sym("c")].into_iter().collect();
+        let b: SymbolSet = [sym("b"), sym("c"), sym("d")].into_iter().collect();
+
+        let union = a.union(&b);
+        assert_eq!(union.len(), 4);
+        assert!(["a", "b", "c", "d"].into_iter().all(|n| union.contains(&sym(n))));
+
+        let intersection = a.intersection(&b);
+        assert_eq!(intersection, [sym("b"), sym("c")].into_iter().collect());
+        assert!(intersection.is_subset(&a) && intersection.is_subset(&b));
+        assert_eq!(a.difference(&b), std::iter::once(sym("a")).collect());
+
+        let mut a = a;
+        assert!(!a.insert(sym("a")));
+        assert!(a.insert(sym("e")));
+        assert!(a.remove(&sym("b")));
+        assert!(!a.contains(&sym("b")));
+        assert_eq!(a.len(), 3);
+    };
+    membership_is_equality {
+        use std::str::FromStr;
+        // long enough to not be inlined
+        let long = "some/rather/long/module/name/that/is/definitely/interned";
+        let uris = [
+            format!("http://example.com?a=archive&m={long}&s=symbol"),
+            format!("http://example.com?a=archive&p=some/path&m={long}&s=symbol"),
+            format!("http://example.com?a=other/archive&m={long}&s=symbol"),
+            "http://example.com?a=archive&m=module&s=symbol".to_string(),
+            format!("http://example.com?a=archive&d=doc&l=en&e={long}"),
+            format!("http://example.com?a=archive&d=doc&l=de&e={long}"),
+        ];
+        let set: UriSet<Uri> = uris
+            .iter()
+            .map(|s| Uri::from_str(s).expect("works"))
+            .collect();
+        assert_eq!(set.len(), uris.len());
+        for s in &uris {
+            // freshly parsed, i.e. a distinct value
+            let uri = Uri::from_str(s).expect("works");
+            assert!(set.contains(&uri));
+            assert_eq!(set.iter().filter(|u| **u == uri).count(), 1);
+        }
+        let other = format!("http://example.com?a=archive&m={long}&s=other");
+        assert!(!set.contains(&Uri::from_str(&other).expect("works")));
+        let other = "http://example.org?a=archive&m=module&s=symbol";
+        assert!(!set.contains(&Uri::from_str(other).expect("works")));
+    }
+}
@@ -93,6 +93,17 @@ pub trait UriWithArchive: Into<ArchiveUri> + FtmlUri {
     fn archive_id(&self) -> &ArchiveId {
         &self.archive_uri().id
     }
+
+    /// Whether `self` and `other` live in the same archive, i.e. have equal [`ArchiveUri`]s
+    /// (an interned equality check of the [`BaseUri`](crate::BaseUri)s and [`ArchiveId`]s).
+    ///
+    /// Archives are identified by their id *relative to* a [`BaseUri`](crate::BaseUri), so
+    /// URIs with different bases are never in the same archive, even if their archive ids
+    /// coincide.
+    #[inline]
+    fn same_archive(&self, other: &impl UriWithArchive) -> bool {
+        self.archive_uri() == other.archive_uri()
+    }
 }
 
 /// Trait for URI types that have an associated path within an archive.
@@ -118,6 +129,18 @@ pub trait UriWithPath: UriWithArchive + Into<PathUri> + FtmlUri {
     fn path(&self) -> Option<&UriPath> {
         self.path_uri().path.as_ref()
     }
+
+    /// Whether `self` and `other` live under the same path, i.e. have equal [`PathUri`]s (an
+    /// interned equality check of their [`ArchiveUri`]s and [`UriPath`]s). Paths are compared
+    /// exactly, i.e. `a/b` is not the same path as `a`; URIs without a path are under the same
+    /// path iff they are in the [same archive](UriWithArchive::same_archive).
+    ///
+    /// As with [`same_archive`](UriWithArchive::same_archive), URIs with different bases
+    /// are never under the same path.
+    #[inline]
+    fn same_path(&self, other: &impl UriWithPath) -> bool {
+        self.path_uri() == other.path_uri()
+    }
 }
 
 /// Trait for URI types that represent domain knowledge.
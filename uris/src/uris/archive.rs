@@ -55,7 +55,7 @@ static NO_ARCHIVE_URI: std::sync::LazyLock<ArchiveUri> = std::sync::LazyLock::ne
 /// let meta_archive = ArchiveId::from_str("some/path/meta-inf").unwrap();
 /// assert!(meta_archive.is_meta());
 /// ```
-pub struct ArchiveId(NonEmptyStr<IdStore>);
+pub struct ArchiveId(pub(crate) NonEmptyStr<IdStore>);
 crate::ts!(ArchiveId);
 impl ArchiveId {
     /// Returns a reference to the default "no archive" [`ArchiveId`].
@@ -74,6 +74,27 @@ impl DocumentElementUri {
         &self.name
     }
 
+    /// Returns the (full, possibly multi-step) name of this element relative to `doc`, i.e.
+    /// its [name](Self::name) if it is an element of `doc`.
+    ///
+    /// Returns `None` if the element belongs to a different document - including other
+    /// [`Language`](crate::Language) variants of `doc`, since documents are compared exactly.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ftml_uris::prelude::*;
+    /// # use std::str::FromStr;
+    /// let element_uri = DocumentElementUri::from_str("http://example.com?a=archive&d=doc&l=en&e=section/paragraph").unwrap();
+    /// let doc = DocumentUri::from_str("http://example.com?a=archive&d=doc&l=en").unwrap();
+    /// assert_eq!(element_uri.relative_to(&doc).unwrap().as_ref(),"section/paragraph");
+    /// ````
+    #[inline]
+    #[must_use]
+    pub fn relative_to(&self, doc: &DocumentUri) -> Option<&UriName> {
+        (self.document == *doc).then_some(&self.name)
+    }
+
     /// Returns the parent of this element, if one exists.
     ///
     /// # Examples
@@ -107,6 +128,46 @@ impl DocumentElementUri {
         })
     }
 
+    /// Returns a key identifying this element up to the [`Language`](crate::Language) of its
+    /// document, i.e. the corresponding elements of all translations of a document share the
+    /// same key.
+    ///
+    /// Like [`DocumentUri::language_agnostic_key`], this is meant for grouping, not identity.
+    #[inline]
+    #[must_use]
+    pub fn language_agnostic_key(&self) -> impl std::hash::Hash + Eq + Copy + std::fmt::Debug + '_ {
+        (self.document.language_agnostic_key(), &self.name)
+    }
+
+    /// Returns a stable HTML `id` for this element, e.g. for permalinks (`…#anchor`).
+    ///
+    /// The anchor is `ftml-` followed by the [name](Self::name) of the element, where
+    /// - ASCII letters, digits and `-` are kept as is,
+    /// - `/` becomes `__`, and
+    /// - every other character `c` becomes `_<hex>_`, where `<hex>` is the (lowercase)
+    ///   hexadecimal code point of `c` (e.g. `_` becomes `_5f_`).
+    ///
+    /// This encoding is injective, so distinct elements of the same document get distinct
+    /// anchors; the anchor contains no whitespace and is a valid CSS identifier. The document
+    /// itself is not part of the anchor.
+    #[must_use]
+    pub fn anchor_id(&self) -> String {
+        use std::fmt::Write;
+        let name = self.name.as_ref();
+        let mut ret = String::with_capacity(5 + name.len());
+        ret.push_str("ftml-");
+        for c in name.chars() {
+            match c {
+                'a'..='z' | 'A'..='Z' | '0'..='9' | '-' => ret.push(c),
+                '/' => ret.push_str("__"),
+                c => {
+                    let _ = write!(ret, "_{:x}_", u32::from(c));
+                }
+            }
+        }
+        ret
+    }
+
     /// Internal parsing method used by URI parsing infrastructure.
     ///
     /// This method handles the common parsing logic for module URIs and
@@ -336,6 +397,22 @@ crate::tests! {
     doc_elem {
         tracing::info!("Size of DocumentElementUri: {}",std::mem::size_of::<DocumentElementUri>());
     };
+    anchor_ids {
+        use std::str::FromStr;
+        let elem = |s: &str| {
+            DocumentElementUri::from_str(&format!("http://example.com?a=archive&d=doc&e={s}"))
+                .expect("works")
+                .anchor_id()
+        };
+        let nested = elem("sec/para");
+        let flat = elem("sec__para");
+        assert_eq!(nested, "ftml-sec__para");
+        assert_eq!(flat, "ftml-sec_5f__5f_para");
+        assert_ne!(nested, flat);
+        for anchor in [nested, flat, elem("a.b")] {
+            assert!(anchor.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'));
+        }
+    };
     element_uri_parsing {
         use std::str::FromStr;
         // Valid element URIs
@@ -364,6 +441,20 @@ crate::tests! {
         assert_eq!(parent.to_string(),parent_str);
         assert!(parent.parent().is_none());
     };
+    element_uri_relative_names {
+        use std::str::FromStr;
+        let elem = DocumentElementUri::from_str("http://example.com?a=archive&p=folder&d=math&l=en&e=algebra/group/def").expect("works");
+        let doc = DocumentUri::from_str("http://example.com?a=archive&p=folder&d=math&l=en").expect("works");
+        let name = elem.relative_to(&doc).expect("same document");
+        assert_eq!(name.as_ref(), "algebra/group/def");
+        assert_eq!(name.steps().count(), 3);
+        assert_ne!(name.as_ref(), elem.name().last());
+
+        let translation = DocumentUri::from_str("http://example.com?a=archive&p=folder&d=math&l=de").expect("works");
+        assert!(elem.relative_to(&translation).is_none());
+        let other = DocumentUri::from_str("http://example.com?a=archive&d=math&l=en").expect("works");
+        assert!(elem.relative_to(&other).is_none());
+    };
     element_uri_display {
         use std::str::FromStr;
         let symbol_uri = DocumentElementUri::from_str("http://example.com?a=archive&p=path&d=doc&l=en&e=elem").expect("works");
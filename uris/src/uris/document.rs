@@ -20,6 +20,12 @@ static NO_DOCUMENT: std::sync::LazyLock<DocumentUri> = std::sync::LazyLock::new(
         .unwrap_unchecked()
 });
 
+static ANONYMOUS_PATH: std::sync::LazyLock<PathUri> = std::sync::LazyLock::new(|| unsafe {
+    "http://unknown.source?a=no/archive&p=anonymous"
+        .parse()
+        .unwrap_unchecked()
+});
+
 /// A non-hierarchical name used for document.
 ///
 /// [`SimpleUriName`] represents a [`UriName`](crate::UriName) that can *not* contain forward slashes as
@@ -146,12 +152,91 @@ impl DocumentUri {
     pub(crate) const SEPARATOR: char = 'd';
 
     /// Returns a reference to the default anonymous "no document".
+    ///
+    /// This is a sentinel for content that does not belong to any (known) document, e.g.
+    /// fragments rendered in isolation: such content can be displayed, but nothing extracted
+    /// from it (paragraphs, the document itself, ...) should be recorded anywhere, since
+    /// unrelated fragments would share (and clash on) the same URIs.
     #[inline]
     #[must_use]
     pub fn no_doc() -> &'static Self {
         &NO_DOCUMENT
     }
 
+    /// Returns an anonymous document (see [`no_doc`](Self::no_doc)) distinguishable by the
+    /// given `label`, e.g. to display *something* meaningful for fragments rendered in
+    /// isolation.
+    ///
+    /// This is not a "real" URI: it lives in the same (reserved) archive as
+    /// [`no_doc`](Self::no_doc), at the path `anonymous`, with the (url-encoded) label as
+    /// its name - i.e. e.g. `anonymous_with_label("Example 3")` is
+    /// `http://unknown.source?a=no/archive&p=anonymous&d=Example%203&l=en`. An empty label
+    /// yields [`no_doc`](Self::no_doc) itself.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ftml_uris::prelude::*;
+    /// let doc = DocumentUri::anonymous_with_label("Example 3");
+    /// assert!(doc.is_anonymous());
+    /// assert_eq!(doc.anonymous_label().as_deref(), Some("Example 3"));
+    /// ```
+    #[must_use]
+    pub fn anonymous_with_label(label: &str) -> Self {
+        let Ok(name) = SimpleUriName::from_str(&urlencoding::encode(label)) else {
+            return NO_DOCUMENT.clone();
+        };
+        Self {
+            name,
+            path: ANONYMOUS_PATH.clone(),
+            language: NO_DOCUMENT.language,
+        }
+    }
+
+    /// Whether this is an anonymous document, i.e. [`no_doc`](Self::no_doc) or one of the
+    /// [labeled ones](Self::anonymous_with_label) (or any other document in their reserved
+    /// archive).
+    #[inline]
+    #[must_use]
+    pub fn is_anonymous(&self) -> bool {
+        self.path.archive == NO_DOCUMENT.path.archive
+    }
+
+    /// The label of an anonymous document created with
+    /// [`anonymous_with_label`](Self::anonymous_with_label); `None` for all other documents
+    /// (including [`no_doc`](Self::no_doc)).
+    #[must_use]
+    pub fn anonymous_label(&self) -> Option<String> {
+        if self.path != *ANONYMOUS_PATH {
+            return None;
+        }
+        Some(
+            urlencoding::decode(self.name.as_ref())
+                .map_or_else(|_| self.name.to_string(), std::borrow::Cow::into_owned),
+        )
+    }
+
+    /// Returns a key identifying this document up to its [`Language`], i.e. all translations
+    /// of the same document share the same key.
+    ///
+    /// This is meant for *grouping* (e.g. collecting the language variants of a document);
+    /// it is not an identity - use the [`DocumentUri`] itself for that.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ftml_uris::prelude::*;
+    /// let en: DocumentUri = "http://example.com?a=archive&d=doc&l=en".parse().unwrap();
+    /// let de: DocumentUri = "http://example.com?a=archive&d=doc&l=de".parse().unwrap();
+    /// assert_ne!(en, de);
+    /// assert_eq!(en.language_agnostic_key(), de.language_agnostic_key());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn language_agnostic_key(&self) -> impl std::hash::Hash + Eq + Copy + std::fmt::Debug + '_ {
+        (&self.path, &self.name)
+    }
+
     /// Returns the URI of a document from and archive and its path relative to the archive.
     ///
     /// Attempts to extract the [`Language`] of the document from the filename, dropping the extension (assumed
@@ -193,6 +278,58 @@ impl DocumentUri {
         Ok((a / path) & (name.parse()?, lang))
     }
 
+    /// Like [`from_archive_relpath`](Self::from_archive_relpath), but for an OS file path
+    /// relative to the archive's source directory and with an explicitly given [`Language`].
+    ///
+    /// All directories of `rel` become the path component (joined with `/`, regardless of
+    /// the platform's separator; `.`, `..` and root components are ignored). The name is the
+    /// file name without its extension and without a `.<language>` suffix matching
+    /// `language`; e.g. `foo/bar/baz.en.tex` with [`English`](Language::English) yields
+    /// `&p=foo/bar&d=baz&l=en`.
+    ///
+    /// #### Errors
+    ///
+    /// if `rel` has no file name, or the resulting path or name is invalid (e.g. contains
+    /// illegal characters).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ftml_uris::prelude::*;
+    /// # use std::path::Path;
+    /// let archive_uri: ArchiveUri = "http://example.com?a=some/archive".parse().unwrap();
+    /// let document_uri = DocumentUri::from_relative_path(
+    ///     archive_uri,
+    ///     Path::new("foo/bar/baz.de.tex"),
+    ///     Language::German,
+    /// ).unwrap();
+    /// assert_eq!(document_uri.to_string(),"http://example.com?a=some/archive&p=foo/bar&d=baz&l=de");
+    /// ```
+    pub fn from_relative_path(
+        archive: ArchiveUri,
+        rel: &std::path::Path,
+        language: Language,
+    ) -> Result<Self, UriParseError> {
+        let mut segments = rel
+            .components()
+            .filter_map(|c| match c {
+                std::path::Component::Normal(s) => Some(s.to_string_lossy()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        let Some(file) = segments.pop() else {
+            return Err(SegmentParseError::Empty.into());
+        };
+        let name = file.rsplit_once('.').map_or(&*file, |(name, _)| name);
+        let name = name.strip_suffix(&format!(".{language}")).unwrap_or(name);
+        let path: Option<UriPath> = if segments.is_empty() {
+            None
+        } else {
+            Some(segments.join("/").parse()?)
+        };
+        Ok((archive / path) & (name.parse()?, language))
+    }
+
     /// Returns the URI of a module within this document
     ///
     /// If the name's first segment is equal to the document's name, the module's [`PathUri`] and first name
@@ -464,6 +601,30 @@ crate::tests! {
         assert!(DocumentUri::from_str("http://example.com?a=archive&d=document").is_err());
         assert!(DocumentUri::from_str("http://example.com?a=archive&d=a/b&l=de").is_err());
     };
+    document_uri_from_relative_path {
+        use std::path::Path;
+        let archive: ArchiveUri = "http://example.com?a=archive".parse().expect("works");
+        let nested = DocumentUri::from_relative_path(archive.clone(), Path::new("foo/bar/baz.en.tex"), Language::English).expect("works");
+        assert_eq!(nested.to_string(), "http://example.com?a=archive&p=foo/bar&d=baz&l=en");
+        let top = DocumentUri::from_relative_path(archive.clone(), Path::new("baz.html"), Language::French).expect("works");
+        assert_eq!(top.to_string(), "http://example.com?a=archive&d=baz&l=fr");
+        assert!(DocumentUri::from_relative_path(archive, Path::new(""), Language::English).is_err());
+    };
+    language_agnostic_keys {
+        use crate::DocumentElementUri;
+        use std::str::FromStr;
+        let en = DocumentUri::from_str("http://example.com?a=archive&p=path&d=doc&l=en").expect("works");
+        let de = DocumentUri::from_str("http://example.com?a=archive&p=path&d=doc&l=de").expect("works");
+        let other = DocumentUri::from_str("http://example.com?a=archive&p=path&d=other&l=en").expect("works");
+        assert_ne!(en, de);
+        assert_eq!(en.language_agnostic_key(), de.language_agnostic_key());
+        assert_ne!(en.language_agnostic_key(), other.language_agnostic_key());
+
+        let en_elem = DocumentElementUri::from_str("http://example.com?a=archive&p=path&d=doc&l=en&e=sec").expect("works");
+        let de_elem = DocumentElementUri::from_str("http://example.com?a=archive&p=path&d=doc&l=de&e=sec").expect("works");
+        assert_ne!(en_elem, de_elem);
+        assert_eq!(en_elem.language_agnostic_key(), de_elem.language_agnostic_key());
+    };
     document_uri_display {
         use std::str::FromStr;
         let document_uri = DocumentUri::from_str("http://example.com?a=archive&p=path&d=document&l=fr").expect("works");
@@ -484,5 +645,40 @@ crate::tests! {
 
         let unicode_module = ModuleUri::from_str("http://example.com?a=archive&m=логика/предикаты").expect("works");
         assert_eq!(unicode_module.name.to_string(), "логика/предикаты");
+    };
+    same_archive_and_path {
+        use std::str::FromStr;
+        let module = ModuleUri::from_str("http://example.com?a=archive&p=folder&m=module").expect("works");
+        let same_path = DocumentUri::from_str("http://example.com?a=archive&p=folder&d=doc&l=en").expect("works");
+        let other_path = DocumentUri::from_str("http://example.com?a=archive&p=other&d=doc&l=en").expect("works");
+        let no_path = DocumentUri::from_str("http://example.com?a=archive&d=doc&l=en").expect("works");
+        let other_archive = DocumentUri::from_str("http://example.com?a=other&p=folder&d=doc&l=en").expect("works");
+        let other_base = DocumentUri::from_str("http://example.org?a=archive&p=folder&d=doc&l=en").expect("works");
+
+        assert!(module.same_archive(&same_path) && module.same_path(&same_path));
+        // same archive, different path
+        assert!(module.same_archive(&other_path) && !module.same_path(&other_path));
+        assert!(module.same_archive(&no_path) && !module.same_path(&no_path));
+        assert!(no_path.same_path(no_path.path_uri()));
+        // different archive
+        assert!(!module.same_archive(&other_archive) && !module.same_path(&other_archive));
+        assert!(!module.same_archive(&other_base) && !module.same_path(&other_base));
+    };
+    labeled_anonymous_document {
+        let labeled = DocumentUri::anonymous_with_label("Example 3/b");
+        assert!(labeled.is_anonymous());
+        assert_ne!(labeled, *DocumentUri::no_doc());
+        assert_eq!(labeled.anonymous_label().as_deref(), Some("Example 3/b"));
+        assert_ne!(labeled, DocumentUri::anonymous_with_label("Example 4"));
+        // survives a round trip through its string representation
+        let reparsed: DocumentUri = labeled.to_string().parse().expect("works");
+        assert_eq!(reparsed, labeled);
+
+        assert!(DocumentUri::no_doc().is_anonymous());
+        assert_eq!(DocumentUri::no_doc().anonymous_label(), None);
+        assert_eq!(DocumentUri::anonymous_with_label(""), *DocumentUri::no_doc());
+        let regular: DocumentUri = "http://example.com?a=archive&p=anonymous&d=doc&l=en".parse().expect("works");
+        assert!(!regular.is_anonymous());
+        assert_eq!(regular.anonymous_label(), None);
     }
 }
@@ -136,6 +136,46 @@ impl UriName {
         self.0.segmented::<'/'>()
     }
 
+    /// Returns `true` if the [steps](Self::steps) of `other` are the first steps of this name.
+    ///
+    /// Matching is step-wise, not character-wise: `math/algebra` starts with `math`, but not
+    /// with `ma`. Every name starts with itself.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ftml_uris::prelude::*;
+    /// # use std::str::FromStr;
+    /// let name = UriName::from_str("math/algebra/groups").unwrap();
+    /// assert!(name.starts_with(&UriName::from_str("math/algebra").unwrap()));
+    /// assert!(!name.starts_with(&UriName::from_str("math/alg").unwrap()));
+    /// ```
+    #[must_use]
+    pub fn starts_with(&self, other: &Self) -> bool {
+        let mut steps = self.steps();
+        other.steps().all(|s| steps.next() == Some(s))
+    }
+
+    /// Returns `true` if the [steps](Self::steps) of `other` are the last steps of this name.
+    ///
+    /// Matching is step-wise, not character-wise: `math/algebra` ends with `algebra`, but not
+    /// with `bra`. Every name ends with itself.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ftml_uris::prelude::*;
+    /// # use std::str::FromStr;
+    /// let name = UriName::from_str("math/algebra/groups").unwrap();
+    /// assert!(name.ends_with(&UriName::from_str("algebra/groups").unwrap()));
+    /// assert!(!name.ends_with(&UriName::from_str("math/groups").unwrap()));
+    /// ```
+    #[must_use]
+    pub fn ends_with(&self, other: &Self) -> bool {
+        let mut steps = self.steps().rev();
+        other.steps().rev().all(|s| steps.next() == Some(s))
+    }
+
     /// Returns `true` if this is a top-level name (contains no forward slashes).
     ///
     /// # Examples
@@ -185,6 +225,49 @@ impl UriName {
         }
     }
 
+    /// Returns the canonical form of this name, with all redundant steps removed.
+    ///
+    /// A step is redundant if it is `.` (i.e. refers to the "current" name), so
+    /// `math/./algebra` and `./math/algebra/.` both normalize to `math/algebra`. A name
+    /// consisting *only* of `.` steps normalizes to `.`. Empty steps (i.e. leading, trailing
+    /// or consecutive `/`) are rejected when parsing already, so the canonical form always
+    /// separates steps by exactly one `/`. `..` is *not* considered redundant, since
+    /// names are not (file system) paths.
+    ///
+    /// Since [parsing](FromStr) already normalizes names, this is only relevant for names
+    /// constructed otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ftml_uris::prelude::*;
+    /// # use std::str::FromStr;
+    /// let name = UriName::from_str("math/./algebra").unwrap();
+    /// assert_eq!(name.normalized(), UriName::from_str("math/algebra").unwrap());
+    /// assert_eq!(name.to_string(), "math/algebra");
+    /// ```
+    #[must_use]
+    pub fn normalized(&self) -> Self {
+        if !self.has_redundant_steps() {
+            return self.clone();
+        }
+        let s = self.normalized_steps().collect::<Vec<_>>().join("/");
+        // SAFETY: non-empty and consists of (valid) steps of self
+        Self(unsafe { s.parse().unwrap_unchecked() })
+    }
+
+    #[inline]
+    fn has_redundant_steps(&self) -> bool {
+        self.steps().any(|s| s == ".")
+    }
+
+    /// The [steps](Self::steps) of [`Self::normalized`]
+    fn normalized_steps(&self) -> impl Iterator<Item = &str> {
+        let mut steps = self.steps().filter(|s| *s != ".").peekable();
+        let only_redundant = steps.peek().is_none();
+        only_redundant.then_some(".").into_iter().chain(steps)
+    }
+
     #[must_use]
     pub fn with_last_name(&self, s: &crate::SimpleUriName) -> Self {
         if self.is_simple() {
@@ -203,9 +286,20 @@ impl UriName {
 
 impl FromStr for UriName {
     type Err = SegmentParseError;
-    #[inline]
+    /// Parses (and interns) the [normalized](UriName::normalized) form of `s`, so that
+    /// equivalent names are equal.
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(Self(NonEmptyStr::new_with_sep::<'/'>(s)?))
+        if !s.split('/').any(|step| step == ".") {
+            return Ok(Self(NonEmptyStr::new_with_sep::<'/'>(s)?));
+        }
+        // empty steps are kept, and hence still rejected
+        let mut steps = s.split('/').filter(|step| *step != ".").peekable();
+        let normalized = if steps.peek().is_none() {
+            ".".to_string()
+        } else {
+            steps.collect::<Vec<_>>().join("/")
+        };
+        Ok(Self(NonEmptyStr::new_with_sep::<'/'>(&normalized)?))
     }
 }
 impl std::fmt::Display for UriName {
@@ -592,6 +686,22 @@ crate::tests! {
         assert!(UriName::from_str("/a").is_err());
         assert!(UriName::from_str("a//b").is_err());
     };
+    uri_name_affixes {
+        let name = |s: &str| UriName::from_str(s).expect("works");
+        let abc = name("a/b/c");
+        assert!(abc.ends_with(&name("b/c")));
+        assert!(abc.ends_with(&name("c")));
+        assert!(abc.ends_with(&abc));
+        assert!(!abc.ends_with(&name("a/c")));
+        assert!(!abc.ends_with(&name("x/a/b/c")));
+        assert!(!name("ab/c").ends_with(&name("b/c")));
+
+        assert!(abc.starts_with(&name("a/b")));
+        assert!(abc.starts_with(&abc));
+        assert!(!abc.starts_with(&name("a/c")));
+        assert!(!abc.starts_with(&name("a/b/c/d")));
+        assert!(!name("a/bc").starts_with(&name("a/b")));
+    };
     uri_name_navigation {
         let name = UriName::from_str("math/algebra/groups/theory").expect("works");
 
@@ -53,6 +53,107 @@ pub enum UriParseError {
     NotAString,
 }
 
+impl UriParseError {
+    /// A longer, user-facing explanation of this error than its [`Display`](std::fmt::Display)
+    /// message, e.g. for form validation; names the expected component and gives an example
+    /// of a well-formed uri where applicable.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ftml_uris::prelude::*;
+    /// # use std::str::FromStr;
+    /// let error = SymbolUri::from_str("http://example.com?a=archive&s=symbol").unwrap_err();
+    /// assert!(error.help().starts_with("expected module parameter `&m=...` before `&s=`"));
+    /// ```
+    #[must_use]
+    pub fn help(&self) -> String {
+        use crate::{UriComponentKind as C, UriKind as K};
+        let (parts, example): (&[C], _) = match self {
+            Self::MissingPartFor { uri_kind, .. } | Self::TooManyPartsFor { uri_kind } => {
+                match uri_kind {
+                    K::Base => (&[], "http://example.com"),
+                    K::Archive => (&[C::a], "http://example.com?a=archive"),
+                    K::Path => (&[C::a, C::p], "http://example.com?a=archive&p=path"),
+                    K::Module => (&[C::a, C::m], "http://example.com?a=archive&m=module"),
+                    K::Symbol => (
+                        &[C::a, C::m, C::s],
+                        "http://example.com?a=archive&m=module&s=symbol",
+                    ),
+                    K::Document => (
+                        &[C::a, C::d, C::l],
+                        "http://example.com?a=archive&d=document&l=en",
+                    ),
+                    K::DocumentElement => (
+                        &[C::a, C::d, C::l, C::e],
+                        "http://example.com?a=archive&d=document&l=en&e=element",
+                    ),
+                }
+            }
+            _ => (&[], ""),
+        };
+        match self {
+            Self::MissingPartFor { part, .. } => {
+                let before = parts
+                    .iter()
+                    .skip_while(|p| *p != part)
+                    .nth(1)
+                    .map(|next| format!(" before `&{next}=`"))
+                    .unwrap_or_default();
+                let sep = if *part == C::a { '?' } else { '&' };
+                format!(
+                    "expected {} parameter `{sep}{part}=...`{before}, as in `{example}`",
+                    component_name(*part)
+                )
+            }
+            Self::TooManyPartsFor { .. } => format!(
+                "unexpected parameters after `&{}=...`; expected a uri of the form `{example}`",
+                parts.last().copied().unwrap_or(C::a)
+            ),
+            Self::URL(e) => {
+                format!("expected a valid base url such as `http://example.com` ({e})")
+            }
+            Self::Name(SegmentParseError::IllegalChar(c)) => format!(
+                "the character {c:?} is not allowed in uris; remove any of {}",
+                ILLEGAL_CHARS.map(|c| format!("`{c}`")).join(", ")
+            ),
+            Self::Name(SegmentParseError::Empty) => {
+                "uri components must not be empty, e.g. `&m=` or `a//b`".to_string()
+            }
+            Self::Name(SegmentParseError::TooLong) => "uri component is too long".to_string(),
+            Self::HasQueryOrFragment => {
+                "the base url must not contain a query (`?...`) or fragment (`#...`)".to_string()
+            }
+            Self::CannotBeABase => {
+                "expected an absolute base url such as `http://example.com`".to_string()
+            }
+            Self::UnknownParameter => "unknown parameter; expected parameters are `a` (archive), \
+                `p` (path), `m` (module), `s` (symbol), `d` (document), `l` (language) and \
+                `e` (document element)"
+                .to_string(),
+            Self::InvalidLanguage => {
+                "expected a language code such as `en` or `de` in `&l=...`".to_string()
+            }
+            Self::NotAString => "expected a string".to_string(),
+        }
+    }
+}
+
+const fn component_name(part: crate::UriComponentKind) -> &'static str {
+    use crate::UriComponentKind as C;
+    match part {
+        C::uri => "uri",
+        C::rp => "relative path",
+        C::a => "archive",
+        C::p => "path",
+        C::m => "module",
+        C::d => "document",
+        C::l => "language",
+        C::s => "symbol",
+        C::e => "document element",
+    }
+}
+
 /// Error occuring when trying to parse a Uri segment
 #[derive(Debug, Clone, Copy, Error)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -79,6 +180,13 @@ impl From<strumbra::Error> for SegmentParseError {
     }
 }
 
+/// Error when converting a [`Uri`](crate::Uri) that is neither a
+/// [`SymbolUri`](crate::SymbolUri) nor a [`DocumentElementUri`](crate::DocumentElementUri)
+/// into a [`LeafUri`](crate::LeafUri). Contains the original [`Uri`](crate::Uri).
+#[derive(Debug, Clone, Error)]
+#[error("{} uri {0} is not a leaf (symbol or document element) uri", .0.kind())]
+pub struct NotALeafUri(pub crate::Uri);
+
 impl From<url::ParseError> for UriParseError {
     #[inline]
     fn from(value: url::ParseError) -> Self {
@@ -219,3 +327,22 @@ mod urlerr {
         }
     }
 }
+
+crate::tests! {
+    parse_error_help {
+        use std::str::FromStr;
+        let error = crate::SymbolUri::from_str("http://example.com?a=archive&s=symbol")
+            .expect_err("module is missing");
+        assert!(matches!(
+            error,
+            UriParseError::MissingPartFor { part: crate::UriComponentKind::m, .. }
+        ));
+        let help = error.help();
+        assert!(help.contains("`&m=...`"), "{help}");
+        assert!(help.contains("before `&s=`"), "{help}");
+
+        let error = crate::DocumentUri::from_str("http://example.com?a=archive&d=doc&l=en&m=module")
+            .expect_err("too many parts");
+        assert!(error.help().contains("after `&l=...`"));
+    }
+}
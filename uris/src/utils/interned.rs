@@ -144,10 +144,24 @@ pub fn clear_memory() {
 
 pub type InternMap = (
     dashmap::DashSet<strumbra::SharedString, rustc_hash::FxBuildHasher>,
-    // mutex, so we can lock the whole map for certain actions
-    parking_lot::Mutex<usize>,
+    // mutex, so we can lock the whole map for certain actions; reentrant,
+    // so that a thread holding it (see [`lock_stores`]) can still intern
+    parking_lot::ReentrantMutex<std::cell::Cell<usize>>,
 );
 
+/// Locks all segment stores (ids, archive ids, names, paths) at once, in a fixed
+/// order, until the returned guards are dropped. Other threads trying to insert
+/// new strings block in the meantime; lookups of already interned strings do not.
+pub(crate) fn lock_stores() -> [parking_lot::ReentrantMutexGuard<'static, std::cell::Cell<usize>>; 4]
+{
+    [
+        super::IdStore::get().1.lock(),
+        crate::uris::archive::IdStore::get().1.lock(),
+        crate::uris::module::NameStore::get().1.lock(),
+        crate::uris::paths::PathStore::get().1.lock(),
+    ]
+}
+
 pub trait InternStore {
     const LIMIT: usize;
     fn get() -> &'static InternMap;
@@ -156,9 +170,8 @@ pub trait InternStore {
         Self: Sized,
     {
         let (_, len) = Self::get();
-        let mut len = len.lock();
-        let nlen = Self::clear_only();
-        *len = nlen;
+        let len = len.lock();
+        len.set(Self::clear_only());
     }
     fn clear_only() -> usize
     where
@@ -282,6 +295,33 @@ impl<Store: InternStore> InternedStr<Store> {
         }
     }
 
+    /// Hashes the *representation* of this string - its length, prefix and either the
+    /// inlined remaining bytes or the pointer to the interned allocation - rather than its
+    /// contents. Consistent with `==` (i.e. [`ptr_eq`](Self::ptr_eq)), and cheaper than
+    /// [`Hash`](std::hash::Hash) for long strings, but the values are meaningless outside
+    /// the current process.
+    #[inline]
+    pub fn ptr_hash<H: Hasher>(&self, state: &mut H) {
+        let morphed = self.morph();
+        state.write_u32(morphed.len);
+        state.write(&morphed.prefix);
+        if self.on_stack() {
+            // SAFETY: on_stack() guarantees that trailing contains buf, not ptr
+            state.write(unsafe { &morphed.trailing.buf });
+        } else {
+            // SAFETY: !on_stack() guarantees that trailing contains ptr, not buf
+            state.write_usize(unsafe { morphed.trailing.ptr.ptr.as_ptr() }.addr());
+        }
+    }
+
+    /// Interns `s` in `Store`.
+    ///
+    /// Thread-safe: lookups of already interned strings are lock-free (modulo the
+    /// [`DashSet`](dashmap::DashSet)'s shard locks); inserting a new string happens
+    /// under the store's mutex, and the store is checked again after acquiring it. Hence
+    /// for any two calls with equal strings - on the same or on different threads - the
+    /// results are pointer-equal, as long as one of them is alive (unreferenced strings
+    /// may be dropped from the store once it exceeds [`InternStore::LIMIT`]).
     fn new(s: &str) -> Result<Self, SegmentParseError> {
         if s.len() <= internals::INLINE_LEN as usize {
             if let Some(i) = s.find(super::errors::ILLEGAL_CHARS) {
@@ -298,7 +338,11 @@ impl<Store: InternStore> InternedStr<Store> {
             if let Some(s) = store.get(s) {
                 Ok(Self(s.clone(), PhantomData))
             } else {
-                let mut len = len.lock();
+                let len = len.lock();
+                // another thread might have inserted the same string in the meantime
+                if let Some(s) = store.get(s) {
+                    return Ok(Self(s.clone(), PhantomData));
+                }
                 if let Some(i) = s.find(super::errors::ILLEGAL_CHARS) {
                     // SAFETY: i is defined, so s[i..].chars().next() is defined
                     return unsafe {
@@ -309,10 +353,9 @@ impl<Store: InternStore> InternedStr<Store> {
                 }
                 let s = strumbra::SharedString::try_from(s)?;
                 store.insert(s.clone());
-                *len += 1;
-                if *len > Store::LIMIT {
-                    let nlen = Store::clear_only();
-                    *len = nlen;
+                len.set(len.get() + 1);
+                if len.get() > Store::LIMIT {
+                    len.set(Store::clear_only());
                 }
                 drop(len);
                 Ok(Self(s, PhantomData))
@@ -676,7 +719,7 @@ crate::tests! {
         assert_eq!(test_path, test_path2);
         // check store
         let (store,len) = PathStore::get();
-        assert_eq!(store.len(),*len.lock());
+        assert_eq!(store.len(),len.lock().get());
         assert!(store.len()>2000);
         // force cleanup
         paths.clear();
@@ -99,7 +99,7 @@ macro_rules! tests {
         mod tests {
             #![allow(unused_imports)]
             use super::*;
-            use crate::trace;
+            use crate::{trace, sym};
             use rstest::{rstest,fixture};
             $(
                 #[rstest]
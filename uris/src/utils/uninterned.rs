@@ -58,6 +58,13 @@ impl<A> NonEmptyStr<A> {
         Self(s)
     }
 
+    /// Without interning, there is no pointer identity to exploit; equivalent to
+    /// [`Hash::hash`](std::hash::Hash::hash).
+    #[inline]
+    pub fn ptr_hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        std::hash::Hash::hash(self, state);
+    }
+
     pub fn new(s: &str) -> Result<Self, SegmentParseError> {
         if s.is_empty() {
             Err(SegmentParseError::Empty)
@@ -78,6 +78,70 @@ pub fn iterate_body(cfg: config::FtmlViewerConfig) {
             || ftml_components::Views::cont(orig, false).into_any(),
         )
     });
+    scroll_to_anchor();
+}
+
+/// Returns the element anchor (see
+/// [`DocumentElementUri::anchor_id`](ftml_uris::DocumentElementUri::anchor_id)) designated
+/// by the URL fragment `hash` (i.e. `window.location.hash`, with or without leading `#`),
+/// if any.
+#[must_use]
+pub fn anchor_from_hash(hash: &str) -> Option<&str> {
+    let anchor = hash.strip_prefix('#').unwrap_or(hash).trim();
+    (anchor.len() > "ftml-".len()
+        && anchor.starts_with("ftml-")
+        && anchor
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'))
+    .then_some(anchor)
+}
+
+/// If the URL fragment designates an element anchor (see [`anchor_from_hash`]), scrolls to and
+/// (briefly) highlights that element.
+///
+/// Elements only get rendered after hydration, and possibly much later (e.g. within
+/// lazily loaded inputrefs), so this polls for the element (every 200ms, for at most
+/// 10s) rather than looking for it once.
+fn scroll_to_anchor() {
+    use leptos::{prelude::set_timeout, wasm_bindgen::JsCast};
+    use std::time::Duration;
+    const INTERVAL: Duration = Duration::from_millis(200);
+    const ATTEMPTS: u32 = 50;
+
+    fn attempt(anchor: String, remaining: u32) {
+        let Some(window) = leptos::web_sys::window() else {
+            return;
+        };
+        let Some(elem) = window.document().and_then(|d| d.get_element_by_id(&anchor)) else {
+            if remaining > 0 {
+                set_timeout(move || attempt(anchor, remaining - 1), INTERVAL);
+            } else {
+                tracing::debug!("anchor #{anchor} not found");
+            }
+            return;
+        };
+        tracing::debug!("scrolling to #{anchor}");
+        let rect = ftml_component_utils::js::get_true_rect(&elem);
+        window.scroll_to_with_x_and_y(0.0, rect.y());
+        if let Some(elem) = elem.dyn_ref::<leptos::web_sys::HtmlElement>() {
+            let style = elem.style();
+            let _ = style.set_property("outline", "2px solid #f5c242");
+            set_timeout(
+                move || {
+                    let _ = style.remove_property("outline");
+                },
+                Duration::from_secs(3),
+            );
+        }
+    }
+
+    let Some(hash) = leptos::web_sys::window().and_then(|w| w.location().hash().ok()) else {
+        return;
+    };
+    if let Some(anchor) = anchor_from_hash(&hash) {
+        let anchor = anchor.to_string();
+        set_timeout(move || attempt(anchor, ATTEMPTS), INTERVAL);
+    }
 }
 
 #[wasm_bindgen::prelude::wasm_bindgen]
@@ -104,3 +168,19 @@ pub fn clear_cache() {
     ftml_ontology::terms::clear_term_cache();
     print_cache();
 }
+
+#[cfg(test)]
+mod tests {
+    use super::anchor_from_hash;
+
+    #[test]
+    fn hash_to_anchor() {
+        assert_eq!(anchor_from_hash("#ftml-sec__para"), Some("ftml-sec__para"));
+        assert_eq!(anchor_from_hash("ftml-a_2e_b"), Some("ftml-a_2e_b"));
+        assert_eq!(anchor_from_hash(""), None);
+        assert_eq!(anchor_from_hash("#"), None);
+        assert_eq!(anchor_from_hash("#ftml-"), None);
+        assert_eq!(anchor_from_hash("#introduction"), None);
+        assert_eq!(anchor_from_hash("#ftml-a b"), None);
+    }
+}